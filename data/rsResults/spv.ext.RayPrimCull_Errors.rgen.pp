@@ -0,0 +1,10 @@
+#version 460
+#extension GL_EXT_ray_tracing : enable
+layout(primitive_culling);
+#extension GL_EXT_ray_flags_primitive_culling : enable
+layout(primitive_culling) uniform;
+layout(primitive_culling, binding = 2) uniform accelerationStructureEXT as;
+layout(std140, binding = 2, primitive_culling) buffer block { int x; };
+void main()
+{
+}
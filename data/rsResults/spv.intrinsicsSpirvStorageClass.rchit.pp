@@ -0,0 +1,11 @@
+#version 460
+
+#extension GL_EXT_spirv_intrinsics: enable
+
+
+layout(location = 1) spirv_storage_class(extensions = ["SPV_KHR_ray_tracing"], capabilities = [5353], 5338) vec4 payload;
+
+void main()
+{
+    payload = vec4(0.0, 1.0, 0.0, 1.0);
+}
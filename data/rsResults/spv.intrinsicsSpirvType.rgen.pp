@@ -0,0 +1,20 @@
+#version 460 core
+
+#extension GL_EXT_spirv_intrinsics: enable
+
+
+spirv_instruction (extensions = ["SPV_KHR_ray_query"], capabilities = [4472, 4478], id = 4473)
+void rayQueryInitializeEXT(spirv_by_reference spirv_type (extensions = ["SPV_KHR_ray_query"], capabilities = [4472], id = 4472) rayQuery, spirv_type (extensions = ["SPV_KHR_ray_query"], capabilities = [4472], id = 5341) topLevel, uint rayFlags, uint cullMask, vec3 origin, float tMin, vec3 direction, float tMax);
+
+spirv_instruction (extensions = ["SPV_KHR_ray_query"], capabilities = [4478], id = 4474)
+void rayQueryTerminateEXT(spirv_by_reference spirv_type (extensions = ["SPV_KHR_ray_query"], capabilities = [4472], id = 4472) rayQuery);
+
+layout(binding = 0) uniform spirv_type (extensions = ["SPV_KHR_ray_query"], capabilities = [4472], id = 5341) as;
+
+void main()
+{
+    spirv_type (extensions = ["SPV_KHR_ray_query"], capabilities = [4472], id = 4472) rq;
+
+    rayQueryInitializeEXT(rq, as, 0, 0, vec3(0.0), 0.0, vec3(1.0), 1.0);
+    rayQueryTerminateEXT(rq);
+}
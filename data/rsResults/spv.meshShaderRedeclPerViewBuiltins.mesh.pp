@@ -0,0 +1,47 @@
+#version 450
+
+
+
+#extension GL_NV_mesh_shader : enable
+
+layout(local_size_x = 32) in;
+
+layout(max_vertices=81) out;
+layout(max_primitives=32) out;
+layout(triangles) out;
+
+// test use of redeclared per-view builtin attributes
+
+out gl_MeshPerVertexNV {
+    perviewNV vec4 gl_PositionPerViewNV[gl_MaxMeshViewCountNV];         // explicitly sized view dim
+    perviewNV float gl_ClipDistancePerViewNV[gl_MaxMeshViewCountNV][4]; // explicitly sized view dim
+    perviewNV float gl_CullDistancePerViewNV[gl_MaxMeshViewCountNV][4]; // explicitly sized view dim
+} gl_MeshVerticesNV[];
+
+perprimitiveNV out gl_MeshPerPrimitiveNV {
+    perviewNV int gl_LayerPerViewNV[];                      // implicitly sized view dim
+    perviewNV int gl_ViewportMaskPerViewNV[][1];            // implicitly sized view dim
+} gl_MeshPrimitivesNV[];
+
+void main()
+{
+    uint iid = gl_LocalInvocationID.x;
+    uint viewID = gl_MeshViewIndicesNV[gl_MeshViewCountNV%gl_MaxMeshViewCountNV];
+
+    gl_MeshVerticesNV[iid].gl_PositionPerViewNV[viewID]          = vec4(1.0, 2.0, 3.0, 4.0);
+    gl_MeshVerticesNV[iid].gl_ClipDistancePerViewNV[viewID][2]   = 5.0;
+    gl_MeshVerticesNV[iid].gl_CullDistancePerViewNV[viewID][3]   = 6.0;
+    gl_MeshPrimitivesNV[iid].gl_LayerPerViewNV[viewID]           = 7;
+    gl_MeshPrimitivesNV[iid].gl_ViewportMaskPerViewNV[viewID][0] = 8;
+
+    memoryBarrierShared();     barrier();;
+
+    gl_MeshVerticesNV[iid+1].gl_PositionPerViewNV[viewID]          = gl_MeshVerticesNV[iid].gl_PositionPerViewNV[viewID];
+    gl_MeshVerticesNV[iid+1].gl_ClipDistancePerViewNV[viewID][2]   = gl_MeshVerticesNV[iid].gl_ClipDistancePerViewNV[viewID][2];
+    gl_MeshVerticesNV[iid+1].gl_CullDistancePerViewNV[viewID][3]   = gl_MeshVerticesNV[iid].gl_CullDistancePerViewNV[viewID][3];
+    gl_MeshPrimitivesNV[iid+1].gl_LayerPerViewNV[viewID]           = gl_MeshPrimitivesNV[iid].gl_LayerPerViewNV[viewID];
+    gl_MeshPrimitivesNV[iid+1].gl_ViewportMaskPerViewNV[viewID][0] = gl_MeshPrimitivesNV[iid].gl_ViewportMaskPerViewNV[viewID][0];
+
+    memoryBarrierShared();     barrier();;
+}
+
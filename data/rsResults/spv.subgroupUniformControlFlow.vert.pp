@@ -0,0 +1,9 @@
+#version 460
+
+
+#extension GL_EXT_subgroup_uniform_control_flow : enable
+
+[[random(4)]] void main() [[subgroup_uniform_control_flow]]
+{
+}
+
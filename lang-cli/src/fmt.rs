@@ -0,0 +1,182 @@
+//! Implementation of the `fmt` subcommand
+
+use std::{
+    io::prelude::*,
+    path::{Path, PathBuf},
+};
+
+use argh::FromArgs;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use glsl_lang::{
+    ast::TranslationUnit,
+    lexer::min::str::Lexer,
+    parse::{ParseBuilder, ParseContext},
+    transpiler::glsl::{comments::Comments, FormattingSettings, FormattingState},
+};
+
+/// Name of the configuration file looked up by the formatter
+const CONFIG_FILE_NAME: &str = "glslfmt.toml";
+
+#[derive(Debug, FromArgs)]
+#[argh(subcommand, name = "fmt")]
+/// format GLSL source files
+pub struct FmtCommand {
+    #[argh(switch, short = 'i')]
+    /// write the formatted sources back to the input files instead of the standard output
+    in_place: bool,
+
+    #[argh(switch)]
+    /// do not write anything, but print a diff and exit with a non-zero status if any input
+    /// file is not formatted
+    check: bool,
+
+    #[argh(option)]
+    /// path to the configuration file to use instead of looking for glslfmt.toml
+    config: Option<String>,
+
+    #[argh(positional)]
+    /// input file paths. The standard input is formatted if none are given
+    paths: Vec<String>,
+}
+
+/// Find the configuration file that applies to files in `dir`, by walking up the directory tree
+fn find_config(dir: &Path) -> Option<PathBuf> {
+    let dir = std::path::absolute(dir).ok()?;
+
+    dir.ancestors()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Load formatting settings from the given configuration file
+fn load_settings(path: &Path) -> Result<FormattingSettings> {
+    let config = std::fs::read_to_string(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+
+    toml::from_str(&config)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("failed to parse {}", path.display()))
+}
+
+/// Format a source string, keeping its comments
+fn format_source(source: &str, path: &str, settings: &FormattingSettings) -> Result<String> {
+    // Use the minimal lexer so preprocessor directives are kept as-is instead of being expanded
+    let ctx = ParseContext::new_with_comments();
+    let (tu, ctx, _): (TranslationUnit, _, _) = ParseBuilder::<Lexer, TranslationUnit>::new(source)
+        .context(&ctx)
+        .parse()
+        .map_err(|err| crate::parse_error(err, source, path))?;
+
+    let data = ctx.data();
+    let comments = data
+        .comments()
+        .map(|comments| Comments::new(comments, |_| Some(source)));
+
+    let mut state = FormattingState::from(settings);
+    if let Some(comments) = &comments {
+        state = state.with_comments(comments);
+    }
+
+    let mut output = String::new();
+    glsl_lang::transpiler::glsl::show_translation_unit(&mut output, &tu, state)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("failed to format {}", path))?;
+
+    Ok(output)
+}
+
+impl FmtCommand {
+    /// Run the subcommand, returning `false` if some files were not formatted in check mode
+    pub fn run(self) -> Result<bool> {
+        let explicit_settings = self
+            .config
+            .as_deref()
+            .map(|path| load_settings(Path::new(path)))
+            .transpose()?;
+
+        let settings_for = |dir: &Path| -> Result<FormattingSettings> {
+            if let Some(settings) = &explicit_settings {
+                return Ok(settings.clone());
+            }
+
+            find_config(dir)
+                .map(|path| load_settings(&path))
+                .unwrap_or_else(|| Ok(FormattingSettings::default()))
+        };
+
+        if self.paths.is_empty() {
+            let mut source = String::new();
+            std::io::stdin()
+                .read_to_string(&mut source)
+                .into_diagnostic()?;
+
+            let settings = settings_for(Path::new("."))?;
+            let formatted = format_source(&source, "standard input", &settings)?;
+            return self.output("standard input", None, &source, &formatted);
+        }
+
+        let mut formatted_all = true;
+        for path in &self.paths {
+            let file_path = Path::new(path);
+            let source = std::fs::read_to_string(file_path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("failed to read {}", path))?;
+
+            let settings = settings_for(
+                file_path
+                    .parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .unwrap_or_else(|| Path::new(".")),
+            )?;
+            let formatted = format_source(&source, path, &settings)?;
+            formatted_all &= self.output(path, Some(file_path), &source, &formatted)?;
+        }
+
+        Ok(formatted_all)
+    }
+
+    /// Write the result of formatting a single input
+    fn output(
+        &self,
+        name: &str,
+        path: Option<&Path>,
+        source: &str,
+        formatted: &str,
+    ) -> Result<bool> {
+        if self.check {
+            if source == formatted {
+                return Ok(true);
+            }
+
+            let diff = similar::TextDiff::from_lines(source, formatted);
+            print!(
+                "{}",
+                diff.unified_diff().header(
+                    &format!("{} (original)", name),
+                    &format!("{} (formatted)", name)
+                )
+            );
+
+            return Ok(false);
+        }
+
+        match path {
+            Some(path) if self.in_place => {
+                if source != formatted {
+                    std::fs::write(path, formatted)
+                        .into_diagnostic()
+                        .wrap_err_with(|| format!("failed to write {}", name))?;
+                }
+            }
+            _ => {
+                std::io::stdout()
+                    .write_all(formatted.as_bytes())
+                    .into_diagnostic()?;
+            }
+        }
+
+        Ok(true)
+    }
+}
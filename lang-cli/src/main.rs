@@ -12,10 +12,54 @@
 //!     Declaration@0:0..45 `Block`
 //!       [...]
 //! ```
+//!
+//! Format GLSL sources, using the settings from the nearest `glslfmt.toml` file:
+//! ```bash
+//! $ cargo run -- fmt source.glsl > formatted.glsl
+//! $ cargo run -- fmt --in-place source.glsl
+//! $ cargo run -- fmt --check source.glsl
+//! ```
+//!
+//! The configuration file contains the fields of `FormattingSettings`, missing fields use their
+//! default value:
+//! ```toml
+//! indent_style = { spaces = { count = 2 } }
+//! max_line_width = 100
+//! space_before_else = false
+//! ```
+//!
+//! Print the preprocessed source, with `#line` directives marking included files:
+//! ```bash
+//! $ cargo run -- pp --preserve-comments source.glsl
+//! ```
+//!
+//! Report preprocessor warnings on the standard error, except unused macros:
+//! ```bash
+//! $ cargo run -- pp -W --silence unused-macro source.glsl > /dev/null
+//! ```
+//!
+//! List the macros affecting conditional compilation, and the distinct variants produced by
+//! their combinations, as JSON:
+//! ```bash
+//! $ cargo run -- variants --toggle USE_FOG --values QUALITY=1,2,undef uber.glsl
+//! ```
+//!
+//! Write a GCC/Ninja-compatible depfile listing the files included by the source:
+//! ```bash
+//! $ cargo run -- --depfile main.d --depfile-target main.spv main.glsl > /dev/null
+//! ```
 
 #![deny(missing_docs)]
 
-use std::{io::prelude::*, path::Path};
+mod fmt;
+mod pp;
+#[cfg(feature = "json")]
+mod variants;
+
+use std::{
+    io::prelude::*,
+    path::{Path, PathBuf},
+};
 
 use argh::FromArgs;
 
@@ -24,6 +68,7 @@ use glsl_lang::{
     lexer::full::fs::PreprocessorExt,
     parse::IntoParseBuilderExt,
 };
+use lang_util::located::FileIdResolver;
 
 fn output_text(output: &mut dyn std::io::Write, tu: TranslationUnit) -> std::io::Result<()> {
     writeln!(output, "{}", tu.display())?;
@@ -54,99 +99,207 @@ fn output_glsl(output: &mut dyn std::io::Write, tu: TranslationUnit) -> std::io:
 #[derive(Debug, FromArgs)]
 /// glsl-lang command-line interface
 struct Opts {
+    #[argh(subcommand)]
+    command: Option<Command>,
+
     #[argh(option, default = "\"text\".to_owned()")]
     /// output format (text, json or glsl)
     format: String,
 
+    #[argh(option)]
+    /// write a GCC/Ninja-compatible depfile listing the included files
+    depfile: Option<String>,
+
+    #[argh(option)]
+    /// target of the rules written to the depfile, usually the compiled output (required with
+    /// --depfile)
+    depfile_target: Option<String>,
+
     #[argh(positional)]
     /// input file path
     path: Option<String>,
 }
 
-use miette::{Diagnostic, SourceSpan};
+#[derive(Debug, FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Fmt(fmt::FmtCommand),
+    Pp(pp::PpCommand),
+    #[cfg(feature = "json")]
+    Variants(variants::VariantsCommand),
+}
+
+use glsl_lang::diagnostics::{version_diagnostics, Diagnostic};
+use miette::{LabeledSpan, Severity};
 
-#[derive(Debug, Diagnostic)]
-#[diagnostic(code(glsl_lang::parse::error))]
-struct ParseError<I: std::error::Error + 'static> {
-    inner: lang_util::located::Located<I>,
-    #[source_code]
+/// A [`Diagnostic`] with the source it applies to
+#[derive(Debug)]
+struct SourceDiagnostic {
+    inner: Diagnostic,
+    severity: Severity,
     src: NamedSource<String>,
-    snip: SourceSpan,
-    #[label = "Error occurred here."]
-    bad_bit: SourceSpan,
 }
 
-impl<I: std::error::Error + 'static> std::error::Error for ParseError<I> {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        Some(self.inner.inner())
+impl SourceDiagnostic {
+    fn new(inner: Diagnostic, severity: Severity, source: &str, path: &str) -> Self {
+        Self {
+            inner,
+            severity,
+            src: NamedSource::new(path, source.to_string()),
+        }
     }
 }
 
-impl<I: std::error::Error> std::fmt::Display for ParseError<I> {
+impl std::error::Error for SourceDiagnostic {}
+
+impl std::fmt::Display for SourceDiagnostic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Failed to parse input GLSL at line {} column {}.",
-            self.inner.line() + 1,
-            self.inner.col() + 1
-        )
+        write!(f, "{}", self.inner)
+    }
+}
+
+impl miette::Diagnostic for SourceDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new(self.inner.code))
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        let mut lines = self.inner.notes.clone();
+        if !self.inner.fix_its.is_empty() {
+            let messages: Vec<_> = self
+                .inner
+                .fix_its
+                .iter()
+                .map(|fix_it| fix_it.message.as_str())
+                .collect();
+            lines.push(messages.join(", or "));
+        }
+
+        if lines.is_empty() {
+            return None;
+        }
+
+        Some(Box::new(lines.join("\n")))
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.src)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let range = self.inner.range;
+        let label = (self.severity == Severity::Error).then(|| "Error occurred here.".to_owned());
+
+        Some(Box::new(std::iter::once(LabeledSpan::new_with_span(
+            label,
+            (usize::from(range.start()), usize::from(range.len())),
+        ))))
     }
 }
 
 use miette::{NamedSource, Result};
-fn parse_tu(source: &str, path: &str) -> Result<glsl_lang::ast::TranslationUnit> {
+/// Parse `source`, returning the translation unit and the paths of the files it includes
+fn parse_tu(source: &str, path: &str) -> Result<(glsl_lang::ast::TranslationUnit, Vec<PathBuf>)> {
     let mut processor = glsl_lang_pp::processor::fs::StdProcessor::new();
-    let tu: Result<glsl_lang::ast::TranslationUnit, _> = processor
-        .open_source(
-            source,
-            Path::new(path).parent().unwrap_or_else(|| Path::new(".")),
-        )
-        .builder()
-        .parse()
-        .map(|(mut tu, _, iter)| {
+    let file = processor.open_source(
+        source,
+        Path::new(path).parent().unwrap_or_else(|| Path::new(".")),
+    );
+    let file_id = file.file_id();
+
+    let tu: Result<glsl_lang::ast::TranslationUnit, _> =
+        file.builder().parse().map(|(mut tu, _, iter)| {
             iter.into_directives().inject(&mut tu);
             tu
         });
 
-    match tu {
-        Ok(tu) => Ok(tu),
-        Err(err) => {
-            let pos = err.pos();
-
-            // Find 2 lines before and after
-            let start = usize::from(pos.start());
-            let end = usize::from(pos.end());
-
-            // TODO: '\n' isn't what GLSL calls a line
-            let before = source
-                .rmatch_indices('\n')
-                .filter(|(i, _ch)| *i < start)
-                .map(|(i, _ch)| i + 1)
-                .nth(2)
-                .unwrap_or(0);
-
-            let after = source
-                .match_indices('\n')
-                .filter(|(i, _ch)| *i > end)
-                .map(|(i, _ch)| i)
-                .nth(2)
-                .unwrap_or(source.len());
-
-            Err(ParseError {
-                inner: err,
-                src: NamedSource::new(path, source.to_string()),
-                snip: (before, after.saturating_sub(before)).into(),
-                bad_bit: (usize::from(pos.start()), usize::from(pos.len())).into(),
+    let tu = tu.map_err(|err| {
+        // The error may be located in an included file, in which case the diagnostic has to be
+        // built from the text of that file
+        match err.current_file() {
+            Some(current_file) if current_file != file_id => {
+                let included = processor.ast(current_file).map(|ast| {
+                    let path = processor
+                        .resolve(current_file)
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_else(|| path.to_owned());
+                    (ast.clone().into_inner().0.text().to_string(), path)
+                });
+
+                match included {
+                    Some((source, path)) => parse_error(err, &source, &path),
+                    None => included_parse_error(err, path),
+                }
             }
-            .into())
+            _ => parse_error(err, source, path),
         }
-    }
+    })?;
+
+    // The source block itself has no path, so only its dependencies are listed
+    let graph = processor.include_graph();
+    let dependencies = graph
+        .dependencies(file_id)
+        .into_iter()
+        .filter_map(|file_id| graph.path(file_id).map(Path::to_owned))
+        .collect();
+
+    Ok((tu, dependencies))
+}
+
+/// Build a diagnostic for a parse error in `source`
+fn parse_error<E>(err: lang_util::error::ParseError<E>, source: &str, path: &str) -> miette::Report
+where
+    E: lang_util::error::LexicalError,
+{
+    SourceDiagnostic::new(
+        Diagnostic::from_parse_error(&err, source),
+        Severity::Error,
+        source,
+        path,
+    )
+    .into()
+}
+
+/// Build a diagnostic for a parse error in an included file whose text is not available
+///
+/// The error is reported without its source excerpt and fix-its, since they would be computed
+/// from the wrong text.
+fn included_parse_error<E>(err: lang_util::error::ParseError<E>, path: &str) -> miette::Report
+where
+    E: lang_util::error::LexicalError,
+{
+    let mut diagnostic = Diagnostic::from_parse_error(&err, "");
+    diagnostic.fix_its.clear();
+
+    miette::miette!("{}: {}", path, diagnostic)
 }
 
 /// CLI entry point
 fn main() -> Result<(), std::io::Error> {
     let args: Opts = argh::from_env();
 
+    if let Some(command) = args.command {
+        let result = match command {
+            Command::Fmt(command) => command.run(),
+            Command::Pp(command) => command.run(),
+            #[cfg(feature = "json")]
+            Command::Variants(command) => command.run(),
+        };
+
+        match result {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(diag) => {
+                eprintln!("{:?}", diag);
+                std::process::exit(2);
+            }
+        }
+    }
+
     // Figure out output format
     let output_fn = match args.format.as_str() {
         "text" => output_text,
@@ -156,6 +309,12 @@ fn main() -> Result<(), std::io::Error> {
         other => panic!("unknown output format: {}", other),
     };
 
+    // The depfile rules are only useful if their target is the built artifact
+    if args.depfile.is_some() && args.depfile_target.is_none() {
+        eprintln!("error: --depfile-target is required when writing a depfile");
+        std::process::exit(2);
+    }
+
     let mut s = String::new();
 
     // Read input from argument or stdin
@@ -165,15 +324,31 @@ fn main() -> Result<(), std::io::Error> {
         std::io::stdin().read_to_string(&mut s)?;
     }
 
-    match parse_tu(
-        s.as_str(),
-        &args
-            .path
-            .as_ref()
-            .map(String::to_owned)
-            .unwrap_or_else(|| "standard input".to_owned()),
-    ) {
-        Ok(tu) => {
+    let path = args
+        .path
+        .as_ref()
+        .map(String::to_owned)
+        .unwrap_or_else(|| "standard input".to_owned());
+
+    match parse_tu(s.as_str(), &path) {
+        Ok((tu, dependencies)) => {
+            if let Some(depfile) = args.depfile.as_deref() {
+                let target = args.depfile_target.as_deref().unwrap();
+                let mut output = std::io::BufWriter::new(std::fs::File::create(depfile)?);
+                glsl_lang_pp::processor::fs::write_depfile(
+                    &mut output,
+                    target,
+                    args.path.iter().map(PathBuf::from).chain(dependencies),
+                )?;
+                output.flush()?;
+            }
+
+            for diagnostic in version_diagnostics(&tu) {
+                let report: miette::Report =
+                    SourceDiagnostic::new(diagnostic, Severity::Warning, &s, &path).into();
+                eprintln!("{:?}", report);
+            }
+
             output_fn(&mut std::io::stdout(), tu)?;
         }
         Err(diag) => {
@@ -0,0 +1,125 @@
+//! Implementation of the `pp` subcommand
+
+use std::{io::prelude::*, path::Path};
+
+use argh::FromArgs;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+
+use glsl_lang_pp::{
+    last::LocatedIterator,
+    processor::{
+        emit::{Emitter, LineMarkers},
+        event::{Event, WarningId},
+        fs::StdProcessor,
+        ProcessorState,
+    },
+};
+
+#[derive(Debug, FromArgs)]
+#[argh(subcommand, name = "pp")]
+/// print the preprocessed source, like glslangValidator -E
+pub struct PpCommand {
+    #[argh(switch)]
+    /// keep comments in the output instead of replacing them with spaces
+    preserve_comments: bool,
+
+    #[argh(option, default = "\"number\".to_owned()")]
+    /// how file transitions are marked: with #line directives using file numbers (number) or
+    /// paths, which require GL_GOOGLE_cpp_style_line_directive (path), or not at all (none)
+    line_markers: String,
+
+    #[argh(switch, short = 'W')]
+    /// report preprocessor warnings
+    warnings: bool,
+
+    #[argh(option)]
+    /// silence a preprocessor warning, by name (e.g. unused-macro)
+    silence: Vec<String>,
+
+    #[argh(positional)]
+    /// input file path. The standard input is preprocessed if none is given
+    path: Option<String>,
+}
+
+impl PpCommand {
+    /// Run the subcommand, returning `false` if there were preprocessing errors
+    pub fn run(self) -> Result<bool> {
+        let line_markers = match self.line_markers.as_str() {
+            "none" => LineMarkers::None,
+            "number" => LineMarkers::Number,
+            "path" => LineMarkers::Path,
+            other => return Err(miette!("unknown line marker style: {}", other)),
+        };
+
+        let mut state = ProcessorState::builder().warnings(self.warnings);
+        for name in &self.silence {
+            let id: WarningId = name.parse().into_diagnostic()?;
+            state = state.warning(id, false);
+        }
+
+        let mut processor = StdProcessor::new();
+        let parsed = if let Some(path) = self.path.as_deref() {
+            processor
+                .parse(Path::new(path))
+                .into_diagnostic()
+                .wrap_err_with(|| format!("failed to read {}", path))?
+        } else {
+            let mut source = String::new();
+            std::io::stdin()
+                .read_to_string(&mut source)
+                .into_diagnostic()?;
+
+            processor.parse_source(&source, Path::new("."))
+        };
+
+        let mut emitter = Emitter::new(String::new())
+            .preserve_comments(self.preserve_comments)
+            .line_markers(line_markers);
+        let mut success = true;
+
+        let mut iter = parsed.process(state.finish());
+        while let Some(result) = iter.next() {
+            match result {
+                Ok(event) => {
+                    match &event {
+                        Event::Error {
+                            error,
+                            masked: false,
+                        } => {
+                            eprintln!("{}", error);
+                            success = false;
+                        }
+                        Event::Directive {
+                            directive,
+                            masked: false,
+                        } => {
+                            for error in directive.errors() {
+                                eprintln!("{}", error);
+                                success = false;
+                            }
+                        }
+                        Event::Warning { warning } => {
+                            eprintln!("warning: {}", warning);
+                        }
+                        _ => {}
+                    }
+
+                    emitter
+                        .write_event(&event, iter.location())
+                        .into_diagnostic()?;
+                }
+                Err(error) => {
+                    eprintln!("{}", error);
+                    success = false;
+                }
+            }
+        }
+
+        let output = emitter.finish().into_diagnostic()?;
+        std::io::stdout()
+            .write_all(output.as_bytes())
+            .into_diagnostic()?;
+
+        Ok(success)
+    }
+}
@@ -0,0 +1,181 @@
+//! Implementation of the `variants` subcommand
+
+use std::path::Path;
+
+use argh::FromArgs;
+use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use serde_json::{json, Map, Value};
+
+use glsl_lang::{ast::TranslationUnit, parse::DefaultParse};
+use glsl_lang_pp::processor::{
+    fs::StdProcessor,
+    variants::{Configuration, MacroAnalysis, VariantGenerator, VariantSet},
+    ProcessorState,
+};
+
+#[derive(Debug, FromArgs)]
+#[argh(subcommand, name = "variants")]
+/// print the macros affecting conditional compilation and the matrix of shader variants as JSON
+pub struct VariantsCommand {
+    #[argh(option)]
+    /// macro which is either undefined or defined to 1
+    toggle: Vec<String>,
+
+    #[argh(option)]
+    /// macro with a comma-separated list of values, as NAME=VALUE,VALUE. The value `undef` leaves
+    /// the macro undefined
+    values: Vec<String>,
+
+    #[argh(switch)]
+    /// include the preprocessed text of each variant in the output
+    text: bool,
+
+    #[argh(positional)]
+    /// input file path
+    path: String,
+}
+
+impl VariantsCommand {
+    /// Run the subcommand, returning `false` if a variant could not be preprocessed or parsed
+    pub fn run(self) -> Result<bool> {
+        let mut generator = VariantGenerator::new(ProcessorState::builder());
+        for name in &self.toggle {
+            generator = generator.toggle(name.as_str());
+        }
+
+        for spec in &self.values {
+            let (name, values) = spec
+                .split_once('=')
+                .ok_or_else(|| miette!("expected NAME=VALUE,VALUE, got {}", spec))?;
+
+            generator = generator
+                .values(
+                    name,
+                    values
+                        .split(',')
+                        .map(|value| (value != "undef").then(|| value.into())),
+                )
+                .map_err(|value| miette!("invalid value for {}: {}", name, value))?;
+        }
+
+        let path = Path::new(&self.path);
+        let mut processor = StdProcessor::new();
+        let file_id = processor
+            .parse(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("failed to read {}", self.path))?
+            .file_id();
+
+        // Preprocess all the configurations
+        let mut set = VariantSet::new();
+        let mut matrix = Vec::new();
+        for configuration in generator.configurations() {
+            let state = generator.state(&configuration);
+            let events = processor.parse(path).into_diagnostic()?.process(state);
+
+            matrix.push(json!({
+                "defines": defines_json(&configuration),
+                "variant": set.add(configuration, events),
+            }));
+        }
+
+        // Analyze the input and the files it included in any configuration
+        let graph = processor.include_graph();
+        let mut analysis = MacroAnalysis::new();
+        for id in std::iter::once(file_id).chain(graph.dependencies(file_id)) {
+            if let Some(ast) = processor.ast(id) {
+                analysis.add_file(id, ast);
+            }
+        }
+
+        let macros: Vec<_> = analysis
+            .macros()
+            .iter()
+            .map(|m| {
+                let regions: Vec<_> = m
+                    .regions
+                    .iter()
+                    .map(|region| {
+                        let path = graph
+                            .path(region.source_id())
+                            .map(|path| path.display().to_string());
+
+                        json!({
+                            "path": path,
+                            "start": usize::from(region.start().offset),
+                            "end": usize::from(region.end().offset),
+                        })
+                    })
+                    .collect();
+
+                json!({ "name": m.name.as_str(), "regions": regions })
+            })
+            .collect();
+
+        // Parse the distinct variants
+        let mut success = true;
+        let variants: Vec<_> = set
+            .variants()
+            .iter()
+            .map(|variant| {
+                let errors: Vec<_> = variant
+                    .errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect();
+
+                let parse_error = TranslationUnit::parse(variant.text.as_str())
+                    .err()
+                    .map(|error| error.to_string());
+
+                success &= errors.is_empty() && parse_error.is_none();
+
+                let configurations: Vec<_> =
+                    variant.configurations.iter().map(defines_json).collect();
+
+                let mut value = json!({
+                    "configurations": configurations,
+                    "errors": errors,
+                    "parse_error": parse_error,
+                });
+
+                if self.text {
+                    value["text"] = variant.text.as_str().into();
+                }
+
+                value
+            })
+            .collect();
+
+        let output = json!({
+            "macros": macros,
+            "matrix": matrix,
+            "variants": variants,
+        });
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).into_diagnostic()?
+        );
+
+        Ok(success)
+    }
+}
+
+/// Convert a configuration to a JSON object, with `null` for undefined macros
+fn defines_json(configuration: &Configuration) -> Value {
+    Value::Object(
+        configuration
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    value
+                        .as_ref()
+                        .map(|value| Value::from(value.as_str()))
+                        .unwrap_or(Value::Null),
+                )
+            })
+            .collect::<Map<_, _>>(),
+    )
+}
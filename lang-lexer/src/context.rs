@@ -256,6 +256,13 @@ impl ParseContextData {
         self.names.iter().any(|level| level.contains(name))
     }
 
+    /// Iterate over the known type names, from all nesting levels
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.names
+            .iter()
+            .flat_map(|level| level.iter().map(SmolStr::as_str))
+    }
+
     /// Register `name` as a new type name
     pub fn add_type_name(&mut self, name: ast::Identifier) -> ast::TypeName {
         let name_string = name.0.as_str();
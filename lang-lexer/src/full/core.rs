@@ -1,8 +1,8 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, sync::Arc};
 
 use glsl_lang_pp::{
     last::{self, LocatedIterator, MaybeToken, TokenState, Tokenizer},
-    processor::event::{self, Error, EventDirective, OutputToken},
+    processor::event::{self, Error, EventDirective, MacroExpansion, OutputToken, TokenLike},
     types,
 };
 
@@ -16,11 +16,16 @@ use super::{Directives, LexerPosition, LexicalError, Token};
 
 pub type Item<E> = Result<(LexerPosition, Token, LexerPosition), LexicalError<E>>;
 
+/// Number of expanded tokens whose macro invocations are kept for error reporting
+const MAX_TRACKED_EXPANSIONS: usize = 16;
+
 pub struct LexerCore {
     pub ctx: ParseContext,
     file_id: FileId,
     opts: ParseOptions,
     directives: Vec<EventDirective>,
+    /// Macro invocations the most recent expanded tokens come from
+    expansions: VecDeque<(LexerPosition, Arc<[MacroExpansion]>)>,
 }
 
 pub enum HandleTokenResult<E: std::error::Error + 'static> {
@@ -114,9 +119,24 @@ impl LexerCore {
             file_id,
             opts: *opts,
             directives: Vec::with_capacity(2),
+            expansions: VecDeque::with_capacity(MAX_TRACKED_EXPANSIONS),
         }
     }
 
+    /// Get the macro invocations the token at the given position was expanded from
+    ///
+    /// Only the most recent expanded tokens are tracked, since the parser may look ahead a few
+    /// tokens before reporting an error. Tokens from the same macro invocation share a
+    /// position, so the most recent one is used.
+    pub fn expansions_at(&self, pos: LexerPosition) -> &[MacroExpansion] {
+        self.expansions
+            .iter()
+            .rev()
+            .find(|(token_pos, _)| *token_pos == pos)
+            .map(|(_, expansions)| &expansions[..])
+            .unwrap_or(&[])
+    }
+
     fn lang_token(
         &self,
         source_token: &OutputToken,
@@ -136,7 +156,7 @@ impl LexerCore {
     }
 
     pub fn handle_token<'r, I, E>(
-        &self,
+        &mut self,
         source_token: OutputToken,
         token_kind: types::Token,
         state: TokenState,
@@ -178,6 +198,14 @@ impl LexerCore {
                                 self.ctx.pop_scope();
                             }
 
+                            if let Some(expansions) = TokenLike::expansions(&source_token) {
+                                if self.expansions.len() == MAX_TRACKED_EXPANSIONS {
+                                    self.expansions.pop_front();
+                                }
+
+                                self.expansions.push_back((token.0, expansions.clone()));
+                            }
+
                             token_state.push_item(Ok(token));
                         }
                     }
@@ -1,8 +1,11 @@
 use std::cmp;
 
-use glsl_lang_pp::processor::{
-    event::{DirectiveKind, EventDirective},
-    nodes::VersionProfile,
+use glsl_lang_pp::{
+    processor::{
+        event::{DirectiveKind, EventDirective},
+        nodes::VersionProfile,
+    },
+    types::path::PathType,
 };
 use glsl_lang_types::ast::{
     self, PreprocessorExtensionBehaviorData, PreprocessorExtensionNameData,
@@ -128,6 +131,23 @@ impl Directives {
                 .spanned(start, end),
             ),
 
+            DirectiveKind::Include(include) => include.raw_path().map(|path| {
+                ast::ExternalDeclarationData::Preprocessor(
+                    ast::PreprocessorData::Include(
+                        ast::PreprocessorIncludeData {
+                            path: match path.ty {
+                                PathType::Angle => ast::PathData::Absolute(path.path),
+                                PathType::Quote => ast::PathData::Relative(path.path),
+                            }
+                            .into(),
+                        }
+                        .into(),
+                    )
+                    .spanned(start, end),
+                )
+                .spanned(start, end)
+            }),
+
             _ => None,
         }
     }
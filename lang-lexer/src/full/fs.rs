@@ -2,7 +2,7 @@
 
 use std::path::{Path, PathBuf};
 
-use lang_util::position::LexerPosition;
+use lang_util::{position::LexerPosition, FileId};
 
 use glsl_lang_pp::{
     exts::{Registry, DEFAULT_REGISTRY},
@@ -111,6 +111,10 @@ impl<'r, 'p, F: FileSystem> Iterator for LexerIterator<'r, 'p, F> {
                             self.current_file = path;
                             self.core.handle_file_id(file_id);
                         }
+
+                        Event::Warning { .. } => {
+                            // Preprocessor warnings are not lexical errors, ignore them
+                        }
                     },
 
                     Err(err) => {
@@ -159,12 +163,16 @@ impl<'r, 'p, F: FileSystem> LangLexerIterator for LexerIterator<'r, 'p, F> {
     ) -> lang_util::error::ParseError<Self::Error> {
         let location = self.inner.location();
         let (file_id, lexer) = lang_util::error::error_location(&err);
+        let expansions = self
+            .core
+            .expansions_at(LexerPosition::new(file_id, lexer.start()));
 
         lang_util::error::ParseError::<Self::Error>::builder()
             .pos(lexer)
             .current_file(file_id)
             .resolve(location)
             .resolve_path(&self.inner)
+            .expansions(expansions.iter().cloned())
             .finish(err.into())
     }
 }
@@ -213,6 +221,11 @@ pub struct File<'r, 'p, F: FileSystem> {
 }
 
 impl<'r, 'p, F: FileSystem> File<'r, 'p, F> {
+    /// Get the file identifier of this file
+    pub fn file_id(&self) -> FileId {
+        self.inner.file_id()
+    }
+
     /// Set the default processor state for processing this file
     pub fn with_state(self, state: impl Into<ProcessorState>) -> Self {
         Self {
@@ -92,6 +92,10 @@ impl<'i> Iterator for LexerIterator<'i> {
                             // Ignore
                         }
 
+                        Event::Warning { .. } => {
+                            // Preprocessor warnings are not lexical errors, ignore them
+                        }
+
                         Event::Token {
                             source_token,
                             token_kind,
@@ -151,12 +155,16 @@ impl<'i> LangLexerIterator for LexerIterator<'i> {
         err: lalrpop_util::ParseError<LexerPosition, Token, Self::Error>,
     ) -> lang_util::error::ParseError<Self::Error> {
         let location = self.inner.location();
-        let (_file_id, lexer) = lang_util::error::error_location(&err);
+        let (file_id, lexer) = lang_util::error::error_location(&err);
+        let expansions = self
+            .core
+            .expansions_at(LexerPosition::new(file_id, lexer.start()));
 
         lang_util::error::ParseError::<Self::Error>::builder()
             .pos(lexer)
             .current_file(self.source_id)
             .resolve(location)
+            .expansions(expansions.iter().cloned())
             .finish(err.into())
     }
 }
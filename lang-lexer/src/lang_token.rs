@@ -1,6 +1,6 @@
 use glsl_lang_pp::types;
 
-use glsl_lang_types::ast::NodeSpan;
+use glsl_lang_types::ast::{Literal, NodeSpan};
 use lang_util::position::LexerPosition;
 
 use crate::{ParseContext, Token};
@@ -183,13 +183,109 @@ pub fn lang_token(
                 types::TypeName::SUBPASSINPUTMS => Token::SubpassInputMs,
                 types::TypeName::ISUBPASSINPUTMS => Token::ISubpassInputMs,
                 types::TypeName::USUBPASSINPUTMS => Token::USubpassInputMs,
+                types::TypeName::INT8_T => Token::Int8,
+                types::TypeName::I8VEC2 => Token::I8Vec2,
+                types::TypeName::I8VEC3 => Token::I8Vec3,
+                types::TypeName::I8VEC4 => Token::I8Vec4,
+                types::TypeName::UINT8_T => Token::UInt8,
+                types::TypeName::U8VEC2 => Token::U8Vec2,
+                types::TypeName::U8VEC3 => Token::U8Vec3,
+                types::TypeName::U8VEC4 => Token::U8Vec4,
+                types::TypeName::INT16_T => Token::Int16,
+                types::TypeName::I16VEC2 => Token::I16Vec2,
+                types::TypeName::I16VEC3 => Token::I16Vec3,
+                types::TypeName::I16VEC4 => Token::I16Vec4,
+                types::TypeName::UINT16_T => Token::UInt16,
+                types::TypeName::U16VEC2 => Token::U16Vec2,
+                types::TypeName::U16VEC3 => Token::U16Vec3,
+                types::TypeName::U16VEC4 => Token::U16Vec4,
+                types::TypeName::INT32_T => Token::Int32,
+                types::TypeName::I32VEC2 => Token::I32Vec2,
+                types::TypeName::I32VEC3 => Token::I32Vec3,
+                types::TypeName::I32VEC4 => Token::I32Vec4,
+                types::TypeName::UINT32_T => Token::UInt32,
+                types::TypeName::U32VEC2 => Token::U32Vec2,
+                types::TypeName::U32VEC3 => Token::U32Vec3,
+                types::TypeName::U32VEC4 => Token::U32Vec4,
+                types::TypeName::INT64_T => Token::Int64,
+                types::TypeName::I64VEC2 => Token::I64Vec2,
+                types::TypeName::I64VEC3 => Token::I64Vec3,
+                types::TypeName::I64VEC4 => Token::I64Vec4,
+                types::TypeName::UINT64_T => Token::UInt64,
+                types::TypeName::U64VEC2 => Token::U64Vec2,
+                types::TypeName::U64VEC3 => Token::U64Vec3,
+                types::TypeName::U64VEC4 => Token::U64Vec4,
+                types::TypeName::FLOAT16_T => Token::Float16,
+                types::TypeName::F16VEC2 => Token::F16Vec2,
+                types::TypeName::F16VEC3 => Token::F16Vec3,
+                types::TypeName::F16VEC4 => Token::F16Vec4,
+                types::TypeName::F16MAT2 => Token::F16Mat2,
+                types::TypeName::F16MAT3 => Token::F16Mat3,
+                types::TypeName::F16MAT4 => Token::F16Mat4,
+                types::TypeName::F16MAT2X2 => Token::F16Mat2x2,
+                types::TypeName::F16MAT2X3 => Token::F16Mat2x3,
+                types::TypeName::F16MAT2X4 => Token::F16Mat2x4,
+                types::TypeName::F16MAT3X2 => Token::F16Mat3x2,
+                types::TypeName::F16MAT3X3 => Token::F16Mat3x3,
+                types::TypeName::F16MAT3X4 => Token::F16Mat3x4,
+                types::TypeName::F16MAT4X2 => Token::F16Mat4x2,
+                types::TypeName::F16MAT4X3 => Token::F16Mat4x3,
+                types::TypeName::F16MAT4X4 => Token::F16Mat4x4,
+                types::TypeName::FLOAT32_T => Token::Float32,
+                types::TypeName::F32VEC2 => Token::F32Vec2,
+                types::TypeName::F32VEC3 => Token::F32Vec3,
+                types::TypeName::F32VEC4 => Token::F32Vec4,
+                types::TypeName::F32MAT2 => Token::F32Mat2,
+                types::TypeName::F32MAT3 => Token::F32Mat3,
+                types::TypeName::F32MAT4 => Token::F32Mat4,
+                types::TypeName::F32MAT2X2 => Token::F32Mat2x2,
+                types::TypeName::F32MAT2X3 => Token::F32Mat2x3,
+                types::TypeName::F32MAT2X4 => Token::F32Mat2x4,
+                types::TypeName::F32MAT3X2 => Token::F32Mat3x2,
+                types::TypeName::F32MAT3X3 => Token::F32Mat3x3,
+                types::TypeName::F32MAT3X4 => Token::F32Mat3x4,
+                types::TypeName::F32MAT4X2 => Token::F32Mat4x2,
+                types::TypeName::F32MAT4X3 => Token::F32Mat4x3,
+                types::TypeName::F32MAT4X4 => Token::F32Mat4x4,
+                types::TypeName::FLOAT64_T => Token::Float64,
+                types::TypeName::F64VEC2 => Token::F64Vec2,
+                types::TypeName::F64VEC3 => Token::F64Vec3,
+                types::TypeName::F64VEC4 => Token::F64Vec4,
+                types::TypeName::F64MAT2 => Token::F64Mat2,
+                types::TypeName::F64MAT3 => Token::F64Mat3,
+                types::TypeName::F64MAT4 => Token::F64Mat4,
+                types::TypeName::F64MAT2X2 => Token::F64Mat2x2,
+                types::TypeName::F64MAT2X3 => Token::F64Mat2x3,
+                types::TypeName::F64MAT2X4 => Token::F64Mat2x4,
+                types::TypeName::F64MAT3X2 => Token::F64Mat3x2,
+                types::TypeName::F64MAT3X3 => Token::F64Mat3x3,
+                types::TypeName::F64MAT3X4 => Token::F64Mat3x4,
+                types::TypeName::F64MAT4X2 => Token::F64Mat4x2,
+                types::TypeName::F64MAT4X3 => Token::F64Mat4x3,
+                types::TypeName::F64MAT4X4 => Token::F64Mat4x4,
+                types::TypeName::ACCELERATIONSTRUCTUREEXT => Token::AccelerationStructureExt,
+                types::TypeName::RAYQUERYEXT => Token::RayQueryExt,
                 other => Token::TypeName(other.to_string().into()),
             },
-            types::Token::FLOAT_CONST(val) => Token::FloatConstant(val),
-            types::Token::INT_CONST(val) => Token::IntConstant(val),
-            types::Token::UINT_CONST(val) => Token::UIntConstant(val),
+            types::Token::FLOAT_CONST(val) => Token::FloatConstant(Literal::from_source(val, text)),
+            types::Token::INT_CONST(val) => Token::IntConstant(Literal::from_source(val, text)),
+            types::Token::UINT_CONST(val) => Token::UIntConstant(Literal::from_source(val, text)),
             types::Token::BOOL_CONST(val) => Token::BoolConstant(val),
-            types::Token::DOUBLE_CONST(val) => Token::DoubleConstant(val),
+            types::Token::DOUBLE_CONST(val) => {
+                Token::DoubleConstant(Literal::from_source(val, text))
+            }
+            types::Token::INT16_CONST(val) => Token::Int16Constant(Literal::from_source(val, text)),
+            types::Token::UINT16_CONST(val) => {
+                Token::UInt16Constant(Literal::from_source(val, text))
+            }
+            types::Token::INT64_CONST(val) => Token::Int64Constant(Literal::from_source(val, text)),
+            types::Token::UINT64_CONST(val) => {
+                Token::UInt64Constant(Literal::from_source(val, text))
+            }
+            types::Token::FLOAT16_CONST(val) => {
+                Token::Float16Constant(Literal::from_source(val, text))
+            }
+            types::Token::STRING_CONST(val) => Token::StringConstant(val),
             types::Token::LEFT_OP => Token::LeftOp,
             types::Token::RIGHT_OP => Token::RightOp,
             types::Token::INC_OP => Token::IncOp,
@@ -277,6 +373,29 @@ pub fn lang_token(
             types::Token::HIGHP => Token::HighPrecision,
             types::Token::PRECISION => Token::Precision,
             types::Token::STRUCT => Token::Struct,
+            types::Token::SHADERCALLCOHERENT => Token::ShaderCallCoherent,
+            types::Token::RAYPAYLOADEXT => Token::RayPayloadExt,
+            types::Token::RAYPAYLOADINEXT => Token::RayPayloadInExt,
+            types::Token::HITATTRIBUTEEXT => Token::HitAttributeExt,
+            types::Token::CALLABLEDATAEXT => Token::CallableDataExt,
+            types::Token::CALLABLEDATAINEXT => Token::CallableDataInExt,
+            types::Token::TASKPAYLOADSHAREDEXT => Token::TaskPayloadSharedExt,
+            types::Token::PERPRIMITIVEEXT => Token::PerPrimitiveExt,
+            types::Token::PERPRIMITIVENV => Token::PerPrimitiveNv,
+            types::Token::PERVIEWNV => Token::PerViewNv,
+            types::Token::TASKNV => Token::TaskNv,
+            types::Token::IGNOREINTERSECTIONEXT => Token::IgnoreIntersectionExt,
+            types::Token::TERMINATERAYEXT => Token::TerminateRayExt,
+            types::Token::SPIRV_INSTRUCTION => Token::SpirvInstruction,
+            types::Token::SPIRV_EXECUTION_MODE => Token::SpirvExecutionMode,
+            types::Token::SPIRV_EXECUTION_MODE_ID => Token::SpirvExecutionModeId,
+            types::Token::SPIRV_DECORATE => Token::SpirvDecorate,
+            types::Token::SPIRV_DECORATE_ID => Token::SpirvDecorateId,
+            types::Token::SPIRV_DECORATE_STRING => Token::SpirvDecorateString,
+            types::Token::SPIRV_STORAGE_CLASS => Token::SpirvStorageClass,
+            types::Token::SPIRV_BY_REFERENCE => Token::SpirvByReference,
+            types::Token::SPIRV_LITERAL => Token::SpirvLiteral,
+            types::Token::SPIRV_TYPE => Token::SpirvType,
             types::Token::ATTRIBUTE => Token::Attribute,
             types::Token::VARYING => Token::Varying,
             types::Token::COMMON
@@ -43,7 +43,7 @@ impl<'i> Lexer<'i> {
 enum PpFlags {
     None,
     Rest,
-    MojImport,
+    Include,
     Version,
     Extension,
     DefineName,
@@ -145,7 +145,7 @@ impl<'i> Iterator for LexerIterator<'i> {
                     return Some(Ok((range.start(), Token::PpRest(rest), range.end())));
                 }
 
-                PpFlags::MojImport => {
+                PpFlags::Include => {
                     // Expect an include path
                     debug_assert!(self.pending_tokens.is_empty());
 
@@ -242,7 +242,11 @@ impl<'i> Iterator for LexerIterator<'i> {
                 self.opts.default_version,
                 self.opts.target_vulkan,
                 |name| {
-                    if self.ctx.is_type_name(name.as_ref()) {
+                    // Extensions are not tracked by this lexer, so type names introduced by
+                    // extensions are always recognized
+                    if self.ctx.is_type_name(name.as_ref())
+                        || glsl_lang_pp::types::TypeName::parse_extension(name).is_some()
+                    {
                         TypeNameState::Type
                     } else {
                         TypeNameState::Ident
@@ -370,8 +374,12 @@ impl<'i> Iterator for LexerIterator<'i> {
                             }
                             "ifdef" => Token::PpIfDef,
                             "ifndef" => Token::PpIfNDef,
+                            "include" => {
+                                self.flags = PpFlags::Include;
+                                Token::PpInclude
+                            }
                             "moj_import" => {
-                                self.flags = PpFlags::MojImport;
+                                self.flags = PpFlags::Include;
                                 Token::PpMojImport
                             }
                             "line" => Token::PpLine,
@@ -1,3 +1,4 @@
+use glsl_lang_types::ast::Literal;
 use lang_util::SmolStr;
 
 #[derive(Debug, Clone, PartialEq, lang_util::Token)]
@@ -522,6 +523,174 @@ pub enum Token {
     #[lang_util(token = "usubpassInputMS")]
     USubpassInputMs,
     // End Vulkan-target keywords
+    // Begin GL_EXT_shader_explicit_arithmetic_types keywords
+    #[lang_util(token = "int8_t", kind = "type name")]
+    Int8,
+    #[lang_util(token = "i8vec2", kind = "type name")]
+    I8Vec2,
+    #[lang_util(token = "i8vec3", kind = "type name")]
+    I8Vec3,
+    #[lang_util(token = "i8vec4", kind = "type name")]
+    I8Vec4,
+    #[lang_util(token = "uint8_t", kind = "type name")]
+    UInt8,
+    #[lang_util(token = "u8vec2", kind = "type name")]
+    U8Vec2,
+    #[lang_util(token = "u8vec3", kind = "type name")]
+    U8Vec3,
+    #[lang_util(token = "u8vec4", kind = "type name")]
+    U8Vec4,
+    #[lang_util(token = "int16_t", kind = "type name")]
+    Int16,
+    #[lang_util(token = "i16vec2", kind = "type name")]
+    I16Vec2,
+    #[lang_util(token = "i16vec3", kind = "type name")]
+    I16Vec3,
+    #[lang_util(token = "i16vec4", kind = "type name")]
+    I16Vec4,
+    #[lang_util(token = "uint16_t", kind = "type name")]
+    UInt16,
+    #[lang_util(token = "u16vec2", kind = "type name")]
+    U16Vec2,
+    #[lang_util(token = "u16vec3", kind = "type name")]
+    U16Vec3,
+    #[lang_util(token = "u16vec4", kind = "type name")]
+    U16Vec4,
+    #[lang_util(token = "int32_t", kind = "type name")]
+    Int32,
+    #[lang_util(token = "i32vec2", kind = "type name")]
+    I32Vec2,
+    #[lang_util(token = "i32vec3", kind = "type name")]
+    I32Vec3,
+    #[lang_util(token = "i32vec4", kind = "type name")]
+    I32Vec4,
+    #[lang_util(token = "uint32_t", kind = "type name")]
+    UInt32,
+    #[lang_util(token = "u32vec2", kind = "type name")]
+    U32Vec2,
+    #[lang_util(token = "u32vec3", kind = "type name")]
+    U32Vec3,
+    #[lang_util(token = "u32vec4", kind = "type name")]
+    U32Vec4,
+    #[lang_util(token = "int64_t", kind = "type name")]
+    Int64,
+    #[lang_util(token = "i64vec2", kind = "type name")]
+    I64Vec2,
+    #[lang_util(token = "i64vec3", kind = "type name")]
+    I64Vec3,
+    #[lang_util(token = "i64vec4", kind = "type name")]
+    I64Vec4,
+    #[lang_util(token = "uint64_t", kind = "type name")]
+    UInt64,
+    #[lang_util(token = "u64vec2", kind = "type name")]
+    U64Vec2,
+    #[lang_util(token = "u64vec3", kind = "type name")]
+    U64Vec3,
+    #[lang_util(token = "u64vec4", kind = "type name")]
+    U64Vec4,
+    #[lang_util(token = "float16_t", kind = "type name")]
+    Float16,
+    #[lang_util(token = "f16vec2", kind = "type name")]
+    F16Vec2,
+    #[lang_util(token = "f16vec3", kind = "type name")]
+    F16Vec3,
+    #[lang_util(token = "f16vec4", kind = "type name")]
+    F16Vec4,
+    #[lang_util(token = "f16mat2", kind = "type name")]
+    F16Mat2,
+    #[lang_util(token = "f16mat3", kind = "type name")]
+    F16Mat3,
+    #[lang_util(token = "f16mat4", kind = "type name")]
+    F16Mat4,
+    #[lang_util(token = "f16mat2x2", kind = "type name")]
+    F16Mat2x2,
+    #[lang_util(token = "f16mat2x3", kind = "type name")]
+    F16Mat2x3,
+    #[lang_util(token = "f16mat2x4", kind = "type name")]
+    F16Mat2x4,
+    #[lang_util(token = "f16mat3x2", kind = "type name")]
+    F16Mat3x2,
+    #[lang_util(token = "f16mat3x3", kind = "type name")]
+    F16Mat3x3,
+    #[lang_util(token = "f16mat3x4", kind = "type name")]
+    F16Mat3x4,
+    #[lang_util(token = "f16mat4x2", kind = "type name")]
+    F16Mat4x2,
+    #[lang_util(token = "f16mat4x3", kind = "type name")]
+    F16Mat4x3,
+    #[lang_util(token = "f16mat4x4", kind = "type name")]
+    F16Mat4x4,
+    #[lang_util(token = "float32_t", kind = "type name")]
+    Float32,
+    #[lang_util(token = "f32vec2", kind = "type name")]
+    F32Vec2,
+    #[lang_util(token = "f32vec3", kind = "type name")]
+    F32Vec3,
+    #[lang_util(token = "f32vec4", kind = "type name")]
+    F32Vec4,
+    #[lang_util(token = "f32mat2", kind = "type name")]
+    F32Mat2,
+    #[lang_util(token = "f32mat3", kind = "type name")]
+    F32Mat3,
+    #[lang_util(token = "f32mat4", kind = "type name")]
+    F32Mat4,
+    #[lang_util(token = "f32mat2x2", kind = "type name")]
+    F32Mat2x2,
+    #[lang_util(token = "f32mat2x3", kind = "type name")]
+    F32Mat2x3,
+    #[lang_util(token = "f32mat2x4", kind = "type name")]
+    F32Mat2x4,
+    #[lang_util(token = "f32mat3x2", kind = "type name")]
+    F32Mat3x2,
+    #[lang_util(token = "f32mat3x3", kind = "type name")]
+    F32Mat3x3,
+    #[lang_util(token = "f32mat3x4", kind = "type name")]
+    F32Mat3x4,
+    #[lang_util(token = "f32mat4x2", kind = "type name")]
+    F32Mat4x2,
+    #[lang_util(token = "f32mat4x3", kind = "type name")]
+    F32Mat4x3,
+    #[lang_util(token = "f32mat4x4", kind = "type name")]
+    F32Mat4x4,
+    #[lang_util(token = "float64_t", kind = "type name")]
+    Float64,
+    #[lang_util(token = "f64vec2", kind = "type name")]
+    F64Vec2,
+    #[lang_util(token = "f64vec3", kind = "type name")]
+    F64Vec3,
+    #[lang_util(token = "f64vec4", kind = "type name")]
+    F64Vec4,
+    #[lang_util(token = "f64mat2", kind = "type name")]
+    F64Mat2,
+    #[lang_util(token = "f64mat3", kind = "type name")]
+    F64Mat3,
+    #[lang_util(token = "f64mat4", kind = "type name")]
+    F64Mat4,
+    #[lang_util(token = "f64mat2x2", kind = "type name")]
+    F64Mat2x2,
+    #[lang_util(token = "f64mat2x3", kind = "type name")]
+    F64Mat2x3,
+    #[lang_util(token = "f64mat2x4", kind = "type name")]
+    F64Mat2x4,
+    #[lang_util(token = "f64mat3x2", kind = "type name")]
+    F64Mat3x2,
+    #[lang_util(token = "f64mat3x3", kind = "type name")]
+    F64Mat3x3,
+    #[lang_util(token = "f64mat3x4", kind = "type name")]
+    F64Mat3x4,
+    #[lang_util(token = "f64mat4x2", kind = "type name")]
+    F64Mat4x2,
+    #[lang_util(token = "f64mat4x3", kind = "type name")]
+    F64Mat4x3,
+    #[lang_util(token = "f64mat4x4", kind = "type name")]
+    F64Mat4x4,
+    // End GL_EXT_shader_explicit_arithmetic_types keywords
+    // Begin GL_EXT_ray_query keywords
+    #[lang_util(token = "accelerationStructureEXT", kind = "type name")]
+    AccelerationStructureExt,
+    #[lang_util(token = "rayQueryEXT", kind = "type name")]
+    RayQueryExt,
+    // End GL_EXT_ray_query keywords
     #[lang_util(token = "struct", kind = "struct", kind = "keyword")]
     Struct,
     #[lang_util(token = "void", kind = "type name")]
@@ -556,20 +725,86 @@ pub enum Token {
         kind = "type qualifier"
     )]
     Subroutine,
+    // Begin GL_EXT_ray_tracing, GL_EXT_mesh_shader, GL_NV_mesh_shader keywords
+    #[lang_util(
+        token = "shadercallcoherent",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    ShaderCallCoherent,
+    #[lang_util(token = "rayPayloadEXT", kind = "storage qualifier", kind = "type qualifier")]
+    RayPayloadExt,
+    #[lang_util(token = "rayPayloadInEXT", kind = "storage qualifier", kind = "type qualifier")]
+    RayPayloadInExt,
+    #[lang_util(token = "hitAttributeEXT", kind = "storage qualifier", kind = "type qualifier")]
+    HitAttributeExt,
+    #[lang_util(token = "callableDataEXT", kind = "storage qualifier", kind = "type qualifier")]
+    CallableDataExt,
+    #[lang_util(token = "callableDataInEXT", kind = "storage qualifier", kind = "type qualifier")]
+    CallableDataInExt,
+    #[lang_util(token = "taskPayloadSharedEXT", kind = "storage qualifier", kind = "type qualifier")]
+    TaskPayloadSharedExt,
+    #[lang_util(token = "perprimitiveEXT", kind = "interpolation qualifier", kind = "type qualifier")]
+    PerPrimitiveExt,
+    #[lang_util(token = "perprimitiveNV", kind = "interpolation qualifier", kind = "type qualifier")]
+    PerPrimitiveNv,
+    #[lang_util(token = "perviewNV", kind = "interpolation qualifier", kind = "type qualifier")]
+    PerViewNv,
+    #[lang_util(token = "taskNV", kind = "interpolation qualifier", kind = "type qualifier")]
+    TaskNv,
+    #[lang_util(token = "ignoreIntersectionEXT", kind = "keyword")]
+    IgnoreIntersectionExt,
+    #[lang_util(token = "terminateRayEXT", kind = "keyword")]
+    TerminateRayExt,
+    // End GL_EXT_ray_tracing, GL_EXT_mesh_shader, GL_NV_mesh_shader keywords
+    // Begin GL_EXT_spirv_intrinsics keywords
+    #[lang_util(token = "spirv_instruction", kind = "type qualifier")]
+    SpirvInstruction,
+    #[lang_util(token = "spirv_execution_mode", kind = "type qualifier")]
+    SpirvExecutionMode,
+    #[lang_util(token = "spirv_execution_mode_id", kind = "type qualifier")]
+    SpirvExecutionModeId,
+    #[lang_util(token = "spirv_decorate", kind = "type qualifier")]
+    SpirvDecorate,
+    #[lang_util(token = "spirv_decorate_id", kind = "type qualifier")]
+    SpirvDecorateId,
+    #[lang_util(token = "spirv_decorate_string", kind = "type qualifier")]
+    SpirvDecorateString,
+    #[lang_util(token = "spirv_storage_class", kind = "type qualifier")]
+    SpirvStorageClass,
+    #[lang_util(token = "spirv_by_reference", kind = "type qualifier")]
+    SpirvByReference,
+    #[lang_util(token = "spirv_literal", kind = "type qualifier")]
+    SpirvLiteral,
+    #[lang_util(token = "spirv_type", kind = "keyword")]
+    SpirvType,
+    // End GL_EXT_spirv_intrinsics keywords
     #[lang_util(parser = "ident", kind = "identifier")]
     Identifier(SmolStr),
     #[lang_util(parser = "ty_name", kind = "type name")]
     TypeName(SmolStr), // Cast from Identifier depending on known type names
     #[lang_util(parser = "float_constant", kind = "literal")]
-    FloatConstant(f32),
+    FloatConstant(Literal<f32>),
     #[lang_util(parser = "int_constant", kind = "literal")]
-    IntConstant(i32),
+    IntConstant(Literal<i32>),
     #[lang_util(parser = "uint_constant", kind = "literal")]
-    UIntConstant(u32),
+    UIntConstant(Literal<u32>),
     #[lang_util(parser = "bool_constant", kind = "literal")]
     BoolConstant(bool),
     #[lang_util(parser = "double_constant", kind = "literal")]
-    DoubleConstant(f64),
+    DoubleConstant(Literal<f64>),
+    #[lang_util(parser = "int16_constant", kind = "literal")]
+    Int16Constant(Literal<i16>),
+    #[lang_util(parser = "uint16_constant", kind = "literal")]
+    UInt16Constant(Literal<u16>),
+    #[lang_util(parser = "int64_constant", kind = "literal")]
+    Int64Constant(Literal<i64>),
+    #[lang_util(parser = "uint64_constant", kind = "literal")]
+    UInt64Constant(Literal<u64>),
+    #[lang_util(parser = "float16_constant", kind = "literal")]
+    Float16Constant(Literal<f32>),
+    #[lang_util(parser = "string_constant", kind = "literal")]
+    StringConstant(SmolStr),
     #[lang_util(token = "<<", kind = "binary operator", kind = "operator")]
     LeftOp,
     #[lang_util(token = ">>", kind = "binary operator", kind = "operator")]
@@ -712,6 +947,8 @@ pub enum Token {
     PpIfDef,
     #[lang_util(display = "#ifndef", parser(display), kind = "preprocessor directive")]
     PpIfNDef,
+    #[lang_util(display = "#include", parser(display), kind = "preprocessor directive")]
+    PpInclude,
     #[lang_util(display = "#moj_import", parser(display), kind = "preprocessor directive")]
     PpMojImport,
     #[lang_util(display = "#line", parser(display), kind = "preprocessor directive")]
@@ -774,6 +1011,7 @@ impl Token {
                 | Self::PpIf
                 | Self::PpIfDef
                 | Self::PpIfNDef
+                | Self::PpInclude
                 | Self::PpMojImport
                 | Self::PpLine
                 | Self::PpPragma
@@ -788,6 +1026,7 @@ impl Token {
         match self {
             Self::Identifier(s) => s,
             Self::TypeName(s) => s,
+            Self::StringConstant(s) => s,
             Self::PpPathRelative(s) => s,
             Self::PpPathAbsolute(s) => s,
             _ => panic!("cannot convert token {:?}, to str", self),
@@ -797,10 +1036,13 @@ impl Token {
 
 macro_rules! impl_from {
     ($t:ty => $i:ident) => {
+        impl_from!($t => $i, |i| i);
+    };
+    ($t:ty => $i:ident, $f:expr) => {
         impl From<Token> for $t {
             fn from(value: Token) -> Self {
                 match value {
-                    Token::$i(i) => i,
+                    Token::$i(i) => $f(i),
                     other => panic!(concat!("cannot convert {:?} into ", stringify!($i)), other),
                 }
             }
@@ -808,11 +1050,23 @@ macro_rules! impl_from {
     };
 }
 
-impl_from!(i32 => IntConstant);
-impl_from!(u32 => UIntConstant);
-impl_from!(f32 => FloatConstant);
-impl_from!(f64 => DoubleConstant);
+impl_from!(i32 => IntConstant, |i: Literal<i32>| i.value());
+impl_from!(u32 => UIntConstant, |i: Literal<u32>| i.value());
+impl_from!(f32 => FloatConstant, |i: Literal<f32>| i.value());
+impl_from!(f64 => DoubleConstant, |i: Literal<f64>| i.value());
+impl_from!(Literal<i32> => IntConstant);
+impl_from!(Literal<u32> => UIntConstant);
+impl_from!(Literal<f32> => FloatConstant);
+impl_from!(Literal<f64> => DoubleConstant);
 impl_from!(bool => BoolConstant);
+impl_from!(i16 => Int16Constant, |i: Literal<i16>| i.value());
+impl_from!(u16 => UInt16Constant, |i: Literal<u16>| i.value());
+impl_from!(i64 => Int64Constant, |i: Literal<i64>| i.value());
+impl_from!(u64 => UInt64Constant, |i: Literal<u64>| i.value());
+impl_from!(Literal<i16> => Int16Constant);
+impl_from!(Literal<u16> => UInt16Constant);
+impl_from!(Literal<i64> => Int64Constant);
+impl_from!(Literal<u64> => UInt64Constant);
 
 impl From<Token> for String {
     fn from(value: Token) -> Self {
@@ -6,7 +6,14 @@ fn main() {
 
     // Generate interned strings
     string_cache_codegen::AtomType::new("exts::names::ExtNameAtom", "ext_name!")
-        .atoms(&["GL_GOOGLE_cpp_style_line_directive"])
+        .atoms(&[
+            "GL_ARB_shading_language_include",
+            "GL_EXT_control_flow_attributes",
+            "GL_EXT_spirv_intrinsics",
+            "GL_EXT_subgroup_uniform_control_flow",
+            "GL_GOOGLE_cpp_style_line_directive",
+            "GL_GOOGLE_include_directive",
+        ])
         .write_to_file(&out_dir.join("ext_names.rs"))
         .expect("failed to generate atoms");
 
@@ -173,6 +180,90 @@ fn main() {
             "subpassInputMS",
             "isubpassInputMS",
             "usubpassInputMS",
+            // GL_EXT_shader_explicit_arithmetic_types
+            "int8_t",
+            "i8vec2",
+            "i8vec3",
+            "i8vec4",
+            "uint8_t",
+            "u8vec2",
+            "u8vec3",
+            "u8vec4",
+            "int16_t",
+            "i16vec2",
+            "i16vec3",
+            "i16vec4",
+            "uint16_t",
+            "u16vec2",
+            "u16vec3",
+            "u16vec4",
+            "int32_t",
+            "i32vec2",
+            "i32vec3",
+            "i32vec4",
+            "uint32_t",
+            "u32vec2",
+            "u32vec3",
+            "u32vec4",
+            "int64_t",
+            "i64vec2",
+            "i64vec3",
+            "i64vec4",
+            "uint64_t",
+            "u64vec2",
+            "u64vec3",
+            "u64vec4",
+            "float16_t",
+            "f16vec2",
+            "f16vec3",
+            "f16vec4",
+            "f16mat2",
+            "f16mat3",
+            "f16mat4",
+            "f16mat2x2",
+            "f16mat2x3",
+            "f16mat2x4",
+            "f16mat3x2",
+            "f16mat3x3",
+            "f16mat3x4",
+            "f16mat4x2",
+            "f16mat4x3",
+            "f16mat4x4",
+            "float32_t",
+            "f32vec2",
+            "f32vec3",
+            "f32vec4",
+            "f32mat2",
+            "f32mat3",
+            "f32mat4",
+            "f32mat2x2",
+            "f32mat2x3",
+            "f32mat2x4",
+            "f32mat3x2",
+            "f32mat3x3",
+            "f32mat3x4",
+            "f32mat4x2",
+            "f32mat4x3",
+            "f32mat4x4",
+            "float64_t",
+            "f64vec2",
+            "f64vec3",
+            "f64vec4",
+            "f64mat2",
+            "f64mat3",
+            "f64mat4",
+            "f64mat2x2",
+            "f64mat2x3",
+            "f64mat2x4",
+            "f64mat3x2",
+            "f64mat3x3",
+            "f64mat3x4",
+            "f64mat4x2",
+            "f64mat4x3",
+            "f64mat4x4",
+            // GL_EXT_ray_query
+            "accelerationStructureEXT",
+            "rayQueryEXT",
             // Reserved for future use
             "hvec2",
             "hvec3",
@@ -230,6 +321,31 @@ fn main() {
             "highp",
             "precision",
             "struct",
+            // GL_EXT_ray_tracing, GL_EXT_mesh_shader, GL_NV_mesh_shader
+            "shadercallcoherent",
+            "rayPayloadEXT",
+            "rayPayloadInEXT",
+            "hitAttributeEXT",
+            "callableDataEXT",
+            "callableDataInEXT",
+            "taskPayloadSharedEXT",
+            "perprimitiveEXT",
+            "perprimitiveNV",
+            "perviewNV",
+            "taskNV",
+            "ignoreIntersectionEXT",
+            "terminateRayEXT",
+            // GL_EXT_spirv_intrinsics
+            "spirv_instruction",
+            "spirv_execution_mode",
+            "spirv_execution_mode_id",
+            "spirv_decorate",
+            "spirv_decorate_id",
+            "spirv_decorate_string",
+            "spirv_storage_class",
+            "spirv_by_reference",
+            "spirv_literal",
+            "spirv_type",
             // Reserved for future use
             "common",
             "partition",
@@ -117,6 +117,7 @@ impl Default for Registry {
                 ),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_sample_shading"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_separate_shader_objects"), vec![]),
+                ExtensionSpec::new(ExtNameAtom::from("GL_ARB_shader_ballot"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_shader_bit_encoding"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_shader_draw_parameters"), vec![]),
                 ExtensionSpec::new(
@@ -168,8 +169,10 @@ impl Default for Registry {
                 ),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_shader_texture_lod"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_shading_language_420pack"), vec![]),
+                ExtensionSpec::new(ext_name!("GL_ARB_shading_language_include"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_shading_language_packing"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_sparse_texture2"), vec![]),
+                ExtensionSpec::new(ExtNameAtom::from("GL_ARB_sparse_texture_clamp"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_ARB_tessellation_shader"), vec![]),
                 ExtensionSpec::new(
                     ExtNameAtom::from("GL_ARB_texture_cube_map_array"),
@@ -208,11 +211,15 @@ impl Default for Registry {
                     vec![TypeNameAtom::from("__samplerExternal2DY2YEXT")],
                 ),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_blend_func_extended"), vec![]),
+                ExtensionSpec::new(ExtNameAtom::from("GL_EXT_buffer_reference"), vec![]),
+                ExtensionSpec::new(ExtNameAtom::from("GL_EXT_buffer_reference2"), vec![]),
+                ExtensionSpec::new(ExtNameAtom::from("GL_EXT_buffer_reference_uvec2"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_control_flow_attributes"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_device_group"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_frag_depth"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_geometry_shader"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_gpu_shader5"), vec![]),
+                ExtensionSpec::new(ExtNameAtom::from("GL_EXT_mesh_shader"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_multiview"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_nonuniform_qualifier"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_null_initializer"), vec![]),
@@ -224,14 +231,19 @@ impl Default for Registry {
                 ExtensionSpec::new(
                     ExtNameAtom::from("GL_EXT_ray_query"),
                     vec![
-                        TypeNameAtom::from("accelerationStructureEXT"),
-                        TypeNameAtom::from("rayQueryEXT"),
+                        type_name!("accelerationStructureEXT"),
+                        type_name!("rayQueryEXT"),
                     ],
                 ),
+                ExtensionSpec::new(
+                    ExtNameAtom::from("GL_EXT_ray_tracing"),
+                    vec![type_name!("accelerationStructureEXT")],
+                ),
                 ExtensionSpec::new(
                     ExtNameAtom::from("GL_EXT_samplerless_texture_functions"),
                     vec![],
                 ),
+                ExtensionSpec::new(ExtNameAtom::from("GL_EXT_scalar_block_layout"), vec![]),
                 ExtensionSpec::new(
                     ExtNameAtom::from("GL_EXT_shader_explicit_arithmetic_types"),
                     vec![
@@ -447,6 +459,11 @@ impl Default for Registry {
                     vec![],
                 ),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_shader_texture_lod"), vec![]),
+                ExtensionSpec::new(ExtNameAtom::from("GL_EXT_spirv_intrinsics"), vec![]),
+                ExtensionSpec::new(
+                    ExtNameAtom::from("GL_EXT_subgroup_uniform_control_flow"),
+                    vec![],
+                ),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_terminate_invocation"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_EXT_tessellation_shader"), vec![]),
                 ExtensionSpec::new(
@@ -473,6 +490,7 @@ impl Default for Registry {
                     ],
                 ),
                 ExtensionSpec::new(ext_name!("GL_GOOGLE_cpp_style_line_directive"), vec![]),
+                ExtensionSpec::new(ext_name!("GL_GOOGLE_include_directive"), vec![]),
                 ExtensionSpec::new(ExtNameAtom::from("GL_KHR_blend_equation_advanced"), vec![]),
                 ExtensionSpec::new(
                     ExtNameAtom::from("GL_KHR_shader_subgroup_arithmetic"),
@@ -7,7 +7,9 @@ use lang_util::{located::FileIdResolver, FileId};
 use crate::{
     exts::{names::ExtNameAtom, ExtensionSpec, Registry},
     processor::{
-        event::{self, DirectiveKind, Error, ErrorKind, EventDirective, OutputToken, TokenLike},
+        event::{
+            self, DirectiveKind, Error, ErrorKind, EventDirective, OutputToken, TokenLike, Warning,
+        },
         expand::ExpandLocation,
         nodes::{Extension, ExtensionBehavior, ExtensionName},
     },
@@ -39,6 +41,9 @@ pub enum Event {
         directive: EventDirective,
         masked: bool,
     },
+    Warning {
+        warning: Warning,
+    },
 }
 
 pub trait LocatedIterator {
@@ -130,6 +135,10 @@ impl<'r> TypeTable<'r> {
         }
     }
 
+    fn extension_enabled(&self, name: &ExtNameAtom) -> bool {
+        self.extensions.contains_key(name)
+    }
+
     fn handle_extension(&mut self, extension: &Extension) -> bool {
         match &extension.name {
             ExtensionName::All => {
@@ -160,11 +169,18 @@ impl<'r> TypeTable<'r> {
         token: &impl TokenLike,
         location: &ExpandLocation,
     ) -> (Token, Option<TypeNameState>, Option<Error>) {
-        let (token_kind, state) =
+        let (mut token_kind, state) =
             token::token_from_syntax_kind(token, self.current_version, self.target_vulkan, |tn| {
                 self.is_type_name(tn)
             });
 
+        // String literals are only valid as arguments of GL_EXT_spirv_intrinsics qualifiers
+        if matches!(token_kind, Token::STRING_CONST(_))
+            && !self.extension_enabled(&ext_name!("GL_EXT_spirv_intrinsics"))
+        {
+            token_kind = Token::ERROR(crate::types::token::ErrorKind::InvalidToken);
+        }
+
         let error = if let Some(TypeNameState::WarnType(extension)) = &state {
             Some(
                 Error::builder()
@@ -219,6 +235,8 @@ pub struct Tokenizer<'r, I> {
     inner: I,
     type_table: TypeTable<'r>,
     pending_error: Option<Error>,
+    /// true if the last non-trivia token was a `[`
+    after_lbracket: bool,
 }
 
 impl<'r, I: LocatedIterator> Tokenizer<'r, I> {
@@ -232,6 +250,7 @@ impl<'r, I: LocatedIterator> Tokenizer<'r, I> {
             inner: inner.into_iter(),
             type_table: TypeTable::new(registry, current_version, target_vulkan),
             pending_error: None,
+            after_lbracket: false,
         }
     }
 
@@ -250,6 +269,41 @@ impl<'r, I: LocatedIterator> Tokenizer<'r, I> {
     pub fn location(&self) -> &crate::processor::expand::ExpandLocation {
         self.inner.location()
     }
+
+    /// Check that attributes (`[[...]]`) are only used with an extension that introduces them
+    fn check_attribute(&mut self, token: &OutputToken, token_kind: &Token) -> Option<Error> {
+        match token_kind {
+            Token::WS | Token::COMMENT => {}
+            Token::LBRACKET => {
+                if std::mem::replace(&mut self.after_lbracket, true)
+                    && !self
+                        .type_table
+                        .extension_enabled(&ext_name!("GL_EXT_control_flow_attributes"))
+                    // Function attributes are introduced by GL_EXT_subgroup_uniform_control_flow
+                    && !self
+                        .type_table
+                        .extension_enabled(&ext_name!("GL_EXT_subgroup_uniform_control_flow"))
+                {
+                    return Some(
+                        Error::builder()
+                            .pos(token.text_range())
+                            .resolve_file(self.inner.location())
+                            .finish(ErrorKind::ext_required(
+                                ext_name!("GL_EXT_control_flow_attributes"),
+                                "attribute",
+                                token.text_range(),
+                                self.inner.location(),
+                            )),
+                    );
+                }
+            }
+            _ => {
+                self.after_lbracket = false;
+            }
+        }
+
+        None
+    }
 }
 
 impl<'r, E, I: Iterator<Item = Result<event::Event, E>> + LocatedIterator> Iterator
@@ -281,7 +335,8 @@ impl<'r, E, I: Iterator<Item = Result<event::Event, E>> + LocatedIterator> Itera
                     let (token_kind, state, error) = self.tokenize_single(&token);
 
                     if !masked {
-                        self.pending_error = error;
+                        self.pending_error =
+                            error.or_else(|| self.check_attribute(&token, &token_kind));
                     }
 
                     Event::Token {
@@ -316,6 +371,7 @@ impl<'r, E, I: Iterator<Item = Result<event::Event, E>> + LocatedIterator> Itera
 
                     Event::Directive { directive, masked }
                 }
+                event::Event::Warning { warning } => Event::Warning { warning },
             }),
             Err(err) => Err(err),
         })
@@ -360,6 +416,47 @@ mod tests {
         assert_eq!(parse("1E-34F"), &[FLOAT_CONST(1E-34)]);
     }
 
+    #[test]
+    fn test_string_constant() {
+        use super::Token::*;
+
+        fn parse(src: &str) -> Vec<super::Token> {
+            crate::processor::str::process(src, crate::processor::ProcessorState::default())
+                .tokenize(100, false, &crate::exts::DEFAULT_REGISTRY)
+                .filter_map(|evt| evt.as_token().map(|(_, kind, _)| kind.clone()))
+                .filter(|kind| *kind != WS)
+                .collect()
+        }
+
+        assert_eq!(
+            parse(r#""boo""#),
+            &[ERROR(crate::types::token::ErrorKind::InvalidToken)]
+        );
+        assert_eq!(
+            parse("#extension GL_EXT_spirv_intrinsics : enable\n\"a \\\"b\\\" \\\\ c\\n\""),
+            &[STRING_CONST("a \"b\" \\ c\n".into())]
+        );
+    }
+
+    #[test]
+    fn test_attribute_requires_extension() {
+        fn has_error(src: &str) -> bool {
+            crate::processor::str::process(src, crate::processor::ProcessorState::default())
+                .tokenize(100, false, &crate::exts::DEFAULT_REGISTRY)
+                .any(|evt| matches!(evt, Ok(Event::Error { .. })))
+        }
+
+        assert!(has_error("[[unroll]] for (;;) {}"));
+        assert!(has_error("[ [unroll] ] for (;;) {}"));
+        assert!(!has_error("a[b[0]] = 1;"));
+        assert!(!has_error(
+            "#extension GL_EXT_control_flow_attributes : enable\n[[unroll]] for (;;) {}"
+        ));
+        assert!(!has_error(
+            "#extension GL_EXT_subgroup_uniform_control_flow : enable\nvoid main() [[subgroup_uniform_control_flow]] {}"
+        ));
+    }
+
     #[test]
     /// Ensure that we can extract #(...) for glsl-lang-quote. This is not part of the spec so this
     /// explains why we need to re-examine tokens using Tokenizer::tokenize_single.
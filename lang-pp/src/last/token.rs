@@ -23,6 +23,8 @@ pub(super) fn token_from_syntax_kind(
         SyntaxKind::PP_EMPTY
         | SyntaxKind::PP_MOJ_IMPORT
         | SyntaxKind::PP_MOJ_IMPORT_PATH
+        | SyntaxKind::PP_INCLUDE
+        | SyntaxKind::PP_INCLUDE_PATH
         | SyntaxKind::PP_DEFINE
         | SyntaxKind::PP_DEFINE_ARGS
         | SyntaxKind::PP_DEFINE_ARG
@@ -49,7 +51,6 @@ pub(super) fn token_from_syntax_kind(
         | SyntaxKind::ERROR
         | SyntaxKind::ROOT
         | SyntaxKind::_LAST
-        | SyntaxKind::QUOTE_STRING
         | SyntaxKind::ANGLE_STRING
         | SyntaxKind::BACKSLASH
         | SyntaxKind::DEFINED
@@ -204,6 +205,9 @@ pub(super) fn token_from_syntax_kind(
         SyntaxKind::COMMENT => {
             return (COMMENT, None);
         }
+        SyntaxKind::QUOTE_STRING => {
+            return (Token::parse_quote_string(&value.text().to_string()), None);
+        }
     }
 
     // Either IDENT_KW or DIGITS, we need to examine the text to know more
@@ -32,8 +32,8 @@ enum State {
     MultiCommentStar,
     /// Any kind of horizontal whitespace
     Whitespace,
-    /// Inside a quote string
-    QuoteString,
+    /// Inside a quote string, possibly after a backslash
+    QuoteString { escaped: bool },
     /// Insite an angle string
     AngleString,
 }
@@ -162,7 +162,7 @@ impl<'i> Iterator for PreLexer<'i> {
                                     // Also clear the string flag, since it should've started with
                                     // < instead
                                     self.expect_angle_string = false;
-                                    State::QuoteString
+                                    State::QuoteString { escaped: false }
                                 }
                                 "<" if self.expect_angle_string => {
                                     // Clear the string flag
@@ -188,7 +188,7 @@ impl<'i> Iterator for PreLexer<'i> {
                             token: NewlineTokenKind::NEWLINE,
                             range,
                         }) => {
-                            // A newline, this completes a potential #moj_import
+                            // A newline, this completes a potential #include or #moj_import
                             self.expect_angle_string = false;
 
                             // A newline
@@ -434,10 +434,11 @@ impl<'i> Iterator for PreLexer<'i> {
                     }
                 }
 
-                State::QuoteString => {
+                State::QuoteString { escaped } => {
                     if let Some((token, text)) = self.peek_token() {
                         // Release text borrow
-                        let end_quote = text == "\"";
+                        let end_quote = !escaped && text == "\"";
+                        let backslash = !escaped && text == "\\";
 
                         // Always consume the token
                         self.next_token();
@@ -448,7 +449,7 @@ impl<'i> Iterator for PreLexer<'i> {
                         if end_quote {
                             return Some(TextToken::new(QUOTE_STRING, self.start));
                         } else {
-                            State::QuoteString
+                            State::QuoteString { escaped: backslash }
                         }
                     } else {
                         // No more tokens, bump an error
@@ -4,7 +4,7 @@ use lang_util::{SmolStr, TextRange};
 
 use crate::lexer;
 
-use super::{ErrorKind, ExpectAny, ParserRun, SyntaxKind::*};
+use super::{ErrorKind, ExpectAny, ParserRun, SyntaxKind, SyntaxKind::*};
 
 type InputToken = lexer::Token;
 
@@ -80,9 +80,14 @@ fn if_section_or_control_line(parser: &mut ParserRun) {
             let result = match name.as_ref() {
                 "moj_import" => {
                     parser.bump();
-                    pp_moj_import(parser);
+                    pp_include_path(parser, PP_MOJ_IMPORT_PATH);
                     Some(PP_MOJ_IMPORT)
                 }
+                "include" => {
+                    parser.bump();
+                    pp_include_path(parser, PP_INCLUDE_PATH);
+                    Some(PP_INCLUDE)
+                }
                 "define" => {
                     parser.bump();
                     pp_define(parser);
@@ -280,14 +285,14 @@ fn pp_rs_ident(
     Ok(())
 }
 
-fn pp_moj_import(parser: &mut ParserRun) {
+fn pp_include_path(parser: &mut ParserRun, kind: SyntaxKind) {
     // We're about to parse a path
     parser.input.set_expect_angle_string(true);
 
     parser.skip_trivia();
 
     // Consume include path
-    parser.start_node(PP_MOJ_IMPORT_PATH);
+    parser.start_node(kind);
     pp_tokens(parser);
     parser.finish_node();
 
@@ -132,6 +132,10 @@ pub enum SyntaxKind {
     PP_MOJ_IMPORT,
     /// #moj_import path
     PP_MOJ_IMPORT_PATH,
+    /// #include
+    PP_INCLUDE,
+    /// #include path
+    PP_INCLUDE_PATH,
     /// #define
     PP_DEFINE,
     PP_DEFINE_ARGS,
@@ -69,6 +69,36 @@ fn test_moj_import() {
     );
 }
 
+#[test]
+fn test_include() {
+    check(
+        parse("#include <x/a.glsl>\n"),
+        expect![[r##"
+            ROOT@0..20
+              PP_INCLUDE@0..20
+                HASH@0..1 "#"
+                IDENT_KW@1..8 "include"
+                WS@8..9 " "
+                PP_INCLUDE_PATH@9..19
+                  ANGLE_STRING@9..19 "<x/a.glsl>"
+                NEWLINE@19..20 "\n"
+        "##]],
+    );
+    check(
+        parse("#include \"y/b.glsl\"\n"),
+        expect![[r##"
+            ROOT@0..20
+              PP_INCLUDE@0..20
+                HASH@0..1 "#"
+                IDENT_KW@1..8 "include"
+                WS@8..9 " "
+                PP_INCLUDE_PATH@9..19
+                  QUOTE_STRING@9..19 "\"y/b.glsl\""
+                NEWLINE@19..20 "\n"
+        "##]],
+    );
+}
+
 #[test]
 fn test_define() {
     check(
@@ -1,34 +1,74 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use lang_util::{FileId, SmolStr};
 
 mod definition;
 use definition::Definition;
 
+pub mod emit;
+
 pub mod event;
 
 pub mod expand;
 
-mod expr;
+pub mod expr;
 
 pub mod fs;
 
+pub mod handlers;
+use handlers::{HandlerInput, HandlerOutput, Handlers};
+
 pub mod nodes;
-use nodes::{Define, DefineObject, Version};
+use nodes::{Define, DefineObject, Extension, Version};
+
+use event::{Warning, WarningId};
 
 use crate::{
     exts::Registry,
     processor::nodes::{ExtensionBehavior, ExtensionName},
 };
 
+pub mod partial;
+
 pub mod str;
 
+pub mod variants;
+
+/// Which flavor of `#include` directive is currently enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncludeMode {
+    /// `#include` is not enabled
+    None,
+    /// `#include` as specified by GL_ARB_shading_language_include
+    ArbInclude {
+        /// true if the extension was enabled with the `warn` behavior
+        warn: bool,
+    },
+    /// `#include` as specified by GL_GOOGLE_include_directive
+    GoogleInclude {
+        /// true if the extension was enabled with the `warn` behavior
+        warn: bool,
+    },
+}
+
 /// Current state of the preprocessor
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProcessorState {
     definitions: HashMap<SmolStr, Definition>,
     version: Version,
+    include_mode: IncludeMode,
     cpp_style_line: bool,
+    /// Enabled warnings
+    warnings: HashSet<WarningId>,
+    /// Warnings for the macros which have been defined but not used yet
+    unused_macros: HashMap<SmolStr, Warning>,
+    /// true if non-preprocessor tokens have been processed
+    seen_tokens: bool,
+    /// User-registered pragma and directive handlers
+    handlers: Rc<Handlers>,
 }
 
 impl ProcessorState {
@@ -60,9 +100,84 @@ impl ProcessorState {
         }
     }
 
+    /// Update the state according to an #extension directive
+    pub fn extension(&mut self, extension: &Extension) {
+        self.add_extension(&extension.name, extension.behavior);
+    }
+
+    fn add_extension(&mut self, name: &ExtensionName, behavior: ExtensionBehavior) {
+        let name = match name {
+            ExtensionName::All => {
+                // `all` can only be used to warn or disable
+                if !behavior.is_active() {
+                    self.include_mode = IncludeMode::None;
+                    self.cpp_style_line = false;
+                }
+
+                return;
+            }
+            ExtensionName::Specific(name) => name,
+        };
+
+        let warn = behavior == ExtensionBehavior::Warn;
+        if *name == ext_name!("GL_ARB_shading_language_include") {
+            self.set_include_mode(IncludeMode::ArbInclude { warn }, behavior);
+        } else if *name == ext_name!("GL_GOOGLE_include_directive") {
+            self.set_include_mode(IncludeMode::GoogleInclude { warn }, behavior);
+
+            // GL_GOOGLE_include_directive implies GL_GOOGLE_cpp_style_line_directive
+            if behavior.is_active() {
+                self.cpp_style_line = true;
+            }
+        } else if *name == ext_name!("GL_GOOGLE_cpp_style_line_directive") {
+            self.cpp_style_line = behavior.is_active();
+        }
+    }
+
+    fn with_extensions(mut self, extensions: &[(ExtensionName, ExtensionBehavior)]) -> Self {
+        for (name, behavior) in extensions {
+            self.add_extension(name, *behavior);
+        }
+
+        self
+    }
+
+    fn set_include_mode(&mut self, include_mode: IncludeMode, behavior: ExtensionBehavior) {
+        if behavior.is_active() {
+            self.include_mode = include_mode;
+        } else if std::mem::discriminant(&self.include_mode)
+            == std::mem::discriminant(&include_mode)
+        {
+            // Only disable the flavor of #include that was enabled
+            self.include_mode = IncludeMode::None;
+        }
+    }
+
+    /// Get the currently enabled flavor of `#include` directive
+    pub fn include_mode(&self) -> IncludeMode {
+        self.include_mode
+    }
+
     fn cpp_style_line(&self) -> bool {
         self.cpp_style_line
     }
+
+    /// Return true if the given warning is enabled
+    pub fn warning_enabled(&self, id: WarningId) -> bool {
+        self.warnings.contains(&id)
+    }
+
+    /// Record that a macro was expanded or tested
+    fn use_macro(&mut self, name: &str) {
+        self.unused_macros.remove(name);
+    }
+
+    /// Take the warnings for the macros which were defined but never used, ordered by position
+    fn take_unused_macros(&mut self) -> Vec<Warning> {
+        let mut warnings: Vec<_> = self.unused_macros.drain().map(|(_, w)| w).collect();
+        warnings.sort_by_key(|warning| (warning.current_file(), warning.pos().start()));
+        warnings
+    }
 }
 
 impl Default for ProcessorState {
@@ -78,6 +193,8 @@ pub struct ProcessorStateBuilder<'r> {
     es_profile: bool,
     extensions: Vec<(ExtensionName, ExtensionBehavior)>,
     definitions: Vec<Define>,
+    warnings: HashSet<WarningId>,
+    handlers: Handlers,
     registry: &'r Registry,
 }
 
@@ -98,6 +215,8 @@ impl<'r> ProcessorStateBuilder<'r> {
             es_profile: self.es_profile,
             extensions: self.extensions,
             definitions: self.definitions,
+            warnings: self.warnings,
+            handlers: self.handlers,
         }
     }
 
@@ -133,6 +252,53 @@ impl<'r> ProcessorStateBuilder<'r> {
         self
     }
 
+    /// Enable or disable all the preprocessor warnings
+    pub fn warnings(self, enabled: bool) -> Self {
+        Self {
+            warnings: if enabled {
+                WarningId::ALL.into_iter().collect()
+            } else {
+                Default::default()
+            },
+            ..self
+        }
+    }
+
+    /// Enable or disable a single preprocessor warning
+    pub fn warning(mut self, id: WarningId, enabled: bool) -> Self {
+        if enabled {
+            self.warnings.insert(id);
+        } else {
+            self.warnings.remove(&id);
+        }
+
+        self
+    }
+
+    /// Register a handler for `#pragma name ...` directives
+    ///
+    /// See the [handlers] module for details.
+    pub fn pragma_handler(
+        mut self,
+        name: impl Into<SmolStr>,
+        handler: impl Fn(&HandlerInput) -> HandlerOutput + 'static,
+    ) -> Self {
+        self.handlers.add_pragma(name.into(), Rc::new(handler));
+        self
+    }
+
+    /// Register a handler for custom `#name ...` directives
+    ///
+    /// See the [handlers] module for details.
+    pub fn directive_handler(
+        mut self,
+        name: impl Into<SmolStr>,
+        handler: impl Fn(&HandlerInput) -> HandlerOutput + 'static,
+    ) -> Self {
+        self.handlers.add_directive(name.into(), Rc::new(handler));
+        self
+    }
+
     pub fn finish(self) -> ProcessorState {
         let one = DefineObject::one();
 
@@ -165,8 +331,14 @@ impl<'r> ProcessorStateBuilder<'r> {
                 .map(|definition| (definition.name().into(), definition))
                 .collect(),
             version: Version::default(),
+            include_mode: IncludeMode::None,
             cpp_style_line: false,
+            warnings: self.warnings,
+            unused_macros: Default::default(),
+            seen_tokens: false,
+            handlers: Rc::new(self.handlers),
         }
+        .with_extensions(&self.extensions)
     }
 }
 
@@ -184,6 +356,8 @@ impl Default for ProcessorStateBuilder<'static> {
             es_profile: false,
             extensions: Default::default(),
             definitions: Default::default(),
+            warnings: Default::default(),
+            handlers: Default::default(),
             registry: &*crate::exts::DEFAULT_REGISTRY,
         }
     }
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     rc::Rc,
+    sync::Arc,
 };
 
 use itertools::Itertools;
@@ -17,7 +18,7 @@ use crate::{
 };
 
 use super::{
-    event::{Event, OutputToken, ProcessingError, ProcessingErrorKind, TokenLike},
+    event::{Event, MacroExpansion, OutputToken, ProcessingError, ProcessingErrorKind, TokenLike},
     expand::ExpandLocation,
     nodes::{Define, DefineFunction, DefineKind, DefineObject},
     ProcessorState,
@@ -48,6 +49,26 @@ impl Definition {
         }
     }
 
+    /// Get the body of this definition, if it was defined by a `#define` directive
+    fn body(&self) -> Option<&SyntaxNode> {
+        match self {
+            Definition::Regular(d, _) => Some(match d.kind() {
+                DefineKind::Object(object) => object.body(),
+                DefineKind::Function(function) => function.body(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Get the span of the `#define` directive for this definition
+    ///
+    /// This is `None` for built-in macros, and macros which were not defined in a source.
+    pub fn span(&self) -> Option<NodeSpan> {
+        self.body()
+            .and_then(SyntaxNode::parent)
+            .map(|node| NodeSpan::new(self.file_id(), node.text_range()))
+    }
+
     pub fn protected(&self) -> bool {
         match self {
             Definition::Regular(d, _) => d.protected(),
@@ -427,13 +448,9 @@ impl Definition {
                         false,
                     )
                 } else {
-                    // Replace the range of substitued tokens with the macro call range
-                    OutputToken::new(
-                        token.kind(),
-                        <OutputToken as TokenLike>::text(&token),
-                        entire_range,
-                    )
-                    .into()
+                    // The range of substituted tokens is replaced with the macro call range after
+                    // rescanning, so nested invocations can be located in the macro body
+                    token.into()
                 }
             })
             .collect()
@@ -541,6 +558,8 @@ pub struct MacroInvocation<'d> {
     definition: &'d Definition,
     tokens: MacroCall,
     range: NodeSpan,
+    /// Chain of invocations leading to this one, including this one
+    expansions: Arc<[MacroExpansion]>,
 }
 
 enum MacroCall {
@@ -568,7 +587,7 @@ impl<'d> MacroInvocation<'d> {
         first_token: Q,
         mut iterator: I,
         location: &ExpandLocation,
-        text_range: Option<NodeSpan>,
+        parent: Option<(NodeSpan, &Definition)>,
         token_fn: impl Fn(Q) -> P,
     ) -> Result<Option<(Self, I)>, ProcessingError>
     where
@@ -576,6 +595,7 @@ impl<'d> MacroInvocation<'d> {
         P: TokenLike,
     {
         let first_token = token_fn(first_token);
+        let text_range = parent.map(|(range, _)| range);
 
         let (tokens, computed_range) = if definition.object_like() {
             (MacroCall::Object, first_token.text_range())
@@ -593,7 +613,7 @@ impl<'d> MacroInvocation<'d> {
                         NodeOrToken::Node(node) => {
                             // Node, i.e. a processing directive. unexpected here
                             return Err(ProcessingError::builder()
-                                .pos(first_token.text_range())
+                                .pos(text_range.unwrap_or(first_token.text_range()))
                                 .resolve_file(location)
                                 .finish(ProcessingErrorKind::UnexpectedDirective {
                                     ident: definition.name().into(),
@@ -680,7 +700,7 @@ impl<'d> MacroInvocation<'d> {
 
             if args.len() != definition.arg_count() {
                 return Err(ProcessingError::builder()
-                    .pos(first_token.text_range())
+                    .pos(text_range.unwrap_or(first_token.text_range()))
                     .resolve_file(location)
                     .finish(ProcessingErrorKind::MismatchedArguments {
                         ident: definition.name().into(),
@@ -703,11 +723,39 @@ impl<'d> MacroInvocation<'d> {
             )
         };
 
+        // Nested invocations are located by the macro name, since their arguments may come from
+        // different sources
+        let invocation = if parent.is_some() {
+            first_token.text_range()
+        } else {
+            computed_range
+        };
+
+        let expansion = MacroExpansion {
+            name: definition.name().into(),
+            invocation,
+            line: invocation_line(
+                invocation,
+                location,
+                parent.map(|(_, definition)| definition),
+                first_token.expansions(),
+            ),
+            definition: definition.span(),
+        };
+
+        let expansions = first_token
+            .expansions()
+            .into_iter()
+            .flat_map(|expansions| expansions.iter().cloned())
+            .chain(std::iter::once(expansion))
+            .collect();
+
         Ok(Some((
             Self {
                 definition,
                 tokens,
                 range: text_range.unwrap_or(computed_range),
+                expansions,
             },
             iterator,
         )))
@@ -727,7 +775,7 @@ impl<'d> MacroInvocation<'d> {
         tokens: Vec<impl TokenLike>,
         location: &ExpandLocation,
         subs_stack: &mut HashSet<SmolStr>,
-        range: Option<NodeSpan>,
+        parent: Option<(NodeSpan, &Definition)>,
     ) -> Vec<Event> {
         // Macros are recursive, so we need to scan again for further substitutions
         let mut result = Vec::with_capacity(tokens.len());
@@ -760,7 +808,7 @@ impl<'d> MacroInvocation<'d> {
                             token.clone(),
                             iterator.clone(),
                             location,
-                            range,
+                            parent,
                             |token| token,
                         ) {
                             Ok(Some((invocation, new_iterator))) => {
@@ -827,10 +875,19 @@ impl<'d> MacroInvocation<'d> {
                 .substitute_function(&function, self.range, location),
         };
 
+        // Record the invocation chain on the substituted tokens
+        let events = events.into_iter().map(|event| match event {
+            Event::Token { token, masked } => Event::Token {
+                token: token.with_expansions(self.expansions.clone()),
+                masked,
+            },
+            other => other,
+        });
+
         // Disable recursion for the current name
         subs_stack.insert(self.definition.name().into());
 
-        let range = Some(self.range);
+        let parent = Some((self.range, self.definition));
 
         // We use itertools group_by to insert the error events in the right locations in the
         // output sequence. This means we split the token sequence at errors and this wouldn't
@@ -852,12 +909,20 @@ impl<'d> MacroInvocation<'d> {
                             .collect::<Vec<_>>(),
                         location,
                         subs_stack,
-                        range,
+                        parent,
                     )
                 } else {
                     events.collect()
                 }
             })
+            .map(|event| match event {
+                // Replace the range of substituted tokens with the macro call range
+                Event::Token { token, masked } => Event::Token {
+                    token: token.with_text_range(self.range),
+                    masked,
+                },
+                other => other,
+            })
             .collect();
 
         subs_stack.remove(self.definition.name());
@@ -865,3 +930,42 @@ impl<'d> MacroInvocation<'d> {
         result
     }
 }
+
+/// Find the line of a macro invocation
+///
+/// # Parameters
+///
+/// * `invocation`: span of the invocation
+/// * `location`: location in the current file
+/// * `parent`: definition of the macro whose body contains the invocation, if any
+/// * `expansions`: invocations leading to this one
+fn invocation_line(
+    invocation: NodeSpan,
+    location: &ExpandLocation,
+    parent: Option<&Definition>,
+    expansions: Option<&Arc<[MacroExpansion]>>,
+) -> u32 {
+    if invocation.source_id() == location.current_file() {
+        return location.offset_to_line_and_col(invocation.start().offset).0;
+    }
+
+    // The invocation is in the body of a macro defined in another file: count the lines in the
+    // source of the definition
+    if let Some(root) = parent
+        .filter(|definition| definition.file_id() == invocation.source_id())
+        .and_then(Definition::body)
+        .and_then(|body| body.ancestors().last())
+    {
+        let mut line = 0;
+        root.text()
+            .slice(..invocation.start().offset)
+            .for_each_chunk(|chunk| line += chunk.matches('\n').count());
+        return line as u32;
+    }
+
+    // Otherwise, fall back to the line of the enclosing invocation
+    expansions
+        .and_then(|expansions| expansions.last())
+        .map(|expansion| expansion.line)
+        .unwrap_or_default()
+}
@@ -0,0 +1,330 @@
+//! Preprocessed text output
+
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+use lang_util::FileId;
+
+use crate::parser::SyntaxKind::*;
+
+use super::{
+    event::{DirectiveKind, Event},
+    expand::{ExpandLocation, LocationString},
+};
+
+/// Largest number of lines skipped using blank lines instead of a `#line` directive
+const MAX_BLANK_LINES: u32 = 8;
+
+/// How file transitions are marked in the preprocessed output
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineMarkers {
+    /// Do not insert `#line` directives. Skipped lines are replaced with blank lines.
+    None,
+    /// Insert `#line N fileno` directives
+    #[default]
+    Number,
+    /// Insert `#line N "file"` directives, which require GL_GOOGLE_cpp_style_line_directive to
+    /// be enabled by the consumer of the output. Source blocks, which have no path, use their
+    /// file number instead.
+    Path,
+}
+
+/// Writer for the fully expanded text of a preprocessed source
+///
+/// The line structure of the input is kept where possible: inactive blocks, directives and
+/// removed comments are replaced with blank lines, and `#line` directives are inserted when the
+/// output enters or leaves an included file. The `#version`, `#extension`, `#pragma` and `#line`
+/// directives are kept in the output.
+///
+/// Each event must be written with the location of the iterator that produced it, so the
+/// emitter can find the lines of the tokens.
+#[derive(Debug)]
+pub struct Emitter<W: fmt::Write> {
+    output: W,
+    preserve_comments: bool,
+    line_markers: LineMarkers,
+    /// Paths of the files entered so far
+    paths: HashMap<FileId, PathBuf>,
+    /// File and raw line the output is currently at
+    current: Option<(FileId, u32)>,
+    at_line_start: bool,
+}
+
+impl<W: fmt::Write> Emitter<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            preserve_comments: false,
+            line_markers: LineMarkers::default(),
+            paths: HashMap::new(),
+            current: None,
+            at_line_start: true,
+        }
+    }
+
+    /// Set whether comments are written to the output. They are replaced with a space by default.
+    pub fn preserve_comments(self, preserve_comments: bool) -> Self {
+        Self {
+            preserve_comments,
+            ..self
+        }
+    }
+
+    /// Set how file transitions are marked in the output
+    pub fn line_markers(self, line_markers: LineMarkers) -> Self {
+        Self {
+            line_markers,
+            ..self
+        }
+    }
+
+    /// Write the output for an event
+    ///
+    /// # Parameters
+    ///
+    /// * `event`: event to write
+    /// * `location`: location of the iterator which returned this event
+    pub fn write_event(&mut self, event: &Event, location: &ExpandLocation) -> fmt::Result {
+        match event {
+            Event::EnterFile { file_id, path, .. } => {
+                if !path.as_os_str().is_empty() {
+                    self.paths.insert(*file_id, path.clone());
+                }
+
+                Ok(())
+            }
+            Event::Token {
+                token,
+                masked: false,
+            } => {
+                let range = token.text_range();
+                let anchored = self.sync(location, range.source_id(), range.start().offset)?;
+
+                match token.kind() {
+                    NEWLINE if anchored => self.write_lines("\n"),
+                    NEWLINE => self.write_str(" "),
+                    COMMENT if self.preserve_comments && anchored => self.write_lines(token.text()),
+                    COMMENT => self.write_str(" "),
+                    _ if anchored => self.write_lines(token.text()),
+                    _ => self.write_str(token.text()),
+                }
+            }
+            Event::Directive {
+                directive,
+                masked: false,
+            } => match directive.kind() {
+                DirectiveKind::Version(_)
+                | DirectiveKind::Extension(_)
+                | DirectiveKind::Pragma(_)
+                | DirectiveKind::Line(_) => {
+                    let range = directive.text_range();
+                    self.sync(location, range.source_id(), range.start().offset)?;
+                    self.write_lines(&directive.to_string())
+                }
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Terminate the output with a newline, and return it
+    pub fn finish(mut self) -> Result<W, fmt::Error> {
+        if !self.at_line_start {
+            self.write_str("\n")?;
+        }
+
+        Ok(self.output)
+    }
+
+    /// Move the output to the line of a token, and return `true` if the token is at or after the
+    /// current line of its file
+    ///
+    /// Tokens from macro expansions have the location of their definition, so they are written
+    /// as-is on the current line.
+    fn sync(
+        &mut self,
+        location: &ExpandLocation,
+        source_id: FileId,
+        offset: lang_util::TextSize,
+    ) -> Result<bool, fmt::Error> {
+        let file_id = location.current_file();
+        if source_id != file_id {
+            return Ok(false);
+        }
+
+        let line = location.offset_to_raw_line_and_col(offset).0;
+
+        match self.current {
+            None => {
+                // Start of the output, do not insert a #line before a #version directive
+                self.current = Some((file_id, 0));
+                for _ in 0..line {
+                    self.write_lines("\n")?;
+                }
+            }
+            Some((current_file, _)) if current_file != file_id => {
+                if self.line_markers == LineMarkers::None {
+                    self.current = Some((file_id, line));
+                } else {
+                    self.write_line_marker(location, line)?;
+                }
+            }
+            Some((_, current_line)) if line < current_line => {
+                return Ok(false);
+            }
+            Some(_) => {
+                self.skip_lines(location, line)?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Move the output forward to the given line of the current file
+    fn skip_lines(&mut self, location: &ExpandLocation, line: u32) -> fmt::Result {
+        let (_, current_line) = self.current.unwrap();
+
+        if line - current_line > MAX_BLANK_LINES && self.line_markers != LineMarkers::None {
+            return self.write_line_marker(location, line);
+        }
+
+        for _ in current_line..line {
+            self.write_lines("\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a `#line` directive so the next line is the given line of the current file
+    fn write_line_marker(&mut self, location: &ExpandLocation, line: u32) -> fmt::Result {
+        if !self.at_line_start {
+            self.write_str("\n")?;
+        }
+
+        let file_id = location.current_file();
+        let number = location.line_to_line_number(line) + 1;
+
+        match location.string() {
+            LocationString::String(path) => writeln!(self.output, "#line {} \"{}\"", number, path)?,
+            LocationString::Number(file_number) => match self.paths.get(&file_id) {
+                Some(path) if self.line_markers == LineMarkers::Path => {
+                    writeln!(self.output, "#line {} \"{}\"", number, path.display())?
+                }
+                _ => writeln!(self.output, "#line {} {}", number, file_number)?,
+            },
+        }
+
+        self.current = Some((file_id, line));
+        self.at_line_start = true;
+        Ok(())
+    }
+
+    /// Write text from the current file, counting its newlines
+    fn write_lines(&mut self, s: &str) -> fmt::Result {
+        let newlines = s.matches('\n').count() as u32;
+        if let Some((_, line)) = &mut self.current {
+            *line += newlines;
+        }
+
+        self.write_str(s)
+    }
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if let Some(last) = s.chars().last() {
+            self.at_line_start = last == '\n';
+        }
+
+        self.output.write_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{
+        last::LocatedIterator,
+        processor::{
+            fs::{MemoryFs, MemoryProcessor},
+            str::process,
+            ProcessorState,
+        },
+    };
+
+    use super::*;
+
+    fn emit_events<E: fmt::Debug>(
+        mut iter: impl Iterator<Item = Result<Event, E>> + LocatedIterator,
+        emitter: Emitter<String>,
+    ) -> String {
+        let mut emitter = emitter;
+        while let Some(event) = iter.next() {
+            emitter
+                .write_event(&event.unwrap(), iter.location())
+                .unwrap();
+        }
+
+        emitter.finish().unwrap()
+    }
+
+    fn emit_str(input: &str, preserve_comments: bool) -> String {
+        emit_events(
+            process(input, ProcessorState::default()),
+            Emitter::new(String::new()).preserve_comments(preserve_comments),
+        )
+    }
+
+    #[test]
+    fn test_emit_macros() {
+        let input = r#"#version 460 core
+#define A(x) (x + 1) /* add one */
+#if 0
+float skipped;
+#endif
+float a = A(2); // expanded
+"#;
+
+        assert_eq!(
+            emit_str(input, false),
+            "#version 460 core\n\n\n\n\nfloat a = (2 + 1);  \n"
+        );
+        assert_eq!(
+            emit_str(input, true),
+            "#version 460 core\n\n\n\n\nfloat a = (2 + 1); // expanded\n"
+        );
+    }
+
+    #[test]
+    fn test_emit_line_markers() {
+        let fs: MemoryFs = [
+            (
+                "/main.glsl",
+                "#version 460\n#extension GL_GOOGLE_include_directive : require\n#include \"lib.glsl\"\nfloat b;\n",
+            ),
+            ("/lib.glsl", "float a;\n"),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut processor = MemoryProcessor::new_with_fs(fs);
+        let mut emit = |emitter| {
+            emit_events(
+                processor
+                    .parse(Path::new("/main.glsl"))
+                    .unwrap()
+                    .process(ProcessorState::default()),
+                emitter,
+            )
+        };
+
+        // File numbers are used by default, since paths require an extension
+        assert_eq!(
+            emit(Emitter::new(String::new())),
+            "#version 460\n#extension GL_GOOGLE_include_directive : require\n#line 1 1\nfloat a;\n#line 4 0\nfloat b;\n"
+        );
+
+        assert_eq!(
+            emit(Emitter::new(String::new()).line_markers(LineMarkers::Path)),
+            "#version 460\n#extension GL_GOOGLE_include_directive : require\n#line 1 \"/lib.glsl\"\nfloat a;\n#line 4 \"/main.glsl\"\nfloat b;\n"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use derive_more::From;
 
@@ -19,6 +19,8 @@ use super::{
 mod send;
 pub use send::*;
 
+pub use lang_util::position::MacroExpansion;
+
 pub type ProcessingError = lang_util::located::Located<ProcessingErrorKind>;
 
 #[derive(Debug, Clone, PartialEq, Eq, From)]
@@ -26,6 +28,7 @@ pub enum ProcessingErrorKind {
     ExtraEndIf,
     ExtraElse,
     ExtraElif,
+    MissingEndIf,
     ProtectedDefine {
         ident: SmolStr,
         is_undef: bool,
@@ -48,6 +51,11 @@ pub enum ProcessingErrorKind {
     ImportNotFound {
         path: ParsedPath,
     },
+    IncludeNotSupported,
+    #[from(ignore)]
+    IncludeNotFound {
+        path: ParsedPath,
+    },
     InvalidTokenPaste {
         token: Option<SmolStr>,
     },
@@ -66,8 +74,13 @@ pub enum ProcessingErrorKind {
     DirectiveUndef(nodes::IfDefError),
     DirectiveError(nodes::ErrorError),
     DirectiveInclude(nodes::MojImportError),
+    DirectiveGlslInclude(nodes::IncludeError),
     DirectiveLine(nodes::LineError),
     DirectivePragma(nodes::PragmaError),
+    HandlerError {
+        directive: SmolStr,
+        message: String,
+    },
 }
 
 impl std::error::Error for ProcessingErrorKind {}
@@ -84,6 +97,9 @@ impl std::fmt::Display for ProcessingErrorKind {
             ProcessingErrorKind::ExtraElif => {
                 write!(f, "unmatched #elif")
             }
+            ProcessingErrorKind::MissingEndIf => {
+                write!(f, "missing #endif")
+            }
             ProcessingErrorKind::ProtectedDefine { ident, is_undef } => {
                 let directive = if *is_undef { "undef" } else { "define" };
 
@@ -120,6 +136,12 @@ impl std::fmt::Display for ProcessingErrorKind {
             ProcessingErrorKind::ImportNotFound { path } => {
                 write!(f, "'#moj_import' : could not find file for {}", path)
             }
+            ProcessingErrorKind::IncludeNotSupported => {
+                write!(f, "'#include' : required extension not requested: GL_GOOGLE_include_directive or GL_ARB_shading_language_include")
+            }
+            ProcessingErrorKind::IncludeNotFound { path } => {
+                write!(f, "'#include' : could not find file for {}", path)
+            }
             ProcessingErrorKind::InvalidTokenPaste { token } => {
                 if let Some(token) = token {
                     if token.ends_with(" ##") {
@@ -170,12 +192,18 @@ impl std::fmt::Display for ProcessingErrorKind {
             ProcessingErrorKind::DirectiveInclude(inner) => {
                 write!(f, "'#moj_import' : {}", inner)
             }
+            ProcessingErrorKind::DirectiveGlslInclude(inner) => {
+                write!(f, "'#include' : {}", inner)
+            }
             ProcessingErrorKind::DirectiveLine(inner) => {
                 write!(f, "'#line' : {}", inner)
             }
             ProcessingErrorKind::DirectivePragma(inner) => {
                 write!(f, "'#pragma' : {}", inner)
             }
+            ProcessingErrorKind::HandlerError { directive, message } => {
+                write!(f, "'#{}' : {}", directive, message)
+            }
         }
     }
 }
@@ -197,6 +225,12 @@ pub enum ErrorKind {
         raw_line: u32,
         pos: NodeSpan,
     },
+    ExtRequired {
+        extension: ExtNameAtom,
+        name: &'static str,
+        raw_line: u32,
+        pos: NodeSpan,
+    },
 }
 
 impl std::error::Error for ErrorKind {}
@@ -210,6 +244,15 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::UnsupportedExt { extension, .. } => {
                 write!(f, "extension not supported: {}", extension)
             }
+            ErrorKind::ExtRequired {
+                extension, name, ..
+            } => {
+                write!(
+                    f,
+                    "'{}' : required extension not requested: {}",
+                    name, extension
+                )
+            }
         }
     }
 }
@@ -228,6 +271,21 @@ impl ErrorKind {
         }
     }
 
+    pub fn ext_required(
+        extension: ExtNameAtom,
+        name: &'static str,
+        pos: NodeSpan,
+        location: &ExpandLocation,
+    ) -> Self {
+        let raw_line = location.offset_to_raw_line_and_col(pos.start().offset).0;
+        Self::ExtRequired {
+            extension,
+            name,
+            raw_line,
+            pos,
+        }
+    }
+
     pub fn warn_ext_use(
         extension: ExtNameAtom,
         name: Option<TypeNameAtom>,
@@ -244,6 +302,138 @@ impl ErrorKind {
     }
 }
 
+pub type Warning = lang_util::located::Located<WarningKind>;
+
+/// Identifier of a preprocessor warning, used to enable or silence it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WarningId {
+    /// A macro is redefined with a different replacement list
+    MacroRedefinition,
+    /// `#undef` of a name which is not a defined macro
+    UndefUndefined,
+    /// An identifier which is not a defined macro is evaluated as 0 in `#if` or `#elif`
+    UndefinedIdentifier,
+    /// A macro is defined but never expanded nor tested
+    UnusedMacro,
+    /// `#extension` occurring after non-preprocessor tokens
+    ExtensionAfterTokens,
+    /// A macro name is reserved for use by the implementation
+    ReservedName,
+}
+
+impl WarningId {
+    /// All the preprocessor warnings
+    pub const ALL: [WarningId; 6] = [
+        WarningId::MacroRedefinition,
+        WarningId::UndefUndefined,
+        WarningId::UndefinedIdentifier,
+        WarningId::UnusedMacro,
+        WarningId::ExtensionAfterTokens,
+        WarningId::ReservedName,
+    ];
+
+    /// Name of the warning, as accepted by [FromStr](std::str::FromStr)
+    pub fn name(&self) -> &'static str {
+        match self {
+            WarningId::MacroRedefinition => "macro-redefinition",
+            WarningId::UndefUndefined => "undef-undefined",
+            WarningId::UndefinedIdentifier => "undefined-identifier",
+            WarningId::UnusedMacro => "unused-macro",
+            WarningId::ExtensionAfterTokens => "extension-after-tokens",
+            WarningId::ReservedName => "reserved-name",
+        }
+    }
+}
+
+impl std::fmt::Display for WarningId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for WarningId {
+    type Err = UnknownWarningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|id| id.name() == s)
+            .copied()
+            .ok_or_else(|| UnknownWarningError { name: s.into() })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown preprocessor warning: {name}")]
+pub struct UnknownWarningError {
+    pub name: SmolStr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    MacroRedefinition { ident: SmolStr },
+    UndefUndefined { ident: SmolStr },
+    UndefinedIdentifier { ident: SmolStr },
+    UnusedMacro { ident: SmolStr },
+    ExtensionAfterTokens { extension: ExtensionName },
+    ReservedName { ident: SmolStr },
+}
+
+impl WarningKind {
+    /// Get the identifier of this warning
+    pub fn id(&self) -> WarningId {
+        match self {
+            WarningKind::MacroRedefinition { .. } => WarningId::MacroRedefinition,
+            WarningKind::UndefUndefined { .. } => WarningId::UndefUndefined,
+            WarningKind::UndefinedIdentifier { .. } => WarningId::UndefinedIdentifier,
+            WarningKind::UnusedMacro { .. } => WarningId::UnusedMacro,
+            WarningKind::ExtensionAfterTokens { .. } => WarningId::ExtensionAfterTokens,
+            WarningKind::ReservedName { .. } => WarningId::ReservedName,
+        }
+    }
+}
+
+impl std::fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WarningKind::MacroRedefinition { ident } => {
+                write!(
+                    f,
+                    "'#define' : Macro redefined; different substitutions: {}",
+                    ident
+                )
+            }
+            WarningKind::UndefUndefined { ident } => {
+                write!(f, "'#undef' : name is not a defined macro: {}", ident)
+            }
+            WarningKind::UndefinedIdentifier { ident } => {
+                write!(
+                    f,
+                    "'preprocessor evaluation' : undefined identifier evaluated as 0: {}",
+                    ident
+                )
+            }
+            WarningKind::UnusedMacro { ident } => {
+                write!(f, "'#define' : macro is never used: {}", ident)
+            }
+            WarningKind::ExtensionAfterTokens { extension } => {
+                write!(
+                    f,
+                    "'#extension' : extension directive after non-preprocessor tokens: {}",
+                    extension
+                )
+            }
+            WarningKind::ReservedName { ident } => {
+                write!(
+                    f,
+                    "'#define' : names containing consecutive underscores are reserved: {}",
+                    ident
+                )
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, From)]
 pub enum DirectiveKind {
     Empty(nodes::Empty),
@@ -259,15 +449,22 @@ pub enum DirectiveKind {
     Undef(nodes::Undef),
     Error(nodes::Error),
     MojImport(nodes::MojImport),
+    Include(nodes::Include),
     Line(nodes::Line),
     Pragma(nodes::Pragma),
     Invalid(nodes::Invalid),
+    Custom(nodes::Custom),
 }
 
 pub trait TokenLike: Clone {
     fn kind(&self) -> SyntaxKind;
     fn text(&self) -> TokenText;
     fn text_range(&self) -> NodeSpan;
+
+    /// Get the macro invocations this token was expanded from, outermost first
+    fn expansions(&self) -> Option<&Arc<[MacroExpansion]>> {
+        None
+    }
 }
 
 impl TokenLike for (parser::SyntaxToken, FileId) {
@@ -303,6 +500,7 @@ pub struct OutputToken {
     kind: SyntaxKind,
     text: SmolStr,
     source_range: NodeSpan,
+    expansions: Option<Arc<[MacroExpansion]>>,
 }
 
 impl OutputToken {
@@ -311,6 +509,7 @@ impl OutputToken {
             kind,
             text: text.into(),
             source_range,
+            expansions: None,
         }
     }
 
@@ -319,11 +518,29 @@ impl OutputToken {
             kind: SyntaxKind::ERROR,
             text: SmolStr::default(),
             source_range,
+            expansions: None,
         }
     }
 
     pub fn from_token(value: &impl TokenLike) -> Self {
-        Self::new(value.kind(), value.text(), value.text_range())
+        Self {
+            expansions: value.expansions().cloned(),
+            ..Self::new(value.kind(), value.text(), value.text_range())
+        }
+    }
+
+    pub(crate) fn with_expansions(self, expansions: Arc<[MacroExpansion]>) -> Self {
+        Self {
+            expansions: Some(expansions),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_text_range(self, source_range: NodeSpan) -> Self {
+        Self {
+            source_range,
+            ..self
+        }
     }
 
     pub fn kind(&self) -> SyntaxKind {
@@ -334,9 +551,21 @@ impl OutputToken {
         self.text.as_str()
     }
 
+    /// Get the range of this token in its source
+    ///
+    /// For tokens produced by macro expansions, this is the range of the outermost macro
+    /// invocation. See [`OutputToken::expansions`] for the complete chain of invocations.
     pub fn text_range(&self) -> NodeSpan {
         self.source_range
     }
+
+    /// Get the macro invocations this token was expanded from, outermost first
+    ///
+    /// The last invocation is the one whose body or arguments contain this token. This is empty
+    /// for tokens which were not produced by a macro expansion.
+    pub fn expansions(&self) -> &[MacroExpansion] {
+        self.expansions.as_deref().unwrap_or(&[])
+    }
 }
 
 impl From<(parser::SyntaxToken, FileId)> for OutputToken {
@@ -364,6 +593,10 @@ impl TokenLike for OutputToken {
     fn text_range(&self) -> NodeSpan {
         self.source_range
     }
+
+    fn expansions(&self) -> Option<&Arc<[MacroExpansion]>> {
+        self.expansions.as_ref()
+    }
 }
 
 impl std::fmt::Debug for OutputToken {
@@ -438,6 +671,9 @@ pub enum Event {
         directive: EventDirective,
         masked: bool,
     },
+    Warning {
+        warning: Warning,
+    },
 }
 
 impl Event {
@@ -520,6 +756,19 @@ impl Event {
         }
     }
 
+    pub fn warning(
+        kind: WarningKind,
+        pos: impl Into<TextRange>,
+        location: &ExpandLocation,
+    ) -> Self {
+        Self::Warning {
+            warning: Warning::builder()
+                .pos(pos)
+                .resolve_file(location)
+                .finish(kind),
+        }
+    }
+
     pub fn map_error<T: Into<ErrorKind>>(e: lang_util::located::Located<T>, masked: bool) -> Self {
         Self::Error {
             error: e.map(Into::into),
@@ -4,7 +4,7 @@ use rowan::TextRange;
 
 use lang_util::FileId;
 
-use super::{parser::SyntaxNode, Error, Event, OutputToken};
+use super::{parser::SyntaxNode, Error, Event, OutputToken, Warning};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SendNode {
@@ -63,6 +63,9 @@ pub enum SendEvent {
         masked: bool,
         errors: Vec<Error>,
     },
+    Warning {
+        warning: Warning,
+    },
 }
 
 impl From<Event> for SendEvent {
@@ -84,6 +87,7 @@ impl From<Event> for SendEvent {
                 masked,
                 errors: directive.errors,
             },
+            Event::Warning { warning } => Self::Warning { warning },
         }
     }
 }
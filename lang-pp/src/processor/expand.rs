@@ -6,27 +6,30 @@ use std::{
 
 use arrayvec::ArrayVec;
 use derive_more::From;
-use rowan::{NodeOrToken, SyntaxElementChildren, TextSize};
+use rowan::{NodeOrToken, SyntaxElementChildren, TextRange, TextSize};
 
 use lang_util::{
     located::{HasFileNumber, Resolver},
-    FileId,
+    position::NodeSpan,
+    FileId, SmolStr,
 };
 
 use crate::{
     parser::{self, Ast, PreprocessorLang, SyntaxKind::*, SyntaxNode, SyntaxToken},
     types::path::ParsedPath,
-    util::{LineMap, Unescaped},
+    util::{LineMap, TokenText, Unescaped},
 };
 
 use super::{
     definition::{Definition, MacroInvocation},
-    event::{Event, ProcessingErrorKind},
+    event::{Event, OutputToken, ProcessingErrorKind, TokenLike, Warning, WarningId, WarningKind},
+    handlers::{self, Handler, HandlerData, HandlerInput},
     nodes::{
-        Define, Directive, DirectiveResult, Elif, Else, Empty, EndIf, Error as ErrorDirective,
-        Extension, If, IfDef, IfNDef, Invalid, Line, MojImport, ParsedLine, Pragma, Undef, Version,
+        Custom, Define, Directive, DirectiveResult, Elif, Else, Empty, EndIf,
+        Error as ErrorDirective, Extension, If, IfDef, IfIdents, IfNDef, Include, Invalid, Line,
+        MojImport, ParsedLine, ParsedPragma, Pragma, Undef, Version,
     },
-    ProcessorState,
+    IncludeMode, ProcessorState,
 };
 
 mod if_stack;
@@ -80,6 +83,13 @@ impl ExpandLocation {
         }
     }
 
+    pub(crate) fn with_line_map(current_file: FileId, line_map: LineMap) -> Self {
+        Self {
+            line_map,
+            ..Self::new(current_file)
+        }
+    }
+
     pub fn current_file(&self) -> FileId {
         self.current_file
     }
@@ -147,6 +157,8 @@ pub(crate) struct ExpandOne {
     if_stack: IfStack,
     location: ExpandLocation,
     state: ExpandState,
+    /// Events to return before resuming the iteration
+    pending_events: VecDeque<Event>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -202,6 +214,7 @@ impl ExpandOne {
             if_stack: IfStack::new(),
             location: ExpandLocation::new(file_id),
             state: ExpandState::Init { ast, current_state },
+            pending_events: VecDeque::new(),
         }
     }
 
@@ -237,6 +250,13 @@ impl ExpandOne {
         &self.location
     }
 
+    fn warn(&mut self, current_state: &ProcessorState, kind: WarningKind, pos: TextRange) {
+        if current_state.warning_enabled(kind.id()) {
+            self.pending_events
+                .push_back(Event::warning(kind, pos, &self.location));
+        }
+    }
+
     fn handle_node(
         &mut self,
         current_state: &mut ProcessorState,
@@ -270,7 +290,23 @@ impl ExpandOne {
                     (self.location.current_file(), node).try_into();
 
                 match directive {
-                    Ok(directive) => Event::directive(directive, !active),
+                    Ok(directive) => {
+                        if active {
+                            if current_state.seen_tokens {
+                                self.warn(
+                                    current_state,
+                                    WarningKind::ExtensionAfterTokens {
+                                        extension: directive.name.clone(),
+                                    },
+                                    directive.node().text_range(),
+                                );
+                            }
+
+                            current_state.extension(&directive);
+                        }
+
+                        Event::directive(directive, !active)
+                    }
                     Err(error) => Event::directive_error(error, &self.location, !active),
                 }
             }
@@ -281,6 +317,9 @@ impl ExpandOne {
 
                 match directive {
                     Ok(define) => {
+                        let pos = define.node().text_range();
+                        let mut warnings: ArrayVec<_, 2> = ArrayVec::new();
+
                         let error = if active {
                             if define.name().starts_with("GL_") {
                                 Some(ProcessingErrorKind::ProtectedDefine {
@@ -288,6 +327,12 @@ impl ExpandOne {
                                     is_undef: false,
                                 })
                             } else {
+                                if define.name().contains("__") {
+                                    warnings.push(WarningKind::ReservedName {
+                                        ident: define.name().into(),
+                                    });
+                                }
+
                                 let definition = Definition::Regular(
                                     (*define).clone().into(),
                                     self.location.current_file(),
@@ -301,7 +346,14 @@ impl ExpandOne {
                                                 is_undef: false,
                                             })
                                         } else {
-                                            // TODO: Check that we are not overwriting an incompatible definition
+                                            if let Definition::Regular(existing, _) = entry.get() {
+                                                if !existing.is_identical(&define) {
+                                                    warnings.push(WarningKind::MacroRedefinition {
+                                                        ident: define.name().into(),
+                                                    });
+                                                }
+                                            }
+
                                             *entry.get_mut() = definition;
 
                                             None
@@ -317,6 +369,25 @@ impl ExpandOne {
                             None
                         };
 
+                        for kind in warnings {
+                            self.warn(current_state, kind, pos);
+                        }
+
+                        if active
+                            && error.is_none()
+                            && current_state.warning_enabled(WarningId::UnusedMacro)
+                        {
+                            current_state.unused_macros.insert(
+                                define.name().into(),
+                                Warning::builder()
+                                    .pos(pos)
+                                    .resolve_file(&self.location)
+                                    .finish(WarningKind::UnusedMacro {
+                                        ident: define.name().into(),
+                                    }),
+                            );
+                        }
+
                         Event::directive_errors(define, !active, error, &self.location)
                     }
                     Err(error) => Event::directive_error(error, &self.location, !active),
@@ -330,6 +401,10 @@ impl ExpandOne {
                 let (result, ret) = match directive {
                     Ok(ifdef) => {
                         let is_defined = current_state.definitions.contains_key(&ifdef.ident);
+                        if active {
+                            current_state.use_macro(&ifdef.ident);
+                        }
+
                         (is_defined, Event::directive(ifdef, !active))
                     }
                     Err(error) => (true, Event::directive_error(error, &self.location, !active)),
@@ -347,6 +422,10 @@ impl ExpandOne {
                     Ok(ifndef) => {
                         // Update masking state
                         let is_defined = current_state.definitions.contains_key(&ifndef.ident);
+                        if active {
+                            current_state.use_macro(&ifndef.ident);
+                        }
+
                         (!is_defined, Event::directive(ifndef, !active))
                     }
                     Err((error, node)) => (
@@ -370,7 +449,10 @@ impl ExpandOne {
                 let (result, ret) = match directive {
                     Ok(if_) => {
                         let (value, error) = if active {
-                            if_.eval(current_state, &self.location)
+                            let (value, error, idents) =
+                                if_.eval_idents(current_state, &self.location);
+                            self.on_condition(current_state, idents, if_.node().text_range());
+                            (value, error)
                         } else {
                             (true, None)
                         };
@@ -401,7 +483,10 @@ impl ExpandOne {
                     Ok(elif_) => {
                         if self.if_stack.if_group_active() {
                             let (value, error) = if active {
-                                elif_.eval(current_state, &self.location)
+                                let (value, error, idents) =
+                                    elif_.eval_idents(current_state, &self.location);
+                                self.on_condition(current_state, idents, elif_.node().text_range());
+                                (value, error)
                             } else {
                                 (true, None)
                             };
@@ -474,9 +559,17 @@ impl ExpandOne {
                                     Some(undef.ident.clone())
                                 } else {
                                     current_state.definitions.remove(&undef.ident);
+                                    current_state.use_macro(&undef.ident);
                                     None
                                 }
                             } else {
+                                self.warn(
+                                    current_state,
+                                    WarningKind::UndefUndefined {
+                                        ident: undef.ident.clone(),
+                                    },
+                                    undef.node().text_range(),
+                                );
                                 None
                             }
                         } else {
@@ -554,6 +647,48 @@ impl ExpandOne {
                     (Err(error), _, _) => Event::directive_error(error, &self.location, !active),
                 }
             }
+            PP_INCLUDE => {
+                let active = self.if_stack.active();
+                // Parse the directive itself
+                let directive: DirectiveResult<Include> =
+                    (self.location.current_file(), node).try_into();
+
+                // Perform macro substitution to get the path. If this fails, the directive is
+                // malformed and shouldn't be processed.
+                let (directive, path) = match directive {
+                    Ok(directive) => match directive.path(current_state, &self.location) {
+                        Ok(path) => (Ok(directive), Some(path)),
+                        Err(err) => (Err((err, directive.node().clone())), None),
+                    },
+                    err => (err, None),
+                };
+
+                match (directive, path, active) {
+                    (Ok(include), _, true) if current_state.include_mode() == IncludeMode::None => {
+                        // #include requires one of the include extensions
+                        Event::directive_errors(
+                            include,
+                            false,
+                            std::iter::once(ProcessingErrorKind::IncludeNotSupported),
+                            &self.location,
+                        )
+                    }
+                    (Ok(include), Some(path), true) => {
+                        // #include, enter nested file
+                        let node = include.node().clone();
+                        return HandleNodeResult::EnterFile(
+                            Event::directive(include, !active),
+                            node,
+                            path,
+                        );
+                    }
+                    (Ok(include), _, active) => {
+                        // Forward the directive
+                        Event::directive(include, !active)
+                    }
+                    (Err(error), _, _) => Event::directive_error(error, &self.location, !active),
+                }
+            }
             PP_LINE => {
                 let active = self.if_stack.active();
                 // Parse the directive itself
@@ -611,16 +746,42 @@ impl ExpandOne {
                     (self.location.current_file(), node).try_into();
 
                 match directive {
-                    Ok(pragma) => Event::directive(pragma, !active),
+                    Ok(pragma) if active => {
+                        Event::directive(self.handle_pragma(current_state, pragma), false)
+                    }
+                    Ok(pragma) => Event::directive(pragma, true),
                     Err(error) => Event::directive_error(error, &self.location, !active),
                 }
             }
             ERROR => {
-                // Unknown preprocessor directive, these are already reported as parse errors
-                Event::directive(
-                    Directive::new(self.location.current_file(), node, Invalid),
-                    !self.if_stack.active(),
-                )
+                let active = self.if_stack.active();
+                let custom = handlers::name_and_args(&node).and_then(|(name, args)| {
+                    current_state
+                        .handlers
+                        .directive(&name)
+                        .cloned()
+                        .map(|handler| (name, args, handler))
+                });
+
+                if let Some((name, args, handler)) = custom {
+                    // Custom directive, processed by a user-registered handler
+                    let data = if active {
+                        self.run_handler(&handler, &name, &name, &node, args)
+                    } else {
+                        None
+                    };
+
+                    Event::directive(
+                        Directive::new(self.location.current_file(), node, Custom::new(name, data)),
+                        !active,
+                    )
+                } else {
+                    // Unknown preprocessor directive, these are already reported as parse errors
+                    Event::directive(
+                        Directive::new(self.location.current_file(), node, Invalid),
+                        !active,
+                    )
+                }
             }
             _ => {
                 // Should never happen if all preprocessor directives are implemented
@@ -630,13 +791,130 @@ impl ExpandOne {
         .into()
     }
 
+    /// Process an unknown pragma using the user-registered handler for its name, if any
+    fn handle_pragma(
+        &mut self,
+        current_state: &ProcessorState,
+        pragma: Directive<Pragma>,
+    ) -> Directive<Pragma> {
+        let custom = match pragma.value() {
+            ParsedPragma::Unknown(body) => handlers::name_and_args(body),
+            _ => None,
+        }
+        .and_then(|(name, args)| {
+            current_state
+                .handlers
+                .pragma(&name)
+                .cloned()
+                .map(|handler| (name, args, handler))
+        });
+
+        if let Some((name, args, handler)) = custom {
+            let data = self.run_handler(&handler, "pragma", &name, pragma.node(), args);
+            let (file_id, inner, node) = pragma.into_inner();
+            Directive::new(file_id, node, inner.with_custom(name, data))
+        } else {
+            pragma
+        }
+    }
+
+    /// Run a user-registered handler, queueing the errors and tokens it produced
+    fn run_handler(
+        &mut self,
+        handler: &Handler,
+        directive: &str,
+        name: &str,
+        node: &SyntaxNode,
+        args: Vec<SyntaxToken>,
+    ) -> Option<HandlerData> {
+        let (data, source, errors) = handler(&HandlerInput::new(name, node, args)).into_inner();
+
+        for (message, pos) in errors {
+            self.pending_events.push_back(Event::error(
+                ProcessingErrorKind::HandlerError {
+                    directive: directive.into(),
+                    message,
+                },
+                pos,
+                &self.location,
+                false,
+            ));
+        }
+
+        if let Some(source) = source {
+            let (root, parse_errors, _) = parser::Parser::new(&source).parse().into_inner();
+
+            // Injected sources are plain token sequences: anything the preprocessor would have to
+            // interpret is rejected instead of being emitted as raw tokens
+            let message = if let Some(error) = parse_errors.first() {
+                Some(format!("invalid injected source: {}", error.inner()))
+            } else if root.children().next().is_some() {
+                Some("injected source must not contain preprocessor directives".to_owned())
+            } else {
+                None
+            };
+
+            if let Some(message) = message {
+                self.pending_events.push_back(Event::error(
+                    ProcessingErrorKind::HandlerError {
+                        directive: directive.into(),
+                        message,
+                    },
+                    node.text_range(),
+                    &self.location,
+                    false,
+                ));
+            } else {
+                // Injected tokens are located at the directive
+                let pos = NodeSpan::new(self.location.current_file(), node.text_range());
+
+                self.pending_events.extend(
+                    root.children_with_tokens()
+                        .filter_map(NodeOrToken::into_token)
+                        .map(|token| {
+                            Event::from(OutputToken::new(
+                                token.kind(),
+                                TokenText::raw(token.text()),
+                                pos,
+                            ))
+                        }),
+                );
+            }
+        }
+
+        data
+    }
+
+    fn on_condition(
+        &mut self,
+        current_state: &mut ProcessorState,
+        idents: IfIdents,
+        pos: TextRange,
+    ) {
+        for name in &idents.used {
+            current_state.use_macro(name);
+        }
+
+        for ident in idents.undefined {
+            self.warn(
+                current_state,
+                WarningKind::UndefinedIdentifier { ident },
+                pos,
+            );
+        }
+    }
+
     fn handle_token(
         &mut self,
-        current_state: ProcessorState,
+        mut current_state: ProcessorState,
         token: SyntaxToken,
         iterator: SyntaxElementChildren<PreprocessorLang>,
         errors: Vec<parser::Error>,
     ) -> Option<Event> {
+        if self.if_stack.active() && !token.kind().is_trivia() && !token.kind().is_newline() {
+            current_state.seen_tokens = true;
+        }
+
         // Look for macro substitutions unless the current group is masked
         if let Some(definition) = (if self.if_stack.active() && token.kind() == IDENT_KW {
             Some(Unescaped::new(token.text()).to_string())
@@ -655,10 +933,25 @@ impl ExpandOne {
             ) {
                 Ok(Some((invocation, new_iterator))) => {
                     // We successfully parsed a macro invocation
+                    let name = SmolStr::from(definition.name());
+                    let events: VecDeque<_> =
+                        invocation.substitute(&current_state, &self.location).into();
+
+                    // Record the macros used by the expansion
+                    current_state.use_macro(&name);
+                    for expansion in events
+                        .iter()
+                        .filter_map(Event::as_token)
+                        .filter_map(TokenLike::expansions)
+                        .flat_map(|expansions| expansions.iter())
+                    {
+                        current_state.use_macro(&expansion.name);
+                    }
+
                     self.state = ExpandState::ExpandedTokens {
                         iterator: new_iterator,
                         errors,
-                        events: invocation.substitute(&current_state, &self.location).into(),
+                        events,
                         current_state,
                     };
                 }
@@ -759,6 +1052,10 @@ impl Iterator for ExpandOne {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
+            if let Some(event) = self.pending_events.pop_front() {
+                return Some(event.into());
+            }
+
             match std::mem::replace(&mut self.state, ExpandState::Complete) {
                 ExpandState::Init { ast, current_state } => {
                     let (root, errors, line_map) = ast.into_inner();
@@ -785,6 +1082,14 @@ impl Iterator for ExpandOne {
                                 let error = errors.pop().unwrap();
                                 let pos = node_or_token.text_range();
 
+                                // Unknown directives processed by a user-registered handler are
+                                // not errors
+                                let handled = matches!(
+                                    error.inner(),
+                                    parser::ErrorKind::UnknownPreprocessorDirective { name }
+                                        if current_state.handlers.directive(name).is_some()
+                                );
+
                                 self.state = ExpandState::PendingOne {
                                     iterator,
                                     errors,
@@ -792,6 +1097,10 @@ impl Iterator for ExpandOne {
                                     current_state,
                                 };
 
+                                if handled {
+                                    continue;
+                                }
+
                                 return Some(
                                     Event::error(
                                         error.into_inner(),
@@ -118,9 +118,9 @@ impl IfStack {
             return Err(IfError::ExtraElif);
         }
 
-        // Is the next block active?
+        // Is the next block active? It is only if no other block before was active.
         // Note that we use active, since we popped the current level off
-        if self.active() && expr {
+        if self.active() && expr && top == IfState::None {
             self.stack.push(top.activate());
         } else {
             self.stack.push(top.deactivate());
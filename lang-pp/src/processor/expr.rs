@@ -1,22 +1,68 @@
+//! Evaluation of `#if` conditions
+
 use std::iter::Peekable;
 
+use rowan::NodeOrToken;
+use thiserror::Error;
+
+use lang_util::{FileId, SmolStr};
+
 use crate::{
-    parser::SyntaxKind::{self, *},
+    parser::{
+        self,
+        SyntaxKind::{self, *},
+        SyntaxToken,
+    },
     types::Token,
-    util::Unescaped,
+    util::{LineMap, Unescaped},
 };
 
-use super::{event::OutputToken, ProcessorState};
+use super::{
+    definition::MacroInvocation,
+    event::{self, Event, OutputToken},
+    expand::ExpandLocation,
+    ProcessorState,
+};
+
+/// Error in the evaluation of a constant expression
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EvalError {
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("integer overflow")]
+    Overflow,
+    #[error("negative shift amount")]
+    NegativeShift,
+    #[error("undefined identifier: {ident}")]
+    UndefinedIdentifier { ident: SmolStr },
+}
+
+/// How identifiers which are not defined macros are evaluated in conditions
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UndefinedPolicy {
+    /// Undefined identifiers evaluate to zero, as specified for the preprocessor
+    #[default]
+    Zero,
+    /// Undefined identifiers evaluate to zero, and are reported in [Evaluation::undefined]
+    Warn,
+    /// Undefined identifiers are an error
+    Error,
+}
 
 #[derive(Debug, Clone)]
-pub struct ExprEvaluator<'i, I: Iterator<Item = &'i OutputToken>> {
+pub(crate) struct ExprEvaluator<'i, I: Iterator<Item = &'i OutputToken>> {
     input: Peekable<I>,
     state: &'i ProcessorState,
+    undefined_policy: UndefinedPolicy,
+    /// Undefined identifiers which were evaluated to zero
+    undefined: Vec<SmolStr>,
+    /// true if a malformed `defined` operator was encountered
+    malformed_defined: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EvalResult {
-    Constant(Result<i32, ()>),
+    Constant(Result<i32, EvalError>),
     Token(OutputToken),
 }
 
@@ -25,9 +71,24 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         Self {
             input: input.peekable(),
             state,
+            undefined_policy: UndefinedPolicy::default(),
+            undefined: Vec::new(),
+            malformed_defined: false,
+        }
+    }
+
+    pub fn undefined_policy(self, undefined_policy: UndefinedPolicy) -> Self {
+        Self {
+            undefined_policy,
+            ..self
         }
     }
 
+    /// Take the undefined identifiers which were evaluated to zero so far
+    pub fn take_undefined(&mut self) -> Vec<SmolStr> {
+        std::mem::take(&mut self.undefined)
+    }
+
     fn bump(&mut self) -> Option<&'i OutputToken> {
         loop {
             let token = self.input.next();
@@ -60,7 +121,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         self.peek().map(|token| token.kind())
     }
 
-    fn primary(&mut self) -> Option<Result<i32, ()>> {
+    fn primary(&mut self) -> Option<Result<i32, EvalError>> {
         let token = self.peek()?;
 
         match token.kind() {
@@ -78,8 +139,19 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
                 }
             }
             IDENT_KW => {
-                self.bump();
-                return Some(Ok(0));
+                let ident: SmolStr = Unescaped::new(self.bump()?.text()).to_string().into();
+
+                return Some(match self.undefined_policy {
+                    UndefinedPolicy::Zero => Ok(0),
+                    UndefinedPolicy::Warn => {
+                        if !self.undefined.contains(&ident) {
+                            self.undefined.push(ident);
+                        }
+
+                        Ok(0)
+                    }
+                    UndefinedPolicy::Error => Err(EvalError::UndefinedIdentifier { ident }),
+                });
             }
             LPAREN => {
                 self.bump();
@@ -98,7 +170,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         None
     }
 
-    fn unary(&mut self) -> Option<Result<i32, ()>> {
+    fn unary(&mut self) -> Option<Result<i32, EvalError>> {
         match self.peek_kind()? {
             PLUS => {
                 self.bump();
@@ -106,7 +178,9 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
             }
             DASH => {
                 self.bump();
-                self.unary().map(|result| result.map(|val| -val))
+                self.unary().map(|result| {
+                    result.and_then(|val| val.checked_neg().ok_or(EvalError::Overflow))
+                })
             }
             TILDE => {
                 self.bump();
@@ -120,72 +194,90 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
             DEFINED => {
                 self.bump();
 
-                match self.peek()?.kind() {
-                    IDENT_KW => {
-                        // Free-standing form, get the ident name
-                        let ident = Unescaped::new(self.bump()?.text()).to_string();
-                        Some(Ok(if self.state.get_definition(&ident).is_some() {
-                            1
-                        } else {
-                            0
-                        }))
-                    }
-                    LPAREN => {
-                        // Parenthesis form
-                        self.bump();
+                let result = self.defined();
+                if result.is_none() {
+                    self.malformed_defined = true;
+                }
 
-                        // Try to find an ident
-                        if let Some(ident) = self.peek().and_then(|token| {
-                            if token.kind() == IDENT_KW {
-                                Some(Unescaped::new(token.text()).to_string())
-                            } else {
-                                None
-                            }
-                        }) {
-                            // Found an ident, bump it
-                            self.bump();
-
-                            // Look for the RPAREN
-                            if self.peek()?.kind() == RPAREN {
-                                self.bump();
-                            } else {
-                                // Missing RPAREN or extra tokens
-                                return None;
-                            }
-
-                            return Some(Ok(if self.state.get_definition(&ident).is_some() {
-                                1
-                            } else {
-                                0
-                            }));
-                        }
+                result
+            }
+            _ => self.primary(),
+        }
+    }
 
-                        // Invalid
+    /// Evaluate the operand of a `defined` operator
+    fn defined(&mut self) -> Option<Result<i32, EvalError>> {
+        match self.peek()?.kind() {
+            IDENT_KW => {
+                // Free-standing form, get the ident name
+                let ident = Unescaped::new(self.bump()?.text()).to_string();
+                Some(Ok(if self.state.get_definition(&ident).is_some() {
+                    1
+                } else {
+                    0
+                }))
+            }
+            LPAREN => {
+                // Parenthesis form
+                self.bump();
+
+                // Try to find an ident
+                if let Some(ident) = self.peek().and_then(|token| {
+                    if token.kind() == IDENT_KW {
+                        Some(Unescaped::new(token.text()).to_string())
+                    } else {
                         None
                     }
-                    _ => {
-                        // Invalid
-                        None
+                }) {
+                    // Found an ident, bump it
+                    self.bump();
+
+                    // Look for the RPAREN
+                    if self.peek()?.kind() == RPAREN {
+                        self.bump();
+                    } else {
+                        // Missing RPAREN or extra tokens
+                        return None;
                     }
+
+                    return Some(Ok(if self.state.get_definition(&ident).is_some() {
+                        1
+                    } else {
+                        0
+                    }));
                 }
+
+                // Invalid
+                None
+            }
+            _ => {
+                // Invalid
+                None
             }
-            _ => self.primary(),
         }
     }
 
     fn binary_op(
-        lhs: Option<Result<i32, ()>>,
-        rhs: Option<Result<i32, ()>>,
-        f: impl FnOnce(i32, i32) -> Result<(i32, bool), ()>,
-    ) -> Option<Result<i32, ()>> {
+        lhs: Option<Result<i32, EvalError>>,
+        rhs: Option<Result<i32, EvalError>>,
+        f: impl FnOnce(i32, i32) -> Result<(i32, bool), EvalError>,
+    ) -> Option<Result<i32, EvalError>> {
         lhs.zip(rhs).map(|(lhs, rhs)| {
             lhs.and_then(|a| {
-                rhs.and_then(|b| f(a, b).and_then(|(val, ovf)| if ovf { Err(()) } else { Ok(val) }))
+                rhs.and_then(|b| {
+                    f(a, b).and_then(|(val, ovf)| {
+                        if ovf {
+                            Err(EvalError::Overflow)
+                        } else {
+                            Ok(val)
+                        }
+                    })
+                })
             })
         })
     }
 
-    fn multiplicative(&mut self) -> Option<Result<i32, ()>> {
+    fn multiplicative(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.unary();
 
         while let Some(kind) = self.peek_kind() {
@@ -198,7 +290,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
                     self.bump();
                     lhs = Self::binary_op(lhs, self.unary(), |a, b| {
                         if b == 0 {
-                            Err(())
+                            Err(EvalError::DivisionByZero)
                         } else {
                             Ok(a.overflowing_div(b))
                         }
@@ -208,7 +300,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
                     self.bump();
                     lhs = Self::binary_op(lhs, self.unary(), |a, b| {
                         if b == 0 {
-                            Err(())
+                            Err(EvalError::DivisionByZero)
                         } else {
                             Ok(a.overflowing_rem(b))
                         }
@@ -223,7 +315,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn additive(&mut self) -> Option<Result<i32, ()>> {
+    fn additive(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.multiplicative();
 
         while let Some(kind) = self.peek_kind() {
@@ -255,7 +347,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn shift(&mut self) -> Option<Result<i32, ()>> {
+    fn shift(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.additive();
 
         while let Some(kind) = self.peek_kind() {
@@ -264,7 +356,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
                     self.bump();
                     lhs = Self::binary_op(lhs, self.additive(), |a, b| {
                         if b < 0 {
-                            Err(())
+                            Err(EvalError::NegativeShift)
                         } else {
                             Ok(a.overflowing_shl(b as u32))
                         }
@@ -274,7 +366,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
                     self.bump();
                     lhs = Self::binary_op(lhs, self.additive(), |a, b| {
                         if b < 0 {
-                            Err(())
+                            Err(EvalError::NegativeShift)
                         } else {
                             Ok(a.overflowing_shr(b as u32))
                         }
@@ -289,7 +381,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn relational(&mut self) -> Option<Result<i32, ()>> {
+    fn relational(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.shift();
 
         while let Some(kind) = self.peek_kind() {
@@ -327,7 +419,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn equality(&mut self) -> Option<Result<i32, ()>> {
+    fn equality(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.relational();
 
         while let Some(kind) = self.peek_kind() {
@@ -353,7 +445,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn and(&mut self) -> Option<Result<i32, ()>> {
+    fn and(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.equality();
 
         while self
@@ -368,7 +460,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn xor(&mut self) -> Option<Result<i32, ()>> {
+    fn xor(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.and();
 
         while self.peek_kind().map(|kind| kind == CARET).unwrap_or(false) {
@@ -379,7 +471,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn or(&mut self) -> Option<Result<i32, ()>> {
+    fn or(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.xor();
 
         while self.peek_kind().map(|kind| kind == BAR).unwrap_or(false) {
@@ -390,7 +482,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn logical_and(&mut self) -> Option<Result<i32, ()>> {
+    fn logical_and(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.or();
 
         while self.peek_kind().map(|kind| kind == AND_OP).unwrap_or(false) {
@@ -403,7 +495,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn logical_or(&mut self) -> Option<Result<i32, ()>> {
+    fn logical_or(&mut self) -> Option<Result<i32, EvalError>> {
         let mut lhs = self.logical_and();
 
         while self.peek_kind().map(|kind| kind == OR_OP).unwrap_or(false) {
@@ -416,7 +508,7 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> ExprEvaluator<'i, I> {
         lhs
     }
 
-    fn expr(&mut self) -> Option<Result<i32, ()>> {
+    fn expr(&mut self) -> Option<Result<i32, EvalError>> {
         self.logical_or()
     }
 
@@ -436,6 +528,158 @@ impl<'i, I: Iterator<Item = &'i OutputToken>> Iterator for ExprEvaluator<'i, I>
     }
 }
 
+/// Error returned when parsing or evaluating a [Condition]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConditionError {
+    #[error("invalid syntax in condition")]
+    InvalidSyntax,
+    #[error("missing expression")]
+    MissingExpr,
+    #[error("malformed defined operator")]
+    MalformedDefined,
+    #[error("unexpected token: {token}")]
+    UnexpectedToken { token: SmolStr },
+    #[error("extra tokens at end of expression")]
+    ExtraTokens,
+    #[error("macro expansion failed: {0}")]
+    Expansion(event::Error),
+    #[error(transparent)]
+    Eval(#[from] EvalError),
+}
+
+/// Result of the evaluation of a [Condition]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    /// Value of the condition
+    pub value: i32,
+    /// Identifiers which were not defined macros and were evaluated to zero, when using
+    /// [UndefinedPolicy::Warn]
+    pub undefined: Vec<SmolStr>,
+}
+
+impl Evaluation {
+    /// Return `true` if the condition holds
+    pub fn is_true(&self) -> bool {
+        self.value != 0
+    }
+}
+
+/// A preprocessor condition, as written after an `#if` directive
+///
+/// Conditions are evaluated the same way as `#if` directives: macros are expanded, then the
+/// resulting constant expression is evaluated.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    tokens: Vec<SyntaxToken>,
+    line_map: LineMap,
+}
+
+impl Condition {
+    /// Parse a condition, such as `defined(FOO) && BAR > 2`
+    pub fn parse(input: &str) -> Result<Self, ConditionError> {
+        let (root, errors, line_map) = parser::Parser::new(input).parse().into_inner();
+
+        if !errors.is_empty() {
+            return Err(ConditionError::InvalidSyntax);
+        }
+
+        let tokens = root
+            .children_with_tokens()
+            .map(|node_or_token| match node_or_token {
+                NodeOrToken::Token(token) => Ok(token),
+                // Directives are not allowed in conditions
+                NodeOrToken::Node(_) => Err(ConditionError::InvalidSyntax),
+            })
+            .filter(|token| {
+                token
+                    .as_ref()
+                    .map(|token| !token.kind().is_newline())
+                    .unwrap_or(true)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { tokens, line_map })
+    }
+
+    /// Get the identifiers the condition refers to, in order of first appearance
+    ///
+    /// This includes the operands of `defined`, but not the macros used by the expansions of
+    /// these identifiers.
+    pub fn referenced_macros(&self) -> Vec<SmolStr> {
+        let mut names = Vec::new();
+
+        for token in self.tokens.iter().filter(|token| token.kind() == IDENT_KW) {
+            let name: SmolStr = Unescaped::new(token.text()).to_string().into();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+
+    /// Evaluate the condition
+    ///
+    /// # Parameters
+    ///
+    /// * `state`: preprocessor state holding the macro definitions
+    /// * `undefined_policy`: how identifiers which are not defined macros are evaluated
+    pub fn eval(
+        &self,
+        state: &ProcessorState,
+        undefined_policy: UndefinedPolicy,
+    ) -> Result<Evaluation, ConditionError> {
+        let file_id = FileId::default();
+        let location = ExpandLocation::with_line_map(file_id, self.line_map.clone());
+
+        // Perform macro substitution
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|token| (token.clone(), file_id))
+            .collect();
+
+        let mut output = Vec::new();
+        for event in MacroInvocation::substitute_vec(state, tokens, &location) {
+            match event {
+                Event::Token { token, .. } => output.push(token),
+                Event::Error { error, .. } => return Err(ConditionError::Expansion(error)),
+                _ => {}
+            }
+        }
+
+        let mut evaluator =
+            ExprEvaluator::new(output.iter(), state).undefined_policy(undefined_policy);
+        let first = evaluator.next();
+        let rest = evaluator.next();
+
+        if evaluator.malformed_defined {
+            return Err(ConditionError::MalformedDefined);
+        }
+
+        let value = match first {
+            Some(EvalResult::Constant(value)) => value?,
+            Some(EvalResult::Token(token)) => {
+                return Err(ConditionError::UnexpectedToken {
+                    token: token.text().into(),
+                })
+            }
+            None => return Err(ConditionError::MissingExpr),
+        };
+
+        match rest {
+            Some(EvalResult::Token(token)) => Err(ConditionError::UnexpectedToken {
+                token: token.text().into(),
+            }),
+            Some(EvalResult::Constant(_)) => Err(ConditionError::ExtraTokens),
+            None => Ok(Evaluation {
+                value,
+                undefined: evaluator.undefined,
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -451,14 +695,14 @@ mod tests {
         },
     };
 
-    use super::ExprEvaluator;
+    use super::{Condition, ConditionError, EvalError, ExprEvaluator, UndefinedPolicy};
 
     use self::EvalResult::*;
 
     /// Wrapper structure to compare token kinds with PartialEq instead of tokens
     #[derive(Debug, Clone, PartialEq)]
     enum EvalResult {
-        Constant(Result<i32, ()>),
+        Constant(Result<i32, EvalError>),
         Token(SyntaxKind),
     }
 
@@ -545,12 +789,12 @@ mod tests {
         assert_eq!(&eval("1 / 2"), &[Constant(Ok(0))]);
         assert_eq!(&eval("2 / 3"), &[Constant(Ok(0))]);
         assert_eq!(&eval("6 / 2"), &[Constant(Ok(3))]);
-        assert_eq!(&eval("1 / 0"), &[Constant(Err(()))]);
+        assert_eq!(&eval("1 / 0"), &[Constant(Err(EvalError::DivisionByZero))]);
 
         assert_eq!(&eval("1 % 2"), &[Constant(Ok(1))]);
         assert_eq!(&eval("2 % 3"), &[Constant(Ok(2))]);
         assert_eq!(&eval("6 % 2"), &[Constant(Ok(0))]);
-        assert_eq!(&eval("1 % 0"), &[Constant(Err(()))]);
+        assert_eq!(&eval("1 % 0"), &[Constant(Err(EvalError::DivisionByZero))]);
     }
 
     #[test]
@@ -639,6 +883,88 @@ mod tests {
 
     #[test]
     fn test_overflow() {
-        assert_eq!(&eval("1 << 60"), &[Constant(Err(()))]);
+        assert_eq!(&eval("1 << 60"), &[Constant(Err(EvalError::Overflow))]);
+    }
+
+    fn condition_state() -> ProcessorState {
+        ProcessorState::builder()
+            .definition(Define::object(
+                "FOO".into(),
+                DefineObject::from_str("1").unwrap(),
+                false,
+            ))
+            .definition(Define::object(
+                "BAR".into(),
+                DefineObject::from_str("FOO + 2").unwrap(),
+                false,
+            ))
+            .finish()
+    }
+
+    fn eval_condition(input: &str, policy: UndefinedPolicy) -> Result<i32, ConditionError> {
+        Condition::parse(input)?
+            .eval(&condition_state(), policy)
+            .map(|evaluation| evaluation.value)
+    }
+
+    #[test]
+    fn test_condition() {
+        use UndefinedPolicy::*;
+
+        assert_eq!(eval_condition("defined(FOO) && BAR > 2", Zero), Ok(1));
+        assert_eq!(eval_condition("defined BAZ || BAR == 4", Zero), Ok(0));
+        assert_eq!(eval_condition("BAZ + 1", Zero), Ok(1));
+
+        assert_eq!(
+            Condition::parse("defined(FOO) && BAR > 2")
+                .unwrap()
+                .referenced_macros(),
+            vec!["FOO", "BAR"]
+        );
+    }
+
+    #[test]
+    fn test_condition_errors() {
+        use UndefinedPolicy::*;
+
+        assert_eq!(
+            eval_condition("BAR / (FOO - 1)", Zero),
+            Err(ConditionError::Eval(EvalError::DivisionByZero))
+        );
+        assert_eq!(
+            eval_condition("BAZ + 1", Error),
+            Err(ConditionError::Eval(EvalError::UndefinedIdentifier {
+                ident: "BAZ".into()
+            }))
+        );
+        assert_eq!(
+            eval_condition("defined(FOO", Zero),
+            Err(ConditionError::MalformedDefined)
+        );
+        assert_eq!(
+            eval_condition("defined 1", Zero),
+            Err(ConditionError::MalformedDefined)
+        );
+        assert_eq!(eval_condition("", Zero), Err(ConditionError::MissingExpr));
+        assert_eq!(
+            eval_condition("1 2", Zero),
+            Err(ConditionError::ExtraTokens)
+        );
+        assert_eq!(
+            eval_condition("1 + ;", Zero),
+            Err(ConditionError::UnexpectedToken { token: ";".into() })
+        );
+        assert_eq!(
+            eval_condition("#define X", Zero),
+            Err(ConditionError::InvalidSyntax)
+        );
+
+        // Warnings for undefined identifiers
+        let evaluation = Condition::parse("BAZ || !QUX || BAZ")
+            .unwrap()
+            .eval(&condition_state(), Warn)
+            .unwrap();
+        assert!(evaluation.is_true());
+        assert_eq!(evaluation.undefined, vec!["BAZ", "QUX"]);
     }
 }
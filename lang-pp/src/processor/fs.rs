@@ -1,5 +1,5 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
@@ -7,19 +7,20 @@ use bimap::BiHashMap;
 
 use lang_util::{
     located::{FileIdResolver, Located, LocatedBuilder},
+    position::NodeSpan,
     FileId,
 };
 
 use crate::{
     last::LocatedIterator,
-    parser::{Ast, Parser},
+    parser::{Ast, Parser, SyntaxKind::PP_INCLUDE},
     types::path::{ParsedPath, PathType},
 };
 
 use super::{
-    event::{Event, ProcessingErrorKind},
+    event::{DirectiveKind, Event, ProcessingErrorKind, Warning},
     expand::{ExpandEvent, ExpandOne},
-    ProcessorState,
+    IncludeMode, ProcessorState,
 };
 
 pub trait FileSystem {
@@ -51,10 +52,17 @@ impl FileSystem for Std {
 
 pub type StdProcessor = Processor<Std>;
 
+mod graph;
+pub use graph::*;
+
+mod memory;
+pub use memory::*;
+
 pub struct ExpandStack<'p, F: FileSystem> {
     processor: &'p mut Processor<F>,
     stack: Vec<ExpandOne>,
     state: Option<ProcessorState>,
+    unused_macros: std::vec::IntoIter<Warning>,
 }
 
 impl<'p, F: FileSystem> ExpandStack<'p, F> {
@@ -77,6 +85,10 @@ impl<'p, F: FileSystem> Iterator for ExpandStack<'p, F> {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
+            if let Some(warning) = self.unused_macros.next() {
+                return Some(Ok(Event::Warning { warning }));
+            }
+
             if let Some(mut expand) = self.stack.pop() {
                 let result = expand.next();
 
@@ -86,6 +98,32 @@ impl<'p, F: FileSystem> Iterator for ExpandStack<'p, F> {
                             // Put it back on the stack
                             self.stack.push(expand);
 
+                            if let Event::Directive {
+                                directive,
+                                masked: true,
+                            } = &event
+                            {
+                                let path = match directive.kind() {
+                                    DirectiveKind::Include(include) => Some(include.raw_path()),
+                                    DirectiveKind::MojImport(import) => Some(import.raw_path()),
+                                    _ => None,
+                                };
+
+                                if let Some(path) = path {
+                                    let pos = directive.text_range();
+                                    let location = self.stack.last().unwrap().location();
+
+                                    self.processor.record_skipped_include(SkippedInclude {
+                                        from: pos.source_id(),
+                                        path,
+                                        pos,
+                                        line: location
+                                            .offset_to_raw_line_and_col(pos.range().start())
+                                            .0,
+                                    });
+                                }
+                            }
+
                             return Some(match event {
                                 Event::EnterFile { file_id, .. } => {
                                     if let Some((canonical_path, input_path)) =
@@ -119,14 +157,43 @@ impl<'p, F: FileSystem> Iterator for ExpandStack<'p, F> {
 
                             // We are supposed to enter a new file
                             // First, parse it using the preprocessor
-                            if let Some(resolved_path) = self
-                                .processor
-                                .resolve_relative_to_id(location.current_file(), &path)
-                            {
+                            let is_include = node.kind() == PP_INCLUDE;
+                            let resolved_path = match state.include_mode() {
+                                IncludeMode::ArbInclude { .. } if is_include => self
+                                    .processor
+                                    .resolve_named_string(location.current_file(), &path),
+                                IncludeMode::GoogleInclude { .. } if is_include => self
+                                    .processor
+                                    .resolve_google_include(location.current_file(), &path),
+                                _ => self
+                                    .processor
+                                    .resolve_relative_to_id(location.current_file(), &path),
+                            };
+
+                            if let Some(resolved_path) = resolved_path {
                                 // TODO: Allow passing an encoding from somewhere
                                 match self.processor.parse(&resolved_path) {
                                     Ok(parsed) => {
+                                        let file_id = parsed.file_id();
                                         self.stack.push(parsed.expand_one(state));
+
+                                        // Get the location of the directive again, now that
+                                        // the processor isn't borrowed anymore
+                                        let location = self.stack[self.stack.len() - 2].location();
+                                        self.processor.record_include(IncludeEdge {
+                                            from: location.current_file(),
+                                            to: file_id,
+                                            path,
+                                            pos: NodeSpan::new(
+                                                location.current_file(),
+                                                node.text_range(),
+                                            ),
+                                            line: location
+                                                .offset_to_raw_line_and_col(
+                                                    node.text_range().start(),
+                                                )
+                                                .0,
+                                        });
                                     }
                                     Err(error) => {
                                         // Just return the error, we'll keep iterating on the lower
@@ -141,21 +208,31 @@ impl<'p, F: FileSystem> Iterator for ExpandStack<'p, F> {
                             } else {
                                 // Resolving the path failed, throw an error located at the
                                 // right place
+                                let error = if is_include {
+                                    ProcessingErrorKind::IncludeNotFound { path }
+                                } else {
+                                    ProcessingErrorKind::ImportNotFound { path }
+                                };
+
                                 return Some(Ok(Event::error(
-                                    ProcessingErrorKind::ImportNotFound { path },
+                                    error,
                                     node.text_range(),
                                     location,
                                     false,
                                 )));
                             }
                         }
-                        ExpandEvent::Completed(state) => {
+                        ExpandEvent::Completed(mut state) => {
                             if let Some(last) = self.stack.last_mut() {
                                 // Propagate the updated state upwards in the stack
                                 last.set_state(state);
                             } else {
-                                // No more, store the final state
+                                // No more, report the macros which are still unused and store
+                                // the final state. The completed expansion is kept on the stack
+                                // so the location of these warnings can be resolved.
+                                self.unused_macros = state.take_unused_macros().into_iter();
                                 self.state = Some(state);
+                                self.stack.push(expand);
                             }
                         }
                     }
@@ -191,6 +268,7 @@ impl<'p, F: FileSystem> ParsedFile<'p, F> {
             processor: self.processor,
             stack: vec![ExpandOne::new((self.file_id, ast), initial_state)],
             state: None,
+            unused_macros: Vec::new().into_iter(),
         }
     }
 
@@ -245,10 +323,19 @@ pub struct Processor<F: FileSystem> {
     file_cache: HashMap<FileId, Ast>,
     /// Mapping from canonical paths to FileIds
     file_ids: BiHashMap<PathOrSource, FileId>,
-    /// Mapping from #moj_import/input paths to canonical paths
+    /// Mapping from #include/#moj_import/input paths to canonical paths
     canonical_paths: BiHashMap<PathBuf, PathBuf>,
     /// List of include paths in resolution order
     system_paths: Vec<PathBuf>,
+    /// Named strings for GL_ARB_shading_language_include
+    named_strings: HashMap<PathBuf, String>,
+    /// Include directives which entered a file
+    include_edges: Vec<IncludeEdge>,
+    /// Include directives in inactive conditional blocks
+    skipped_includes: Vec<SkippedInclude>,
+    /// Include directives already recorded, since the same file may be processed multiple times
+    seen_includes: HashSet<IncludeEdge>,
+    seen_skipped_includes: HashSet<SkippedInclude>,
     /// Filesystem abstraction
     fs: F,
 }
@@ -260,6 +347,11 @@ impl<F: FileSystem> Processor<F> {
             file_ids: BiHashMap::with_capacity(1),
             canonical_paths: BiHashMap::with_capacity(1),
             system_paths: Vec::new(),
+            named_strings: HashMap::new(),
+            include_edges: Vec::new(),
+            skipped_includes: Vec::new(),
+            seen_includes: HashSet::new(),
+            seen_skipped_includes: HashSet::new(),
             fs,
         }
     }
@@ -289,17 +381,98 @@ impl<F: FileSystem> Processor<F> {
         &mut self.system_paths
     }
 
-    fn resolve_relative_to_id(&self, relative_to: FileId, path: &ParsedPath) -> Option<PathBuf> {
-        let parent = self
+    /// Register a named string for use with GL_ARB_shading_language_include
+    ///
+    /// Named strings take precedence over files with the same path on the filesystem.
+    ///
+    /// # Parameters
+    ///
+    /// * `name`: name of the string, which should start with a `/`
+    /// * `source`: GLSL source of the string
+    pub fn add_named_string(&mut self, name: impl Into<PathBuf>, source: impl Into<String>) {
+        self.named_strings.insert(name.into(), source.into());
+    }
+
+    pub fn named_strings(&self) -> &HashMap<PathBuf, String> {
+        &self.named_strings
+    }
+
+    /// Get the graph of the files entered through #include and #moj_import directives
+    ///
+    /// This covers all the files processed so far with this processor.
+    pub fn include_graph(&self) -> IncludeGraph {
+        let paths = self
             .file_ids
-            .get_by_right(&relative_to)
+            .iter()
+            .filter_map(|(key, file_id)| key.as_path().map(|path| (*file_id, path.clone())))
+            .collect();
+
+        IncludeGraph::new(
+            paths,
+            self.include_edges.clone(),
+            self.skipped_includes.clone(),
+        )
+    }
+
+    /// Get the parsed contents of a file processed so far
+    pub fn ast(&self, file_id: FileId) -> Option<&Ast> {
+        self.file_cache.get(&file_id)
+    }
+
+    fn record_include(&mut self, edge: IncludeEdge) {
+        if self.seen_includes.insert(edge.clone()) {
+            self.include_edges.push(edge);
+        }
+    }
+
+    fn record_skipped_include(&mut self, skipped: SkippedInclude) {
+        if self.seen_skipped_includes.insert(skipped.clone()) {
+            self.skipped_includes.push(skipped);
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.named_strings.contains_key(path) || self.fs.exists(path)
+    }
+
+    fn parent_of_id(&self, file_id: FileId) -> Option<&Path> {
+        self.file_ids
+            .get_by_right(&file_id)
             .and_then(|key| match key {
                 PathOrSource::Source(_, dir) => Some(dir.as_path()),
                 PathOrSource::Path(path) => self
                     .canonical_paths
                     .get_by_right(path)
                     .and_then(|path| path.parent()),
-            })?;
+            })
+    }
+
+    /// Resolve an #include path according to GL_ARB_shading_language_include
+    ///
+    /// Paths starting with a `/` are used as-is. Relative quoted paths are first looked up
+    /// relative to the including string, then both relative quoted paths and angle paths are
+    /// looked up in the system paths.
+    fn resolve_named_string(&self, relative_to: FileId, path: &ParsedPath) -> Option<PathBuf> {
+        let path_as_pathbuf = PathBuf::from(&path.path);
+
+        if path.path.starts_with('/') {
+            return Some(path_as_pathbuf);
+        }
+
+        let parent = match path.ty {
+            PathType::Angle => None,
+            PathType::Quote => self.parent_of_id(relative_to),
+        };
+
+        parent
+            .into_iter()
+            .chain(self.system_paths.iter().map(PathBuf::as_path))
+            .map(|dir| dir.join(&path_as_pathbuf))
+            .find(|full_path| self.exists(full_path))
+    }
+
+    fn resolve_relative_to_id(&self, relative_to: FileId, path: &ParsedPath) -> Option<PathBuf> {
+        let parent = self.parent_of_id(relative_to)?;
 
         self.resolve_relative_to_path(parent, path)
     }
@@ -321,18 +494,46 @@ impl<F: FileSystem> Processor<F> {
         match path.ty {
             PathType::Angle => self.system_paths.iter().find_map(|system_path| {
                 let full_path = system_path.join(&path_as_pathbuf);
-                self.fs.exists(&full_path).then_some(full_path)
+                self.exists(&full_path).then_some(full_path)
             }),
             PathType::Quote => Some(parent.as_ref().join(path_as_pathbuf)),
         }
     }
 
+    /// Resolve a GL_GOOGLE_include_directive `#include` path
+    ///
+    /// Quoted paths which don't exist next to the including file are looked up in the system
+    /// paths, as glslang does.
+    fn resolve_google_include(&self, relative_to: FileId, path: &ParsedPath) -> Option<PathBuf> {
+        let resolved = self.resolve_relative_to_id(relative_to, path);
+
+        match resolved {
+            Some(full_path) if path.ty == PathType::Quote && !self.exists(&full_path) => {
+                // Search the system paths as if the path was in angle brackets
+                let angle_path = ParsedPath {
+                    path: path.path.clone(),
+                    ty: PathType::Angle,
+                };
+
+                self.resolve_relative_to_id(relative_to, &angle_path)
+                    .or(Some(full_path))
+            }
+            other => other,
+        }
+    }
+
     pub fn parse(&mut self, path: &Path) -> Result<ParsedFile<F>, F::Error> {
         // Find the canonical path. Not using the entry API because cloning a path is expensive.
         let canonical_path = if let Some(canonical_path) = self.canonical_paths.get_by_left(path) {
             canonical_path
         } else {
-            let canonical_path = self.fs.canonicalize(path)?;
+            // Named strings are not part of the filesystem, so their name is their canonical path
+            let canonical_path = if self.named_strings.contains_key(path) {
+                path.to_owned()
+            } else {
+                self.fs.canonicalize(path)?
+            };
+
             self.canonical_paths.insert(path.to_owned(), canonical_path);
             self.canonical_paths.get_by_left(path).unwrap()
         };
@@ -354,7 +555,10 @@ impl<F: FileSystem> Processor<F> {
             }),
             Entry::Vacant(entry) => {
                 // Read the file
-                let input = self.fs.read(canonical_path)?;
+                let input = match self.named_strings.get(canonical_path) {
+                    Some(source) => source.as_str().into(),
+                    None => self.fs.read(canonical_path)?,
+                };
                 // Parse it
                 let ast = Parser::new(&input).parse();
                 // Check that the root node covers the entire range
@@ -425,3 +629,157 @@ impl<'p, F: FileSystem> LocatedIterator for ExpandStack<'p, F> {
         self.stack.last().unwrap().location()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::processor::{
+        event::{ErrorKind, Event, ProcessingErrorKind},
+        ProcessorState,
+    };
+
+    use super::StdProcessor;
+
+    /// Preprocess the given source, returning the paths of the included files and the errors
+    fn process(processor: &mut StdProcessor, source: &str) -> (Vec<PathBuf>, Vec<ErrorKind>) {
+        let mut paths = Vec::new();
+        let mut errors = Vec::new();
+
+        for event in processor
+            .parse_source(source, Path::new("."))
+            .process(ProcessorState::default())
+        {
+            match event.expect("failed to read file") {
+                Event::EnterFile { path, .. } if path != Path::new("") => paths.push(path),
+                Event::Error { error, .. } => errors.push(error.into_inner()),
+                Event::Directive { directive, .. } => errors.extend(
+                    directive
+                        .into_errors()
+                        .into_iter()
+                        .map(|error| error.into_inner()),
+                ),
+                _ => {}
+            }
+        }
+
+        (paths, errors)
+    }
+
+    #[test]
+    fn test_include_requires_extension() {
+        let mut processor = StdProcessor::new();
+        processor.add_named_string("/common.glsl", "float x;\n");
+
+        let (paths, errors) = process(&mut processor, "#include \"/common.glsl\"\n");
+
+        assert!(paths.is_empty());
+        assert_eq!(
+            errors,
+            vec![ErrorKind::Processing(
+                ProcessingErrorKind::IncludeNotSupported
+            )]
+        );
+    }
+
+    #[test]
+    fn test_include_not_found() {
+        let mut processor = StdProcessor::new();
+
+        let (paths, errors) = process(
+            &mut processor,
+            "#extension GL_GOOGLE_include_directive : require\n#include <missing.glsl>\n",
+        );
+
+        assert!(paths.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            ErrorKind::Processing(ProcessingErrorKind::IncludeNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_quoted_include_falls_back_to_system_paths() {
+        let mut processor = StdProcessor::new();
+        processor.system_paths_mut().push("/lib".into());
+        processor.add_named_string("/src/a.glsl", "#include \"common.glsl\"\n");
+        processor.add_named_string("/src/common.glsl", "float local;\n");
+        processor.add_named_string("/lib/common.glsl", "float system;\n");
+        processor.add_named_string("/lib/other.glsl", "float other;\n");
+
+        let (paths, errors) = process(
+            &mut processor,
+            "#extension GL_GOOGLE_include_directive : require\n#include \"/src/a.glsl\"\n#include \"other.glsl\"\n",
+        );
+
+        // Files next to the including file take precedence over the system paths
+        assert_eq!(errors, vec![]);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/src/a.glsl"),
+                PathBuf::from("/src/common.glsl"),
+                PathBuf::from("/lib/other.glsl")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arb_include_named_strings() {
+        let mut processor = StdProcessor::new();
+        processor.system_paths_mut().push("/lib".into());
+        processor.add_named_string("/lib/a.glsl", "#include \"b.glsl\"\n");
+        processor.add_named_string("/lib/b.glsl", "float b;\n");
+        processor.add_named_string("/lib/c.glsl", "float c;\n");
+
+        let (paths, errors) = process(
+            &mut processor,
+            "#extension GL_ARB_shading_language_include : require\n#include \"/lib/a.glsl\"\n#include <c.glsl>\n",
+        );
+
+        assert_eq!(errors, vec![]);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/lib/a.glsl"),
+                PathBuf::from("/lib/b.glsl"),
+                PathBuf::from("/lib/c.glsl")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_macros_across_includes() {
+        use crate::{last::LocatedIterator, processor::event::WarningKind};
+
+        let mut processor = StdProcessor::new();
+        processor.add_named_string("/defs.glsl", "#define USED 1\n#define UNUSED 2\n");
+
+        let mut iter = processor
+            .parse_source(
+                "#extension GL_ARB_shading_language_include : require\n#include \"/defs.glsl\"\nint x = USED;\n",
+                Path::new("."),
+            )
+            .process(ProcessorState::builder().warnings(true).finish());
+
+        let mut warnings = Vec::new();
+        while let Some(event) = iter.next() {
+            if let Event::Warning { warning } = event.expect("failed to read file") {
+                // The location is still available after the end of the input
+                iter.location();
+                warnings.push((warning.inner().clone(), warning.line()));
+            }
+        }
+
+        assert_eq!(
+            warnings,
+            vec![(
+                WarningKind::UnusedMacro {
+                    ident: "UNUSED".into()
+                },
+                1
+            )]
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+};
+
+use lang_util::{position::NodeSpan, FileId};
+
+use crate::types::path::ParsedPath;
+
+/// An #include or #moj_import directive which entered a file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IncludeEdge {
+    /// File containing the directive
+    pub from: FileId,
+    /// File entered by the directive
+    pub to: FileId,
+    /// Path of the directive, after macro substitution
+    pub path: ParsedPath,
+    /// Location of the directive
+    pub pos: NodeSpan,
+    /// Line of the directive in the including file, starting at 0
+    pub line: u32,
+}
+
+/// An #include or #moj_import directive which was skipped because it is in an inactive
+/// conditional block
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SkippedInclude {
+    /// File containing the directive
+    pub from: FileId,
+    /// Path of the directive, if it is written as a string literal
+    pub path: Option<ParsedPath>,
+    /// Location of the directive
+    pub pos: NodeSpan,
+    /// Line of the directive in the including file, starting at 0
+    pub line: u32,
+}
+
+/// Graph of the files entered through #include and #moj_import directives
+///
+/// See [`Processor::include_graph`](super::Processor::include_graph).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct IncludeGraph {
+    paths: HashMap<FileId, PathBuf>,
+    edges: Vec<IncludeEdge>,
+    skipped: Vec<SkippedInclude>,
+}
+
+impl IncludeGraph {
+    pub(super) fn new(
+        paths: HashMap<FileId, PathBuf>,
+        edges: Vec<IncludeEdge>,
+        skipped: Vec<SkippedInclude>,
+    ) -> Self {
+        Self {
+            paths,
+            edges,
+            skipped,
+        }
+    }
+
+    /// Get all the edges of this graph, in the order the directives were processed
+    pub fn edges(&self) -> &[IncludeEdge] {
+        &self.edges
+    }
+
+    /// Get the directives which were skipped because they are in inactive conditional blocks
+    pub fn skipped(&self) -> &[SkippedInclude] {
+        &self.skipped
+    }
+
+    /// Get the canonical path of a file. Source blocks have no path.
+    pub fn path(&self, file_id: FileId) -> Option<&Path> {
+        self.paths.get(&file_id).map(PathBuf::as_path)
+    }
+
+    /// Iterate over the edges starting at the given file
+    pub fn includes(&self, file_id: FileId) -> impl Iterator<Item = &IncludeEdge> {
+        self.edges.iter().filter(move |edge| edge.from == file_id)
+    }
+
+    /// Get the files entered from the given file, directly or not, in the order they are first
+    /// entered
+    pub fn dependencies(&self, root: FileId) -> Vec<FileId> {
+        let mut seen = HashSet::new();
+        seen.insert(root);
+
+        let mut result = Vec::new();
+        let mut stack = vec![root];
+
+        while let Some(file_id) = stack.pop() {
+            if file_id != root {
+                result.push(file_id);
+            }
+
+            // Push in reverse order so the first include is visited first
+            let includes: Vec<_> = self
+                .includes(file_id)
+                .filter(|edge| seen.insert(edge.to))
+                .map(|edge| edge.to)
+                .collect();
+            stack.extend(includes.into_iter().rev());
+        }
+
+        result
+    }
+
+    /// Get the paths of the given file and of the files it depends on
+    pub fn dependency_paths(&self, root: FileId) -> Vec<&Path> {
+        std::iter::once(root)
+            .chain(self.dependencies(root))
+            .filter_map(|file_id| self.path(file_id))
+            .collect()
+    }
+}
+
+/// Write a GCC/Ninja-compatible depfile
+///
+/// # Parameters
+///
+/// * `output`: writer for the depfile
+/// * `target`: path of the file built from the dependencies
+/// * `dependencies`: paths of the dependencies, usually obtained from
+///   [`IncludeGraph::dependency_paths`]
+///
+/// # Errors
+///
+/// Returns an [`io::ErrorKind::InvalidInput`] error if a path contains a newline, since it can't
+/// be represented in a depfile.
+pub fn write_depfile(
+    output: &mut dyn io::Write,
+    target: impl AsRef<Path>,
+    dependencies: impl IntoIterator<Item = impl AsRef<Path>>,
+) -> io::Result<()> {
+    write!(output, "{}:", escape_depfile_path(target.as_ref())?)?;
+
+    for dependency in dependencies {
+        write!(
+            output,
+            " \\\n  {}",
+            escape_depfile_path(dependency.as_ref())?
+        )?;
+    }
+
+    writeln!(output)
+}
+
+fn escape_depfile_path(path: &Path) -> io::Result<String> {
+    let path = path.to_string_lossy();
+    let mut result = String::with_capacity(path.len());
+    // Number of backslashes immediately preceding the current character
+    let mut backslashes = 0;
+
+    for c in path.chars() {
+        match c {
+            ' ' | '\t' => {
+                // Backslashes before whitespace are escapes, so the ones in the path must be
+                // doubled, in the same way as GCC
+                result.extend(std::iter::repeat('\\').take(backslashes + 1));
+                result.push(c);
+            }
+            '#' => result.push_str("\\#"),
+            '$' => result.push_str("$$"),
+            '\n' | '\r' => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("newlines are not supported in depfile paths: {:?}", path),
+                ));
+            }
+            _ => result.push(c),
+        }
+
+        if c == '\\' {
+            backslashes += 1;
+        } else {
+            backslashes = 0;
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::processor::{
+        fs::{MemoryFs, MemoryProcessor},
+        ProcessorState,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_include_graph() {
+        let fs: MemoryFs = [
+            (
+                "/shaders/main.glsl",
+                r#"#extension GL_GOOGLE_include_directive : require
+#include "a.glsl"
+#if 0
+#include "disabled.glsl"
+#endif
+#include "b.glsl"
+"#,
+            ),
+            ("/shaders/a.glsl", "#include \"common.glsl\"\n"),
+            ("/shaders/b.glsl", "\n#include \"common.glsl\"\n"),
+            ("/shaders/common.glsl", "float a;\n"),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut processor = MemoryProcessor::new_with_fs(fs);
+        let parsed = processor.parse(Path::new("/shaders/main.glsl")).unwrap();
+        let root = parsed.file_id();
+        for event in parsed.process(ProcessorState::default()) {
+            event.unwrap();
+        }
+
+        let graph = processor.include_graph();
+        let edges: Vec<_> = graph
+            .edges()
+            .iter()
+            .map(|edge| {
+                (
+                    graph.path(edge.from).unwrap().to_owned(),
+                    graph.path(edge.to).unwrap().to_owned(),
+                    edge.line,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            edges,
+            vec![
+                ("/shaders/main.glsl".into(), "/shaders/a.glsl".into(), 1),
+                ("/shaders/a.glsl".into(), "/shaders/common.glsl".into(), 0),
+                ("/shaders/main.glsl".into(), "/shaders/b.glsl".into(), 5),
+                ("/shaders/b.glsl".into(), "/shaders/common.glsl".into(), 1),
+            ]
+        );
+
+        assert_eq!(
+            graph.dependency_paths(root),
+            vec![
+                Path::new("/shaders/main.glsl"),
+                Path::new("/shaders/a.glsl"),
+                Path::new("/shaders/common.glsl"),
+                Path::new("/shaders/b.glsl"),
+            ]
+        );
+
+        let skipped = graph.skipped();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].from, root);
+        assert_eq!(skipped[0].line, 3);
+        assert_eq!(
+            skipped[0].path,
+            Some(ParsedPath {
+                path: "disabled.glsl".into(),
+                ty: crate::types::path::PathType::Quote,
+            })
+        );
+    }
+
+    #[test]
+    fn test_write_depfile() {
+        let mut output = Vec::new();
+        write_depfile(
+            &mut output,
+            "out dir/main.spv",
+            ["/shaders/main.glsl", "/shaders/#lib/$common.glsl"],
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "out\\ dir/main.spv: \\\n  /shaders/main.glsl \\\n  /shaders/\\#lib/$$common.glsl\n"
+        );
+
+        // Backslashes before spaces are doubled
+        let mut output = Vec::new();
+        write_depfile(&mut output, "main.spv", ["a\\ b", "c\\\\ d", "e\\f"]).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "main.spv: \\\n  a\\\\\\ b \\\n  c\\\\\\\\\\ d \\\n  e\\f\n"
+        );
+
+        // Newlines can't be represented
+        let error = write_depfile(&mut Vec::new(), "main.spv", ["a\nb"]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
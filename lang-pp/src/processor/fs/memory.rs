@@ -0,0 +1,354 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use super::{FileSystem, Processor, Std};
+
+/// Lexically normalize a path
+///
+/// This removes `.` components and duplicate separators, and resolves `..` components against
+/// the previous component. Leading `..` components are kept for relative paths, and dropped for
+/// absolute paths. The filesystem is never accessed, so symbolic links are not resolved.
+pub fn normalize_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut result = PathBuf::new();
+    // Number of normal components in result, which can be popped by a `..`
+    let mut depth = 0;
+
+    for component in path.as_ref().components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => {
+                result.push(component);
+            }
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth > 0 {
+                    result.pop();
+                    depth -= 1;
+                } else if !result.has_root() {
+                    result.push(component);
+                }
+            }
+            Component::Normal(name) => {
+                result.push(name);
+                depth += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// In-memory filesystem
+///
+/// Files are stored in a map from normalized virtual paths to their source. Paths are
+/// normalized using [`normalize_path`] when inserting and looking up files, so `/a/./b.glsl`,
+/// `/a//b.glsl` and `/a/c/../b.glsl` all refer to the same file.
+#[derive(Default, Debug, Clone)]
+pub struct MemoryFs {
+    /// Map from lookup keys to canonical paths and sources
+    files: HashMap<PathBuf, (PathBuf, String)>,
+    case_insensitive: bool,
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether paths are case-sensitive. Paths are case-sensitive by default.
+    ///
+    /// When paths are case-insensitive, the canonical path of a file is the path it was first
+    /// inserted with.
+    pub fn case_sensitive(self, case_sensitive: bool) -> Self {
+        let mut result = Self {
+            files: HashMap::with_capacity(self.files.len()),
+            case_insensitive: !case_sensitive,
+        };
+
+        for (canonical_path, source) in self.files.into_values() {
+            result.insert(canonical_path, source);
+        }
+
+        result
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        !self.case_insensitive
+    }
+
+    fn key(&self, path: &Path) -> PathBuf {
+        let path = normalize_path(path);
+
+        if self.case_insensitive {
+            path.to_string_lossy().to_lowercase().into()
+        } else {
+            path
+        }
+    }
+
+    /// Add a file to this filesystem, returning the previous source for this path if any
+    pub fn insert(&mut self, path: impl AsRef<Path>, source: impl Into<String>) -> Option<String> {
+        let path = path.as_ref();
+        let key = self.key(path);
+
+        match self.files.get_mut(&key) {
+            Some((_, existing)) => Some(std::mem::replace(existing, source.into())),
+            None => {
+                self.files
+                    .insert(key, (normalize_path(path), source.into()));
+                None
+            }
+        }
+    }
+
+    /// Remove a file from this filesystem, returning its source if it existed
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> Option<String> {
+        let key = self.key(path.as_ref());
+        self.files.remove(&key).map(|(_, source)| source)
+    }
+
+    /// Get the source of a file
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&str> {
+        self.files
+            .get(&self.key(path.as_ref()))
+            .map(|(_, source)| source.as_str())
+    }
+
+    /// Iterate over the canonical paths of the files in this filesystem
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files
+            .values()
+            .map(|(canonical_path, _)| canonical_path.as_path())
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{}: file not found", path.display()),
+        )
+    }
+}
+
+impl FileSystem for MemoryFs {
+    type Error = io::Error;
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Self::Error> {
+        self.files
+            .get(&self.key(path))
+            .map(|(canonical_path, _)| canonical_path.clone())
+            .ok_or_else(|| Self::not_found(path))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.contains_key(&self.key(path))
+    }
+
+    fn read(&self, path: &Path) -> Result<Cow<'_, str>, Self::Error> {
+        self.get(path)
+            .map(Cow::Borrowed)
+            .ok_or_else(|| Self::not_found(path))
+    }
+}
+
+impl<P: AsRef<Path>, S: Into<String>> FromIterator<(P, S)> for MemoryFs {
+    fn from_iter<T: IntoIterator<Item = (P, S)>>(iter: T) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<P: AsRef<Path>, S: Into<String>> Extend<(P, S)> for MemoryFs {
+    fn extend<T: IntoIterator<Item = (P, S)>>(&mut self, iter: T) {
+        for (path, source) in iter {
+            self.insert(path, source);
+        }
+    }
+}
+
+pub type MemoryProcessor = Processor<MemoryFs>;
+
+/// Filesystem layering in-memory files over another filesystem
+///
+/// Files in the overlay shadow the files of the base filesystem with the same path, which is
+/// useful to preprocess unsaved editor buffers. Overlay paths are matched after lexical
+/// normalization, while paths missing from the overlay are forwarded to the base filesystem
+/// as-is.
+#[derive(Default, Debug, Clone)]
+pub struct OverlayFs<F: FileSystem = Std> {
+    overlay: MemoryFs,
+    base: F,
+}
+
+impl<F: FileSystem> OverlayFs<F> {
+    pub fn new(base: F) -> Self {
+        Self {
+            overlay: MemoryFs::new(),
+            base,
+        }
+    }
+
+    pub fn with_overlay(base: F, overlay: MemoryFs) -> Self {
+        Self { overlay, base }
+    }
+
+    pub fn overlay(&self) -> &MemoryFs {
+        &self.overlay
+    }
+
+    pub fn overlay_mut(&mut self) -> &mut MemoryFs {
+        &mut self.overlay
+    }
+
+    pub fn base(&self) -> &F {
+        &self.base
+    }
+
+    pub fn into_inner(self) -> (MemoryFs, F) {
+        (self.overlay, self.base)
+    }
+}
+
+impl<F: FileSystem> FileSystem for OverlayFs<F> {
+    type Error = F::Error;
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, Self::Error> {
+        match self.overlay.canonicalize(path) {
+            Ok(canonical_path) => Ok(canonical_path),
+            Err(_) => self.base.canonicalize(path),
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.overlay.exists(path) || self.base.exists(path)
+    }
+
+    fn read(&self, path: &Path) -> Result<Cow<'_, str>, Self::Error> {
+        match self.overlay.get(path) {
+            Some(source) => Ok(Cow::Borrowed(source)),
+            None => self.base.read(path),
+        }
+    }
+}
+
+pub type OverlayProcessor = Processor<OverlayFs>;
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::processor::{event::Event, ProcessorState};
+
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("/a//b/./c.glsl"), Path::new("/a/b/c.glsl"));
+        assert_eq!(normalize_path("/a/b/../c.glsl"), Path::new("/a/c.glsl"));
+        assert_eq!(normalize_path("/../a.glsl"), Path::new("/a.glsl"));
+        assert_eq!(normalize_path("a/../../b.glsl"), Path::new("../b.glsl"));
+        assert_eq!(normalize_path("./a.glsl"), Path::new("a.glsl"));
+    }
+
+    #[test]
+    fn test_memory_fs() {
+        let mut fs: MemoryFs = [("/shaders/a.glsl", "float a;\n")].into_iter().collect();
+
+        assert!(fs.exists(Path::new("/shaders/./a.glsl")));
+        assert!(fs.exists(Path::new("/shaders/lib/../a.glsl")));
+        assert!(!fs.exists(Path::new("/shaders/A.glsl")));
+        assert_eq!(
+            fs.canonicalize(Path::new("/shaders//a.glsl")).unwrap(),
+            PathBuf::from("/shaders/a.glsl")
+        );
+        assert_eq!(
+            fs.canonicalize(Path::new("/shaders/b.glsl"))
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::NotFound
+        );
+
+        assert_eq!(
+            fs.insert("/shaders/a.glsl", "float b;\n").as_deref(),
+            Some("float a;\n")
+        );
+        assert_eq!(fs.len(), 1);
+        assert_eq!(fs.read(Path::new("/shaders/a.glsl")).unwrap(), "float b;\n");
+    }
+
+    #[test]
+    fn test_memory_fs_case_insensitive() {
+        let mut fs = MemoryFs::new().case_sensitive(false);
+        fs.insert("/Shaders/Common.glsl", "float a;\n");
+
+        assert!(fs.exists(Path::new("/shaders/common.GLSL")));
+        assert_eq!(
+            fs.canonicalize(Path::new("/SHADERS/common.glsl")).unwrap(),
+            PathBuf::from("/Shaders/Common.glsl")
+        );
+
+        // Switching back to case-sensitive paths keeps the canonical paths
+        let fs = fs.case_sensitive(true);
+        assert!(fs.exists(Path::new("/Shaders/Common.glsl")));
+        assert!(!fs.exists(Path::new("/shaders/common.glsl")));
+    }
+
+    #[test]
+    fn test_overlay_fs() {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+        let mut fs = OverlayFs::<Std>::default();
+        fs.overlay_mut().insert(&manifest, "float a;\n");
+        fs.overlay_mut().insert("/unsaved.glsl", "float b;\n");
+
+        assert_eq!(fs.read(&manifest).unwrap(), "float a;\n");
+        assert_eq!(fs.read(Path::new("/unsaved.glsl")).unwrap(), "float b;\n");
+
+        let build = Path::new(env!("CARGO_MANIFEST_DIR")).join("build.rs");
+        assert!(fs.exists(&build));
+        assert!(fs.read(&build).unwrap().contains("fn main()"));
+    }
+
+    #[test]
+    fn test_memory_processor() {
+        let fs: MemoryFs = [
+            (
+                "/shaders/main.glsl",
+                "#extension GL_GOOGLE_include_directive : require\n#include \"lib/../common.glsl\"\n",
+            ),
+            ("/shaders/common.glsl", "float a;\n"),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut processor = MemoryProcessor::new_with_fs(fs);
+        let paths: Vec<_> = processor
+            .parse(Path::new("/shaders/main.glsl"))
+            .unwrap()
+            .process(ProcessorState::default())
+            .filter_map(|event| match event.unwrap() {
+                Event::EnterFile { canonical_path, .. } => Some(canonical_path),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/shaders/main.glsl"),
+                PathBuf::from("/shaders/common.glsl")
+            ]
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! User-registered handlers for pragmas and custom directives
+//!
+//! Handlers are registered on a [ProcessorStateBuilder](super::ProcessorStateBuilder), using
+//! [pragma_handler](super::ProcessorStateBuilder::pragma_handler) for `#pragma name ...` and
+//! [directive_handler](super::ProcessorStateBuilder::directive_handler) for `#name ...`
+//! directives. They are only invoked for active directives.
+//!
+//! Pragma handlers are only consulted for pragmas which are not understood by the preprocessor
+//! (i.e. `STDGL`, `optimize` and `debug` can't be overridden), and directive handlers for
+//! directive names which are not built-in.
+
+use std::{any::Any, collections::HashMap, fmt, rc::Rc, sync::Arc};
+
+use rowan::NodeOrToken;
+
+use lang_util::{SmolStr, TextRange};
+
+use crate::{
+    parser::{SyntaxKind::*, SyntaxNode, SyntaxToken},
+    util::Unescaped,
+};
+
+/// Structured data produced by a handler
+///
+/// The data is shared between clones, and two instances are equal if they point to the same
+/// data.
+#[derive(Clone)]
+pub struct HandlerData(Arc<dyn Any + Send + Sync>);
+
+impl HandlerData {
+    pub fn new<T: Any + Send + Sync>(data: T) -> Self {
+        Self(Arc::new(data))
+    }
+
+    /// Get a reference to the data, if it is of type `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl fmt::Debug for HandlerData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HandlerData(..)")
+    }
+}
+
+impl PartialEq for HandlerData {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for HandlerData {}
+
+/// Pragma or directive passed to a handler
+#[derive(Debug)]
+pub struct HandlerInput<'a> {
+    name: &'a str,
+    node: &'a SyntaxNode,
+    args: Vec<SyntaxToken>,
+}
+
+impl<'a> HandlerInput<'a> {
+    pub(crate) fn new(name: &'a str, node: &'a SyntaxNode, args: Vec<SyntaxToken>) -> Self {
+        Self { name, node, args }
+    }
+
+    /// Name of the pragma or directive
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Syntax node of the whole directive
+    pub fn node(&self) -> &SyntaxNode {
+        self.node
+    }
+
+    /// Tokens following the name, excluding whitespace and comments
+    pub fn args(&self) -> &[SyntaxToken] {
+        &self.args
+    }
+
+    /// Text of the tokens following the name, with line continuations removed
+    pub fn args_text(&self) -> String {
+        let (first, last) = match (self.args.first(), self.args.last()) {
+            (Some(first), Some(last)) => (first.text_range().start(), last.text_range().end()),
+            _ => return String::new(),
+        };
+
+        self.node
+            .descendants_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+            .filter(|token| token.text_range().start() >= first && token.text_range().end() <= last)
+            .map(|token| Unescaped::new(token.text()).to_string().into_owned())
+            .collect()
+    }
+
+    /// Range of the whole directive, to report errors about it
+    pub fn text_range(&self) -> TextRange {
+        self.node.text_range()
+    }
+}
+
+/// Result of a handler
+#[derive(Debug, Default, Clone)]
+pub struct HandlerOutput {
+    data: Option<HandlerData>,
+    source: Option<String>,
+    errors: Vec<(String, TextRange)>,
+}
+
+impl HandlerOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach structured data to the directive event
+    pub fn data<T: Any + Send + Sync>(self, data: T) -> Self {
+        Self {
+            data: Some(HandlerData::new(data)),
+            ..self
+        }
+    }
+
+    /// Inject the tokens of `source` in place of the directive
+    ///
+    /// The injected tokens are not subject to macro expansion, and are located at the
+    /// directive. Sources containing preprocessor directives or which fail to parse are not
+    /// injected, and a [HandlerError](super::event::ProcessingErrorKind::HandlerError) is
+    /// reported instead.
+    pub fn inject(self, source: impl Into<String>) -> Self {
+        Self {
+            source: Some(source.into()),
+            ..self
+        }
+    }
+
+    /// Report an error at the given range of the directive
+    pub fn error(mut self, message: impl Into<String>, pos: TextRange) -> Self {
+        self.errors.push((message.into(), pos));
+        self
+    }
+
+    pub(crate) fn into_inner(
+        self,
+    ) -> (
+        Option<HandlerData>,
+        Option<String>,
+        Vec<(String, TextRange)>,
+    ) {
+        (self.data, self.source, self.errors)
+    }
+}
+
+/// Handler for a pragma or a custom directive
+pub type Handler = Rc<dyn Fn(&HandlerInput) -> HandlerOutput>;
+
+#[derive(Default, Clone)]
+pub(crate) struct Handlers {
+    pragmas: HashMap<SmolStr, Handler>,
+    directives: HashMap<SmolStr, Handler>,
+}
+
+impl Handlers {
+    pub fn add_pragma(&mut self, name: SmolStr, handler: Handler) {
+        self.pragmas.insert(name, handler);
+    }
+
+    pub fn add_directive(&mut self, name: SmolStr, handler: Handler) {
+        self.directives.insert(name, handler);
+    }
+
+    pub fn pragma(&self, name: &str) -> Option<&Handler> {
+        self.pragmas.get(name)
+    }
+
+    pub fn directive(&self, name: &str) -> Option<&Handler> {
+        self.directives.get(name)
+    }
+}
+
+impl fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handlers")
+            .field("pragmas", &self.pragmas.keys().collect::<Vec<_>>())
+            .field("directives", &self.directives.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl PartialEq for Handlers {
+    fn eq(&self, other: &Self) -> bool {
+        fn same(a: &HashMap<SmolStr, Handler>, b: &HashMap<SmolStr, Handler>) -> bool {
+            a.len() == b.len()
+                && a.iter().all(|(name, handler)| {
+                    b.get(name)
+                        .map(|other| Rc::ptr_eq(handler, other))
+                        .unwrap_or(false)
+                })
+        }
+
+        same(&self.pragmas, &other.pragmas) && same(&self.directives, &other.directives)
+    }
+}
+
+impl Eq for Handlers {}
+
+/// Split the tokens of a directive into its name and arguments
+///
+/// The name is the first identifier, and the arguments are the remaining tokens, excluding
+/// whitespace and comments.
+pub(crate) fn name_and_args(node: &SyntaxNode) -> Option<(SmolStr, Vec<SyntaxToken>)> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|token| !token.kind().is_whitespace() && token.kind() != HASH);
+
+    let name = tokens.next().filter(|token| token.kind() == IDENT_KW)?;
+    Some((
+        Unescaped::new(name.text()).to_string().into(),
+        tokens.collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::processor::{
+        event::{DirectiveKind, Event, ProcessingErrorKind},
+        nodes::ParsedPragma,
+        str::process,
+        ProcessorState, ProcessorStateBuilder,
+    };
+
+    use super::HandlerOutput;
+
+    fn events(input: &str, state: ProcessorStateBuilder) -> Vec<Event> {
+        process(input, state.finish())
+            .map(|event| event.unwrap())
+            .collect()
+    }
+
+    fn tokens(events: &[Event]) -> String {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Token {
+                    token,
+                    masked: false,
+                } => Some(token.text()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn errors(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Error {
+                    error,
+                    masked: false,
+                } => Some(vec![error.to_string()]),
+                Event::Directive {
+                    directive,
+                    masked: false,
+                } => Some(directive.errors().iter().map(ToString::to_string).collect()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn test_pragma_handler() {
+        let state = ProcessorState::builder().pragma_handler("shader_feature", |input| {
+            HandlerOutput::new().data(
+                input
+                    .args()
+                    .iter()
+                    .map(|token| token.text().to_owned())
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        let events = events(
+            "#pragma shader_feature A B C\n#pragma optimize(on)\n#pragma other\n",
+            state,
+        );
+
+        let pragmas: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Directive { directive, .. } => match directive.kind() {
+                    DirectiveKind::Pragma(pragma) => Some(pragma.value().clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(pragmas.len(), 3);
+        match &pragmas[0] {
+            ParsedPragma::Custom { name, data } => {
+                assert_eq!(name, "shader_feature");
+                assert_eq!(
+                    data.as_ref().unwrap().downcast_ref::<Vec<String>>(),
+                    Some(&vec!["A".to_owned(), "B".to_owned(), "C".to_owned()])
+                );
+            }
+            other => panic!("unexpected pragma: {:?}", other),
+        }
+
+        // Built-in and unregistered pragmas are not affected
+        assert_eq!(pragmas[1], ParsedPragma::Optimize(true));
+        assert!(matches!(pragmas[2], ParsedPragma::Unknown(_)));
+        assert_eq!(errors(&events), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_directive_handler() {
+        let calls = Rc::new(Cell::new(0));
+
+        let state = ProcessorState::builder().directive_handler("import", {
+            let calls = calls.clone();
+            move |input| {
+                calls.set(calls.get() + 1);
+                HandlerOutput::new()
+                    .data(input.args_text())
+                    .inject(format!("float {}_x;", input.args_text()))
+            }
+        });
+
+        let events = events(
+            "#import module\n#if 0\n#import masked\n#endif\nint y;\n#unknown\n",
+            state,
+        );
+
+        // Masked directives are not processed
+        assert_eq!(calls.get(), 1);
+        assert_eq!(tokens(&events), "float module_x;int y;\n");
+
+        let custom: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Directive { directive, masked } => match directive.kind() {
+                    DirectiveKind::Custom(custom) => Some((
+                        custom.name().to_owned(),
+                        custom
+                            .data()
+                            .and_then(|data| data.downcast_ref::<String>())
+                            .cloned(),
+                        *masked,
+                    )),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            custom,
+            vec![
+                ("import".to_owned(), Some("module".to_owned()), false),
+                ("import".to_owned(), None, true)
+            ]
+        );
+
+        // Unregistered directives are still errors
+        assert_eq!(errors(&events).len(), 1);
+    }
+
+    #[test]
+    fn test_handler_errors() {
+        let state =
+            ProcessorState::builder().pragma_handler("target", |input| match input.args() {
+                [version] if version.text().parse::<f32>().is_ok() => HandlerOutput::new(),
+                _ => HandlerOutput::new().error("expected a version number", input.text_range()),
+            });
+
+        let events = events("#pragma target 4.5\n#pragma target\n", state);
+
+        let errors: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Error { error, .. } => Some((error.inner().clone(), error.line())),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![(
+                ProcessingErrorKind::HandlerError {
+                    directive: "pragma".into(),
+                    message: "expected a version number".to_owned(),
+                }
+                .into(),
+                1
+            )]
+        );
+    }
+
+    #[test]
+    fn test_inject_rejects_directives() {
+        let state = ProcessorState::builder().directive_handler("inline", |input| {
+            match input.args_text().as_str() {
+                "define" => HandlerOutput::new().inject("#define B 1\nint b;\n"),
+                args => HandlerOutput::new().inject(format!("{}\n", args)),
+            }
+        });
+
+        let events = events("#inline int a;\n#inline define\nint c;\n", state);
+
+        // Only the valid injection and the regular tokens are emitted
+        assert_eq!(tokens(&events), "int a;\nint c;\n");
+        assert_eq!(
+            errors(&events),
+            vec!["0:2:1: '#inline' : injected source must not contain preprocessor directives"]
+        );
+    }
+}
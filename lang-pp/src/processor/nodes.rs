@@ -9,7 +9,7 @@ use lang_util::{FileId, SmolStr};
 use crate::{
     exts::names::ExtNameAtom,
     parser::{SyntaxKind::*, SyntaxNode, SyntaxToken},
-    processor::expr::{EvalResult, ExprEvaluator},
+    processor::expr::{EvalResult, ExprEvaluator, UndefinedPolicy},
     types::{
         path::{ParsedPath, PathType},
         Token,
@@ -19,8 +19,9 @@ use crate::{
 
 use super::{
     definition::{trim_ws, MacroInvocation},
-    event::{Event, OutputToken, SendEvent},
+    event::{Event, OutputToken, SendEvent, TokenLike},
     expand::ExpandLocation,
+    handlers::HandlerData,
     ProcessorState,
 };
 
@@ -580,6 +581,46 @@ impl Define {
     pub fn protected(&self) -> bool {
         self.protected
     }
+
+    /// Return true if `other` is an identical redefinition of this macro
+    ///
+    /// Redefinitions are identical if they have the same parameters and replacement list, where
+    /// whitespace separations are compared regardless of their length.
+    pub fn is_identical(&self, other: &Define) -> bool {
+        fn replacement_list(body: &SyntaxNode) -> Vec<Option<String>> {
+            let mut tokens: Vec<Option<String>> = Vec::new();
+
+            for token in body
+                .descendants_with_tokens()
+                .filter_map(NodeOrToken::into_token)
+            {
+                if token.kind().is_trivia() || token.kind().is_newline() {
+                    if tokens.last().map(Option::is_some).unwrap_or(false) {
+                        tokens.push(None);
+                    }
+                } else {
+                    tokens.push(Some(token.text().to_owned()));
+                }
+            }
+
+            if let Some(None) = tokens.last() {
+                tokens.pop();
+            }
+
+            tokens
+        }
+
+        match (&self.kind, &other.kind) {
+            (DefineKind::Object(a), DefineKind::Object(b)) => {
+                replacement_list(a.body()) == replacement_list(b.body())
+            }
+            (DefineKind::Function(a), DefineKind::Function(b)) => {
+                a.arg_names() == b.arg_names()
+                    && replacement_list(a.body()) == replacement_list(b.body())
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
@@ -739,6 +780,98 @@ impl TryFrom<(FileId, SyntaxNode)> for Error {
     }
 }
 
+/// Error encountered while parsing the path of an #include or #moj_import directive
+enum PathError {
+    Missing,
+    Malformed { tokens: Vec<SendEvent> },
+    ExtraTokens { tokens: Vec<OutputToken> },
+    InvalidLiteral { token: OutputToken },
+}
+
+fn parse_path(
+    file_id: FileId,
+    path: &SyntaxNode,
+    current_state: &ProcessorState,
+    location: &ExpandLocation,
+) -> Result<ParsedPath, PathError> {
+    // Perform macro substitution
+    let tokens = path
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .map(|token| (token, file_id))
+        .collect();
+    let subs_events = MacroInvocation::substitute_vec(current_state, tokens, location);
+
+    // Make sure they are all tokens
+    if !subs_events.iter().all(Event::is_token) {
+        return Err(PathError::Malformed {
+            tokens: subs_events.into_iter().map(Into::into).collect(),
+        });
+    }
+
+    let subs_tokens: Vec<_> = subs_events
+        .into_iter()
+        .filter_map(Event::into_token)
+        .collect();
+
+    // Discard trivial tokens
+    let subs_tokens = trim_ws(&subs_tokens);
+
+    // By now, the include should either be a quote string or an angle string, and this should
+    // be the only token
+    if subs_tokens.is_empty() {
+        return Err(PathError::Missing);
+    } else if subs_tokens.len() > 1 {
+        return Err(PathError::ExtraTokens {
+            tokens: subs_tokens.to_vec(),
+        });
+    }
+
+    // unwrap: we just checked there is one
+    let first_token = subs_tokens.first().unwrap();
+    let ty = match first_token.kind() {
+        ANGLE_STRING => PathType::Angle,
+        QUOTE_STRING => PathType::Quote,
+        _ => {
+            return Err(PathError::InvalidLiteral {
+                token: first_token.clone(),
+            });
+        }
+    };
+
+    let text = first_token.text();
+    let text = &text[1..text.len() - 1];
+
+    Ok(ParsedPath {
+        path: text.to_string(),
+        ty,
+    })
+}
+
+fn parse_raw_path(path: &SyntaxNode) -> Option<ParsedPath> {
+    let mut tokens = path
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .filter(|token| !token.kind().is_whitespace());
+
+    let token = tokens.next()?;
+    if tokens.next().is_some() {
+        return None;
+    }
+
+    let ty = match token.kind() {
+        ANGLE_STRING => PathType::Angle,
+        QUOTE_STRING => PathType::Quote,
+        _ => return None,
+    };
+
+    let text = token.text();
+    Some(ParsedPath {
+        path: text[1..text.len() - 1].to_string(),
+        ty,
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MojImport {
     file_id: FileId,
@@ -751,59 +884,14 @@ impl MojImport {
         current_state: &ProcessorState,
         location: &ExpandLocation,
     ) -> Result<ParsedPath, MojImportError> {
-        // Perform macro substitution
-        let tokens = self
-            .path
-            .children_with_tokens()
-            .filter_map(NodeOrToken::into_token)
-            .map(|token| (token, self.file_id))
-            .collect();
-        let subs_events = MacroInvocation::substitute_vec(current_state, tokens, location);
-
-        // Make sure they are all tokens
-        if !subs_events.iter().all(Event::is_token) {
-            return Err(MojImportError::MalformedPath {
-                tokens: subs_events.into_iter().map(Into::into).collect(),
-            });
-        }
-
-        let subs_tokens: Vec<_> = subs_events
-            .into_iter()
-            .filter_map(Event::into_token)
-            .collect();
-
-        // Discard trivial tokens
-        let subs_tokens = trim_ws(&subs_tokens);
-
-        // By now, the include should either be a quote string or an angle string, and this should
-        // be the only token
-        if subs_tokens.is_empty() {
-            return Err(MojImportError::MissingPath);
-        } else if subs_tokens.len() > 1 {
-            return Err(MojImportError::ExtraTokens {
-                tokens: subs_tokens.to_vec(),
-            });
-        }
-
-        // unwrap: we just checked there is one
-        let first_token = subs_tokens.first().unwrap();
-        let ty = match first_token.kind() {
-            ANGLE_STRING => PathType::Angle,
-            QUOTE_STRING => PathType::Quote,
-            _ => {
-                return Err(MojImportError::InvalidPathLiteral {
-                    token: first_token.clone(),
-                });
-            }
-        };
-
-        let text = first_token.text();
-        let text = &text[1..text.len() - 1];
+        parse_path(self.file_id, &self.path, current_state, location).map_err(Into::into)
+    }
 
-        Ok(ParsedPath {
-            path: text.to_string(),
-            ty,
-        })
+    /// Get the path of this directive, if it is written as a string literal
+    ///
+    /// Contrary to [`MojImport::path`], this does not perform macro substitution.
+    pub fn raw_path(&self) -> Option<ParsedPath> {
+        parse_raw_path(&self.path)
     }
 }
 
@@ -819,6 +907,17 @@ pub enum MojImportError {
     InvalidPathLiteral { token: OutputToken },
 }
 
+impl From<PathError> for MojImportError {
+    fn from(value: PathError) -> Self {
+        match value {
+            PathError::Missing => Self::MissingPath,
+            PathError::Malformed { tokens } => Self::MalformedPath { tokens },
+            PathError::ExtraTokens { tokens } => Self::ExtraTokens { tokens },
+            PathError::InvalidLiteral { token } => Self::InvalidPathLiteral { token },
+        }
+    }
+}
+
 impl TryFrom<(FileId, SyntaxNode)> for MojImport {
     type Error = MojImportError;
 
@@ -833,6 +932,66 @@ impl TryFrom<(FileId, SyntaxNode)> for MojImport {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Include {
+    file_id: FileId,
+    path: SyntaxNode,
+}
+
+impl Include {
+    pub fn path(
+        &self,
+        current_state: &ProcessorState,
+        location: &ExpandLocation,
+    ) -> Result<ParsedPath, IncludeError> {
+        parse_path(self.file_id, &self.path, current_state, location).map_err(Into::into)
+    }
+
+    /// Get the path of this directive, if it is written as a string literal
+    ///
+    /// Contrary to [`Include::path`], this does not perform macro substitution.
+    pub fn raw_path(&self) -> Option<ParsedPath> {
+        parse_raw_path(&self.path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum IncludeError {
+    #[error("missing path for #include directive")]
+    MissingPath,
+    #[error("malformed path")]
+    MalformedPath { tokens: Vec<SendEvent> },
+    #[error("extra tokens in #include path")]
+    ExtraTokens { tokens: Vec<OutputToken> },
+    #[error("invalid path literal")]
+    InvalidPathLiteral { token: OutputToken },
+}
+
+impl From<PathError> for IncludeError {
+    fn from(value: PathError) -> Self {
+        match value {
+            PathError::Missing => Self::MissingPath,
+            PathError::Malformed { tokens } => Self::MalformedPath { tokens },
+            PathError::ExtraTokens { tokens } => Self::ExtraTokens { tokens },
+            PathError::InvalidLiteral { token } => Self::InvalidPathLiteral { token },
+        }
+    }
+}
+
+impl TryFrom<(FileId, SyntaxNode)> for Include {
+    type Error = IncludeError;
+
+    fn try_from((file_id, value): (FileId, SyntaxNode)) -> Result<Self, Self::Error> {
+        Ok(Self {
+            file_id,
+            path: value
+                .children()
+                .find(|node| node.kind() == PP_INCLUDE_PATH)
+                .ok_or(Self::Error::MissingPath)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Line {
     file_id: FileId,
@@ -918,10 +1077,8 @@ impl Line {
                 match token {
                     EvalResult::Constant(value) => Ok(ParsedLine::LineAndFileNumber(
                         line_number,
-                        if let Ok(value) =
-                            value.and_then(|val| if val >= 0 { Ok(val as _) } else { Err(()) })
-                        {
-                            Ok(value)
+                        if let Some(value) = value.as_ref().ok().filter(|val| **val >= 0) {
+                            Ok(*value as _)
                         } else {
                             Err(LineError::InvalidPath {
                                 token: token.clone(),
@@ -997,20 +1154,51 @@ pub enum IfEvalError {
     InvalidExpr { token: EvalResult },
 }
 
+/// Identifiers involved in the evaluation of an `#if` or `#elif` condition
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IfIdents {
+    /// Macros which were expanded or tested by the condition
+    pub used: Vec<SmolStr>,
+    /// Identifiers which were not defined macros, and were evaluated as 0
+    pub undefined: Vec<SmolStr>,
+}
+
 fn eval_inner(
     definition_file_id: FileId,
     body: &SyntaxNode,
     current_state: &ProcessorState,
     location: &ExpandLocation,
+    idents: &mut IfIdents,
 ) -> (bool, Option<IfEvalError>) {
     // Perform macro substitution
-    let tokens = body
+    let tokens: Vec<_> = body
         .children_with_tokens()
         .filter_map(NodeOrToken::into_token)
         .map(|token| (token, definition_file_id))
         .collect();
+
+    // Macros tested or expanded directly by the condition
+    for (token, _) in tokens.iter().filter(|(token, _)| token.kind() == IDENT_KW) {
+        let name: SmolStr = Unescaped::new(token.text()).to_string().into();
+        if current_state.get_definition(&name).is_some() && !idents.used.contains(&name) {
+            idents.used.push(name);
+        }
+    }
+
     let subs_events = MacroInvocation::substitute_vec(current_state, tokens, location);
 
+    // Macros expanded while expanding the condition
+    for expansion in subs_events
+        .iter()
+        .filter_map(Event::as_token)
+        .filter_map(TokenLike::expansions)
+        .flat_map(|expansions| expansions.iter())
+    {
+        if !idents.used.contains(&expansion.name) {
+            idents.used.push(expansion.name.clone());
+        }
+    }
+
     // Make sure they are all tokens
     if !subs_events.iter().all(Event::is_token) {
         return (
@@ -1022,11 +1210,13 @@ fn eval_inner(
     }
 
     // Evalute the expressions in the line directive
-    let eval_results: Vec<_> = ExprEvaluator::new(
+    let mut evaluator = ExprEvaluator::new(
         subs_events.iter().filter_map(Event::as_token),
         current_state,
     )
-    .collect();
+    .undefined_policy(UndefinedPolicy::Warn);
+    let eval_results: Vec<_> = evaluator.by_ref().collect();
+    idents.undefined = evaluator.take_undefined();
 
     // Check that we have at least "something" to evaluate
     if eval_results.is_empty() {
@@ -1059,9 +1249,16 @@ fn eval_if<E: From<IfEvalError>>(
     body: &SyntaxNode,
     current_state: &ProcessorState,
     location: &ExpandLocation,
-) -> (bool, Option<E>) {
-    let (result, e) = eval_inner(definition_file_id, body, current_state, location);
-    (result, e.map(E::from))
+) -> (bool, Option<E>, IfIdents) {
+    let mut idents = IfIdents::default();
+    let (result, e) = eval_inner(
+        definition_file_id,
+        body,
+        current_state,
+        location,
+        &mut idents,
+    );
+    (result, e.map(E::from), idents)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
@@ -1084,6 +1281,16 @@ impl If {
         current_state: &ProcessorState,
         location: &ExpandLocation,
     ) -> (bool, Option<IfError>) {
+        let (result, error, _) = self.eval_idents(current_state, location);
+        (result, error)
+    }
+
+    /// Evaluate the condition, returning the identifiers involved in its evaluation
+    pub fn eval_idents(
+        &self,
+        current_state: &ProcessorState,
+        location: &ExpandLocation,
+    ) -> (bool, Option<IfError>, IfIdents) {
         eval_if(self.file_id, &self.body, current_state, location)
     }
 }
@@ -1114,6 +1321,16 @@ impl Elif {
         current_state: &ProcessorState,
         location: &ExpandLocation,
     ) -> (bool, Option<ElifError>) {
+        let (result, error, _) = self.eval_idents(current_state, location);
+        (result, error)
+    }
+
+    /// Evaluate the condition, returning the identifiers involved in its evaluation
+    pub fn eval_idents(
+        &self,
+        current_state: &ProcessorState,
+        location: &ExpandLocation,
+    ) -> (bool, Option<ElifError>, IfIdents) {
         eval_if(self.file_id, &self.body, current_state, location)
     }
 }
@@ -1183,6 +1400,13 @@ impl Pragma {
         self.raw.as_str()
     }
 
+    pub(crate) fn with_custom(self, name: SmolStr, data: Option<HandlerData>) -> Self {
+        Self {
+            value: ParsedPragma::Custom { name, data },
+            ..self
+        }
+    }
+
     fn parse_function_pragma(tokens: &[SyntaxToken]) -> Option<bool> {
         if tokens.len() == 4 {
             let value = Unescaped::new(tokens[2].text()).to_string();
@@ -1206,6 +1430,11 @@ pub enum ParsedPragma {
     Optimize(bool),
     Debug(bool),
     Unknown(SyntaxNode),
+    /// Pragma processed by a user-registered handler
+    Custom {
+        name: SmolStr,
+        data: Option<HandlerData>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
@@ -1275,3 +1504,27 @@ impl TryFrom<(FileId, SyntaxNode)> for Pragma {
         })
     }
 }
+
+/// A custom directive, processed by a user-registered handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Custom {
+    name: SmolStr,
+    data: Option<HandlerData>,
+}
+
+impl Custom {
+    pub fn new(name: SmolStr, data: Option<HandlerData>) -> Self {
+        Self { name, data }
+    }
+
+    /// Name of the directive
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Data produced by the handler. This is `None` if the directive was masked, or the handler
+    /// didn't produce any.
+    pub fn data(&self) -> Option<&HandlerData> {
+        self.data.as_ref()
+    }
+}
@@ -0,0 +1,713 @@
+//! Partial preprocessing
+//!
+//! A [PartialProcessor] resolves the conditional groups of a source which only depend on known
+//! macros, and keeps the other ones intact so they can be resolved later by another
+//! preprocessor. This is useful to bake a configuration into a shader library while leaving the
+//! switches of its consumers untouched.
+
+use std::collections::HashSet;
+
+use rowan::NodeOrToken;
+
+use lang_util::{FileId, SmolStr, TextRange};
+
+use crate::{
+    parser::{self, Ast, SyntaxKind::*, SyntaxNode, SyntaxToken},
+    util::Unescaped,
+};
+
+use super::{
+    definition::{Definition, MacroInvocation},
+    event::{Error, ErrorKind, Event, OutputToken, ProcessingErrorKind},
+    expand::ExpandLocation,
+    expr::{EvalResult, ExprEvaluator},
+    nodes::{Define, DirectiveResult, IfDef, IfNDef, Undef},
+    ProcessorState,
+};
+
+/// How macros which are neither defined nor known to be undefined are handled
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownPolicy {
+    /// Conditional groups which depend on unknown macros are kept in the output
+    #[default]
+    Keep,
+    /// Unknown macros are considered undefined, so all conditional groups are resolved
+    Undefined,
+}
+
+/// Result of partially preprocessing a source
+#[derive(Debug, Clone)]
+pub struct PartialOutput {
+    /// Preprocessed text
+    pub text: String,
+    /// Errors encountered while processing the source
+    pub errors: Vec<Error>,
+}
+
+/// Preprocessor which only resolves conditional groups whose conditions are decidable
+///
+/// The known macros are the ones defined in the initial [ProcessorState], the ones declared
+/// using [PartialProcessor::undefined], and the ones defined or undefined by the source outside
+/// of kept conditional groups. Known macros are expanded everywhere in the output, including the
+/// conditions of kept groups. `#define` and `#undef` directives are kept in the output, as well
+/// as all other directives.
+///
+/// Conditions which cannot be evaluated are kept so the next preprocessor can report them.
+#[derive(Debug, Clone)]
+pub struct PartialProcessor {
+    state: ProcessorState,
+    undefined: HashSet<SmolStr>,
+    unknown_policy: UnknownPolicy,
+}
+
+impl PartialProcessor {
+    /// Create a new partial processor
+    ///
+    /// # Parameters
+    ///
+    /// * `state`: state holding the known macro definitions. The `__LINE__`, `__FILE__` and
+    ///   `__VERSION__` macros are left for the next preprocessor.
+    pub fn new(state: ProcessorState) -> Self {
+        let mut state = state;
+        state
+            .definitions
+            .retain(|_, definition| matches!(definition, Definition::Regular(_, _)));
+
+        Self {
+            state,
+            undefined: HashSet::new(),
+            unknown_policy: UnknownPolicy::default(),
+        }
+    }
+
+    /// Declare a macro as known to be undefined
+    pub fn undefined(mut self, name: impl Into<SmolStr>) -> Self {
+        let name = name.into();
+        self.state.definitions.remove(&name);
+        self.undefined.insert(name);
+        self
+    }
+
+    /// Set how unknown macros are handled
+    pub fn unknown_policy(self, unknown_policy: UnknownPolicy) -> Self {
+        Self {
+            unknown_policy,
+            ..self
+        }
+    }
+
+    /// Partially preprocess a string
+    pub fn process_str(&self, input: &str) -> PartialOutput {
+        self.process(FileId::new(0), parser::Parser::new(input).parse())
+    }
+
+    /// Partially preprocess a parsed source
+    ///
+    /// `#include` directives are kept as-is, the included files are not processed.
+    pub fn process(&self, file_id: FileId, ast: Ast) -> PartialOutput {
+        let (root, parse_errors, line_map) = ast.into_inner();
+
+        let mut run = PartialRun {
+            state: self.state.clone(),
+            undefined: self.undefined.clone(),
+            unknown_policy: self.unknown_policy,
+            location: ExpandLocation::with_line_map(file_id, line_map),
+            groups: Vec::new(),
+            pending: Vec::new(),
+            output: PartialOutput {
+                text: String::new(),
+                errors: Vec::new(),
+            },
+        };
+
+        for error in parse_errors {
+            let pos = error.pos();
+            run.push_error(error.into_inner(), pos);
+        }
+
+        for node_or_token in root.children_with_tokens() {
+            match node_or_token {
+                NodeOrToken::Node(node) => {
+                    run.flush();
+                    run.handle_node(node);
+                }
+                NodeOrToken::Token(token) => {
+                    if run.emitting() {
+                        run.pending.push(token);
+                    }
+                }
+            }
+        }
+
+        run.flush();
+
+        // Close the groups left open by the source
+        while let Some(group) = run.groups.pop() {
+            run.push_error(ProcessingErrorKind::MissingEndIf, group.pos);
+
+            if group.opened {
+                run.output.text.push_str("#endif\n");
+            }
+        }
+
+        run.output
+    }
+}
+
+/// Conditional group being processed
+#[derive(Debug, Clone, Copy)]
+struct Group {
+    /// Range of the directive opening this group
+    pos: TextRange,
+    /// An #if directive for this group was written to the output
+    opened: bool,
+    /// A branch of this group was taken for sure, the following ones are dropped
+    done: bool,
+    /// The current branch is written to the output
+    emitting: bool,
+}
+
+impl Group {
+    fn dropped(pos: TextRange) -> Self {
+        Self {
+            pos,
+            opened: false,
+            done: true,
+            emitting: false,
+        }
+    }
+}
+
+struct PartialRun {
+    state: ProcessorState,
+    undefined: HashSet<SmolStr>,
+    unknown_policy: UnknownPolicy,
+    location: ExpandLocation,
+    groups: Vec<Group>,
+    /// Text tokens waiting for macro expansion
+    pending: Vec<SyntaxToken>,
+    output: PartialOutput,
+}
+
+impl PartialRun {
+    fn emitting(&self) -> bool {
+        self.groups.iter().all(|group| group.emitting)
+    }
+
+    /// Return `true` if the current position is in a kept conditional group
+    fn uncertain(&self) -> bool {
+        self.groups.iter().any(|group| group.opened)
+    }
+
+    fn is_known(&self, name: &str) -> bool {
+        // Built-in macros are always left to the next preprocessor
+        if matches!(name, "__LINE__" | "__FILE__" | "__VERSION__") {
+            return false;
+        }
+
+        self.unknown_policy == UnknownPolicy::Undefined
+            || self.undefined.contains(name)
+            || self.state.definitions.contains_key(name)
+    }
+
+    fn push_error(&mut self, error: impl Into<ErrorKind>, pos: TextRange) {
+        self.output.errors.push(
+            Error::builder()
+                .pos(pos)
+                .resolve_file(&self.location)
+                .finish(error.into()),
+        );
+    }
+
+    fn write_events(&mut self, events: Vec<Event>) {
+        for event in events {
+            match event {
+                Event::Token { token, .. } => self.output.text.push_str(token.text()),
+                Event::Error { error, .. } => self.output.errors.push(error),
+                _ => {}
+            }
+        }
+    }
+
+    /// Expand the known macros in the pending text tokens, and write them to the output
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let file_id = self.location.current_file();
+        let tokens = std::mem::take(&mut self.pending)
+            .into_iter()
+            .map(|token| (token, file_id))
+            .collect();
+
+        let events = MacroInvocation::substitute_vec(&self.state, tokens, &self.location);
+        self.write_events(events);
+    }
+
+    fn write_node(&mut self, node: &SyntaxNode) {
+        self.output.text.push_str(&node.text().to_string());
+    }
+
+    /// Record the effect of a `#define` or `#undef` directive on the known macros
+    fn update_definition(&mut self, name: SmolStr, definition: Option<Define>) {
+        if self.uncertain() {
+            // The directive may not be processed, so the macro is now unknown
+            self.state.definitions.remove(&name);
+            self.undefined.remove(&name);
+        } else if let Some(define) = definition {
+            self.undefined.remove(&name);
+            self.state.definitions.insert(
+                name,
+                Definition::Regular(define.into(), self.location.current_file()),
+            );
+        } else {
+            self.state.definitions.remove(&name);
+            self.undefined.insert(name);
+        }
+    }
+
+    fn handle_node(&mut self, node: SyntaxNode) {
+        let file_id = self.location.current_file();
+
+        match node.kind() {
+            PP_IF | PP_IFDEF | PP_IFNDEF => {
+                if !self.emitting() {
+                    self.groups.push(Group::dropped(node.text_range()));
+                    return;
+                }
+
+                let value = match node.kind() {
+                    PP_IF => {
+                        let (value, condition) = self.eval_condition(&node);
+                        if value.is_none() {
+                            self.output.text.push_str(&format!("#if {}\n", condition));
+                        }
+
+                        value
+                    }
+                    PP_IFDEF => {
+                        let directive: DirectiveResult<IfDef> = (file_id, node.clone()).try_into();
+                        let value = directive
+                            .ok()
+                            .and_then(|ifdef| self.eval_defined(&ifdef.ident));
+                        if value.is_none() {
+                            self.write_node(&node);
+                        }
+
+                        value
+                    }
+                    _ => {
+                        let directive: DirectiveResult<IfNDef> = (file_id, node.clone()).try_into();
+                        let value = directive
+                            .ok()
+                            .and_then(|ifndef| self.eval_defined(&ifndef.ident))
+                            .map(|defined| !defined);
+                        if value.is_none() {
+                            self.write_node(&node);
+                        }
+
+                        value
+                    }
+                };
+
+                self.groups.push(match value {
+                    Some(value) => Group {
+                        pos: node.text_range(),
+                        opened: false,
+                        done: value,
+                        emitting: value,
+                    },
+                    None => Group {
+                        pos: node.text_range(),
+                        opened: true,
+                        done: false,
+                        emitting: true,
+                    },
+                });
+            }
+            PP_ELIF | PP_ELSE => {
+                let group = if let Some(group) = self.groups.pop() {
+                    group
+                } else {
+                    let error = if node.kind() == PP_ELIF {
+                        ProcessingErrorKind::ExtraElif
+                    } else {
+                        ProcessingErrorKind::ExtraElse
+                    };
+
+                    self.push_error(error, node.text_range());
+                    return;
+                };
+
+                // Do not evaluate the condition if the group is dropped, or a previous branch
+                // was taken
+                if group.done || !self.emitting() {
+                    self.groups.push(Group {
+                        emitting: false,
+                        ..group
+                    });
+                    return;
+                }
+
+                let (value, condition) = if node.kind() == PP_ELIF {
+                    self.eval_condition(&node)
+                } else {
+                    (Some(true), String::new())
+                };
+
+                self.groups.push(match value {
+                    Some(true) => {
+                        if group.opened {
+                            self.output.text.push_str("#else\n");
+                        }
+
+                        Group {
+                            done: true,
+                            emitting: true,
+                            ..group
+                        }
+                    }
+                    Some(false) => Group {
+                        emitting: false,
+                        ..group
+                    },
+                    None => {
+                        self.output.text.push_str(&format!(
+                            "#{} {}\n",
+                            if group.opened { "elif" } else { "if" },
+                            condition
+                        ));
+
+                        Group {
+                            opened: true,
+                            done: false,
+                            emitting: true,
+                            ..group
+                        }
+                    }
+                });
+            }
+            PP_ENDIF => {
+                if let Some(group) = self.groups.pop() {
+                    if group.opened {
+                        self.output.text.push_str("#endif\n");
+                    }
+                } else {
+                    self.push_error(ProcessingErrorKind::ExtraEndIf, node.text_range());
+                }
+            }
+            _ if !self.emitting() => {}
+            PP_DEFINE => {
+                let directive: DirectiveResult<Define> = (file_id, node.clone()).try_into();
+                if let Ok(define) = directive {
+                    let define = (*define).clone();
+                    self.update_definition(define.name().into(), Some(define));
+                }
+
+                self.write_node(&node);
+            }
+            PP_UNDEF => {
+                let directive: DirectiveResult<Undef> = (file_id, node.clone()).try_into();
+                if let Ok(undef) = directive {
+                    self.update_definition(undef.ident.clone(), None);
+                }
+
+                self.write_node(&node);
+            }
+            _ => {
+                self.write_node(&node);
+            }
+        }
+    }
+
+    /// Evaluate `defined` for the given macro, returning `None` if it is unknown
+    fn eval_defined(&self, ident: &str) -> Option<bool> {
+        self.is_known(ident)
+            .then(|| self.state.definitions.contains_key(ident))
+    }
+
+    /// Evaluate the condition of an `#if` or `#elif` directive
+    ///
+    /// Returns the value of the condition if it is decidable, and its text with the known macros
+    /// expanded.
+    fn eval_condition(&mut self, node: &SyntaxNode) -> (Option<bool>, String) {
+        let body = match node.children().find(|node| node.kind() == PP_IF_EXPR) {
+            Some(body) => body,
+            None => return (None, String::new()),
+        };
+
+        // Perform macro substitution
+        let file_id = self.location.current_file();
+        let tokens = body
+            .children_with_tokens()
+            .filter_map(NodeOrToken::into_token)
+            .map(|token| (token, file_id))
+            .collect();
+        let events = MacroInvocation::substitute_vec(&self.state, tokens, &self.location);
+
+        let mut tokens = Vec::with_capacity(events.len());
+        for event in events {
+            match event {
+                Event::Token { token, .. } => tokens.push(token),
+                Event::Error { error, .. } => self.output.errors.push(error),
+                _ => {}
+            }
+        }
+
+        // Replace the known defined expressions with their values
+        let (condition, unknown) = self.condition_text(&tokens);
+        if unknown {
+            return (None, condition);
+        }
+
+        let mut results = ExprEvaluator::new(tokens.iter(), &self.state);
+        let value = match (results.next(), results.next()) {
+            (Some(EvalResult::Constant(Ok(value))), None) => Some(value != 0),
+            _ => None,
+        };
+
+        (value, condition)
+    }
+
+    /// Build the text of a condition, replacing `defined` expressions on known macros with their
+    /// values. Also returns `true` if the condition depends on unknown macros.
+    fn condition_text(&self, tokens: &[OutputToken]) -> (String, bool) {
+        let mut text = String::new();
+        let mut unknown = false;
+
+        let mut iter = tokens.iter().enumerate();
+        while let Some((i, token)) = iter.next() {
+            match token.kind() {
+                DEFINED => {
+                    // Find the operand, with or without parentheses
+                    let mut rest = tokens[i + 1..]
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, token)| !token.kind().is_trivia());
+
+                    let operand = match rest.next() {
+                        Some((j, token)) if token.kind() == IDENT_KW => Some((j, token)),
+                        Some((_, token)) if token.kind() == LPAREN => {
+                            match (rest.next(), rest.next()) {
+                                (Some((_, ident)), Some((j, rparen)))
+                                    if ident.kind() == IDENT_KW && rparen.kind() == RPAREN =>
+                                {
+                                    Some((j, ident))
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+
+                    if let Some((j, ident)) = operand {
+                        let name = Unescaped::new(ident.text()).to_string();
+
+                        if let Some(defined) = self.eval_defined(&name) {
+                            text.push_str(if defined { "1" } else { "0" });
+
+                            // Skip the operand tokens
+                            for _ in 0..=j {
+                                iter.next();
+                            }
+
+                            continue;
+                        }
+                    }
+
+                    unknown = true;
+                    text.push_str(token.text());
+                }
+                IDENT_KW => {
+                    let name = Unescaped::new(token.text()).to_string();
+                    if !self.is_known(&name) {
+                        unknown = true;
+                    }
+
+                    text.push_str(token.text());
+                }
+                WS | NEWLINE | COMMENT => text.push(' '),
+                _ => text.push_str(token.text()),
+            }
+        }
+
+        (text.trim().to_owned(), unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::processor::nodes::{Define, DefineObject};
+
+    use super::*;
+
+    fn state() -> ProcessorState {
+        ProcessorState::builder()
+            .definition(Define::object(
+                "QUALITY".into(),
+                DefineObject::from_str("2").unwrap(),
+                false,
+            ))
+            .definition(Define::object(
+                "USE_SHADOWS".into(),
+                DefineObject::from_str("1").unwrap(),
+                false,
+            ))
+            .finish()
+    }
+
+    #[test]
+    fn test_partial_known() {
+        let output = PartialProcessor::new(state())
+            .undefined("USE_BLOOM")
+            .process_str(
+                "#if QUALITY > 1
+float high = QUALITY;
+#else
+float low;
+#endif
+#ifdef USE_BLOOM
+float bloom;
+#elif defined(USE_SHADOWS)
+float shadows;
+#endif
+",
+            );
+
+        assert!(output.errors.is_empty());
+        assert_eq!(output.text, "float high = 2;\nfloat shadows;\n");
+    }
+
+    #[test]
+    fn test_partial_unknown() {
+        let output = PartialProcessor::new(state()).process_str(
+            "#ifdef USE_FOG
+float fog = QUALITY;
+#if QUALITY == 2
+float medium;
+#endif
+#elif QUALITY > 1 && defined(USE_SHADOWS)
+float shadows;
+#endif
+#if FOG_MODE == QUALITY || defined USE_SHADOWS
+float mode;
+#elif 0
+float never;
+#elif defined(USE_HDR)
+float hdr;
+#else
+float other;
+#endif
+",
+        );
+
+        assert!(output.errors.is_empty());
+        assert_eq!(
+            output.text,
+            "#ifdef USE_FOG
+float fog = 2;
+float medium;
+#else
+float shadows;
+#endif
+#if FOG_MODE == 2 || 1
+float mode;
+#elif defined(USE_HDR)
+float hdr;
+#else
+float other;
+#endif
+"
+        );
+    }
+
+    #[test]
+    fn test_partial_defines() {
+        let input = "#ifdef USE_FOG
+#define FOG_SCALE 2
+#endif
+#define LOCAL 1
+#if defined(FOG_SCALE)
+float fog;
+#endif
+#if LOCAL
+float local;
+#endif
+";
+
+        let output = PartialProcessor::new(state()).process_str(input);
+        assert_eq!(
+            output.text,
+            "#ifdef USE_FOG
+#define FOG_SCALE 2
+#endif
+#define LOCAL 1
+#if defined(FOG_SCALE)
+float fog;
+#endif
+float local;
+"
+        );
+
+        // With unknown macros considered undefined, everything is resolved
+        let output = PartialProcessor::new(state())
+            .unknown_policy(UnknownPolicy::Undefined)
+            .process_str(input);
+        assert_eq!(output.text, "#define LOCAL 1\nfloat local;\n");
+    }
+
+    #[test]
+    fn test_partial_builtins() {
+        let output = PartialProcessor::new(state())
+            .unknown_policy(UnknownPolicy::Undefined)
+            .process_str(
+                "#if __VERSION__ >= 450
+float modern;
+#endif
+#ifdef __LINE__
+float line = __LINE__;
+#endif
+",
+            );
+
+        // Built-in macros are never resolved, whatever the policy
+        assert!(output.errors.is_empty());
+        assert_eq!(
+            output.text,
+            "#if __VERSION__ >= 450
+float modern;
+#endif
+#ifdef __LINE__
+float line = __LINE__;
+#endif
+"
+        );
+    }
+
+    #[test]
+    fn test_partial_unterminated() {
+        let output = PartialProcessor::new(state()).process_str(
+            "#ifdef USE_FOG
+float fog;
+#if 0
+float never;
+",
+        );
+
+        assert_eq!(output.text, "#ifdef USE_FOG\nfloat fog;\n#endif\n");
+        assert_eq!(
+            output
+                .errors
+                .iter()
+                .map(|error| (error.to_string(), error.line()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("0:3:1: missing #endif".to_owned(), 2),
+                ("0:1:1: missing #endif".to_owned(), 0)
+            ]
+        );
+    }
+}
@@ -8,7 +8,7 @@ use lang_util::{
 use crate::{last::LocatedIterator, parser, types::path::ParsedPath};
 
 use super::{
-    event::Event,
+    event::{Event, Warning},
     expand::{ExpandEvent, ExpandOne},
     ProcessorState,
 };
@@ -29,12 +29,14 @@ pub fn process(input: &str, state: ProcessorState) -> ExpandStr {
     ExpandStr {
         inner: ExpandOne::new((file_id, ast), state),
         final_state: None,
+        unused_macros: Vec::new().into_iter(),
     }
 }
 
 pub struct ExpandStr {
     inner: ExpandOne,
     final_state: Option<ProcessorState>,
+    unused_macros: std::vec::IntoIter<Warning>,
 }
 
 impl ExpandStr {
@@ -56,6 +58,10 @@ impl Iterator for ExpandStr {
     type Item = Result<Event, Located<ProcessStrError>>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(warning) = self.unused_macros.next() {
+            return Some(Ok(Event::Warning { warning }));
+        }
+
         let event = self.inner.next()?;
         match event {
             ExpandEvent::Event(event) => Some(Ok(event)),
@@ -63,9 +69,14 @@ impl Iterator for ExpandStr {
                 .pos(node.text_range())
                 .resolve_file(self.inner.location())
                 .finish(ProcessStrError::ImportRequested(path)))),
-            ExpandEvent::Completed(state) => {
+            ExpandEvent::Completed(mut state) => {
+                // Report the macros which are still unused at the end of the input
+                self.unused_macros = state.take_unused_macros().into_iter();
                 self.final_state = Some(state);
-                None
+
+                self.unused_macros
+                    .next()
+                    .map(|warning| Ok(Event::Warning { warning }))
             }
         }
     }
@@ -85,4 +96,194 @@ mod tests {
     fn test_error_send() {
         assert_send::<super::ProcessStrError>();
     }
+
+    #[test]
+    fn test_macro_expansions() {
+        use crate::processor::{event::Event, ProcessorState};
+
+        let input = "#define ONE 1
+#define ADD(a, b) (a + b)
+#define ADD_ONE(x) ADD(x, ONE)
+int x = ADD_ONE(2);
+";
+
+        let tokens: Vec<_> = super::process(input, ProcessorState::default())
+            .filter_map(|event| match event.unwrap() {
+                Event::Token { token, .. } if !token.kind().is_whitespace() => Some(token),
+                _ => None,
+            })
+            .collect();
+
+        let expansions = |text: &str| {
+            tokens
+                .iter()
+                .find(|token| token.text() == text)
+                .unwrap()
+                .expansions()
+                .iter()
+                .map(|expansion| (expansion.name.to_string(), expansion.line))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(expansions("int"), vec![]);
+        assert_eq!(
+            expansions("2"),
+            vec![("ADD_ONE".to_owned(), 3), ("ADD".to_owned(), 2)]
+        );
+        assert_eq!(
+            expansions("1"),
+            vec![
+                ("ADD_ONE".to_owned(), 3),
+                ("ADD".to_owned(), 2),
+                ("ONE".to_owned(), 2)
+            ]
+        );
+
+        // The range of expanded tokens is the outermost invocation
+        let one = tokens.iter().find(|token| token.text() == "1").unwrap();
+        let call = input.find("ADD_ONE(2)").unwrap();
+        assert_eq!(usize::from(one.text_range().range().start()), call,);
+        assert_eq!(
+            one.expansions()[0].invocation.range(),
+            one.text_range().range()
+        );
+
+        // Definitions point to the #define directives
+        let definition = one.expansions()[2].definition.unwrap();
+        assert_eq!(&input[definition.range()], "#define ONE 1\n",);
+    }
+
+    #[test]
+    fn test_elif_after_taken_branch() {
+        use crate::processor::{event::Event, ProcessorState};
+
+        let tokens = |input: &str| -> String {
+            super::process(input, ProcessorState::default())
+                .filter_map(|event| match event.unwrap() {
+                    Event::Token {
+                        token,
+                        masked: false,
+                    } if !token.kind().is_whitespace() => Some(token.text().to_owned()),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        assert_eq!(tokens("#if 1\na\n#elif 1\nb\n#endif\n"), "a");
+        assert_eq!(tokens("#if 0\na\n#elif 1\nb\n#elif 1\nc\n#endif\n"), "b");
+        assert_eq!(tokens("#if 1\na\n#elif 1\nb\n#else\nc\n#endif\n"), "a");
+    }
+
+    fn warnings(
+        input: &str,
+        state: crate::processor::ProcessorStateBuilder,
+    ) -> Vec<(crate::processor::event::WarningKind, u32)> {
+        use crate::processor::event::Event;
+
+        super::process(input, state.finish())
+            .filter_map(|event| match event.unwrap() {
+                Event::Warning { warning } => Some((warning.inner().clone(), warning.line())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_warnings() {
+        use crate::processor::{event::WarningKind, ProcessorState};
+
+        let input = "#define A 1
+#define A  1
+#define A 2
+#undef B
+#define __C 3
+#if A + D
+#endif
+#ifdef __C
+#endif
+int x;
+#extension GL_OES_standard_derivatives : enable
+#define UNUSED(x) x
+";
+
+        // Warnings are disabled by default
+        assert_eq!(warnings(input, ProcessorState::builder()), vec![]);
+
+        assert_eq!(
+            warnings(input, ProcessorState::builder().warnings(true)),
+            vec![
+                (WarningKind::MacroRedefinition { ident: "A".into() }, 2),
+                (WarningKind::UndefUndefined { ident: "B".into() }, 3),
+                (
+                    WarningKind::ReservedName {
+                        ident: "__C".into()
+                    },
+                    4
+                ),
+                (WarningKind::UndefinedIdentifier { ident: "D".into() }, 5),
+                (
+                    WarningKind::ExtensionAfterTokens {
+                        extension: "GL_OES_standard_derivatives".into()
+                    },
+                    10
+                ),
+                (
+                    WarningKind::UnusedMacro {
+                        ident: "UNUSED".into()
+                    },
+                    11
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_macros() {
+        use crate::processor::{
+            event::{WarningId, WarningKind},
+            ProcessorState,
+        };
+
+        let input = "#define ONE 1
+#define TWO (ONE + ONE)
+#define TESTED
+#define REMOVED
+#define UNUSED
+#undef REMOVED
+#ifndef TESTED
+#endif
+int x = TWO;
+";
+
+        let state = ProcessorState::builder()
+            .warnings(true)
+            .warning(WarningId::UndefUndefined, false);
+
+        assert_eq!(
+            warnings(input, state.clone()),
+            vec![(
+                WarningKind::UnusedMacro {
+                    ident: "UNUSED".into()
+                },
+                4
+            )]
+        );
+
+        // Silenced warnings are not reported
+        assert_eq!(
+            warnings(input, state.warning(WarningId::UnusedMacro, false)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_warning_ids() {
+        use crate::processor::event::WarningId;
+
+        for id in WarningId::ALL {
+            assert_eq!(id.name().parse::<WarningId>().unwrap(), id);
+        }
+
+        assert!("unknown".parse::<WarningId>().is_err());
+    }
 }
@@ -0,0 +1,469 @@
+//! Conditional compilation analysis and shader variant generation
+
+use std::{collections::HashMap, str::FromStr};
+
+use rowan::NodeOrToken;
+use thiserror::Error;
+
+use lang_util::{position::NodeSpan, FileId, SmolStr, TextRange};
+
+use crate::{
+    last::LocatedIterator,
+    parser::{Ast, SyntaxKind::*, SyntaxNode},
+    util::Unescaped,
+};
+
+use super::{
+    emit::Emitter,
+    event::{Error, Event},
+    nodes::{Define, DefineObject, DirectiveResult, IfDef, IfNDef},
+    ProcessorState, ProcessorStateBuilder,
+};
+
+/// A macro which affects conditional compilation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionalMacro {
+    /// Name of the macro
+    pub name: SmolStr,
+    /// Conditional groups whose conditions refer to this macro, from the opening directive to
+    /// the matching `#endif`
+    pub regions: Vec<NodeSpan>,
+}
+
+/// Analysis of the macros which affect conditional compilation
+///
+/// Only the macros which are directly referred to in `#if`, `#ifdef`, `#ifndef` and `#elif`
+/// conditions are listed, in order of first appearance. All conditional groups are considered,
+/// including the ones nested in other groups.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct MacroAnalysis {
+    macros: Vec<ConditionalMacro>,
+    indices: HashMap<SmolStr, usize>,
+}
+
+impl MacroAnalysis {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the conditional groups of a parsed file to the analysis
+    pub fn add_file(&mut self, file_id: FileId, ast: &Ast) {
+        let root = SyntaxNode::new_root(ast.green_node().clone());
+
+        // Start of the current groups, and the macros their conditions refer to
+        let mut groups: Vec<(TextRange, Vec<SmolStr>)> = Vec::new();
+
+        for node in root.children() {
+            match node.kind() {
+                PP_IF | PP_IFDEF | PP_IFNDEF => {
+                    groups.push((node.text_range(), condition_macros(file_id, &node)));
+                }
+                PP_ELIF => {
+                    if let Some((_, names)) = groups.last_mut() {
+                        for name in condition_macros(file_id, &node) {
+                            if !names.contains(&name) {
+                                names.push(name);
+                            }
+                        }
+                    }
+                }
+                PP_ENDIF => {
+                    if let Some((start, names)) = groups.pop() {
+                        self.add_region(file_id, start.cover(node.text_range()), names);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Unterminated groups extend to the end of the file
+        while let Some((start, names)) = groups.pop() {
+            self.add_region(file_id, start.cover(root.text_range()), names);
+        }
+    }
+
+    fn add_region(&mut self, file_id: FileId, range: TextRange, names: Vec<SmolStr>) {
+        for name in names {
+            let index = *self.indices.entry(name.clone()).or_insert_with(|| {
+                self.macros.push(ConditionalMacro {
+                    name,
+                    regions: Vec::new(),
+                });
+
+                self.macros.len() - 1
+            });
+
+            self.macros[index]
+                .regions
+                .push(NodeSpan::new(file_id, range));
+        }
+    }
+
+    /// Get the macros which affect conditional compilation
+    pub fn macros(&self) -> &[ConditionalMacro] {
+        &self.macros
+    }
+}
+
+/// Get the names of the macros a conditional directive refers to
+fn condition_macros(file_id: FileId, node: &SyntaxNode) -> Vec<SmolStr> {
+    match node.kind() {
+        PP_IFDEF => {
+            let directive: DirectiveResult<IfDef> = (file_id, node.clone()).try_into();
+            directive
+                .ok()
+                .map(|ifdef| ifdef.ident.clone())
+                .into_iter()
+                .collect()
+        }
+        PP_IFNDEF => {
+            let directive: DirectiveResult<IfNDef> = (file_id, node.clone()).try_into();
+            directive
+                .ok()
+                .map(|ifndef| ifndef.ident.clone())
+                .into_iter()
+                .collect()
+        }
+        _ => {
+            let mut names = Vec::new();
+
+            for token in node
+                .children()
+                .filter(|node| node.kind() == PP_IF_EXPR)
+                .flat_map(|body| body.descendants_with_tokens())
+                .filter_map(NodeOrToken::into_token)
+                .filter(|token| token.kind() == IDENT_KW)
+            {
+                let name: SmolStr = Unescaped::new(token.text()).to_string().into();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+
+            names
+        }
+    }
+}
+
+/// Values of the toggled macros for one variant. A value of `None` means the macro is undefined.
+pub type Configuration = Vec<(SmolStr, Option<SmolStr>)>;
+
+/// Generator for the configurations of a shader with feature toggles
+#[derive(Clone)]
+pub struct VariantGenerator<'r> {
+    base: ProcessorStateBuilder<'r>,
+    macros: Vec<(SmolStr, Vec<Option<SmolStr>>)>,
+}
+
+impl<'r> VariantGenerator<'r> {
+    /// Create a new variant generator
+    ///
+    /// # Parameters
+    ///
+    /// * `base`: builder for the state shared by all variants
+    pub fn new(base: ProcessorStateBuilder<'r>) -> Self {
+        Self {
+            base,
+            macros: Vec::new(),
+        }
+    }
+
+    /// Add a macro which is either undefined, or defined to `1`
+    pub fn toggle(self, name: impl Into<SmolStr>) -> Self {
+        self.values(name, [None, Some("1".into())]).unwrap()
+    }
+
+    /// Add a macro with the given set of values, where `None` leaves the macro undefined
+    ///
+    /// Returns the first value which is not a valid macro body as an error.
+    pub fn values(
+        mut self,
+        name: impl Into<SmolStr>,
+        values: impl IntoIterator<Item = Option<SmolStr>>,
+    ) -> Result<Self, SmolStr> {
+        let values: Vec<_> = values.into_iter().collect();
+
+        if let Some(invalid) = values
+            .iter()
+            .flatten()
+            .find(|value| DefineObject::from_str(value).is_err())
+        {
+            return Err(invalid.clone());
+        }
+
+        self.macros.push((name.into(), values));
+        Ok(self)
+    }
+
+    /// Get all the combinations of the macro values, with the first macro varying the slowest
+    pub fn configurations(&self) -> Vec<Configuration> {
+        self.macros
+            .iter()
+            .fold(vec![Vec::new()], |configurations, (name, values)| {
+                configurations
+                    .iter()
+                    .flat_map(|configuration| {
+                        values.iter().map(move |value| {
+                            let mut configuration = configuration.clone();
+                            configuration.push((name.clone(), value.clone()));
+                            configuration
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    /// Get the initial preprocessor state for a configuration
+    pub fn state(&self, configuration: &Configuration) -> ProcessorState {
+        configuration
+            .iter()
+            .filter_map(|(name, value)| {
+                let object = DefineObject::from_str(value.as_ref()?).ok()?;
+                Some(Define::object(name.clone(), object, false))
+            })
+            .fold(self.base.clone(), |builder, definition| {
+                builder.definition(definition)
+            })
+            .finish()
+    }
+}
+
+/// Error encountered while preprocessing a variant
+#[derive(Debug, Error)]
+pub enum VariantError<E: std::error::Error + 'static> {
+    /// Error reported by the preprocessor
+    #[error("{0}")]
+    Processing(Error),
+    /// Error returned by the event iterator, such as an I/O error
+    #[error("{0}")]
+    Input(E),
+}
+
+/// A preprocessed shader variant
+#[derive(Debug)]
+pub struct Variant<E: std::error::Error + 'static> {
+    /// Configurations which produce this variant
+    pub configurations: Vec<Configuration>,
+    /// Preprocessed text of the variant, for its first configuration
+    pub text: String,
+    /// Errors encountered while preprocessing the first configuration
+    pub errors: Vec<VariantError<E>>,
+}
+
+/// Set of preprocessed shader variants
+///
+/// Variants which are token-identical, ignoring whitespace and comments, are merged together.
+#[derive(Debug)]
+pub struct VariantSet<E: std::error::Error + 'static> {
+    variants: Vec<Variant<E>>,
+    indices: HashMap<Vec<(crate::parser::SyntaxKind, SmolStr)>, usize>,
+}
+
+impl<E: std::error::Error + 'static> VariantSet<E> {
+    pub fn new() -> Self {
+        Self {
+            variants: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Preprocess a configuration, and add it to the set
+    ///
+    /// # Parameters
+    ///
+    /// * `configuration`: values of the macros for this variant
+    /// * `iter`: preprocessor events, using the state from [VariantGenerator::state]
+    ///
+    /// # Returns
+    ///
+    /// The index of the variant the configuration produces.
+    pub fn add(
+        &mut self,
+        configuration: Configuration,
+        mut iter: impl Iterator<Item = Result<Event, E>> + LocatedIterator,
+    ) -> usize {
+        let mut emitter = Emitter::new(String::new());
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+
+        while let Some(result) = iter.next() {
+            match result {
+                Ok(event) => {
+                    match &event {
+                        Event::Token {
+                            token,
+                            masked: false,
+                        } if !token.kind().is_whitespace() => {
+                            tokens.push((token.kind(), token.text().into()));
+                        }
+                        Event::Error {
+                            error,
+                            masked: false,
+                        } => {
+                            errors.push(VariantError::Processing(error.clone()));
+                        }
+                        Event::Directive {
+                            directive,
+                            masked: false,
+                        } => {
+                            errors.extend(
+                                directive
+                                    .errors()
+                                    .iter()
+                                    .cloned()
+                                    .map(VariantError::Processing),
+                            );
+                        }
+                        _ => {}
+                    }
+
+                    // Writing to a String does not fail
+                    let _ = emitter.write_event(&event, iter.location());
+                }
+                Err(error) => {
+                    errors.push(VariantError::Input(error));
+                }
+            }
+        }
+
+        if let Some(index) = self.indices.get(&tokens) {
+            self.variants[*index].configurations.push(configuration);
+            return *index;
+        }
+
+        let index = self.variants.len();
+        self.indices.insert(tokens, index);
+        self.variants.push(Variant {
+            configurations: vec![configuration],
+            text: emitter.finish().unwrap_or_default(),
+            errors,
+        });
+
+        index
+    }
+
+    /// Get the distinct variants, in order of first appearance
+    pub fn variants(&self) -> &[Variant<E>] {
+        &self.variants
+    }
+
+    pub fn into_variants(self) -> Vec<Variant<E>> {
+        self.variants
+    }
+}
+
+impl<E: std::error::Error + 'static> Default for VariantSet<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::processor::str::{parse, process};
+
+    const SOURCE: &str = "#ifdef USE_FOG
+float fog;
+#if QUALITY > 1 && defined(USE_SHADOWS)
+float shadows;
+#endif
+#endif
+#ifndef USE_SHADOWS
+float no_shadows;
+#elif QUALITY == 2
+float high;
+#endif
+";
+
+    #[test]
+    fn test_macro_analysis() {
+        let mut analysis = MacroAnalysis::new();
+        analysis.add_file(FileId::new(0), &parse(SOURCE));
+
+        let macros: Vec<_> = analysis
+            .macros()
+            .iter()
+            .map(|m| {
+                (
+                    m.name.as_str(),
+                    m.regions
+                        .iter()
+                        .map(|region| SOURCE[region.range()].lines().next().unwrap())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            macros,
+            vec![
+                (
+                    "QUALITY",
+                    vec![
+                        "#if QUALITY > 1 && defined(USE_SHADOWS)",
+                        "#ifndef USE_SHADOWS"
+                    ]
+                ),
+                (
+                    "USE_SHADOWS",
+                    vec![
+                        "#if QUALITY > 1 && defined(USE_SHADOWS)",
+                        "#ifndef USE_SHADOWS"
+                    ]
+                ),
+                ("USE_FOG", vec!["#ifdef USE_FOG"]),
+            ]
+        );
+
+        // Regions end with the #endif directive
+        let fog = &analysis.macros()[2].regions[0];
+        assert!(SOURCE[fog.range()].ends_with("#endif\n#endif\n"));
+    }
+
+    #[test]
+    fn test_variants() {
+        let generator = VariantGenerator::new(ProcessorState::builder())
+            .toggle("USE_FOG")
+            .toggle("USE_SHADOWS")
+            .values("QUALITY", [Some("1".into()), Some("2".into())])
+            .unwrap();
+
+        let configurations = generator.configurations();
+        assert_eq!(configurations.len(), 8);
+        assert_eq!(
+            configurations[1],
+            vec![
+                ("USE_FOG".into(), None),
+                ("USE_SHADOWS".into(), None),
+                ("QUALITY".into(), Some("2".into()))
+            ]
+        );
+
+        let mut set = VariantSet::new();
+        let indices: Vec<_> = configurations
+            .into_iter()
+            .map(|configuration| {
+                let state = generator.state(&configuration);
+                set.add(configuration, process(SOURCE, state))
+            })
+            .collect();
+
+        // Without shadows, the quality does not matter
+        assert_eq!(indices, vec![0, 0, 1, 2, 3, 3, 4, 5]);
+        assert_eq!(set.variants().len(), 6);
+        assert_eq!(set.variants()[0].text.trim(), "float no_shadows;");
+        assert_eq!(
+            set.variants()[5]
+                .text
+                .split_whitespace()
+                .collect::<Vec<_>>(),
+            vec!["float", "fog;", "float", "shadows;", "float", "high;"]
+        );
+        assert!(set
+            .variants()
+            .iter()
+            .all(|variant| variant.errors.is_empty()));
+    }
+}
@@ -491,6 +491,252 @@ pub enum TypeName {
     /// "usubpassInputMS"
     #[display("usubpassInputMS")]
     USUBPASSINPUTMS,
+    /// "int8_t"
+    #[display("int8_t")]
+    INT8_T,
+    /// "i8vec2"
+    #[display("i8vec2")]
+    I8VEC2,
+    /// "i8vec3"
+    #[display("i8vec3")]
+    I8VEC3,
+    /// "i8vec4"
+    #[display("i8vec4")]
+    I8VEC4,
+    /// "uint8_t"
+    #[display("uint8_t")]
+    UINT8_T,
+    /// "u8vec2"
+    #[display("u8vec2")]
+    U8VEC2,
+    /// "u8vec3"
+    #[display("u8vec3")]
+    U8VEC3,
+    /// "u8vec4"
+    #[display("u8vec4")]
+    U8VEC4,
+    /// "int16_t"
+    #[display("int16_t")]
+    INT16_T,
+    /// "i16vec2"
+    #[display("i16vec2")]
+    I16VEC2,
+    /// "i16vec3"
+    #[display("i16vec3")]
+    I16VEC3,
+    /// "i16vec4"
+    #[display("i16vec4")]
+    I16VEC4,
+    /// "uint16_t"
+    #[display("uint16_t")]
+    UINT16_T,
+    /// "u16vec2"
+    #[display("u16vec2")]
+    U16VEC2,
+    /// "u16vec3"
+    #[display("u16vec3")]
+    U16VEC3,
+    /// "u16vec4"
+    #[display("u16vec4")]
+    U16VEC4,
+    /// "int32_t"
+    #[display("int32_t")]
+    INT32_T,
+    /// "i32vec2"
+    #[display("i32vec2")]
+    I32VEC2,
+    /// "i32vec3"
+    #[display("i32vec3")]
+    I32VEC3,
+    /// "i32vec4"
+    #[display("i32vec4")]
+    I32VEC4,
+    /// "uint32_t"
+    #[display("uint32_t")]
+    UINT32_T,
+    /// "u32vec2"
+    #[display("u32vec2")]
+    U32VEC2,
+    /// "u32vec3"
+    #[display("u32vec3")]
+    U32VEC3,
+    /// "u32vec4"
+    #[display("u32vec4")]
+    U32VEC4,
+    /// "int64_t"
+    #[display("int64_t")]
+    INT64_T,
+    /// "i64vec2"
+    #[display("i64vec2")]
+    I64VEC2,
+    /// "i64vec3"
+    #[display("i64vec3")]
+    I64VEC3,
+    /// "i64vec4"
+    #[display("i64vec4")]
+    I64VEC4,
+    /// "uint64_t"
+    #[display("uint64_t")]
+    UINT64_T,
+    /// "u64vec2"
+    #[display("u64vec2")]
+    U64VEC2,
+    /// "u64vec3"
+    #[display("u64vec3")]
+    U64VEC3,
+    /// "u64vec4"
+    #[display("u64vec4")]
+    U64VEC4,
+    /// "float16_t"
+    #[display("float16_t")]
+    FLOAT16_T,
+    /// "f16vec2"
+    #[display("f16vec2")]
+    F16VEC2,
+    /// "f16vec3"
+    #[display("f16vec3")]
+    F16VEC3,
+    /// "f16vec4"
+    #[display("f16vec4")]
+    F16VEC4,
+    /// "f16mat2"
+    #[display("f16mat2")]
+    F16MAT2,
+    /// "f16mat3"
+    #[display("f16mat3")]
+    F16MAT3,
+    /// "f16mat4"
+    #[display("f16mat4")]
+    F16MAT4,
+    /// "f16mat2x2"
+    #[display("f16mat2x2")]
+    F16MAT2X2,
+    /// "f16mat2x3"
+    #[display("f16mat2x3")]
+    F16MAT2X3,
+    /// "f16mat2x4"
+    #[display("f16mat2x4")]
+    F16MAT2X4,
+    /// "f16mat3x2"
+    #[display("f16mat3x2")]
+    F16MAT3X2,
+    /// "f16mat3x3"
+    #[display("f16mat3x3")]
+    F16MAT3X3,
+    /// "f16mat3x4"
+    #[display("f16mat3x4")]
+    F16MAT3X4,
+    /// "f16mat4x2"
+    #[display("f16mat4x2")]
+    F16MAT4X2,
+    /// "f16mat4x3"
+    #[display("f16mat4x3")]
+    F16MAT4X3,
+    /// "f16mat4x4"
+    #[display("f16mat4x4")]
+    F16MAT4X4,
+    /// "float32_t"
+    #[display("float32_t")]
+    FLOAT32_T,
+    /// "f32vec2"
+    #[display("f32vec2")]
+    F32VEC2,
+    /// "f32vec3"
+    #[display("f32vec3")]
+    F32VEC3,
+    /// "f32vec4"
+    #[display("f32vec4")]
+    F32VEC4,
+    /// "f32mat2"
+    #[display("f32mat2")]
+    F32MAT2,
+    /// "f32mat3"
+    #[display("f32mat3")]
+    F32MAT3,
+    /// "f32mat4"
+    #[display("f32mat4")]
+    F32MAT4,
+    /// "f32mat2x2"
+    #[display("f32mat2x2")]
+    F32MAT2X2,
+    /// "f32mat2x3"
+    #[display("f32mat2x3")]
+    F32MAT2X3,
+    /// "f32mat2x4"
+    #[display("f32mat2x4")]
+    F32MAT2X4,
+    /// "f32mat3x2"
+    #[display("f32mat3x2")]
+    F32MAT3X2,
+    /// "f32mat3x3"
+    #[display("f32mat3x3")]
+    F32MAT3X3,
+    /// "f32mat3x4"
+    #[display("f32mat3x4")]
+    F32MAT3X4,
+    /// "f32mat4x2"
+    #[display("f32mat4x2")]
+    F32MAT4X2,
+    /// "f32mat4x3"
+    #[display("f32mat4x3")]
+    F32MAT4X3,
+    /// "f32mat4x4"
+    #[display("f32mat4x4")]
+    F32MAT4X4,
+    /// "float64_t"
+    #[display("float64_t")]
+    FLOAT64_T,
+    /// "f64vec2"
+    #[display("f64vec2")]
+    F64VEC2,
+    /// "f64vec3"
+    #[display("f64vec3")]
+    F64VEC3,
+    /// "f64vec4"
+    #[display("f64vec4")]
+    F64VEC4,
+    /// "f64mat2"
+    #[display("f64mat2")]
+    F64MAT2,
+    /// "f64mat3"
+    #[display("f64mat3")]
+    F64MAT3,
+    /// "f64mat4"
+    #[display("f64mat4")]
+    F64MAT4,
+    /// "f64mat2x2"
+    #[display("f64mat2x2")]
+    F64MAT2X2,
+    /// "f64mat2x3"
+    #[display("f64mat2x3")]
+    F64MAT2X3,
+    /// "f64mat2x4"
+    #[display("f64mat2x4")]
+    F64MAT2X4,
+    /// "f64mat3x2"
+    #[display("f64mat3x2")]
+    F64MAT3X2,
+    /// "f64mat3x3"
+    #[display("f64mat3x3")]
+    F64MAT3X3,
+    /// "f64mat3x4"
+    #[display("f64mat3x4")]
+    F64MAT3X4,
+    /// "f64mat4x2"
+    #[display("f64mat4x2")]
+    F64MAT4X2,
+    /// "f64mat4x3"
+    #[display("f64mat4x3")]
+    F64MAT4X3,
+    /// "f64mat4x4"
+    #[display("f64mat4x4")]
+    F64MAT4X4,
+    /// "accelerationStructureEXT"
+    #[display("accelerationStructureEXT")]
+    ACCELERATIONSTRUCTUREEXT,
+    /// "rayQueryEXT"
+    #[display("rayQueryEXT")]
+    RAYQUERYEXT,
     /// Reserved for future use
     RESERVED(TypeNameAtom),
     /// Generic type name
@@ -525,6 +771,194 @@ impl TypeName {
         None
     }
 
+    /// Parse a type name that is only valid when one of the extensions that declare it is enabled
+    pub fn parse_extension(type_name_atom: &TypeNameAtom) -> Option<Self> {
+        Self::parse_explicit_arithmetic(type_name_atom)
+            .or_else(|| Self::parse_ray_query(type_name_atom))
+    }
+
+    /// Parse a type name introduced by the GL_EXT_shader_explicit_arithmetic_types extensions
+    fn parse_explicit_arithmetic(type_name_atom: &TypeNameAtom) -> Option<Self> {
+        use TypeName::*;
+
+        Some(if *type_name_atom == type_name!("int8_t") {
+            INT8_T
+        } else if *type_name_atom == type_name!("i8vec2") {
+            I8VEC2
+        } else if *type_name_atom == type_name!("i8vec3") {
+            I8VEC3
+        } else if *type_name_atom == type_name!("i8vec4") {
+            I8VEC4
+        } else if *type_name_atom == type_name!("uint8_t") {
+            UINT8_T
+        } else if *type_name_atom == type_name!("u8vec2") {
+            U8VEC2
+        } else if *type_name_atom == type_name!("u8vec3") {
+            U8VEC3
+        } else if *type_name_atom == type_name!("u8vec4") {
+            U8VEC4
+        } else if *type_name_atom == type_name!("int16_t") {
+            INT16_T
+        } else if *type_name_atom == type_name!("i16vec2") {
+            I16VEC2
+        } else if *type_name_atom == type_name!("i16vec3") {
+            I16VEC3
+        } else if *type_name_atom == type_name!("i16vec4") {
+            I16VEC4
+        } else if *type_name_atom == type_name!("uint16_t") {
+            UINT16_T
+        } else if *type_name_atom == type_name!("u16vec2") {
+            U16VEC2
+        } else if *type_name_atom == type_name!("u16vec3") {
+            U16VEC3
+        } else if *type_name_atom == type_name!("u16vec4") {
+            U16VEC4
+        } else if *type_name_atom == type_name!("int32_t") {
+            INT32_T
+        } else if *type_name_atom == type_name!("i32vec2") {
+            I32VEC2
+        } else if *type_name_atom == type_name!("i32vec3") {
+            I32VEC3
+        } else if *type_name_atom == type_name!("i32vec4") {
+            I32VEC4
+        } else if *type_name_atom == type_name!("uint32_t") {
+            UINT32_T
+        } else if *type_name_atom == type_name!("u32vec2") {
+            U32VEC2
+        } else if *type_name_atom == type_name!("u32vec3") {
+            U32VEC3
+        } else if *type_name_atom == type_name!("u32vec4") {
+            U32VEC4
+        } else if *type_name_atom == type_name!("int64_t") {
+            INT64_T
+        } else if *type_name_atom == type_name!("i64vec2") {
+            I64VEC2
+        } else if *type_name_atom == type_name!("i64vec3") {
+            I64VEC3
+        } else if *type_name_atom == type_name!("i64vec4") {
+            I64VEC4
+        } else if *type_name_atom == type_name!("uint64_t") {
+            UINT64_T
+        } else if *type_name_atom == type_name!("u64vec2") {
+            U64VEC2
+        } else if *type_name_atom == type_name!("u64vec3") {
+            U64VEC3
+        } else if *type_name_atom == type_name!("u64vec4") {
+            U64VEC4
+        } else if *type_name_atom == type_name!("float16_t") {
+            FLOAT16_T
+        } else if *type_name_atom == type_name!("f16vec2") {
+            F16VEC2
+        } else if *type_name_atom == type_name!("f16vec3") {
+            F16VEC3
+        } else if *type_name_atom == type_name!("f16vec4") {
+            F16VEC4
+        } else if *type_name_atom == type_name!("f16mat2") {
+            F16MAT2
+        } else if *type_name_atom == type_name!("f16mat3") {
+            F16MAT3
+        } else if *type_name_atom == type_name!("f16mat4") {
+            F16MAT4
+        } else if *type_name_atom == type_name!("f16mat2x2") {
+            F16MAT2X2
+        } else if *type_name_atom == type_name!("f16mat2x3") {
+            F16MAT2X3
+        } else if *type_name_atom == type_name!("f16mat2x4") {
+            F16MAT2X4
+        } else if *type_name_atom == type_name!("f16mat3x2") {
+            F16MAT3X2
+        } else if *type_name_atom == type_name!("f16mat3x3") {
+            F16MAT3X3
+        } else if *type_name_atom == type_name!("f16mat3x4") {
+            F16MAT3X4
+        } else if *type_name_atom == type_name!("f16mat4x2") {
+            F16MAT4X2
+        } else if *type_name_atom == type_name!("f16mat4x3") {
+            F16MAT4X3
+        } else if *type_name_atom == type_name!("f16mat4x4") {
+            F16MAT4X4
+        } else if *type_name_atom == type_name!("float32_t") {
+            FLOAT32_T
+        } else if *type_name_atom == type_name!("f32vec2") {
+            F32VEC2
+        } else if *type_name_atom == type_name!("f32vec3") {
+            F32VEC3
+        } else if *type_name_atom == type_name!("f32vec4") {
+            F32VEC4
+        } else if *type_name_atom == type_name!("f32mat2") {
+            F32MAT2
+        } else if *type_name_atom == type_name!("f32mat3") {
+            F32MAT3
+        } else if *type_name_atom == type_name!("f32mat4") {
+            F32MAT4
+        } else if *type_name_atom == type_name!("f32mat2x2") {
+            F32MAT2X2
+        } else if *type_name_atom == type_name!("f32mat2x3") {
+            F32MAT2X3
+        } else if *type_name_atom == type_name!("f32mat2x4") {
+            F32MAT2X4
+        } else if *type_name_atom == type_name!("f32mat3x2") {
+            F32MAT3X2
+        } else if *type_name_atom == type_name!("f32mat3x3") {
+            F32MAT3X3
+        } else if *type_name_atom == type_name!("f32mat3x4") {
+            F32MAT3X4
+        } else if *type_name_atom == type_name!("f32mat4x2") {
+            F32MAT4X2
+        } else if *type_name_atom == type_name!("f32mat4x3") {
+            F32MAT4X3
+        } else if *type_name_atom == type_name!("f32mat4x4") {
+            F32MAT4X4
+        } else if *type_name_atom == type_name!("float64_t") {
+            FLOAT64_T
+        } else if *type_name_atom == type_name!("f64vec2") {
+            F64VEC2
+        } else if *type_name_atom == type_name!("f64vec3") {
+            F64VEC3
+        } else if *type_name_atom == type_name!("f64vec4") {
+            F64VEC4
+        } else if *type_name_atom == type_name!("f64mat2") {
+            F64MAT2
+        } else if *type_name_atom == type_name!("f64mat3") {
+            F64MAT3
+        } else if *type_name_atom == type_name!("f64mat4") {
+            F64MAT4
+        } else if *type_name_atom == type_name!("f64mat2x2") {
+            F64MAT2X2
+        } else if *type_name_atom == type_name!("f64mat2x3") {
+            F64MAT2X3
+        } else if *type_name_atom == type_name!("f64mat2x4") {
+            F64MAT2X4
+        } else if *type_name_atom == type_name!("f64mat3x2") {
+            F64MAT3X2
+        } else if *type_name_atom == type_name!("f64mat3x3") {
+            F64MAT3X3
+        } else if *type_name_atom == type_name!("f64mat3x4") {
+            F64MAT3X4
+        } else if *type_name_atom == type_name!("f64mat4x2") {
+            F64MAT4X2
+        } else if *type_name_atom == type_name!("f64mat4x3") {
+            F64MAT4X3
+        } else if *type_name_atom == type_name!("f64mat4x4") {
+            F64MAT4X4
+        } else {
+            return None;
+        })
+    }
+
+    /// Parse a type name introduced by the GL_EXT_ray_query and GL_EXT_ray_tracing extensions
+    fn parse_ray_query(type_name_atom: &TypeNameAtom) -> Option<Self> {
+        use TypeName::*;
+
+        if *type_name_atom == type_name!("accelerationStructureEXT") {
+            Some(ACCELERATIONSTRUCTUREEXT)
+        } else if *type_name_atom == type_name!("rayQueryEXT") {
+            Some(RAYQUERYEXT)
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn parse(
         name: &str,
         version: u16,
@@ -1135,6 +1569,11 @@ impl TypeName {
             );
         }
 
+        // Extension type names
+        if let Some(type_name) = Self::parse_extension(&type_name_atom) {
+            return type_name.gate(false, false, type_name_atom, is_type_name);
+        }
+
         // Reserved for future use
         if type_name_atom == type_name!("hvec2")
             || type_name_atom == type_name!("hvec3")
@@ -1180,6 +1619,24 @@ pub enum Token {
     /// Double constant
     #[lang_util(parser = "DOUBLE_CONST", kind = "double constant", kind = "literal")]
     DOUBLE_CONST(f64),
+    /// 16-bit int constant
+    #[lang_util(parser = "INT16_CONST", kind = "int16 constant", kind = "literal")]
+    INT16_CONST(i16),
+    /// 16-bit unsigned int constant
+    #[lang_util(parser = "UINT16_CONST", kind = "uint16 constant", kind = "literal")]
+    UINT16_CONST(u16),
+    /// 64-bit int constant
+    #[lang_util(parser = "INT64_CONST", kind = "int64 constant", kind = "literal")]
+    INT64_CONST(i64),
+    /// 64-bit unsigned int constant
+    #[lang_util(parser = "UINT64_CONST", kind = "uint64 constant", kind = "literal")]
+    UINT64_CONST(u64),
+    /// 16-bit float constant
+    #[lang_util(parser = "FLOAT16_CONST", kind = "float16 constant", kind = "literal")]
+    FLOAT16_CONST(f32),
+    /// String constant
+    #[lang_util(parser = "STRING_CONST", kind = "string constant", kind = "literal")]
+    STRING_CONST(SmolStr),
     // Multi-char tokens
     /// <<
     #[lang_util(token = "<<", kind = "binary operator", kind = "operator")]
@@ -1502,6 +1959,119 @@ pub enum Token {
     /// "struct"
     #[lang_util(token = "struct", kind = "struct", kind = "keyword")]
     STRUCT,
+    /// "shadercallcoherent"
+    #[lang_util(
+        token = "shadercallcoherent",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    SHADERCALLCOHERENT,
+    /// "rayPayloadEXT"
+    #[lang_util(
+        token = "rayPayloadEXT",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    RAYPAYLOADEXT,
+    /// "rayPayloadInEXT"
+    #[lang_util(
+        token = "rayPayloadInEXT",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    RAYPAYLOADINEXT,
+    /// "hitAttributeEXT"
+    #[lang_util(
+        token = "hitAttributeEXT",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    HITATTRIBUTEEXT,
+    /// "callableDataEXT"
+    #[lang_util(
+        token = "callableDataEXT",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    CALLABLEDATAEXT,
+    /// "callableDataInEXT"
+    #[lang_util(
+        token = "callableDataInEXT",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    CALLABLEDATAINEXT,
+    /// "taskPayloadSharedEXT"
+    #[lang_util(
+        token = "taskPayloadSharedEXT",
+        kind = "storage qualifier",
+        kind = "type qualifier"
+    )]
+    TASKPAYLOADSHAREDEXT,
+    /// "perprimitiveEXT"
+    #[lang_util(
+        token = "perprimitiveEXT",
+        kind = "interpolation qualifier",
+        kind = "type qualifier"
+    )]
+    PERPRIMITIVEEXT,
+    /// "perprimitiveNV"
+    #[lang_util(
+        token = "perprimitiveNV",
+        kind = "interpolation qualifier",
+        kind = "type qualifier"
+    )]
+    PERPRIMITIVENV,
+    /// "perviewNV"
+    #[lang_util(
+        token = "perviewNV",
+        kind = "interpolation qualifier",
+        kind = "type qualifier"
+    )]
+    PERVIEWNV,
+    /// "taskNV"
+    #[lang_util(
+        token = "taskNV",
+        kind = "interpolation qualifier",
+        kind = "type qualifier"
+    )]
+    TASKNV,
+    /// "ignoreIntersectionEXT"
+    #[lang_util(token = "ignoreIntersectionEXT", kind = "keyword")]
+    IGNOREINTERSECTIONEXT,
+    /// "terminateRayEXT"
+    #[lang_util(token = "terminateRayEXT", kind = "keyword")]
+    TERMINATERAYEXT,
+    /// "spirv_instruction"
+    #[lang_util(token = "spirv_instruction", kind = "type qualifier")]
+    SPIRV_INSTRUCTION,
+    /// "spirv_execution_mode"
+    #[lang_util(token = "spirv_execution_mode", kind = "type qualifier")]
+    SPIRV_EXECUTION_MODE,
+    /// "spirv_execution_mode_id"
+    #[lang_util(token = "spirv_execution_mode_id", kind = "type qualifier")]
+    SPIRV_EXECUTION_MODE_ID,
+    /// "spirv_decorate"
+    #[lang_util(token = "spirv_decorate", kind = "type qualifier")]
+    SPIRV_DECORATE,
+    /// "spirv_decorate_id"
+    #[lang_util(token = "spirv_decorate_id", kind = "type qualifier")]
+    SPIRV_DECORATE_ID,
+    /// "spirv_decorate_string"
+    #[lang_util(token = "spirv_decorate_string", kind = "type qualifier")]
+    SPIRV_DECORATE_STRING,
+    /// "spirv_storage_class"
+    #[lang_util(token = "spirv_storage_class", kind = "type qualifier")]
+    SPIRV_STORAGE_CLASS,
+    /// "spirv_by_reference"
+    #[lang_util(token = "spirv_by_reference", kind = "type qualifier")]
+    SPIRV_BY_REFERENCE,
+    /// "spirv_literal"
+    #[lang_util(token = "spirv_literal", kind = "type qualifier")]
+    SPIRV_LITERAL,
+    /// "spirv_type"
+    #[lang_util(token = "spirv_type", kind = "keyword")]
+    SPIRV_TYPE,
     // Reserved for future use
     /// "common"
     #[lang_util(token = "common", kind = "reserved keyword")]
@@ -1623,6 +2193,16 @@ pub enum ErrorKind {
     InvalidFloatLiteral,
     #[display("invalid double literal")]
     InvalidDoubleLiteral,
+    #[display("invalid int16 literal")]
+    InvalidInt16Literal,
+    #[display("invalid uint16 literal")]
+    InvalidUInt16Literal,
+    #[display("invalid int64 literal")]
+    InvalidInt64Literal,
+    #[display("invalid uint64 literal")]
+    InvalidUInt64Literal,
+    #[display("invalid float16 literal")]
+    InvalidFloat16Literal,
 }
 
 impl Token {
@@ -1785,8 +2365,11 @@ impl Token {
             | lexer::Token::WS => {
                 return (WS, None);
             }
-            lexer::Token::QUOTE_STRING
-            | lexer::Token::ANGLE_STRING
+            lexer::Token::QUOTE_STRING => {
+                let text = Unescaped::new(token.raw(source)).to_string();
+                return (Token::parse_quote_string(&text), None);
+            }
+            lexer::Token::ANGLE_STRING
             | lexer::Token::BACKSLASH
             | lexer::Token::ERROR
             | lexer::Token::PP_CONCAT => {
@@ -1820,6 +2403,35 @@ impl Token {
         }
     }
 
+    pub(crate) fn parse_quote_string(text: &str) -> Self {
+        let text = text
+            .strip_prefix('"')
+            .and_then(|text| text.strip_suffix('"'))
+            .unwrap_or(text);
+
+        // Process escape sequences
+        let mut result = String::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                result.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some('r') => result.push('\r'),
+                Some('0') => result.push('\0'),
+                // Unknown escapes (including \\, \" and \') stand for the escaped character
+                Some(c) => result.push(c),
+                None => result.push('\\'),
+            }
+        }
+
+        Token::STRING_CONST(result.into())
+    }
+
     pub(crate) fn parse_kw(keyword_atom: &KeywordAtom) -> Option<Self> {
         use Token::*;
 
@@ -1912,6 +2524,54 @@ impl Token {
         } else if *keyword_atom == keyword!("struct") {
             Some(STRUCT)
         }
+        // GL_EXT_ray_tracing, GL_EXT_mesh_shader, GL_NV_mesh_shader
+        else if *keyword_atom == keyword!("shadercallcoherent") {
+            Some(SHADERCALLCOHERENT)
+        } else if *keyword_atom == keyword!("rayPayloadEXT") {
+            Some(RAYPAYLOADEXT)
+        } else if *keyword_atom == keyword!("rayPayloadInEXT") {
+            Some(RAYPAYLOADINEXT)
+        } else if *keyword_atom == keyword!("hitAttributeEXT") {
+            Some(HITATTRIBUTEEXT)
+        } else if *keyword_atom == keyword!("callableDataEXT") {
+            Some(CALLABLEDATAEXT)
+        } else if *keyword_atom == keyword!("callableDataInEXT") {
+            Some(CALLABLEDATAINEXT)
+        } else if *keyword_atom == keyword!("taskPayloadSharedEXT") {
+            Some(TASKPAYLOADSHAREDEXT)
+        } else if *keyword_atom == keyword!("perprimitiveEXT") {
+            Some(PERPRIMITIVEEXT)
+        } else if *keyword_atom == keyword!("perprimitiveNV") {
+            Some(PERPRIMITIVENV)
+        } else if *keyword_atom == keyword!("perviewNV") {
+            Some(PERVIEWNV)
+        } else if *keyword_atom == keyword!("taskNV") {
+            Some(TASKNV)
+        } else if *keyword_atom == keyword!("ignoreIntersectionEXT") {
+            Some(IGNOREINTERSECTIONEXT)
+        } else if *keyword_atom == keyword!("terminateRayEXT") {
+            Some(TERMINATERAYEXT)
+        } else if *keyword_atom == keyword!("spirv_instruction") {
+            Some(SPIRV_INSTRUCTION)
+        } else if *keyword_atom == keyword!("spirv_execution_mode") {
+            Some(SPIRV_EXECUTION_MODE)
+        } else if *keyword_atom == keyword!("spirv_execution_mode_id") {
+            Some(SPIRV_EXECUTION_MODE_ID)
+        } else if *keyword_atom == keyword!("spirv_decorate") {
+            Some(SPIRV_DECORATE)
+        } else if *keyword_atom == keyword!("spirv_decorate_id") {
+            Some(SPIRV_DECORATE_ID)
+        } else if *keyword_atom == keyword!("spirv_decorate_string") {
+            Some(SPIRV_DECORATE_STRING)
+        } else if *keyword_atom == keyword!("spirv_storage_class") {
+            Some(SPIRV_STORAGE_CLASS)
+        } else if *keyword_atom == keyword!("spirv_by_reference") {
+            Some(SPIRV_BY_REFERENCE)
+        } else if *keyword_atom == keyword!("spirv_literal") {
+            Some(SPIRV_LITERAL)
+        } else if *keyword_atom == keyword!("spirv_type") {
+            Some(SPIRV_TYPE)
+        }
         // Reserved for future use
         else if *keyword_atom == keyword!("common") {
             Some(COMMON)
@@ -1990,24 +2650,48 @@ impl Token {
         }
     }
 
+    fn strip_width_suffix(text: &str) -> (IntWidth, &str) {
+        if let Some(stripped) = text.strip_suffix(['l', 'L']) {
+            (IntWidth::Long, stripped)
+        } else if let Some(stripped) = text.strip_suffix(['s', 'S']) {
+            (IntWidth::Short, stripped)
+        } else {
+            (IntWidth::Int, text)
+        }
+    }
+
     fn parse_int(text: &str, radix: u32) -> Result<Self, ErrorKind> {
         use Token::*;
 
+        let (width, text) = Self::strip_width_suffix(text);
         let (unsigned, text) = Self::strip_suffix(text);
 
-        // Hexadecimal constant
-        if unsigned {
-            if radix == 8 && text.is_empty() {
-                Ok(UINT_CONST(0))
-            } else {
-                u32::from_str_radix(text, radix)
-                    .map(UINT_CONST)
-                    .map_err(|_| ErrorKind::InvalidUIntLiteral)
-            }
+        // A lone 0 followed by a suffix is parsed as an empty octal constant
+        let text = if radix == 8 && text.is_empty() {
+            "0"
         } else {
-            i32::from_str_radix(text, radix)
+            text
+        };
+
+        match (width, unsigned) {
+            (IntWidth::Int, false) => i32::from_str_radix(text, radix)
                 .map(INT_CONST)
-                .map_err(|_| ErrorKind::InvalidIntLiteral)
+                .map_err(|_| ErrorKind::InvalidIntLiteral),
+            (IntWidth::Int, true) => u32::from_str_radix(text, radix)
+                .map(UINT_CONST)
+                .map_err(|_| ErrorKind::InvalidUIntLiteral),
+            (IntWidth::Short, false) => i16::from_str_radix(text, radix)
+                .map(INT16_CONST)
+                .map_err(|_| ErrorKind::InvalidInt16Literal),
+            (IntWidth::Short, true) => u16::from_str_radix(text, radix)
+                .map(UINT16_CONST)
+                .map_err(|_| ErrorKind::InvalidUInt16Literal),
+            (IntWidth::Long, false) => i64::from_str_radix(text, radix)
+                .map(INT64_CONST)
+                .map_err(|_| ErrorKind::InvalidInt64Literal),
+            (IntWidth::Long, true) => u64::from_str_radix(text, radix)
+                .map(UINT64_CONST)
+                .map_err(|_| ErrorKind::InvalidUInt64Literal),
         }
     }
 
@@ -2029,6 +2713,10 @@ impl Token {
                     .parse()
                     .map(DOUBLE_CONST)
                     .map_err(|_| ErrorKind::InvalidDoubleLiteral)
+            } else if let Some(half) = text.strip_suffix("hf").or_else(|| text.strip_suffix("HF")) {
+                half.parse()
+                    .map(FLOAT16_CONST)
+                    .map_err(|_| ErrorKind::InvalidFloat16Literal)
             } else if let Some(float) = text.strip_suffix(['f', 'F']).or(Some(text)) {
                 float
                     .parse()
@@ -2066,6 +2754,17 @@ impl Token {
     }
 }
 
+/// Width of an integer constant, as specified by its suffix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntWidth {
+    /// 16-bit constant (`s` suffix)
+    Short,
+    /// 32-bit constant (no suffix)
+    Int,
+    /// 64-bit constant (`l` suffix)
+    Long,
+}
+
 #[cfg(test)]
 mod tests {
     use super::Token::{self, *};
@@ -2150,4 +2849,41 @@ mod tests {
             UINT_CONST(0xffffffffu32)
         );
     }
+
+    #[test]
+    fn test_parse_int16_constant() {
+        assert_eq!(Token::parse_digits("0s"), INT16_CONST(0));
+        assert_eq!(Token::parse_digits("12s"), INT16_CONST(12));
+        assert_eq!(Token::parse_digits("012S"), INT16_CONST(0o12));
+        assert_eq!(Token::parse_digits("0x7fffs"), INT16_CONST(0x7fff));
+        assert_eq!(Token::parse_digits("0us"), UINT16_CONST(0));
+        assert_eq!(Token::parse_digits("0xffffUS"), UINT16_CONST(0xffff));
+        assert_eq!(
+            Token::parse_digits("70000s"),
+            ERROR(super::ErrorKind::InvalidInt16Literal)
+        );
+    }
+
+    #[test]
+    fn test_parse_int64_constant() {
+        assert_eq!(Token::parse_digits("0l"), INT64_CONST(0));
+        assert_eq!(Token::parse_digits("42L"), INT64_CONST(42));
+        assert_eq!(
+            Token::parse_digits("0x7fffffffffffffffl"),
+            INT64_CONST(0x7fffffffffffffff)
+        );
+        assert_eq!(Token::parse_digits("0ul"), UINT64_CONST(0));
+        assert_eq!(
+            Token::parse_digits("0xffffffffffffffffUL"),
+            UINT64_CONST(0xffffffffffffffff)
+        );
+    }
+
+    #[test]
+    fn test_parse_float16_constant() {
+        assert_eq!(Token::parse_digits("0.hf"), FLOAT16_CONST(0.));
+        assert_eq!(Token::parse_digits("1.5hf"), FLOAT16_CONST(1.5));
+        assert_eq!(Token::parse_digits(".035HF"), FLOAT16_CONST(0.035));
+        assert_eq!(Token::parse_digits("1e-3hf"), FLOAT16_CONST(1e-3));
+    }
 }
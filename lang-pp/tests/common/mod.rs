@@ -150,6 +150,8 @@ pub fn test_file(path: impl AsRef<Path>) {
 
                 Event::EnterFile { .. } => {}
 
+                Event::Warning { .. } => {}
+
                 Event::Token {
                     source_token,
                     token_kind: _,
@@ -78,11 +78,18 @@ use crate::tokenize::Tokenize;
 mod quoted;
 mod tokenize;
 
-fn glsl_internal<F>(input: proc_macro::TokenStream) -> proc_macro::TokenStream
+/// Directives enabling the extensions which introduce new syntax
+///
+/// Macro input can't contain preprocessor directives, so translation units are parsed with these
+/// extensions enabled.
+const TRANSLATION_UNIT_PRELUDE: &str = "#extension GL_EXT_control_flow_attributes : enable\n\
+                                        #extension GL_EXT_spirv_intrinsics : enable\n";
+
+fn glsl_internal<F>(prelude: &str, input: proc_macro::TokenStream) -> proc_macro::TokenStream
 where
     F: Parsable + Tokenize + std::fmt::Debug,
 {
-    let s = format!("{}", &input);
+    let s = format!("{}{}", prelude, &input);
     let opts = ParseOptions {
         allow_rs_ident: true,
         ..Default::default()
@@ -103,19 +110,19 @@ where
 /// Parse a translation unit at compile time
 #[proc_macro]
 pub fn glsl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    glsl_internal::<ast::TranslationUnit>(input)
+    glsl_internal::<ast::TranslationUnit>(TRANSLATION_UNIT_PRELUDE, input)
 }
 
 /// Parse a statement at compile time
 #[cfg(any(feature = "quote-statement", feature = "quote-parsable"))]
 #[proc_macro]
 pub fn glsl_statement(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    glsl_internal::<ast::Statement>(input)
+    glsl_internal::<ast::Statement>("", input)
 }
 
 /// Parse an expression at compile time
 #[cfg(any(feature = "quote-expr", feature = "quote-parsable"))]
 #[proc_macro]
 pub fn glsl_expr(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    glsl_internal::<ast::Expr>(input)
+    glsl_internal::<ast::Expr>("", input)
 }
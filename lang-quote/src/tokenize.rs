@@ -31,18 +31,54 @@ impl Tokenize for u32 {
     }
 }
 
-impl Tokenize for f32 {
+impl Tokenize for i16 {
     fn tokenize(&self, stream: &mut TokenStream) {
         self.to_tokens(stream)
     }
 }
 
-impl Tokenize for f64 {
+impl Tokenize for u16 {
+    fn tokenize(&self, stream: &mut TokenStream) {
+        self.to_tokens(stream)
+    }
+}
+
+impl Tokenize for i64 {
     fn tokenize(&self, stream: &mut TokenStream) {
         self.to_tokens(stream)
     }
 }
 
+impl Tokenize for u64 {
+    fn tokenize(&self, stream: &mut TokenStream) {
+        self.to_tokens(stream)
+    }
+}
+
+impl Tokenize for f32 {
+    fn tokenize(&self, stream: &mut TokenStream) {
+        if self.is_finite() {
+            self.to_tokens(stream)
+        } else {
+            // Non-finite values have no literal representation
+            let bits = self.to_bits();
+            stream.extend(quote! { f32::from_bits(#bits) })
+        }
+    }
+}
+
+impl Tokenize for f64 {
+    fn tokenize(&self, stream: &mut TokenStream) {
+        if self.is_finite() {
+            self.to_tokens(stream)
+        } else {
+            // Non-finite values have no literal representation
+            let bits = self.to_bits();
+            stream.extend(quote! { f64::from_bits(#bits) })
+        }
+    }
+}
+
 macro_rules! impl_tokenize {
     ($type_name:ty, $tokenizer:ident) => {
         impl Tokenize for $type_name {
@@ -100,7 +136,8 @@ impl_tokenize!(ast::PreprocessorError, tokenize_preprocessor_error);
 impl_tokenize!(ast::PreprocessorIf, tokenize_preprocessor_if);
 impl_tokenize!(ast::PreprocessorIfDef, tokenize_preprocessor_ifdef);
 impl_tokenize!(ast::PreprocessorIfNDef, tokenize_preprocessor_ifndef);
-impl_tokenize!(ast::PreprocessorMojImport, tokenize_preprocessor_include);
+impl_tokenize!(ast::PreprocessorInclude, tokenize_preprocessor_include);
+impl_tokenize!(ast::PreprocessorMojImport, tokenize_preprocessor_moj_import);
 impl_tokenize!(ast::PreprocessorLine, tokenize_preprocessor_line);
 impl_tokenize!(ast::PreprocessorPragma, tokenize_preprocessor_pragma);
 impl_tokenize!(ast::PreprocessorUndef, tokenize_preprocessor_undef);
@@ -148,6 +185,23 @@ fn tokenize_identifier(i: &ast::Identifier) -> TokenStream {
     }
 }
 
+fn tokenize_literal<T: Tokenize + Copy>(l: &ast::Literal<T>) -> TokenStream {
+    let mut value = TokenStream::new();
+    l.value().tokenize(&mut value);
+
+    if let Some(text) = l.text() {
+        quote! { glsl_lang::ast::Literal::from_source(#value, #text) }
+    } else {
+        let radix = match l.radix() {
+            ast::IntRadix::Decimal => quote! { glsl_lang::ast::IntRadix::Decimal },
+            ast::IntRadix::Octal => quote! { glsl_lang::ast::IntRadix::Octal },
+            ast::IntRadix::Hexadecimal => quote! { glsl_lang::ast::IntRadix::Hexadecimal },
+        };
+
+        quote! { glsl_lang::ast::Literal::new(#value).with_radix(#radix) }
+    }
+}
+
 fn tokenize_type_name(tn: &ast::TypeName) -> TokenStream {
     let t = tn.0.to_owned().quote();
     let span = tokenize_span(&tn.span);
@@ -608,6 +662,268 @@ fn tokenize_type_specifier_non_array(t: &ast::TypeSpecifierNonArray) -> TokenStr
         }
 
         // end GL_KHR_vulkan_glsl types
+
+        // GL_EXT_shader_explicit_arithmetic_types types
+        ast::TypeSpecifierNonArrayData::Int8 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Int8 }
+        }
+        ast::TypeSpecifierNonArrayData::I8Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I8Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::I8Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I8Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::I8Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I8Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::UInt8 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::UInt8 }
+        }
+        ast::TypeSpecifierNonArrayData::U8Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U8Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::U8Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U8Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::U8Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U8Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::Int16 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Int16 }
+        }
+        ast::TypeSpecifierNonArrayData::I16Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I16Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::I16Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I16Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::I16Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I16Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::UInt16 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::UInt16 }
+        }
+        ast::TypeSpecifierNonArrayData::U16Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U16Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::U16Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U16Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::U16Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U16Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::Int32 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Int32 }
+        }
+        ast::TypeSpecifierNonArrayData::I32Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I32Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::I32Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I32Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::I32Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I32Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::UInt32 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::UInt32 }
+        }
+        ast::TypeSpecifierNonArrayData::U32Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U32Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::U32Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U32Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::U32Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U32Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::Int64 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Int64 }
+        }
+        ast::TypeSpecifierNonArrayData::I64Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I64Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::I64Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I64Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::I64Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::I64Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::UInt64 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::UInt64 }
+        }
+        ast::TypeSpecifierNonArrayData::U64Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U64Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::U64Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U64Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::U64Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::U64Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::Float16 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Float16 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat2 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat3 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat4 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat22 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat22 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat23 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat23 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat24 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat24 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat32 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat32 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat33 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat33 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat34 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat34 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat42 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat42 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat43 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat43 }
+        }
+        ast::TypeSpecifierNonArrayData::F16Mat44 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F16Mat44 }
+        }
+        ast::TypeSpecifierNonArrayData::Float32 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Float32 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat2 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat3 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat4 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat22 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat22 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat23 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat23 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat24 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat24 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat32 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat32 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat33 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat33 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat34 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat34 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat42 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat42 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat43 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat43 }
+        }
+        ast::TypeSpecifierNonArrayData::F32Mat44 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F32Mat44 }
+        }
+        ast::TypeSpecifierNonArrayData::Float64 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Float64 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Vec2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Vec2 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Vec3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Vec3 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Vec4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Vec4 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat2 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat2 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat3 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat3 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat4 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat4 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat22 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat22 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat23 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat23 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat24 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat24 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat32 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat32 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat33 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat33 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat34 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat34 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat42 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat42 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat43 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat43 }
+        }
+        ast::TypeSpecifierNonArrayData::F64Mat44 => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::F64Mat44 }
+        }
+
+        // end GL_EXT_shader_explicit_arithmetic_types types
+
+        // GL_EXT_ray_query types
+        ast::TypeSpecifierNonArrayData::AccelerationStructureExt => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::AccelerationStructureExt }
+        }
+        ast::TypeSpecifierNonArrayData::RayQueryExt => {
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::RayQueryExt }
+        }
+
+        // end GL_EXT_ray_query types
+
+        // GL_EXT_spirv_intrinsics types
+        ast::TypeSpecifierNonArrayData::SpirvType(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::TypeSpecifierNonArrayData::SpirvType(vec![#(#args),*]) }
+        }
+
+        // end GL_EXT_spirv_intrinsics types
         ast::TypeSpecifierNonArrayData::Struct(ref s) => {
             let s = tokenize_struct_non_declaration(s);
             quote! { glsl_lang::ast::TypeSpecifierNonArrayData::Struct(#s) }
@@ -789,6 +1105,11 @@ fn tokenize_type_qualifier_spec(q: &ast::TypeQualifierSpec) -> TokenStream {
         ast::TypeQualifierSpecData::Precise => {
             quote! { glsl_lang::ast::TypeQualifierSpecData::Precise }
         }
+
+        ast::TypeQualifierSpecData::Spirv(ref s) => {
+            let s = tokenize_spirv_qualifier(s);
+            quote! { glsl_lang::ast::TypeQualifierSpecData::Spirv(#s) }
+        }
     };
 
     quote! { glsl_lang::ast::TypeQualifierSpec::new(#q, #span) }
@@ -852,6 +1173,34 @@ fn tokenize_storage_qualifier(q: &ast::StorageQualifier) -> TokenStream {
                 glsl_lang::ast::StorageQualifierData::Subroutine(vec![#(#n),*])
             }
         }
+
+        ast::StorageQualifierData::ShaderCallCoherent => {
+            quote! { glsl_lang::ast::StorageQualifierData::ShaderCallCoherent }
+        }
+
+        ast::StorageQualifierData::RayPayloadExt => {
+            quote! { glsl_lang::ast::StorageQualifierData::RayPayloadExt }
+        }
+
+        ast::StorageQualifierData::RayPayloadInExt => {
+            quote! { glsl_lang::ast::StorageQualifierData::RayPayloadInExt }
+        }
+
+        ast::StorageQualifierData::HitAttributeExt => {
+            quote! { glsl_lang::ast::StorageQualifierData::HitAttributeExt }
+        }
+
+        ast::StorageQualifierData::CallableDataExt => {
+            quote! { glsl_lang::ast::StorageQualifierData::CallableDataExt }
+        }
+
+        ast::StorageQualifierData::CallableDataInExt => {
+            quote! { glsl_lang::ast::StorageQualifierData::CallableDataInExt }
+        }
+
+        ast::StorageQualifierData::TaskPayloadSharedExt => {
+            quote! { glsl_lang::ast::StorageQualifierData::TaskPayloadSharedExt }
+        }
     };
 
     quote! { glsl_lang::ast::StorageQualifier::new(#q, #span) }
@@ -889,6 +1238,90 @@ fn tokenize_layout_qualifier_spec(l: &ast::LayoutQualifierSpec) -> TokenStream {
     quote! { glsl_lang::ast::LayoutQualifierSpec::new(#l, #span) }
 }
 
+fn tokenize_spirv_qualifier(q: &ast::SpirvQualifier) -> TokenStream {
+    let span = tokenize_span(&q.span);
+    let q = match q.content {
+        ast::SpirvQualifierData::Instruction(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::SpirvQualifierData::Instruction(vec![#(#args),*]) }
+        }
+        ast::SpirvQualifierData::ExecutionMode(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::SpirvQualifierData::ExecutionMode(vec![#(#args),*]) }
+        }
+        ast::SpirvQualifierData::ExecutionModeId(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::SpirvQualifierData::ExecutionModeId(vec![#(#args),*]) }
+        }
+        ast::SpirvQualifierData::Decorate(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::SpirvQualifierData::Decorate(vec![#(#args),*]) }
+        }
+        ast::SpirvQualifierData::DecorateId(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::SpirvQualifierData::DecorateId(vec![#(#args),*]) }
+        }
+        ast::SpirvQualifierData::DecorateString(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::SpirvQualifierData::DecorateString(vec![#(#args),*]) }
+        }
+        ast::SpirvQualifierData::StorageClass(ref args) => {
+            let args = args.iter().map(tokenize_spirv_argument);
+            quote! { glsl_lang::ast::SpirvQualifierData::StorageClass(vec![#(#args),*]) }
+        }
+        ast::SpirvQualifierData::ByReference => {
+            quote! { glsl_lang::ast::SpirvQualifierData::ByReference }
+        }
+        ast::SpirvQualifierData::Literal => {
+            quote! { glsl_lang::ast::SpirvQualifierData::Literal }
+        }
+    };
+
+    quote! { glsl_lang::ast::SpirvQualifier::new(#q, #span) }
+}
+
+fn tokenize_spirv_argument(a: &ast::SpirvArgument) -> TokenStream {
+    let span = tokenize_span(&a.span);
+    let a = match a.content {
+        ast::SpirvArgumentData::Requirement(ref i, ref values) => {
+            let i = tokenize_identifier(i);
+            let values = values.iter().map(tokenize_spirv_value);
+            quote! { glsl_lang::ast::SpirvArgumentData::Requirement(#i, vec![#(#values),*]) }
+        }
+        ast::SpirvArgumentData::Named(ref i, ref value) => {
+            let i = tokenize_identifier(i);
+            let value = tokenize_spirv_value(value);
+            quote! { glsl_lang::ast::SpirvArgumentData::Named(#i, #value) }
+        }
+        ast::SpirvArgumentData::Value(ref value) => {
+            let value = tokenize_spirv_value(value);
+            quote! { glsl_lang::ast::SpirvArgumentData::Value(#value) }
+        }
+    };
+
+    quote! { glsl_lang::ast::SpirvArgument::new(#a, #span) }
+}
+
+fn tokenize_spirv_value(v: &ast::SpirvValue) -> TokenStream {
+    let span = tokenize_span(&v.span);
+    let v = match v.content {
+        ast::SpirvValueData::String(ref s) => {
+            let s = s.quote();
+            quote! { glsl_lang::ast::SpirvValueData::String(#s) }
+        }
+        ast::SpirvValueData::Expr(ref e) => {
+            let e = (&tokenize_expr(e)).quote();
+            quote! { glsl_lang::ast::SpirvValueData::Expr(#e) }
+        }
+        ast::SpirvValueData::Type(ref t) => {
+            let t = tokenize_type_specifier_non_array(t);
+            quote! { glsl_lang::ast::SpirvValueData::Type(#t) }
+        }
+    };
+
+    quote! { glsl_lang::ast::SpirvValue::new(#v, #span) }
+}
+
 fn tokenize_precision_qualifier(p: &ast::PrecisionQualifier) -> TokenStream {
     let span = tokenize_span(&p.span);
     let p = match p.content {
@@ -916,6 +1349,19 @@ fn tokenize_interpolation_qualifier(i: &ast::InterpolationQualifier) -> TokenStr
         ast::InterpolationQualifierData::NoPerspective => {
             quote! { glsl_lang::ast::InterpolationQualifierData::NoPerspective }
         }
+
+        ast::InterpolationQualifierData::PerPrimitiveExt => {
+            quote! { glsl_lang::ast::InterpolationQualifierData::PerPrimitiveExt }
+        }
+        ast::InterpolationQualifierData::PerPrimitiveNv => {
+            quote! { glsl_lang::ast::InterpolationQualifierData::PerPrimitiveNv }
+        }
+        ast::InterpolationQualifierData::PerViewNv => {
+            quote! { glsl_lang::ast::InterpolationQualifierData::PerViewNv }
+        }
+        ast::InterpolationQualifierData::TaskNv => {
+            quote! { glsl_lang::ast::InterpolationQualifierData::TaskNv }
+        }
     };
 
     quote! { glsl_lang::ast::InterpolationQualifier::new(#i, #span) }
@@ -933,15 +1379,52 @@ fn tokenize_expr(expr: &ast::Expr) -> TokenStream {
             quote! { glsl_lang::ast::ExprData::Variable(#i) }
         }
 
-        ast::ExprData::IntConst(ref x) => quote! { glsl_lang::ast::ExprData::IntConst(#x) },
+        ast::ExprData::IntConst(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::IntConst(#x) }
+        }
 
-        ast::ExprData::UIntConst(ref x) => quote! { glsl_lang::ast::ExprData::UIntConst(#x) },
+        ast::ExprData::UIntConst(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::UIntConst(#x) }
+        }
 
         ast::ExprData::BoolConst(ref x) => quote! { glsl_lang::ast::ExprData::BoolConst(#x) },
 
-        ast::ExprData::FloatConst(ref x) => quote! { glsl_lang::ast::ExprData::FloatConst(#x) },
+        ast::ExprData::FloatConst(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::FloatConst(#x) }
+        }
 
-        ast::ExprData::DoubleConst(ref x) => quote! { glsl_lang::ast::ExprData::DoubleConst(#x) },
+        ast::ExprData::DoubleConst(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::DoubleConst(#x) }
+        }
+
+        ast::ExprData::Int16Const(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::Int16Const(#x) }
+        }
+
+        ast::ExprData::UInt16Const(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::UInt16Const(#x) }
+        }
+
+        ast::ExprData::Int64Const(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::Int64Const(#x) }
+        }
+
+        ast::ExprData::UInt64Const(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::UInt64Const(#x) }
+        }
+
+        ast::ExprData::Float16Const(ref x) => {
+            let x = tokenize_literal(x);
+            quote! { glsl_lang::ast::ExprData::Float16Const(#x) }
+        }
 
         ast::ExprData::Unary(ref op, ref e) => {
             let op = tokenize_unary_op(op);
@@ -1118,6 +1601,12 @@ fn tokenize_declaration(d: &ast::Declaration) -> TokenStream {
             quote! { glsl_lang::ast::DeclarationData::Invariant(#ident) }
         }
 
+        ast::DeclarationData::QualifierRedeclaration(ref q, ref idents) => {
+            let q = tokenize_type_qualifier(q);
+            let idents = idents.iter().map(tokenize_identifier);
+            quote! { glsl_lang::ast::DeclarationData::QualifierRedeclaration(#q, vec![#(#idents),*]) }
+        }
+
         ast::DeclarationData::TypeOnly(ref q) => {
             let q = tokenize_type_qualifier(q);
             quote! { glsl_lang::ast::DeclarationData::TypeOnly(#q) }
@@ -1353,6 +1842,13 @@ fn tokenize_statement(sst: &ast::Statement) -> TokenStream {
             let c = tokenize_compound_statement(c);
             quote! { glsl_lang::ast::StatementData::Compound(#c) }
         }
+
+        ast::StatementData::Attributed(ref a) => {
+            let a = tokenize_attributed_statement(a);
+            quote! { glsl_lang::ast::StatementData::Attributed(#a) }
+        }
+
+        ast::StatementData::Error => quote! { glsl_lang::ast::StatementData::Error },
     };
 
     let span = tokenize_span(&sst.span);
@@ -1369,6 +1865,59 @@ fn tokenize_expr_statement(est: &ast::ExprStatement) -> TokenStream {
     quote! { glsl_lang::ast::ExprStatement::new(#est, #span) }
 }
 
+fn tokenize_attribute(a: &ast::Attribute) -> TokenStream {
+    let span = tokenize_span(&a.span);
+    let a = {
+        let name = tokenize_identifier(&a.name);
+        let arg = a.arg.as_ref().map(|e| (&tokenize_expr(e)).quote()).quote();
+
+        quote! {
+          glsl_lang::ast::AttributeData {
+            name: #name,
+            arg: #arg
+          }
+        }
+    };
+
+    quote! { glsl_lang::ast::Attribute::new(#a, #span) }
+}
+
+fn tokenize_attributed_statement(ast: &ast::AttributedStatement) -> TokenStream {
+    let span = tokenize_span(&ast.span);
+    let ast = {
+        let attributes = ast.attributes.iter().map(tokenize_attribute);
+        let statement = (&tokenize_statement(&ast.statement)).quote();
+
+        quote! {
+          glsl_lang::ast::AttributedStatementData {
+            attributes: vec![#(#attributes),*],
+            statement: #statement
+          }
+        }
+    };
+
+    quote! { glsl_lang::ast::AttributedStatement::new(#ast, #span) }
+}
+
+fn tokenize_attributed_function_definition(afd: &ast::AttributedFunctionDefinition) -> TokenStream {
+    let span = tokenize_span(&afd.span);
+    let afd = {
+        let attributes = afd.attributes.iter().map(tokenize_attribute);
+        let body_attributes = afd.body_attributes.iter().map(tokenize_attribute);
+        let definition = tokenize_function_definition(&afd.definition);
+
+        quote! {
+          glsl_lang::ast::AttributedFunctionDefinitionData {
+            attributes: vec![#(#attributes),*],
+            body_attributes: vec![#(#body_attributes),*],
+            definition: #definition
+          }
+        }
+    };
+
+    quote! { glsl_lang::ast::AttributedFunctionDefinition::new(#afd, #span) }
+}
+
 fn tokenize_selection_statement(sst: &ast::SelectionStatement) -> TokenStream {
     let span = tokenize_span(&sst.span);
     let sst = {
@@ -1465,7 +2014,7 @@ fn tokenize_condition(c: &ast::Condition) -> TokenStream {
     let span = tokenize_span(&c.span);
     let c = match c.content {
         ast::ConditionData::Expr(ref e) => {
-            let e = (&tokenize_expr(e)).quote();
+            let e = tokenize_expr(e);
             quote! { glsl_lang::ast::ConditionData::Expr(#e) }
         }
 
@@ -1509,9 +2058,9 @@ fn tokenize_for_rest_statement(r: &ast::ForRestStatement) -> TokenStream {
             .quote();
 
         quote! {
-          glsl_lang::ast::ForRestStatement {
+          glsl_lang::ast::ForRestStatementData {
             condition: #cond,
-            post: #post
+            post_expr: #post
           }
         }
     };
@@ -1525,6 +2074,12 @@ fn tokenize_jump_statement(j: &ast::JumpStatement) -> TokenStream {
         ast::JumpStatementData::Continue => quote! { glsl_lang::ast::JumpStatementData::Continue },
         ast::JumpStatementData::Break => quote! { glsl_lang::ast::JumpStatementData::Break },
         ast::JumpStatementData::Discard => quote! { glsl_lang::ast::JumpStatementData::Discard },
+        ast::JumpStatementData::IgnoreIntersectionExt => {
+            quote! { glsl_lang::ast::JumpStatementData::IgnoreIntersectionExt }
+        }
+        ast::JumpStatementData::TerminateRayExt => {
+            quote! { glsl_lang::ast::JumpStatementData::TerminateRayExt }
+        }
         ast::JumpStatementData::Return(ref e) => {
             let e = e.as_ref().map(|e| (&tokenize_expr(e)).quote()).quote();
             quote! { glsl_lang::ast::JumpStatementData::Return(#e) }
@@ -1574,8 +2129,13 @@ fn tokenize_preprocessor(pp: &ast::Preprocessor) -> TokenStream {
             quote! { glsl_lang::ast::PreprocessorData::IfNDef(#pind) }
         }
 
+        ast::PreprocessorData::Include(ref pi) => {
+            let pi = tokenize_preprocessor_include(pi);
+            quote! { glsl_lang::ast::PreprocessorData::Include(#pi) }
+        }
+
         ast::PreprocessorData::MojImport(ref pmi) => {
-            let pmi = tokenize_preprocessor_include(pmi);
+            let pmi = tokenize_preprocessor_moj_import(pmi);
             quote! { glsl_lang::ast::PreprocessorData::MojImport(#pmi) }
         }
 
@@ -1724,7 +2284,7 @@ fn tokenize_preprocessor_ifndef(pind: &ast::PreprocessorIfNDef) -> TokenStream {
     quote! { glsl_lang::ast::PreprocessorIfNDef::new(#pind, #span) }
 }
 
-fn tokenize_preprocessor_include(pi: &ast::PreprocessorMojImport) -> TokenStream {
+fn tokenize_preprocessor_include(pi: &ast::PreprocessorInclude) -> TokenStream {
     let span = tokenize_span(&pi.span);
     let pi = {
         let path = tokenize_path(&pi.path);
@@ -1739,6 +2299,21 @@ fn tokenize_preprocessor_include(pi: &ast::PreprocessorMojImport) -> TokenStream
     quote! { glsl_lang::ast::PreprocessorInclude::new(#pi, #span) }
 }
 
+fn tokenize_preprocessor_moj_import(pmi: &ast::PreprocessorMojImport) -> TokenStream {
+    let span = tokenize_span(&pmi.span);
+    let pmi = {
+        let path = tokenize_path(&pmi.path);
+
+        quote! {
+          glsl_lang::ast::PreprocessorMojImportData {
+            path: #path
+          }
+        }
+    };
+
+    quote! { glsl_lang::ast::PreprocessorMojImport::new(#pmi, #span) }
+}
+
 fn tokenize_preprocessor_line(pl: &ast::PreprocessorLine) -> TokenStream {
     let span = tokenize_span(&pl.span);
     let pl = {
@@ -1916,10 +2491,19 @@ fn tokenize_external_declaration(ed: &ast::ExternalDeclaration) -> TokenStream {
             quote! { glsl_lang::ast::ExternalDeclarationData::FunctionDefinition(#fd) }
         }
 
+        ast::ExternalDeclarationData::AttributedFunctionDefinition(ref fd) => {
+            let fd = tokenize_attributed_function_definition(fd);
+            quote! { glsl_lang::ast::ExternalDeclarationData::AttributedFunctionDefinition(#fd) }
+        }
+
         ast::ExternalDeclarationData::Declaration(ref d) => {
             let d = tokenize_declaration(d);
             quote! { glsl_lang::ast::ExternalDeclarationData::Declaration(#d) }
         }
+
+        ast::ExternalDeclarationData::Error => {
+            quote! { glsl_lang::ast::ExternalDeclarationData::Error }
+        }
     };
 
     let span = tokenize_span(&ed.span);
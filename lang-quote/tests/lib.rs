@@ -132,3 +132,48 @@ fn typeonly_multiview_qualifier() {
         layout (num_views = 2) in;
     };
 }
+
+#[test]
+fn control_flow_attributes() {
+    let _ = glsl! {
+      void main() {
+        [[unroll]] for (int i = 0; i < 8; ++i) {}
+        [[dont_flatten, branch]] if (true) {}
+        [[dependency_length(2)]] while (false) {}
+      }
+    };
+}
+
+#[test]
+fn explicit_arithmetic_literals() {
+    let _ = glsl! {
+      void main() {
+        f(12s, 7us, 0xffffL, 3ul, 1.5hf);
+      }
+    };
+}
+
+#[test]
+fn ray_tracing_qualifiers() {
+    let _ = glsl! {
+      layout(location = 0) rayPayloadInEXT vec4 payload;
+      hitAttributeEXT vec2 attribs;
+      void main() {
+        if (attribs.x > 0.5) ignoreIntersectionEXT;
+        terminateRayEXT;
+      }
+    };
+}
+
+#[test]
+fn spirv_intrinsics_qualifiers() {
+    let _ = glsl! {
+      spirv_decorate(extensions = ["SPV_AMD_shader_explicit_vertex_parameter"], 11, 4992)
+      in vec2 bary;
+      spirv_instruction(set = "GLSL.std.450", id = 35)
+      float modf(float x, spirv_by_reference float i);
+      void main() {
+        spirv_type(capabilities = [4472], id = 4472) rq;
+      }
+    };
+}
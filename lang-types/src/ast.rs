@@ -609,6 +609,267 @@ pub enum TypeSpecifierNonArrayData {
     USubpassInputMs,
 
     // end GL_KHR_vulkan_glsl types
+
+    // GL_EXT_shader_explicit_arithmetic_types types
+    /// `int8_t` type specifier
+    #[lang_util(display(extra = "int8_t"))]
+    Int8,
+    /// `i8vec2` type specifier
+    #[lang_util(display(extra = "i8vec2"))]
+    I8Vec2,
+    /// `i8vec3` type specifier
+    #[lang_util(display(extra = "i8vec3"))]
+    I8Vec3,
+    /// `i8vec4` type specifier
+    #[lang_util(display(extra = "i8vec4"))]
+    I8Vec4,
+    /// `uint8_t` type specifier
+    #[lang_util(display(extra = "uint8_t"))]
+    UInt8,
+    /// `u8vec2` type specifier
+    #[lang_util(display(extra = "u8vec2"))]
+    U8Vec2,
+    /// `u8vec3` type specifier
+    #[lang_util(display(extra = "u8vec3"))]
+    U8Vec3,
+    /// `u8vec4` type specifier
+    #[lang_util(display(extra = "u8vec4"))]
+    U8Vec4,
+    /// `int16_t` type specifier
+    #[lang_util(display(extra = "int16_t"))]
+    Int16,
+    /// `i16vec2` type specifier
+    #[lang_util(display(extra = "i16vec2"))]
+    I16Vec2,
+    /// `i16vec3` type specifier
+    #[lang_util(display(extra = "i16vec3"))]
+    I16Vec3,
+    /// `i16vec4` type specifier
+    #[lang_util(display(extra = "i16vec4"))]
+    I16Vec4,
+    /// `uint16_t` type specifier
+    #[lang_util(display(extra = "uint16_t"))]
+    UInt16,
+    /// `u16vec2` type specifier
+    #[lang_util(display(extra = "u16vec2"))]
+    U16Vec2,
+    /// `u16vec3` type specifier
+    #[lang_util(display(extra = "u16vec3"))]
+    U16Vec3,
+    /// `u16vec4` type specifier
+    #[lang_util(display(extra = "u16vec4"))]
+    U16Vec4,
+    /// `int32_t` type specifier
+    #[lang_util(display(extra = "int32_t"))]
+    Int32,
+    /// `i32vec2` type specifier
+    #[lang_util(display(extra = "i32vec2"))]
+    I32Vec2,
+    /// `i32vec3` type specifier
+    #[lang_util(display(extra = "i32vec3"))]
+    I32Vec3,
+    /// `i32vec4` type specifier
+    #[lang_util(display(extra = "i32vec4"))]
+    I32Vec4,
+    /// `uint32_t` type specifier
+    #[lang_util(display(extra = "uint32_t"))]
+    UInt32,
+    /// `u32vec2` type specifier
+    #[lang_util(display(extra = "u32vec2"))]
+    U32Vec2,
+    /// `u32vec3` type specifier
+    #[lang_util(display(extra = "u32vec3"))]
+    U32Vec3,
+    /// `u32vec4` type specifier
+    #[lang_util(display(extra = "u32vec4"))]
+    U32Vec4,
+    /// `int64_t` type specifier
+    #[lang_util(display(extra = "int64_t"))]
+    Int64,
+    /// `i64vec2` type specifier
+    #[lang_util(display(extra = "i64vec2"))]
+    I64Vec2,
+    /// `i64vec3` type specifier
+    #[lang_util(display(extra = "i64vec3"))]
+    I64Vec3,
+    /// `i64vec4` type specifier
+    #[lang_util(display(extra = "i64vec4"))]
+    I64Vec4,
+    /// `uint64_t` type specifier
+    #[lang_util(display(extra = "uint64_t"))]
+    UInt64,
+    /// `u64vec2` type specifier
+    #[lang_util(display(extra = "u64vec2"))]
+    U64Vec2,
+    /// `u64vec3` type specifier
+    #[lang_util(display(extra = "u64vec3"))]
+    U64Vec3,
+    /// `u64vec4` type specifier
+    #[lang_util(display(extra = "u64vec4"))]
+    U64Vec4,
+    /// `float16_t` type specifier
+    #[lang_util(display(extra = "float16_t"))]
+    Float16,
+    /// `f16vec2` type specifier
+    #[lang_util(display(extra = "f16vec2"))]
+    F16Vec2,
+    /// `f16vec3` type specifier
+    #[lang_util(display(extra = "f16vec3"))]
+    F16Vec3,
+    /// `f16vec4` type specifier
+    #[lang_util(display(extra = "f16vec4"))]
+    F16Vec4,
+    /// `f16mat2` type specifier
+    #[lang_util(display(extra = "f16mat2"))]
+    F16Mat2,
+    /// `f16mat3` type specifier
+    #[lang_util(display(extra = "f16mat3"))]
+    F16Mat3,
+    /// `f16mat4` type specifier
+    #[lang_util(display(extra = "f16mat4"))]
+    F16Mat4,
+    /// `f16mat2x2` type specifier
+    #[lang_util(display(extra = "f16mat2x2"))]
+    F16Mat22,
+    /// `f16mat2x3` type specifier
+    #[lang_util(display(extra = "f16mat2x3"))]
+    F16Mat23,
+    /// `f16mat2x4` type specifier
+    #[lang_util(display(extra = "f16mat2x4"))]
+    F16Mat24,
+    /// `f16mat3x2` type specifier
+    #[lang_util(display(extra = "f16mat3x2"))]
+    F16Mat32,
+    /// `f16mat3x3` type specifier
+    #[lang_util(display(extra = "f16mat3x3"))]
+    F16Mat33,
+    /// `f16mat3x4` type specifier
+    #[lang_util(display(extra = "f16mat3x4"))]
+    F16Mat34,
+    /// `f16mat4x2` type specifier
+    #[lang_util(display(extra = "f16mat4x2"))]
+    F16Mat42,
+    /// `f16mat4x3` type specifier
+    #[lang_util(display(extra = "f16mat4x3"))]
+    F16Mat43,
+    /// `f16mat4x4` type specifier
+    #[lang_util(display(extra = "f16mat4x4"))]
+    F16Mat44,
+    /// `float32_t` type specifier
+    #[lang_util(display(extra = "float32_t"))]
+    Float32,
+    /// `f32vec2` type specifier
+    #[lang_util(display(extra = "f32vec2"))]
+    F32Vec2,
+    /// `f32vec3` type specifier
+    #[lang_util(display(extra = "f32vec3"))]
+    F32Vec3,
+    /// `f32vec4` type specifier
+    #[lang_util(display(extra = "f32vec4"))]
+    F32Vec4,
+    /// `f32mat2` type specifier
+    #[lang_util(display(extra = "f32mat2"))]
+    F32Mat2,
+    /// `f32mat3` type specifier
+    #[lang_util(display(extra = "f32mat3"))]
+    F32Mat3,
+    /// `f32mat4` type specifier
+    #[lang_util(display(extra = "f32mat4"))]
+    F32Mat4,
+    /// `f32mat2x2` type specifier
+    #[lang_util(display(extra = "f32mat2x2"))]
+    F32Mat22,
+    /// `f32mat2x3` type specifier
+    #[lang_util(display(extra = "f32mat2x3"))]
+    F32Mat23,
+    /// `f32mat2x4` type specifier
+    #[lang_util(display(extra = "f32mat2x4"))]
+    F32Mat24,
+    /// `f32mat3x2` type specifier
+    #[lang_util(display(extra = "f32mat3x2"))]
+    F32Mat32,
+    /// `f32mat3x3` type specifier
+    #[lang_util(display(extra = "f32mat3x3"))]
+    F32Mat33,
+    /// `f32mat3x4` type specifier
+    #[lang_util(display(extra = "f32mat3x4"))]
+    F32Mat34,
+    /// `f32mat4x2` type specifier
+    #[lang_util(display(extra = "f32mat4x2"))]
+    F32Mat42,
+    /// `f32mat4x3` type specifier
+    #[lang_util(display(extra = "f32mat4x3"))]
+    F32Mat43,
+    /// `f32mat4x4` type specifier
+    #[lang_util(display(extra = "f32mat4x4"))]
+    F32Mat44,
+    /// `float64_t` type specifier
+    #[lang_util(display(extra = "float64_t"))]
+    Float64,
+    /// `f64vec2` type specifier
+    #[lang_util(display(extra = "f64vec2"))]
+    F64Vec2,
+    /// `f64vec3` type specifier
+    #[lang_util(display(extra = "f64vec3"))]
+    F64Vec3,
+    /// `f64vec4` type specifier
+    #[lang_util(display(extra = "f64vec4"))]
+    F64Vec4,
+    /// `f64mat2` type specifier
+    #[lang_util(display(extra = "f64mat2"))]
+    F64Mat2,
+    /// `f64mat3` type specifier
+    #[lang_util(display(extra = "f64mat3"))]
+    F64Mat3,
+    /// `f64mat4` type specifier
+    #[lang_util(display(extra = "f64mat4"))]
+    F64Mat4,
+    /// `f64mat2x2` type specifier
+    #[lang_util(display(extra = "f64mat2x2"))]
+    F64Mat22,
+    /// `f64mat2x3` type specifier
+    #[lang_util(display(extra = "f64mat2x3"))]
+    F64Mat23,
+    /// `f64mat2x4` type specifier
+    #[lang_util(display(extra = "f64mat2x4"))]
+    F64Mat24,
+    /// `f64mat3x2` type specifier
+    #[lang_util(display(extra = "f64mat3x2"))]
+    F64Mat32,
+    /// `f64mat3x3` type specifier
+    #[lang_util(display(extra = "f64mat3x3"))]
+    F64Mat33,
+    /// `f64mat3x4` type specifier
+    #[lang_util(display(extra = "f64mat3x4"))]
+    F64Mat34,
+    /// `f64mat4x2` type specifier
+    #[lang_util(display(extra = "f64mat4x2"))]
+    F64Mat42,
+    /// `f64mat4x3` type specifier
+    #[lang_util(display(extra = "f64mat4x3"))]
+    F64Mat43,
+    /// `f64mat4x4` type specifier
+    #[lang_util(display(extra = "f64mat4x4"))]
+    F64Mat44,
+
+    // end GL_EXT_shader_explicit_arithmetic_types types
+
+    // GL_EXT_ray_query types
+    /// `accelerationStructureEXT` type specifier
+    #[lang_util(display(extra = "accelerationStructureEXT"))]
+    AccelerationStructureExt,
+    /// `rayQueryEXT` type specifier
+    #[lang_util(display(extra = "rayQueryEXT"))]
+    RayQueryExt,
+
+    // end GL_EXT_ray_query types
+
+    // GL_EXT_spirv_intrinsics types
+    /// `spirv_type` type specifier
+    #[lang_util(display(extra = "spirv_type"))]
+    SpirvType(Vec<SpirvArgument>),
+
+    // end GL_EXT_spirv_intrinsics types
     /// `struct` type specifier
     #[lang_util(display(extra = "struct"))]
     Struct(StructSpecifier),
@@ -748,6 +1009,21 @@ impl_node_content! {
     pub type TypeQualifier = Node<TypeQualifierData>;
 }
 
+impl TypeQualifierData {
+    /// Returns `true` if these qualifiers include the `buffer_reference` layout qualifier
+    pub fn is_buffer_reference(&self) -> bool {
+        self.qualifiers.iter().any(|qualifier| match &**qualifier {
+            TypeQualifierSpecData::Layout(layout) => layout.ids.iter().any(|id| {
+                matches!(
+                    &**id,
+                    LayoutQualifierSpecData::Identifier(ident, _) if ident.as_str() == "buffer_reference"
+                )
+            }),
+            _ => false,
+        })
+    }
+}
+
 /// Type qualifier spec.
 #[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -765,6 +1041,8 @@ pub enum TypeQualifierSpecData {
     Invariant,
     /// `precise` qualifier
     Precise,
+    /// GL_EXT_spirv_intrinsics qualifier
+    Spirv(SpirvQualifier),
 }
 
 impl_node_content! {
@@ -833,6 +1111,27 @@ pub enum StorageQualifierData {
     /// `subroutine` storage qualifier
     #[lang_util(display(extra = "subroutine"))]
     Subroutine(Vec<TypeSpecifier>),
+    /// `shadercallcoherent` storage qualifier
+    #[lang_util(display(extra = "shadercallcoherent"))]
+    ShaderCallCoherent,
+    /// `rayPayloadEXT` storage qualifier
+    #[lang_util(display(extra = "rayPayloadEXT"))]
+    RayPayloadExt,
+    /// `rayPayloadInEXT` storage qualifier
+    #[lang_util(display(extra = "rayPayloadInEXT"))]
+    RayPayloadInExt,
+    /// `hitAttributeEXT` storage qualifier
+    #[lang_util(display(extra = "hitAttributeEXT"))]
+    HitAttributeExt,
+    /// `callableDataEXT` storage qualifier
+    #[lang_util(display(extra = "callableDataEXT"))]
+    CallableDataExt,
+    /// `callableDataInEXT` storage qualifier
+    #[lang_util(display(extra = "callableDataInEXT"))]
+    CallableDataInExt,
+    /// `taskPayloadSharedEXT` storage qualifier
+    #[lang_util(display(extra = "taskPayloadSharedEXT"))]
+    TaskPayloadSharedExt,
 }
 
 impl_node_content! {
@@ -871,6 +1170,81 @@ impl_node_content! {
     pub type LayoutQualifierSpec = Node<LayoutQualifierSpecData>;
 }
 
+/// GL_EXT_spirv_intrinsics qualifier.
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum SpirvQualifierData {
+    /// `spirv_instruction` qualifier
+    #[lang_util(display(extra = "spirv_instruction"))]
+    Instruction(Vec<SpirvArgument>),
+    /// `spirv_execution_mode` qualifier
+    #[lang_util(display(extra = "spirv_execution_mode"))]
+    ExecutionMode(Vec<SpirvArgument>),
+    /// `spirv_execution_mode_id` qualifier
+    #[lang_util(display(extra = "spirv_execution_mode_id"))]
+    ExecutionModeId(Vec<SpirvArgument>),
+    /// `spirv_decorate` qualifier
+    #[lang_util(display(extra = "spirv_decorate"))]
+    Decorate(Vec<SpirvArgument>),
+    /// `spirv_decorate_id` qualifier
+    #[lang_util(display(extra = "spirv_decorate_id"))]
+    DecorateId(Vec<SpirvArgument>),
+    /// `spirv_decorate_string` qualifier
+    #[lang_util(display(extra = "spirv_decorate_string"))]
+    DecorateString(Vec<SpirvArgument>),
+    /// `spirv_storage_class` qualifier
+    #[lang_util(display(extra = "spirv_storage_class"))]
+    StorageClass(Vec<SpirvArgument>),
+    /// `spirv_by_reference` qualifier
+    #[lang_util(display(extra = "spirv_by_reference"))]
+    ByReference,
+    /// `spirv_literal` qualifier
+    #[lang_util(display(extra = "spirv_literal"))]
+    Literal,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<SpirvQualifierData>`.
+    pub type SpirvQualifier = Node<SpirvQualifierData>;
+}
+
+/// Argument of a GL_EXT_spirv_intrinsics qualifier or type specifier.
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum SpirvArgumentData {
+    /// An `ident = [values]` requirement list, such as `extensions = ["SPV_KHR_ray_query"]`
+    Requirement(Identifier, Vec<SpirvValue>),
+    /// An `ident = value` argument, such as `id = 4472`
+    Named(Identifier, SpirvValue),
+    /// A positional argument
+    Value(SpirvValue),
+}
+
+impl_node_content! {
+    /// Type alias for `Node<SpirvArgumentData>`.
+    pub type SpirvArgument = Node<SpirvArgumentData>;
+}
+
+/// Value of a GL_EXT_spirv_intrinsics argument.
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum SpirvValueData {
+    /// String literal, with escape sequences processed and without the surrounding quotes
+    String(SmolStr),
+    /// Constant expression
+    Expr(Box<Expr>),
+    /// Type specifier, as a `spirv_type` parameter
+    Type(TypeSpecifierNonArray),
+}
+
+impl_node_content! {
+    /// Type alias for `Node<SpirvValueData>`.
+    pub type SpirvValue = Node<SpirvValueData>;
+}
+
 /// Precision qualifier.
 #[derive(Clone, Debug, PartialEq, Eq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -906,6 +1280,18 @@ pub enum InterpolationQualifierData {
     /// `noperspective` interpolation qualifier
     #[lang_util(display(extra = "noperspective"))]
     NoPerspective,
+    /// `perprimitiveEXT` interpolation qualifier
+    #[lang_util(display(extra = "perprimitiveEXT"))]
+    PerPrimitiveExt,
+    /// `perprimitiveNV` interpolation qualifier
+    #[lang_util(display(extra = "perprimitiveNV"))]
+    PerPrimitiveNv,
+    /// `perviewNV` interpolation qualifier
+    #[lang_util(display(extra = "perviewNV"))]
+    PerViewNv,
+    /// `taskNV` interpolation qualifier
+    #[lang_util(display(extra = "taskNV"))]
+    TaskNv,
 }
 
 impl_node_content! {
@@ -995,6 +1381,10 @@ pub enum DeclarationData {
     Block(Block),
     /// Invariant declaration
     Invariant(Identifier),
+    /// Redeclaration of variables with additional qualifiers, such as `precise a, b;`
+    ///
+    /// This also covers forward declarations of GL_EXT_buffer_reference blocks.
+    QualifierRedeclaration(TypeQualifier, Vec<Identifier>),
     /// Type-only declaration
     TypeOnly(TypeQualifier),
 }
@@ -1025,6 +1415,14 @@ impl_node_content! {
     pub type Block = Node<BlockData>;
 }
 
+impl BlockData {
+    /// Returns `true` if this is a GL_EXT_buffer_reference block, whose name can then be used as
+    /// a reference type name
+    pub fn is_buffer_reference(&self) -> bool {
+        self.qualifier.is_buffer_reference()
+    }
+}
+
 /// Function identifier.
 #[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1209,6 +1607,140 @@ impl From<Expr> for InitializerData {
     }
 }
 
+/// Radix of an integer literal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub enum IntRadix {
+    /// Decimal literal, e.g. `42`.
+    #[default]
+    Decimal,
+    /// Octal literal, e.g. `052`.
+    Octal,
+    /// Hexadecimal literal, e.g. `0x2A`.
+    Hexadecimal,
+}
+
+impl IntRadix {
+    /// Return the radix a numeric literal was written in
+    pub fn of(text: &str) -> Self {
+        if text.starts_with("0x") || text.starts_with("0X") {
+            Self::Hexadecimal
+        } else if text.starts_with('0')
+            && text[1..].starts_with(|ch: char| ch.is_ascii_digit())
+            && text[1..]
+                .chars()
+                .all(|ch| ch.is_ascii_digit() || ch == 'u' || ch == 'U')
+        {
+            Self::Octal
+        } else {
+            Self::Decimal
+        }
+    }
+}
+
+/// A numeric literal.
+///
+/// Literals parsed from source keep their original spelling, including their radix and suffix, so
+/// they can be written back exactly as they were. Literals created from a value do not have a
+/// spelling, and changing the value of a literal discards it.
+///
+/// Two literals are equal if they have the same value, regardless of their spelling.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct Literal<T> {
+    value: T,
+    text: Option<SmolStr>,
+    radix: IntRadix,
+}
+
+impl<T> Literal<T> {
+    /// Create a new literal from a value, without a source spelling
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            text: None,
+            radix: IntRadix::Decimal,
+        }
+    }
+
+    /// Create a new literal from a value and the source text it was parsed from
+    pub fn from_source(value: T, text: impl Into<SmolStr>) -> Self {
+        let text = text.into();
+
+        Self {
+            value,
+            radix: IntRadix::of(&text),
+            text: Some(text),
+        }
+    }
+
+    /// Set the radix used to write this literal if it has no source spelling
+    pub fn with_radix(self, radix: IntRadix) -> Self {
+        Self { radix, ..self }
+    }
+
+    /// Value of this literal
+    pub fn value(&self) -> T
+    where
+        T: Copy,
+    {
+        self.value
+    }
+
+    /// Set the value of this literal, discarding its source spelling
+    pub fn set_value(&mut self, value: T) {
+        self.value = value;
+        self.text = None;
+    }
+
+    /// Source text of this literal, including its suffix, if it was parsed from source
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Radix this literal was written in
+    pub fn radix(&self) -> IntRadix {
+        self.radix
+    }
+}
+
+impl<T> From<T> for Literal<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Literal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Literal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.text {
+            Some(text) => f.write_str(text),
+            None => self.value.fmt(f),
+        }
+    }
+}
+
+impl<T: lang_util::node::NodeContentDisplay> lang_util::node::NodeContentDisplay for Literal<T> {
+    fn name() -> Option<&'static str> {
+        T::name()
+    }
+
+    fn display_extra(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        lang_util::node::NodeContentDisplay::display_extra(&self.value, f)
+    }
+
+    fn display_children(&self, level: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        lang_util::node::NodeContentDisplay::display_children(&self.value, level, f)
+    }
+}
+
 /// The most general form of an expression.
 ///
 /// As you can see if you read the variant list, in GLSL, an assignment is an expression. This is a
@@ -1223,15 +1755,28 @@ pub enum ExprData {
     /// A variable expression, using an identifier.
     Variable(Identifier),
     /// Integral constant expression.
-    IntConst(i32),
+    IntConst(Literal<i32>),
     /// Unsigned integral constant expression.
-    UIntConst(u32),
+    UIntConst(Literal<u32>),
     /// Boolean constant expression.
     BoolConst(bool),
     /// Single precision floating expression.
-    FloatConst(f32),
+    FloatConst(Literal<f32>),
     /// Double precision floating expression.
-    DoubleConst(f64),
+    DoubleConst(Literal<f64>),
+    /// 16-bit integral constant expression.
+    Int16Const(Literal<i16>),
+    /// 16-bit unsigned integral constant expression.
+    UInt16Const(Literal<u16>),
+    /// 64-bit integral constant expression.
+    Int64Const(Literal<i64>),
+    /// 64-bit unsigned integral constant expression.
+    UInt64Const(Literal<u64>),
+    /// Half precision floating expression.
+    ///
+    /// The value is stored as a single precision float, as Rust does not have a half precision
+    /// floating point type.
+    Float16Const(Literal<f32>),
     /// A unary expression, gathering a single expression and a unary operator.
     Unary(UnaryOp, Box<Expr>),
     /// A binary expression, gathering two expressions and a binary operator.
@@ -1277,12 +1822,24 @@ impl ExprData {
 
 impl From<i32> for ExprData {
     fn from(x: i32) -> ExprData {
+        Self::IntConst(x.into())
+    }
+}
+
+impl From<Literal<i32>> for ExprData {
+    fn from(x: Literal<i32>) -> ExprData {
         Self::IntConst(x)
     }
 }
 
 impl From<u32> for ExprData {
     fn from(x: u32) -> ExprData {
+        Self::UIntConst(x.into())
+    }
+}
+
+impl From<Literal<u32>> for ExprData {
+    fn from(x: Literal<u32>) -> ExprData {
         Self::UIntConst(x)
     }
 }
@@ -1295,16 +1852,76 @@ impl From<bool> for ExprData {
 
 impl From<f32> for ExprData {
     fn from(x: f32) -> ExprData {
+        Self::FloatConst(x.into())
+    }
+}
+
+impl From<Literal<f32>> for ExprData {
+    fn from(x: Literal<f32>) -> ExprData {
         Self::FloatConst(x)
     }
 }
 
 impl From<f64> for ExprData {
     fn from(x: f64) -> ExprData {
+        Self::DoubleConst(x.into())
+    }
+}
+
+impl From<Literal<f64>> for ExprData {
+    fn from(x: Literal<f64>) -> ExprData {
         Self::DoubleConst(x)
     }
 }
 
+impl From<i16> for ExprData {
+    fn from(x: i16) -> ExprData {
+        Self::Int16Const(x.into())
+    }
+}
+
+impl From<Literal<i16>> for ExprData {
+    fn from(x: Literal<i16>) -> ExprData {
+        Self::Int16Const(x)
+    }
+}
+
+impl From<u16> for ExprData {
+    fn from(x: u16) -> ExprData {
+        Self::UInt16Const(x.into())
+    }
+}
+
+impl From<Literal<u16>> for ExprData {
+    fn from(x: Literal<u16>) -> ExprData {
+        Self::UInt16Const(x)
+    }
+}
+
+impl From<i64> for ExprData {
+    fn from(x: i64) -> ExprData {
+        Self::Int64Const(x.into())
+    }
+}
+
+impl From<Literal<i64>> for ExprData {
+    fn from(x: Literal<i64>) -> ExprData {
+        Self::Int64Const(x)
+    }
+}
+
+impl From<u64> for ExprData {
+    fn from(x: u64) -> ExprData {
+        Self::UInt64Const(x.into())
+    }
+}
+
+impl From<Literal<u64>> for ExprData {
+    fn from(x: Literal<u64>) -> ExprData {
+        Self::UInt64Const(x)
+    }
+}
+
 /// All unary operators that exist in GLSL.
 #[derive(Clone, Debug, PartialEq, Eq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1466,8 +2083,12 @@ pub enum ExternalDeclarationData {
     Preprocessor(Preprocessor),
     /// Function definition
     FunctionDefinition(FunctionDefinition),
+    /// Function definition with attributes
+    AttributedFunctionDefinition(AttributedFunctionDefinition),
     /// Declaration
     Declaration(Declaration),
+    /// Region that could not be parsed, only produced when recovering from syntax errors
+    Error,
 }
 
 impl_node_content! {
@@ -1537,6 +2158,10 @@ pub enum StatementData {
     Jump(JumpStatement),
     /// Statement block
     Compound(CompoundStatement),
+    /// Selection, switch or iteration statement with attributes
+    Attributed(AttributedStatement),
+    /// Region that could not be parsed, only produced when recovering from syntax errors
+    Error,
 }
 
 impl_node_content! {
@@ -1587,6 +2212,56 @@ impl_node_content! {
     pub type ExprStatement = Node<ExprStatementData>;
 }
 
+/// Attribute (`[[name]]` or `[[name(arg)]]`), as introduced by GL_EXT_control_flow_attributes.
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct AttributeData {
+    /// Name of the attribute
+    pub name: Identifier,
+    /// Optional argument of the attribute
+    pub arg: Option<Box<Expr>>,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<AttributeData>`.
+    pub type Attribute = Node<AttributeData>;
+}
+
+/// Selection, switch or iteration statement preceded by attributes.
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct AttributedStatementData {
+    /// Attributes of the statement
+    pub attributes: Vec<Attribute>,
+    /// Statement the attributes apply to
+    pub statement: Box<Statement>,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<AttributedStatementData>`.
+    pub type AttributedStatement = Node<AttributedStatementData>;
+}
+
+/// Function definition with attributes, such as `[[subgroup_uniform_control_flow]]`.
+#[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct AttributedFunctionDefinitionData {
+    /// Attributes before the function prototype
+    pub attributes: Vec<Attribute>,
+    /// Attributes between the function prototype and its body
+    pub body_attributes: Vec<Attribute>,
+    /// Function definition the attributes apply to
+    pub definition: FunctionDefinition,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<AttributedFunctionDefinitionData>`.
+    pub type AttributedFunctionDefinition = Node<AttributedFunctionDefinitionData>;
+}
+
 /// Selection statement.
 #[derive(Clone, Debug, PartialEq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1737,6 +2412,12 @@ pub enum JumpStatementData {
     /// `discard` jump statement
     #[lang_util(display(extra = "discard"))]
     Discard,
+    /// `ignoreIntersectionEXT` jump statement
+    #[lang_util(display(extra = "ignoreIntersectionEXT"))]
+    IgnoreIntersectionExt,
+    /// `terminateRayEXT` jump statement
+    #[lang_util(display(extra = "terminateRayEXT"))]
+    TerminateRayExt,
 }
 
 impl_node_content! {
@@ -1777,6 +2458,9 @@ pub enum PreprocessorData {
     /// `#ifndef` preprocessor directive
     #[lang_util(display(extra = "#ifndef"))]
     IfNDef(PreprocessorIfNDef),
+    /// `#include` preprocessor directive
+    #[lang_util(display(extra = "#include"))]
+    Include(PreprocessorInclude),
     /// `#moj_import` preprocessor directive
     #[lang_util(display(extra = "#moj_import"))]
     MojImport(PreprocessorMojImport),
@@ -1908,6 +2592,20 @@ impl_node_content! {
     pub type PreprocessorIfNDef = Node<PreprocessorIfNDefData>;
 }
 
+/// An #include name annotation.
+#[derive(Clone, Debug, PartialEq, Eq, NodeContentDisplay)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct PreprocessorIncludeData {
+    /// Include path
+    pub path: Path,
+}
+
+impl_node_content! {
+    /// Type alias for `Node<PreprocessorIncludeData>`.
+    pub type PreprocessorInclude = Node<PreprocessorIncludeData>;
+}
+
 /// A #moj_import name annotation.
 #[derive(Clone, Debug, PartialEq, Eq, NodeContentDisplay)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -2092,7 +2790,7 @@ impl CommentData {
 
     /// true if this comment is a single-line comment
     pub fn is_single(&self) -> bool {
-        matches!(self, Self::Multi(_))
+        matches!(self, Self::Single(_))
     }
 
     /// true if this comment is a multi-line comment
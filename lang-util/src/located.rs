@@ -7,7 +7,7 @@ use std::{
 
 use text_size::{TextRange, TextSize};
 
-use super::FileId;
+use super::{position::MacroExpansion, FileId};
 
 /// Represents a file location override
 #[derive(Debug, Clone, PartialEq, Eq, derive_more::From)]
@@ -106,6 +106,8 @@ pub struct LocatedBuilder {
     line_number: u32,
     /// Resolved column number
     column: u32,
+    /// Macro invocations the location was expanded from
+    expansions: Vec<MacroExpansion>,
 }
 
 impl LocatedBuilder {
@@ -159,6 +161,14 @@ impl LocatedBuilder {
         Self { column, ..self }
     }
 
+    /// Set the macro invocations the location was expanded from, outermost first
+    pub fn expansions(self, expansions: impl IntoIterator<Item = MacroExpansion>) -> Self {
+        Self {
+            expansions: expansions.into_iter().collect(),
+            ..self
+        }
+    }
+
     /// Resolve the raw offset (see [LocatedBuilder::pos]) to line and column information
     pub fn resolve(self, resolver: &impl Resolver) -> Self {
         let (line, col) = resolver.resolve(self.pos.start());
@@ -195,6 +205,7 @@ impl LocatedBuilder {
             file_override: self.file_override,
             line_number: self.line_number,
             column: self.column,
+            expansions: self.expansions,
         }
     }
 }
@@ -216,6 +227,8 @@ pub struct Located<E> {
     line_number: u32,
     /// Resolved column number
     column: u32,
+    /// Macro invocations the location was expanded from
+    expansions: Vec<MacroExpansion>,
 }
 
 impl<E> Located<E> {
@@ -238,6 +251,7 @@ impl<E> Located<E> {
             file_override: self.file_override,
             line_number: self.line_number,
             column: self.column,
+            expansions: self.expansions,
         }
     }
 
@@ -275,6 +289,11 @@ impl<E> Located<E> {
     pub fn col(&self) -> u32 {
         self.column
     }
+
+    /// Get the macro invocations this location was expanded from, outermost first
+    pub fn expansions(&self) -> &[MacroExpansion] {
+        &self.expansions
+    }
 }
 
 impl<E: Clone> Clone for Located<E> {
@@ -287,6 +306,7 @@ impl<E: Clone> Clone for Located<E> {
             file_override: self.file_override.clone(),
             line_number: self.line_number,
             column: self.column,
+            expansions: self.expansions.clone(),
         }
     }
 }
@@ -300,6 +320,7 @@ impl<E: PartialEq> PartialEq for Located<E> {
             && self.file_override == other.file_override
             && self.line_number == other.line_number
             && self.column == other.column
+            && self.expansions == other.expansions
     }
 }
 
@@ -140,9 +140,12 @@ forward_display!(smol_str::SmolStr => "String");
 forward_display!(String => "String");
 forward_display!(f32 => "FloatConst");
 forward_display!(f64 => "DoubleConst");
+forward_display!(i16 => "Int16Const");
 forward_display!(i32 => "IntConst");
+forward_display!(i64 => "Int64Const");
 forward_display!(u16 => "ShortConst");
 forward_display!(u32 => "UIntConst");
+forward_display!(u64 => "UInt64Const");
 forward_display!(bool => "BoolConst");
 
 /// Trait for displaying a syntax node
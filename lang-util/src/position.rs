@@ -7,7 +7,7 @@ use std::{
 
 use text_size::{TextRange, TextSize};
 
-use crate::FileId;
+use crate::{FileId, SmolStr};
 
 /// A position in the lexer's input
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -176,3 +176,30 @@ impl From<NodeSpan> for TextRange {
         value.range
     }
 }
+
+/// A macro invocation which produced a token
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(rserde::Serialize, rserde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde"))]
+pub struct MacroExpansion {
+    /// Name of the invoked macro
+    pub name: SmolStr,
+    /// Span of the invocation. For invocations nested in the body of another macro, this is the
+    /// span of the macro name in that body.
+    pub invocation: NodeSpan,
+    /// Line of the invocation in its source, starting at 0
+    pub line: u32,
+    /// Span of the `#define` directive of the macro, if it was defined in a source
+    pub definition: Option<NodeSpan>,
+}
+
+impl Display for MacroExpansion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "in expansion of macro `{}` at line {}",
+            self.name,
+            self.line + 1
+        )
+    }
+}
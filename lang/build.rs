@@ -1,3 +1,28 @@
+use std::{env, fs, iter, path::PathBuf};
+
 fn main() {
     lalrpop::process_root().unwrap();
+
+    // LALRPOP enables error recovery for a whole grammar, which would also affect the strict
+    // parsers. The recovering parsers are generated from a copy of the grammar with the
+    // error-recovery feature enabled instead.
+    if env::var_os("CARGO_FEATURE_PARSER_RECOVERING").is_some() {
+        let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+        let grammar = out_dir.join("recovering_parser.lalrpop");
+        fs::copy("src/parser.lalrpop", &grammar).unwrap();
+
+        let features = env::vars()
+            .filter_map(|(name, _)| {
+                name.strip_prefix("CARGO_FEATURE_")
+                    .map(|feature| feature.replace('_', "-").to_ascii_lowercase())
+            })
+            .chain(iter::once("error-recovery".to_owned()));
+
+        lalrpop::Configuration::new()
+            .set_in_dir(&out_dir)
+            .set_out_dir(&out_dir)
+            .set_features(features)
+            .process_file(&grammar)
+            .unwrap();
+    }
 }
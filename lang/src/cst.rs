@@ -0,0 +1,116 @@
+//! Lossless concrete syntax tree for GLSL sources
+//!
+//! Contrary to the [`ast`](crate::ast), the concrete syntax tree (CST) keeps every token of the
+//! input: whitespace, comments, line continuations and parentheses are all part of the tree, and
+//! printing the root node yields back the original source text. This makes it suitable for
+//! tools which need to edit source code while preserving the user's layout, such as formatters
+//! or refactoring tools.
+//!
+//! The tree is built using [rowan](https://docs.rs/rowan) and follows the GLSL grammar:
+//! declarations, statements, expressions, types and qualifiers all have their own nodes, and
+//! preprocessor directives are kept where they appear. Typed wrappers over the untyped
+//! [`SyntaxNode`]s are provided in this module, and the whole tree can be converted to an
+//! [`ast::TranslationUnit`](crate::ast::TranslationUnit) using [`TranslationUnit::to_ast`].
+//! Since the text of the tree is the source text, spans in the resulting AST can be used to find
+//! the matching CST nodes.
+//!
+//! ```
+//! use glsl_lang::cst::{Cst, ExternalDeclaration, Statement};
+//!
+//! let source = "// Entry point\nvoid main() {\n    gl_FragColor = vec4(1.);\n}\n";
+//! let cst = Cst::parse(source);
+//!
+//! // The tree is lossless
+//! assert_eq!(cst.syntax().to_string(), source);
+//!
+//! // Typed wrappers give access to the structure of the source
+//! let tu = cst.tree();
+//! let Some(ExternalDeclaration::FunctionDefinition(main)) = tu.external_declarations().next()
+//! else {
+//!     panic!("expected a function definition");
+//! };
+//! assert_eq!(main.name().unwrap().text(), "main");
+//!
+//! let Some(Statement::Expr(statement)) = main.body().unwrap().statements().next() else {
+//!     panic!("expected an expression statement");
+//! };
+//! assert_eq!(statement.expr().unwrap().to_string(), "gl_FragColor = vec4(1.)");
+//!
+//! // Convert to the AST for further processing
+//! assert_eq!(tu.to_ast().unwrap().0.len(), 1);
+//! ```
+
+use rowan::GreenNode;
+
+use crate::parse::ParseOptions;
+
+mod error;
+pub use error::*;
+
+mod lower;
+
+mod nodes;
+pub use nodes::*;
+
+mod parser;
+
+mod syntax_kind;
+pub use syntax_kind::*;
+
+/// Untyped node of the GLSL concrete syntax tree
+pub type SyntaxNode = rowan::SyntaxNode<GlslLang>;
+/// Token of the GLSL concrete syntax tree
+pub type SyntaxToken = rowan::SyntaxToken<GlslLang>;
+/// Node or token of the GLSL concrete syntax tree
+pub type SyntaxElement = rowan::SyntaxElement<GlslLang>;
+
+/// Result of building the concrete syntax tree of a GLSL source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cst {
+    green_node: GreenNode,
+    errors: Vec<Error>,
+}
+
+impl Cst {
+    /// Build the concrete syntax tree for the given source
+    ///
+    /// Building the tree never fails: syntax errors are recorded in [`Cst::errors`], the tokens
+    /// which could not be parsed are wrapped in [`SyntaxKind::ERROR_NODE`] nodes, and the tree
+    /// still covers the whole input.
+    pub fn parse(source: &str) -> Self {
+        Self::parse_with_options(source, &ParseOptions::default())
+    }
+
+    /// Build the concrete syntax tree for the given source, using the given options
+    ///
+    /// Only the default version and the Vulkan target are used, to determine which words are
+    /// keywords.
+    pub fn parse_with_options(source: &str, opts: &ParseOptions) -> Self {
+        let (green_node, errors) = parser::CstParser::new(source, opts).parse();
+        Self { green_node, errors }
+    }
+
+    /// Get the green (immutable, position-independent) root node of the tree
+    pub fn green_node(&self) -> &GreenNode {
+        &self.green_node
+    }
+
+    /// Get the untyped root node of the tree
+    pub fn syntax(&self) -> SyntaxNode {
+        SyntaxNode::new_root(self.green_node.clone())
+    }
+
+    /// Get the typed root node of the tree
+    pub fn tree(&self) -> TranslationUnit {
+        // unwrap: the root node is always a ROOT node
+        TranslationUnit::cast(self.syntax()).unwrap()
+    }
+
+    /// Get the errors encountered while building the tree
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+}
+
+#[cfg(test)]
+mod tests;
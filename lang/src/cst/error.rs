@@ -0,0 +1,36 @@
+use crate::ast::SmolStr;
+
+/// Error encountered while building the concrete syntax tree
+pub type Error = lang_util::located::Located<ErrorKind>;
+
+/// Kind of error encountered while building the concrete syntax tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A token did not match what the grammar expected at this point
+    UnexpectedToken {
+        /// Source text of the unexpected token
+        found: SmolStr,
+        /// Description of what was expected instead
+        expected: &'static str,
+    },
+    /// The input ended before the end of the current construct
+    UnexpectedEndOfInput {
+        /// Description of what was expected instead
+        expected: &'static str,
+    },
+}
+
+impl std::error::Error for ErrorKind {}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorKind::UnexpectedToken { found, expected } => {
+                write!(f, "expected {}, found `{}`", expected, found)
+            }
+            ErrorKind::UnexpectedEndOfInput { expected } => {
+                write!(f, "expected {}, found end of input", expected)
+            }
+        }
+    }
+}
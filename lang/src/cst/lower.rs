@@ -0,0 +1,1441 @@
+//! Conversion of the concrete syntax tree to the abstract syntax tree
+
+use glsl_lang_pp::{
+    lexer::Lexer,
+    types::{type_names::TypeNameState, Token},
+};
+use lang_util::{node::Node, position::NodeSpan, FileId, NodeContent, SmolStr, TextRange};
+use rowan::NodeOrToken;
+
+use super::*;
+use crate::{ast, parse::ParseOptions};
+
+impl TranslationUnit {
+    /// Convert this concrete syntax tree to an abstract syntax tree
+    ///
+    /// Returns `None` if the tree contains errors. Spans of the resulting AST nodes are offsets
+    /// into the text of the tree, in the file with id 0.
+    ///
+    /// Top-level preprocessor directives are converted the same way the default lexer would.
+    /// Directives nested in declarations or function bodies have no place in the AST, so they are
+    /// skipped.
+    pub fn to_ast(&self) -> Option<ast::TranslationUnit> {
+        if self
+            .syntax()
+            .descendants()
+            .any(|node| node.kind() == SyntaxKind::ERROR_NODE)
+        {
+            return None;
+        }
+
+        self.external_declarations()
+            .map(|external_declaration| lower_external_declaration(&external_declaration))
+            .collect::<Option<_>>()
+            .map(ast::TranslationUnit)
+    }
+}
+
+fn span(range: TextRange) -> Option<NodeSpan> {
+    Some(NodeSpan::new(FileId::new(0), range))
+}
+
+fn spanned<T: NodeContent>(content: T, node: &impl CstNode) -> Node<T> {
+    Node::new(content, span(node.syntax().text_range()))
+}
+
+fn spanned_token<T: NodeContent>(content: T, token: &SyntaxToken) -> Node<T> {
+    Node::new(content, span(token.text_range()))
+}
+
+/// Get the text of a token, without line continuations
+fn unescaped(token: &SyntaxToken) -> SmolStr {
+    let text = token.text();
+
+    match Lexer::new(text).next() {
+        Some(lexed) => lexed.text(text).to_string().into(),
+        None => text.into(),
+    }
+}
+
+/// Classify a token the same way the default lexer does, ignoring type names
+fn classify(token: &SyntaxToken) -> Option<Token> {
+    let text = token.text();
+    let opts = ParseOptions::default();
+
+    Lexer::new(text).next().map(|lexed| {
+        Token::from_token(
+            lexed,
+            text,
+            opts.default_version,
+            opts.target_vulkan,
+            |_| TypeNameState::Ident,
+        )
+        .0
+    })
+}
+
+fn lower_ident(token: &SyntaxToken) -> ast::Identifier {
+    spanned_token(ast::IdentifierData(unescaped(token)), token)
+}
+
+fn builtin_type(name: &str) -> Option<ast::TypeSpecifierNonArrayData> {
+    use ast::TypeSpecifierNonArrayData::*;
+
+    Some(match name {
+        "void" => Void,
+        "bool" => Bool,
+        "int" => Int,
+        "uint" => UInt,
+        "float" => Float,
+        "double" => Double,
+        "vec2" => Vec2,
+        "vec3" => Vec3,
+        "vec4" => Vec4,
+        "dvec2" => DVec2,
+        "dvec3" => DVec3,
+        "dvec4" => DVec4,
+        "bvec2" => BVec2,
+        "bvec3" => BVec3,
+        "bvec4" => BVec4,
+        "ivec2" => IVec2,
+        "ivec3" => IVec3,
+        "ivec4" => IVec4,
+        "uvec2" => UVec2,
+        "uvec3" => UVec3,
+        "uvec4" => UVec4,
+        "mat2" => Mat2,
+        "mat3" => Mat3,
+        "mat4" => Mat4,
+        "mat2x2" => Mat22,
+        "mat2x3" => Mat23,
+        "mat2x4" => Mat24,
+        "mat3x2" => Mat32,
+        "mat3x3" => Mat33,
+        "mat3x4" => Mat34,
+        "mat4x2" => Mat42,
+        "mat4x3" => Mat43,
+        "mat4x4" => Mat44,
+        "dmat2" => DMat2,
+        "dmat3" => DMat3,
+        "dmat4" => DMat4,
+        "dmat2x2" => DMat22,
+        "dmat2x3" => DMat23,
+        "dmat2x4" => DMat24,
+        "dmat3x2" => DMat32,
+        "dmat3x3" => DMat33,
+        "dmat3x4" => DMat34,
+        "dmat4x2" => DMat42,
+        "dmat4x3" => DMat43,
+        "dmat4x4" => DMat44,
+        "sampler1D" => Sampler1D,
+        "image1D" => Image1D,
+        "sampler2D" => Sampler2D,
+        "image2D" => Image2D,
+        "sampler3D" => Sampler3D,
+        "image3D" => Image3D,
+        "samplerCube" => SamplerCube,
+        "imageCube" => ImageCube,
+        "sampler2DRect" => Sampler2DRect,
+        "image2DRect" => Image2DRect,
+        "sampler1DArray" => Sampler1DArray,
+        "image1DArray" => Image1DArray,
+        "sampler2DArray" => Sampler2DArray,
+        "image2DArray" => Image2DArray,
+        "samplerBuffer" => SamplerBuffer,
+        "imageBuffer" => ImageBuffer,
+        "sampler2DMS" => Sampler2DMs,
+        "image2DMS" => Image2DMs,
+        "sampler2DMSArray" => Sampler2DMsArray,
+        "image2DMSArray" => Image2DMsArray,
+        "samplerCubeArray" => SamplerCubeArray,
+        "imageCubeArray" => ImageCubeArray,
+        "sampler1DShadow" => Sampler1DShadow,
+        "sampler2DShadow" => Sampler2DShadow,
+        "sampler2DRectShadow" => Sampler2DRectShadow,
+        "sampler1DArrayShadow" => Sampler1DArrayShadow,
+        "sampler2DArrayShadow" => Sampler2DArrayShadow,
+        "samplerCubeShadow" => SamplerCubeShadow,
+        "samplerCubeArrayShadow" => SamplerCubeArrayShadow,
+        "isampler1D" => ISampler1D,
+        "iimage1D" => IImage1D,
+        "isampler2D" => ISampler2D,
+        "iimage2D" => IImage2D,
+        "isampler3D" => ISampler3D,
+        "iimage3D" => IImage3D,
+        "isamplerCube" => ISamplerCube,
+        "iimageCube" => IImageCube,
+        "isampler2DRect" => ISampler2DRect,
+        "iimage2DRect" => IImage2DRect,
+        "isampler1DArray" => ISampler1DArray,
+        "iimage1DArray" => IImage1DArray,
+        "isampler2DArray" => ISampler2DArray,
+        "iimage2DArray" => IImage2DArray,
+        "isamplerBuffer" => ISamplerBuffer,
+        "iimageBuffer" => IImageBuffer,
+        "isampler2DMS" => ISampler2DMs,
+        "iimage2DMS" => IImage2DMs,
+        "isampler2DMSArray" => ISampler2DMsArray,
+        "iimage2DMSArray" => IImage2DMsArray,
+        "isamplerCubeArray" => ISamplerCubeArray,
+        "iimageCubeArray" => IImageCubeArray,
+        "atomic_uint" => AtomicUInt,
+        "usampler1D" => USampler1D,
+        "uimage1D" => UImage1D,
+        "usampler2D" => USampler2D,
+        "uimage2D" => UImage2D,
+        "usampler3D" => USampler3D,
+        "uimage3D" => UImage3D,
+        "usamplerCube" => USamplerCube,
+        "uimageCube" => UImageCube,
+        "usampler2DRect" => USampler2DRect,
+        "uimage2DRect" => UImage2DRect,
+        "usampler1DArray" => USampler1DArray,
+        "uimage1DArray" => UImage1DArray,
+        "usampler2DArray" => USampler2DArray,
+        "uimage2DArray" => UImage2DArray,
+        "usamplerBuffer" => USamplerBuffer,
+        "uimageBuffer" => UImageBuffer,
+        "usampler2DMS" => USampler2DMs,
+        "uimage2DMS" => UImage2DMs,
+        "usampler2DMSArray" => USampler2DMsArray,
+        "uimage2DMSArray" => UImage2DMsArray,
+        "usamplerCubeArray" => USamplerCubeArray,
+        "uimageCubeArray" => UImageCubeArray,
+        "texture1D" => Texture1D,
+        "texture2D" => Texture2D,
+        "texture3D" => Texture3D,
+        "textureCube" => TextureCube,
+        "texture2DRect" => Texture2DRect,
+        "texture1DArray" => Texture1DArray,
+        "texture2DArray" => Texture2DArray,
+        "textureBuffer" => TextureBuffer,
+        "texture2DMS" => Texture2DMs,
+        "texture2DMSArray" => Texture2DMsArray,
+        "textureCubeArray" => TextureCubeArray,
+        "itexture1D" => ITexture1D,
+        "itexture2D" => ITexture2D,
+        "itexture3D" => ITexture3D,
+        "itextureCube" => ITextureCube,
+        "itexture2DRect" => ITexture2DRect,
+        "itexture1DArray" => ITexture1DArray,
+        "itexture2DArray" => ITexture2DArray,
+        "itextureBuffer" => ITextureBuffer,
+        "itexture2DMS" => ITexture2DMs,
+        "itexture2DMSArray" => ITexture2DMsArray,
+        "itextureCubeArray" => ITextureCubeArray,
+        "sampler" => Sampler,
+        "samplerShadow" => SamplerShadow,
+        "subpassInput" => SubpassInput,
+        "isubpassInput" => ISubpassInput,
+        "usubpassInput" => USubpassInput,
+        "subpassInputMS" => SubpassInputMs,
+        "isubpassInputMS" => ISubpassInputMs,
+        "usubpassInputMS" => USubpassInputMs,
+        "int8_t" => Int8,
+        "i8vec2" => I8Vec2,
+        "i8vec3" => I8Vec3,
+        "i8vec4" => I8Vec4,
+        "uint8_t" => UInt8,
+        "u8vec2" => U8Vec2,
+        "u8vec3" => U8Vec3,
+        "u8vec4" => U8Vec4,
+        "int16_t" => Int16,
+        "i16vec2" => I16Vec2,
+        "i16vec3" => I16Vec3,
+        "i16vec4" => I16Vec4,
+        "uint16_t" => UInt16,
+        "u16vec2" => U16Vec2,
+        "u16vec3" => U16Vec3,
+        "u16vec4" => U16Vec4,
+        "int32_t" => Int32,
+        "i32vec2" => I32Vec2,
+        "i32vec3" => I32Vec3,
+        "i32vec4" => I32Vec4,
+        "uint32_t" => UInt32,
+        "u32vec2" => U32Vec2,
+        "u32vec3" => U32Vec3,
+        "u32vec4" => U32Vec4,
+        "int64_t" => Int64,
+        "i64vec2" => I64Vec2,
+        "i64vec3" => I64Vec3,
+        "i64vec4" => I64Vec4,
+        "uint64_t" => UInt64,
+        "u64vec2" => U64Vec2,
+        "u64vec3" => U64Vec3,
+        "u64vec4" => U64Vec4,
+        "float16_t" => Float16,
+        "f16vec2" => F16Vec2,
+        "f16vec3" => F16Vec3,
+        "f16vec4" => F16Vec4,
+        "f16mat2" => F16Mat2,
+        "f16mat3" => F16Mat3,
+        "f16mat4" => F16Mat4,
+        "f16mat2x2" => F16Mat22,
+        "f16mat2x3" => F16Mat23,
+        "f16mat2x4" => F16Mat24,
+        "f16mat3x2" => F16Mat32,
+        "f16mat3x3" => F16Mat33,
+        "f16mat3x4" => F16Mat34,
+        "f16mat4x2" => F16Mat42,
+        "f16mat4x3" => F16Mat43,
+        "f16mat4x4" => F16Mat44,
+        "float32_t" => Float32,
+        "f32vec2" => F32Vec2,
+        "f32vec3" => F32Vec3,
+        "f32vec4" => F32Vec4,
+        "f32mat2" => F32Mat2,
+        "f32mat3" => F32Mat3,
+        "f32mat4" => F32Mat4,
+        "f32mat2x2" => F32Mat22,
+        "f32mat2x3" => F32Mat23,
+        "f32mat2x4" => F32Mat24,
+        "f32mat3x2" => F32Mat32,
+        "f32mat3x3" => F32Mat33,
+        "f32mat3x4" => F32Mat34,
+        "f32mat4x2" => F32Mat42,
+        "f32mat4x3" => F32Mat43,
+        "f32mat4x4" => F32Mat44,
+        "float64_t" => Float64,
+        "f64vec2" => F64Vec2,
+        "f64vec3" => F64Vec3,
+        "f64vec4" => F64Vec4,
+        "f64mat2" => F64Mat2,
+        "f64mat3" => F64Mat3,
+        "f64mat4" => F64Mat4,
+        "f64mat2x2" => F64Mat22,
+        "f64mat2x3" => F64Mat23,
+        "f64mat2x4" => F64Mat24,
+        "f64mat3x2" => F64Mat32,
+        "f64mat3x3" => F64Mat33,
+        "f64mat3x4" => F64Mat34,
+        "f64mat4x2" => F64Mat42,
+        "f64mat4x3" => F64Mat43,
+        "f64mat4x4" => F64Mat44,
+        "accelerationStructureEXT" => AccelerationStructureExt,
+        "rayQueryEXT" => RayQueryExt,
+        _ => return None,
+    })
+}
+
+fn lower_external_declaration(
+    external_declaration: &ExternalDeclaration,
+) -> Option<ast::ExternalDeclaration> {
+    let data = match external_declaration {
+        ExternalDeclaration::Preprocessor(directive) => {
+            ast::ExternalDeclarationData::Preprocessor(lower_directive(directive)?)
+        }
+        ExternalDeclaration::Declaration(declaration) => {
+            ast::ExternalDeclarationData::Declaration(lower_declaration(declaration)?)
+        }
+        ExternalDeclaration::FunctionDefinition(definition) => {
+            let prototype = definition.prototype()?;
+            let body = definition.body()?;
+            let function = ast::FunctionDefinitionData {
+                prototype: lower_function_prototype(&prototype)?,
+                statement: lower_compound_statement(&body)?,
+            };
+
+            let attributes = definition.attributes();
+            let body_attributes = definition.body_attributes();
+            if attributes.is_none() && body_attributes.is_none() {
+                ast::ExternalDeclarationData::FunctionDefinition(spanned(function, definition))
+            } else {
+                // The attributed definition spans the attributes, the inner one starts at the
+                // prototype
+                let range = TextRange::new(
+                    prototype.syntax().text_range().start(),
+                    definition.syntax().text_range().end(),
+                );
+
+                ast::ExternalDeclarationData::AttributedFunctionDefinition(spanned(
+                    ast::AttributedFunctionDefinitionData {
+                        attributes: lower_attributes(attributes.as_ref())?,
+                        body_attributes: lower_attributes(body_attributes.as_ref())?,
+                        definition: Node::new(function, span(range)),
+                    },
+                    definition,
+                ))
+            }
+        }
+        ExternalDeclaration::Error(_) => return None,
+    };
+
+    Some(spanned(data, external_declaration))
+}
+
+// Preprocessor directives
+
+/// Concatenate the text of the rest of a directive, like the default lexer does: leading and
+/// trailing trivia is dropped, and line continuations are removed
+fn pp_rest(tokens: &[SyntaxToken]) -> String {
+    let mut rest = String::new();
+    let mut trivia = String::new();
+
+    for token in tokens
+        .iter()
+        .skip_while(|token| token.kind().is_whitespace())
+    {
+        if token.kind().is_whitespace() {
+            trivia.push_str(&unescaped(token));
+        } else {
+            rest.push_str(&trivia);
+            rest.push_str(&unescaped(token));
+            trivia.clear();
+        }
+    }
+
+    rest
+}
+
+fn lower_directive(directive: &PreprocessorDirective) -> Option<ast::Preprocessor> {
+    let tokens: Vec<_> = directive
+        .syntax()
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+        .collect();
+
+    let name = directive.name()?;
+    let rest = &tokens[tokens.iter().position(|token| *token == name)? + 1..];
+    let mut significant = rest.iter().filter(|token| !token.kind().is_whitespace());
+    let range = TextRange::new(
+        rest.first()
+            .map(|token| token.text_range().start())
+            .unwrap_or(name.text_range().end()),
+        directive.syntax().text_range().end(),
+    );
+
+    let data = match unescaped(&name).as_str() {
+        "define" => {
+            let ident_index = rest
+                .iter()
+                .position(|token| !token.kind().is_whitespace())?;
+            let ident = &rest[ident_index];
+            if ident.kind() != SyntaxKind::IDENT_KW {
+                return None;
+            }
+
+            let after_ident = &rest[ident_index + 1..];
+            let define =
+                if after_ident.first().map(|token| token.kind()) == Some(SyntaxKind::LPAREN) {
+                    let rparen_index = after_ident
+                        .iter()
+                        .position(|token| token.kind() == SyntaxKind::RPAREN)?;
+
+                    ast::PreprocessorDefineData::FunctionLike {
+                        ident: lower_ident(ident),
+                        args: after_ident[1..rparen_index]
+                            .iter()
+                            .filter(|token| token.kind() == SyntaxKind::IDENT_KW)
+                            .map(lower_ident)
+                            .collect(),
+                        value: pp_rest(&after_ident[rparen_index + 1..]),
+                    }
+                } else {
+                    ast::PreprocessorDefineData::ObjectLike {
+                        ident: lower_ident(ident),
+                        value: pp_rest(after_ident),
+                    }
+                };
+
+            ast::PreprocessorData::Define(Node::new(define, span(range)))
+        }
+        "else" => {
+            expect_end(&mut significant)?;
+            ast::PreprocessorData::Else
+        }
+        "elif" => ast::PreprocessorData::ElseIf(Node::new(
+            ast::PreprocessorElseIfData {
+                condition: pp_rest(rest),
+            },
+            span(range),
+        )),
+        "endif" => {
+            expect_end(&mut significant)?;
+            ast::PreprocessorData::EndIf
+        }
+        "error" => ast::PreprocessorData::Error(Node::new(
+            ast::PreprocessorErrorData {
+                message: pp_rest(rest),
+            },
+            span(range),
+        )),
+        "if" => ast::PreprocessorData::If(Node::new(
+            ast::PreprocessorIfData {
+                condition: pp_rest(rest),
+            },
+            span(range),
+        )),
+        "ifdef" | "ifndef" | "undef" => {
+            let ident = significant
+                .next()
+                .filter(|token| token.kind() == SyntaxKind::IDENT_KW)
+                .map(lower_ident)?;
+            expect_end(&mut significant)?;
+
+            match name.text() {
+                "ifdef" => ast::PreprocessorData::IfDef(Node::new(
+                    ast::PreprocessorIfDefData { ident },
+                    span(range),
+                )),
+                "ifndef" => ast::PreprocessorData::IfNDef(Node::new(
+                    ast::PreprocessorIfNDefData { ident },
+                    span(range),
+                )),
+                _ => ast::PreprocessorData::Undef(Node::new(
+                    ast::PreprocessorUndefData { name: ident },
+                    span(range),
+                )),
+            }
+        }
+        "include" | "moj_import" => {
+            let rest = pp_rest(rest);
+            let path = if let Some(path) = rest.strip_prefix('"') {
+                ast::PathData::Relative(path.strip_suffix('"').unwrap_or(path).to_owned())
+            } else if let Some(path) = rest.strip_prefix('<') {
+                ast::PathData::Absolute(path.strip_suffix('>').unwrap_or(path).to_owned())
+            } else {
+                return None;
+            };
+            let path = Node::new(path, span(range));
+
+            if name.text() == "include" {
+                ast::PreprocessorData::Include(Node::new(
+                    ast::PreprocessorIncludeData { path },
+                    span(range),
+                ))
+            } else {
+                ast::PreprocessorData::MojImport(Node::new(
+                    ast::PreprocessorMojImportData { path },
+                    span(range),
+                ))
+            }
+        }
+        "line" => {
+            let line = pp_int(significant.next()?)?;
+            let source_string_number = match significant.next() {
+                Some(token) => Some(pp_int(token)?),
+                None => None,
+            };
+            expect_end(&mut significant)?;
+
+            ast::PreprocessorData::Line(Node::new(
+                ast::PreprocessorLineData {
+                    line: line as u32,
+                    source_string_number: source_string_number.map(|n| n as u32),
+                },
+                span(range),
+            ))
+        }
+        "pragma" => ast::PreprocessorData::Pragma(Node::new(
+            ast::PreprocessorPragmaData {
+                command: pp_rest(rest),
+            },
+            span(range),
+        )),
+        "version" => {
+            let version = pp_int(significant.next()?)?;
+            let profile = match significant.next() {
+                Some(token) => Some(spanned_token(
+                    match unescaped(token).as_str() {
+                        "core" => ast::PreprocessorVersionProfileData::Core,
+                        "compatibility" => ast::PreprocessorVersionProfileData::Compatibility,
+                        "es" => ast::PreprocessorVersionProfileData::Es,
+                        _ => return None,
+                    },
+                    token,
+                )),
+                None => None,
+            };
+            expect_end(&mut significant)?;
+
+            ast::PreprocessorData::Version(Node::new(
+                ast::PreprocessorVersionData {
+                    version: version as u16,
+                    profile,
+                },
+                span(range),
+            ))
+        }
+        "extension" => {
+            let extension = significant
+                .next()
+                .filter(|token| matches!(classify(token), Some(Token::IDENT(_))))?;
+            let behavior = match significant.next() {
+                Some(colon) if colon.kind() == SyntaxKind::COLON => {
+                    let token = significant.next()?;
+                    Some(spanned_token(
+                        match unescaped(token).as_str() {
+                            "require" => ast::PreprocessorExtensionBehaviorData::Require,
+                            "enable" => ast::PreprocessorExtensionBehaviorData::Enable,
+                            "warn" => ast::PreprocessorExtensionBehaviorData::Warn,
+                            "disable" => ast::PreprocessorExtensionBehaviorData::Disable,
+                            _ => return None,
+                        },
+                        token,
+                    ))
+                }
+                Some(_) => return None,
+                None => None,
+            };
+            expect_end(&mut significant)?;
+
+            let extension = unescaped(extension);
+            ast::PreprocessorData::Extension(Node::new(
+                ast::PreprocessorExtensionData {
+                    name: Node::new(
+                        if extension == "all" {
+                            ast::PreprocessorExtensionNameData::All
+                        } else {
+                            ast::PreprocessorExtensionNameData::Specific(extension)
+                        },
+                        span(range),
+                    ),
+                    behavior,
+                },
+                span(range),
+            ))
+        }
+        _ => return None,
+    };
+
+    Some(spanned(data, directive))
+}
+
+/// Return `None` if there are tokens left in a directive
+fn expect_end<'t>(mut tokens: impl Iterator<Item = &'t SyntaxToken>) -> Option<()> {
+    tokens.next().is_none().then_some(())
+}
+
+fn pp_int(token: &SyntaxToken) -> Option<i32> {
+    match classify(token)? {
+        Token::INT_CONST(value) => Some(value),
+        _ => None,
+    }
+}
+
+// Declarations
+
+fn lower_declaration(declaration: &Declaration) -> Option<ast::Declaration> {
+    let data = match declaration.kind()? {
+        DeclarationKind::FunctionPrototype(prototype) => {
+            ast::DeclarationData::FunctionPrototype(lower_function_prototype(&prototype)?)
+        }
+        DeclarationKind::InitDeclaratorList(list) => {
+            let head = list.head()?;
+            let (name, array_specifier) = match head.ident() {
+                Some(ident) => (
+                    Some(lower_ident(&ident.ident()?)),
+                    lower_optional(ident.array_specifier(), lower_array_specifier)?,
+                ),
+                None => (None, None),
+            };
+
+            ast::DeclarationData::InitDeclaratorList(spanned(
+                ast::InitDeclaratorListData {
+                    head: spanned(
+                        ast::SingleDeclarationData {
+                            ty: lower_fully_specified_type(&head.ty()?)?,
+                            name,
+                            array_specifier,
+                            initializer: lower_optional(head.initializer(), lower_initializer)?,
+                        },
+                        &head,
+                    ),
+                    tail: list
+                        .tail()
+                        .map(|declaration| {
+                            Some(spanned(
+                                ast::SingleDeclarationNoTypeData {
+                                    ident: lower_arrayed_identifier(&declaration.ident()?)?,
+                                    initializer: lower_optional(
+                                        declaration.initializer(),
+                                        lower_initializer,
+                                    )?,
+                                },
+                                &declaration,
+                            ))
+                        })
+                        .collect::<Option<_>>()?,
+                },
+                &list,
+            ))
+        }
+        DeclarationKind::Precision(precision) => ast::DeclarationData::Precision(
+            lower_precision_qualifier(&precision.precision()?)?,
+            lower_type_specifier(&precision.ty()?)?,
+        ),
+        DeclarationKind::Block(block) => ast::DeclarationData::Block(spanned(
+            ast::BlockData {
+                qualifier: lower_type_qualifier(&block.qualifier()?)?,
+                name: lower_ident(&block.name()?),
+                fields: lower_fields(block.fields())?,
+                identifier: lower_optional(block.identifier(), lower_arrayed_identifier)?,
+            },
+            &block,
+        )),
+        DeclarationKind::Invariant(invariant) => {
+            ast::DeclarationData::Invariant(lower_ident(&invariant.ident()?))
+        }
+        DeclarationKind::QualifierRedeclaration(redeclaration) => {
+            ast::DeclarationData::QualifierRedeclaration(
+                lower_type_qualifier(&redeclaration.qualifier()?)?,
+                redeclaration
+                    .names()
+                    .map(|name| lower_ident(&name))
+                    .collect(),
+            )
+        }
+        DeclarationKind::TypeOnly(qualifier) => {
+            ast::DeclarationData::TypeOnly(lower_type_qualifier(&qualifier)?)
+        }
+    };
+
+    Some(spanned(data, declaration))
+}
+
+/// Lower an optional part of a node: `None` if lowering failed, `Some(None)` if it was absent
+fn lower_optional<N, T>(node: Option<N>, lower: impl FnOnce(&N) -> Option<T>) -> Option<Option<T>> {
+    node.map(|node| lower(&node))
+        .map_or(Some(None), |lowered| lowered.map(Some))
+}
+
+fn lower_function_prototype(prototype: &FunctionPrototype) -> Option<ast::FunctionPrototype> {
+    Some(spanned(
+        ast::FunctionPrototypeData {
+            ty: lower_fully_specified_type(&prototype.ty()?)?,
+            name: lower_ident(&prototype.name()?),
+            parameters: prototype
+                .parameters()
+                .map(|parameter| {
+                    let qualifier = lower_optional(parameter.qualifier(), lower_type_qualifier)?;
+                    let ty = parameter.ty()?;
+
+                    Some(spanned(
+                        match parameter.ident() {
+                            Some(ident) => ast::FunctionParameterDeclarationData::Named(
+                                qualifier,
+                                Node::new(
+                                    ast::FunctionParameterDeclaratorData {
+                                        ty: lower_type_specifier(&ty)?,
+                                        ident: lower_arrayed_identifier(&ident)?,
+                                    },
+                                    span(TextRange::new(
+                                        ty.syntax().text_range().start(),
+                                        ident.syntax().text_range().end(),
+                                    )),
+                                ),
+                            ),
+                            None => ast::FunctionParameterDeclarationData::Unnamed(
+                                qualifier,
+                                lower_type_specifier(&ty)?,
+                            ),
+                        },
+                        &parameter,
+                    ))
+                })
+                .collect::<Option<_>>()?,
+        },
+        prototype,
+    ))
+}
+
+fn lower_arrayed_identifier(ident: &ArrayedIdentifier) -> Option<ast::ArrayedIdentifier> {
+    Some(spanned(
+        ast::ArrayedIdentifierData {
+            ident: lower_ident(&ident.ident()?),
+            array_spec: lower_optional(ident.array_specifier(), lower_array_specifier)?,
+        },
+        ident,
+    ))
+}
+
+fn lower_initializer(initializer: &Initializer) -> Option<ast::Initializer> {
+    Some(spanned(
+        match initializer {
+            Initializer::List(list) => ast::InitializerData::List(
+                list.initializers()
+                    .map(|initializer| lower_initializer(&initializer))
+                    .collect::<Option<_>>()?,
+            ),
+            Initializer::Simple(expr) => ast::InitializerData::Simple(Box::new(lower_expr(expr)?)),
+        },
+        initializer,
+    ))
+}
+
+fn lower_attributes(attributes: Option<&AttributeList>) -> Option<Vec<ast::Attribute>> {
+    let Some(attributes) = attributes else {
+        return Some(Vec::new());
+    };
+
+    attributes
+        .attributes()
+        .map(|attribute| {
+            Some(spanned(
+                ast::AttributeData {
+                    name: lower_ident(&attribute.name()?),
+                    arg: lower_optional(attribute.argument(), lower_expr)?.map(Box::new),
+                },
+                &attribute,
+            ))
+        })
+        .collect()
+}
+
+// Types
+
+fn lower_fully_specified_type(ty: &FullySpecifiedType) -> Option<ast::FullySpecifiedType> {
+    Some(spanned(
+        ast::FullySpecifiedTypeData {
+            qualifier: lower_optional(ty.qualifier(), lower_type_qualifier)?,
+            ty: lower_type_specifier(&ty.ty()?)?,
+        },
+        ty,
+    ))
+}
+
+fn lower_type_specifier(ty: &TypeSpecifier) -> Option<ast::TypeSpecifier> {
+    Some(spanned(
+        ast::TypeSpecifierData {
+            ty: lower_type_specifier_non_array(ty)?,
+            array_specifier: lower_optional(ty.array_specifier(), lower_array_specifier)?,
+        },
+        ty,
+    ))
+}
+
+fn lower_type_specifier_non_array(ty: &TypeSpecifier) -> Option<ast::TypeSpecifierNonArray> {
+    if let Some(specifier) = ty.struct_specifier() {
+        Some(spanned(
+            ast::TypeSpecifierNonArrayData::Struct(spanned(
+                ast::StructSpecifierData {
+                    name: specifier
+                        .name()
+                        .map(|name| spanned_token(ast::TypeNameData(unescaped(&name)), &name)),
+                    fields: lower_fields(specifier.fields())?,
+                },
+                &specifier,
+            )),
+            &specifier,
+        ))
+    } else if let Some(spirv_type) = ty.spirv_type() {
+        Some(spanned(
+            ast::TypeSpecifierNonArrayData::SpirvType(lower_spirv_arguments(
+                spirv_type.arguments(),
+            )?),
+            &spirv_type,
+        ))
+    } else {
+        let name = ty.type_name()?;
+        let text = unescaped(&name);
+
+        Some(spanned_token(
+            builtin_type(&text).unwrap_or_else(|| {
+                ast::TypeSpecifierNonArrayData::TypeName(spanned_token(
+                    ast::TypeNameData(text),
+                    &name,
+                ))
+            }),
+            &name,
+        ))
+    }
+}
+
+fn lower_fields(
+    fields: impl Iterator<Item = StructField>,
+) -> Option<Vec<ast::StructFieldSpecifier>> {
+    fields
+        .map(|field| {
+            Some(spanned(
+                ast::StructFieldSpecifierData {
+                    qualifier: lower_optional(field.qualifier(), lower_type_qualifier)?,
+                    ty: lower_type_specifier(&field.ty()?)?,
+                    identifiers: field
+                        .identifiers()
+                        .map(|ident| lower_arrayed_identifier(&ident))
+                        .collect::<Option<_>>()?,
+                },
+                &field,
+            ))
+        })
+        .collect()
+}
+
+fn lower_array_specifier(array: &ArraySpecifier) -> Option<ast::ArraySpecifier> {
+    Some(spanned(
+        ast::ArraySpecifierData {
+            dimensions: array
+                .dimensions()
+                .map(|dimension| {
+                    Some(spanned(
+                        match dimension.size() {
+                            Some(size) => ast::ArraySpecifierDimensionData::ExplicitlySized(
+                                Box::new(lower_expr(&size)?),
+                            ),
+                            None => ast::ArraySpecifierDimensionData::Unsized,
+                        },
+                        &dimension,
+                    ))
+                })
+                .collect::<Option<_>>()?,
+        },
+        array,
+    ))
+}
+
+// Qualifiers
+
+fn lower_type_qualifier(qualifier: &TypeQualifier) -> Option<ast::TypeQualifier> {
+    Some(spanned(
+        ast::TypeQualifierData {
+            qualifiers: qualifier
+                .qualifiers()
+                .map(|spec| {
+                    Some(spanned(
+                        match &spec {
+                            TypeQualifierSpec::Storage(storage) => {
+                                ast::TypeQualifierSpecData::Storage(lower_storage_qualifier(
+                                    storage,
+                                )?)
+                            }
+                            TypeQualifierSpec::Layout(layout) => {
+                                ast::TypeQualifierSpecData::Layout(lower_layout_qualifier(layout)?)
+                            }
+                            TypeQualifierSpec::Precision(precision) => {
+                                ast::TypeQualifierSpecData::Precision(lower_precision_qualifier(
+                                    precision,
+                                )?)
+                            }
+                            TypeQualifierSpec::Interpolation(interpolation) => {
+                                ast::TypeQualifierSpecData::Interpolation(spanned(
+                                    match interpolation.keyword()?.text() {
+                                        "smooth" => ast::InterpolationQualifierData::Smooth,
+                                        "flat" => ast::InterpolationQualifierData::Flat,
+                                        "noperspective" => {
+                                            ast::InterpolationQualifierData::NoPerspective
+                                        }
+                                        "perprimitiveEXT" => {
+                                            ast::InterpolationQualifierData::PerPrimitiveExt
+                                        }
+                                        "perprimitiveNV" => {
+                                            ast::InterpolationQualifierData::PerPrimitiveNv
+                                        }
+                                        "perviewNV" => ast::InterpolationQualifierData::PerViewNv,
+                                        "taskNV" => ast::InterpolationQualifierData::TaskNv,
+                                        _ => return None,
+                                    },
+                                    interpolation,
+                                ))
+                            }
+                            TypeQualifierSpec::Invariant(_) => {
+                                ast::TypeQualifierSpecData::Invariant
+                            }
+                            TypeQualifierSpec::Precise(_) => ast::TypeQualifierSpecData::Precise,
+                            TypeQualifierSpec::Spirv(spirv) => {
+                                ast::TypeQualifierSpecData::Spirv(lower_spirv_qualifier(spirv)?)
+                            }
+                        },
+                        &spec,
+                    ))
+                })
+                .collect::<Option<_>>()?,
+        },
+        qualifier,
+    ))
+}
+
+fn lower_storage_qualifier(storage: &StorageQualifier) -> Option<ast::StorageQualifier> {
+    use ast::StorageQualifierData::*;
+
+    Some(spanned(
+        match unescaped(&storage.keyword()?).as_str() {
+            "const" => Const,
+            "inout" => InOut,
+            "in" => In,
+            "out" => Out,
+            "centroid" => Centroid,
+            "patch" => Patch,
+            "sample" => Sample,
+            "uniform" => Uniform,
+            "buffer" => Buffer,
+            "shared" => Shared,
+            "coherent" => Coherent,
+            "volatile" => Volatile,
+            "restrict" => Restrict,
+            "readonly" => ReadOnly,
+            "writeonly" => WriteOnly,
+            "attribute" => Attribute,
+            "varying" => Varying,
+            "shadercallcoherent" => ShaderCallCoherent,
+            "rayPayloadEXT" => RayPayloadExt,
+            "rayPayloadInEXT" => RayPayloadInExt,
+            "hitAttributeEXT" => HitAttributeExt,
+            "callableDataEXT" => CallableDataExt,
+            "callableDataInEXT" => CallableDataInExt,
+            "taskPayloadSharedEXT" => TaskPayloadSharedExt,
+            "subroutine" => Subroutine(
+                storage
+                    .subroutine_types()
+                    .map(|ty| lower_type_specifier(&ty))
+                    .collect::<Option<_>>()?,
+            ),
+            _ => return None,
+        },
+        storage,
+    ))
+}
+
+fn lower_layout_qualifier(layout: &LayoutQualifier) -> Option<ast::LayoutQualifier> {
+    Some(spanned(
+        ast::LayoutQualifierData {
+            ids: layout
+                .ids()
+                .map(|id| {
+                    let name = id.name()?;
+
+                    Some(spanned(
+                        if name.text() == "shared" {
+                            ast::LayoutQualifierSpecData::Shared
+                        } else {
+                            ast::LayoutQualifierSpecData::Identifier(
+                                lower_ident(&name),
+                                lower_optional(id.value(), lower_expr)?.map(Box::new),
+                            )
+                        },
+                        &id,
+                    ))
+                })
+                .collect::<Option<_>>()?,
+        },
+        layout,
+    ))
+}
+
+fn lower_precision_qualifier(precision: &PrecisionQualifier) -> Option<ast::PrecisionQualifier> {
+    Some(spanned(
+        match precision.keyword()?.text() {
+            "highp" => ast::PrecisionQualifierData::High,
+            "mediump" => ast::PrecisionQualifierData::Medium,
+            "lowp" => ast::PrecisionQualifierData::Low,
+            _ => return None,
+        },
+        precision,
+    ))
+}
+
+fn lower_spirv_qualifier(spirv: &SpirvQualifier) -> Option<ast::SpirvQualifier> {
+    use ast::SpirvQualifierData::*;
+
+    let arguments = || lower_spirv_arguments(spirv.arguments());
+
+    Some(spanned(
+        match spirv.keyword()?.text() {
+            "spirv_instruction" => Instruction(arguments()?),
+            "spirv_execution_mode" => ExecutionMode(arguments()?),
+            "spirv_execution_mode_id" => ExecutionModeId(arguments()?),
+            "spirv_decorate" => Decorate(arguments()?),
+            "spirv_decorate_id" => DecorateId(arguments()?),
+            "spirv_decorate_string" => DecorateString(arguments()?),
+            "spirv_storage_class" => StorageClass(arguments()?),
+            "spirv_by_reference" => ByReference,
+            "spirv_literal" => Literal,
+            _ => return None,
+        },
+        spirv,
+    ))
+}
+
+fn lower_spirv_arguments(
+    arguments: impl Iterator<Item = SpirvArgument>,
+) -> Option<Vec<ast::SpirvArgument>> {
+    arguments
+        .map(|argument| {
+            let mut values = argument.values().map(|value| lower_spirv_value(&value));
+
+            Some(spanned(
+                match argument.name() {
+                    Some(name) if argument.is_list() => ast::SpirvArgumentData::Requirement(
+                        lower_ident(&name),
+                        values.collect::<Option<_>>()?,
+                    ),
+                    Some(name) => {
+                        ast::SpirvArgumentData::Named(lower_ident(&name), values.next()??)
+                    }
+                    None => ast::SpirvArgumentData::Value(values.next()??),
+                },
+                &argument,
+            ))
+        })
+        .collect()
+}
+
+fn lower_spirv_value(value: &SpirvValue) -> Option<ast::SpirvValue> {
+    match value {
+        SpirvValue::String(token) => match classify(token)? {
+            Token::STRING_CONST(value) => {
+                Some(spanned_token(ast::SpirvValueData::String(value), token))
+            }
+            _ => None,
+        },
+        SpirvValue::Type(ty) => Some(spanned(
+            ast::SpirvValueData::Type(lower_type_specifier_non_array(ty)?),
+            ty,
+        )),
+        SpirvValue::Expr(expr) => Some(spanned(
+            ast::SpirvValueData::Expr(Box::new(lower_expr(expr)?)),
+            expr,
+        )),
+    }
+}
+
+// Statements
+
+fn lower_compound_statement(block: &CompoundStatement) -> Option<ast::CompoundStatement> {
+    Some(spanned(
+        ast::CompoundStatementData {
+            statement_list: lower_statements(block.statements())?,
+        },
+        block,
+    ))
+}
+
+fn lower_statements(statements: impl Iterator<Item = Statement>) -> Option<Vec<ast::Statement>> {
+    statements
+        .map(|statement| lower_statement(&statement))
+        .collect()
+}
+
+fn lower_boxed_statement(statement: Option<Statement>) -> Option<Box<ast::Statement>> {
+    Some(Box::new(lower_statement(&statement?)?))
+}
+
+fn lower_statement(statement: &Statement) -> Option<ast::Statement> {
+    let data = match statement {
+        Statement::Compound(block) => {
+            ast::StatementData::Compound(lower_compound_statement(block)?)
+        }
+        Statement::Expr(expr) => ast::StatementData::Expression(spanned(
+            ast::ExprStatementData(lower_optional(expr.expr(), lower_expr)?),
+            expr,
+        )),
+        Statement::Declaration(declaration) => {
+            ast::StatementData::Declaration(lower_declaration(declaration)?)
+        }
+        Statement::Selection(selection) => {
+            let then_branch = selection.then_branch()?;
+            let rest = match selection.else_branch() {
+                Some(else_branch) => Node::new(
+                    ast::SelectionRestStatementData::Else(
+                        Box::new(lower_statement(&then_branch)?),
+                        Box::new(lower_statement(&else_branch)?),
+                    ),
+                    span(TextRange::new(
+                        then_branch.syntax().text_range().start(),
+                        else_branch.syntax().text_range().end(),
+                    )),
+                ),
+                None => spanned(
+                    ast::SelectionRestStatementData::Statement(Box::new(lower_statement(
+                        &then_branch,
+                    )?)),
+                    &then_branch,
+                ),
+            };
+
+            ast::StatementData::Selection(spanned(
+                ast::SelectionStatementData {
+                    cond: Box::new(lower_expr(&selection.condition()?)?),
+                    rest,
+                },
+                selection,
+            ))
+        }
+        Statement::Switch(switch) => ast::StatementData::Switch(spanned(
+            ast::SwitchStatementData {
+                head: Box::new(lower_expr(&switch.head()?)?),
+                body: lower_statements(switch.statements())?,
+            },
+            switch,
+        )),
+        Statement::CaseLabel(label) => ast::StatementData::CaseLabel(spanned(
+            match label.expr() {
+                Some(expr) => ast::CaseLabelData::Case(Box::new(lower_expr(&expr)?)),
+                None => ast::CaseLabelData::Def,
+            },
+            label,
+        )),
+        Statement::While(statement) => ast::StatementData::Iteration(spanned(
+            ast::IterationStatementData::While(
+                lower_condition(&statement.condition()?)?,
+                lower_boxed_statement(statement.body())?,
+            ),
+            statement,
+        )),
+        Statement::DoWhile(statement) => ast::StatementData::Iteration(spanned(
+            ast::IterationStatementData::DoWhile(
+                lower_boxed_statement(statement.body())?,
+                Box::new(lower_expr(&statement.condition()?)?),
+            ),
+            statement,
+        )),
+        Statement::For(statement) => {
+            let init = statement.init()?;
+            let init_data = match &init {
+                Statement::Expr(expr) => {
+                    ast::ForInitStatementData::Expression(lower_optional(expr.expr(), lower_expr)?)
+                }
+                Statement::Declaration(declaration) => ast::ForInitStatementData::Declaration(
+                    Box::new(lower_declaration(declaration)?),
+                ),
+                _ => return None,
+            };
+
+            let condition = statement.condition();
+            let post_expr = statement.post_expr();
+            let semicolon = statement.semicolon_token()?.text_range();
+            let rest_range = TextRange::new(
+                condition.as_ref().map_or(semicolon.start(), |condition| {
+                    condition.syntax().text_range().start()
+                }),
+                post_expr
+                    .as_ref()
+                    .map_or(semicolon.end(), |expr| expr.syntax().text_range().end()),
+            );
+
+            ast::StatementData::Iteration(spanned(
+                ast::IterationStatementData::For(
+                    spanned(init_data, &init),
+                    Node::new(
+                        ast::ForRestStatementData {
+                            condition: lower_optional(condition, lower_condition)?,
+                            post_expr: lower_optional(post_expr, lower_expr)?.map(Box::new),
+                        },
+                        span(rest_range),
+                    ),
+                    lower_boxed_statement(statement.body())?,
+                ),
+                statement,
+            ))
+        }
+        Statement::Jump(jump) => ast::StatementData::Jump(spanned(
+            match jump.keyword()?.text() {
+                "continue" => ast::JumpStatementData::Continue,
+                "break" => ast::JumpStatementData::Break,
+                "discard" => ast::JumpStatementData::Discard,
+                "ignoreIntersectionEXT" => ast::JumpStatementData::IgnoreIntersectionExt,
+                "terminateRayEXT" => ast::JumpStatementData::TerminateRayExt,
+                "return" => ast::JumpStatementData::Return(
+                    lower_optional(jump.expr(), lower_expr)?.map(Box::new),
+                ),
+                _ => return None,
+            },
+            jump,
+        )),
+        Statement::Attributed(attributed) => ast::StatementData::Attributed(spanned(
+            ast::AttributedStatementData {
+                attributes: lower_attributes(Some(&attributed.attributes()?))?,
+                statement: lower_boxed_statement(attributed.statement())?,
+            },
+            attributed,
+        )),
+        Statement::Error(_) => return None,
+    };
+
+    Some(spanned(data, statement))
+}
+
+fn lower_condition(condition: &Condition) -> Option<ast::Condition> {
+    Some(spanned(
+        match condition.ty() {
+            Some(ty) => ast::ConditionData::Assignment(
+                Box::new(lower_fully_specified_type(&ty)?),
+                lower_ident(&condition.ident()?),
+                lower_initializer(&condition.initializer()?)?,
+            ),
+            None => ast::ConditionData::Expr(lower_expr(&condition.expr()?)?),
+        },
+        condition,
+    ))
+}
+
+// Expressions
+
+fn lower_boxed_expr(expr: Option<Expr>) -> Option<Box<ast::Expr>> {
+    Some(Box::new(lower_expr(&expr?)?))
+}
+
+fn lower_expr(expr: &Expr) -> Option<ast::Expr> {
+    let data = match expr {
+        Expr::Variable(variable) => ast::ExprData::Variable(lower_ident(&variable.ident()?)),
+        Expr::Literal(literal) => {
+            let token = literal.token()?;
+            let text = unescaped(&token);
+
+            match classify(&token)? {
+                Token::INT_CONST(value) => {
+                    ast::ExprData::IntConst(ast::Literal::from_source(value, text))
+                }
+                Token::UINT_CONST(value) => {
+                    ast::ExprData::UIntConst(ast::Literal::from_source(value, text))
+                }
+                Token::FLOAT_CONST(value) => {
+                    ast::ExprData::FloatConst(ast::Literal::from_source(value, text))
+                }
+                Token::DOUBLE_CONST(value) => {
+                    ast::ExprData::DoubleConst(ast::Literal::from_source(value, text))
+                }
+                Token::BOOL_CONST(value) => ast::ExprData::BoolConst(value),
+                Token::INT16_CONST(value) => {
+                    ast::ExprData::Int16Const(ast::Literal::from_source(value, text))
+                }
+                Token::UINT16_CONST(value) => {
+                    ast::ExprData::UInt16Const(ast::Literal::from_source(value, text))
+                }
+                Token::INT64_CONST(value) => {
+                    ast::ExprData::Int64Const(ast::Literal::from_source(value, text))
+                }
+                Token::UINT64_CONST(value) => {
+                    ast::ExprData::UInt64Const(ast::Literal::from_source(value, text))
+                }
+                Token::FLOAT16_CONST(value) => {
+                    ast::ExprData::Float16Const(ast::Literal::from_source(value, text))
+                }
+                _ => return None,
+            }
+        }
+        Expr::Paren(paren) => {
+            // Parentheses are not part of the AST
+            return lower_expr(&paren.expr()?);
+        }
+        Expr::Call(call) => {
+            let function = call.function()?;
+
+            ast::ExprData::FunCall(
+                spanned(
+                    match &function {
+                        FunIdentifier::TypeSpecifier(ty) => ast::FunIdentifierData::TypeSpecifier(
+                            Box::new(lower_type_specifier(ty)?),
+                        ),
+                        FunIdentifier::Expr(expr) => {
+                            ast::FunIdentifierData::Expr(Box::new(lower_expr(expr)?))
+                        }
+                    },
+                    &function,
+                ),
+                call.arg_list()?
+                    .args()
+                    .map(|arg| lower_expr(&arg))
+                    .collect::<Option<_>>()?,
+            )
+        }
+        Expr::Index(index) => ast::ExprData::Bracket(
+            lower_boxed_expr(index.base())?,
+            lower_boxed_expr(index.index())?,
+        ),
+        Expr::Field(field) => ast::ExprData::Dot(
+            lower_boxed_expr(field.base())?,
+            lower_ident(&field.field()?),
+        ),
+        Expr::Postfix(postfix) => {
+            let operand = lower_boxed_expr(postfix.operand())?;
+
+            match postfix.op_token()?.kind() {
+                SyntaxKind::INC_OP => ast::ExprData::PostInc(operand),
+                SyntaxKind::DEC_OP => ast::ExprData::PostDec(operand),
+                _ => return None,
+            }
+        }
+        Expr::Prefix(prefix) => {
+            let op = prefix.op_token()?;
+
+            ast::ExprData::Unary(
+                spanned_token(
+                    match op.kind() {
+                        SyntaxKind::PLUS => ast::UnaryOpData::Add,
+                        SyntaxKind::DASH => ast::UnaryOpData::Minus,
+                        SyntaxKind::BANG => ast::UnaryOpData::Not,
+                        SyntaxKind::TILDE => ast::UnaryOpData::Complement,
+                        SyntaxKind::INC_OP => ast::UnaryOpData::Inc,
+                        SyntaxKind::DEC_OP => ast::UnaryOpData::Dec,
+                        _ => return None,
+                    },
+                    &op,
+                ),
+                lower_boxed_expr(prefix.operand())?,
+            )
+        }
+        Expr::Binary(binary) => {
+            use ast::BinaryOpData::*;
+
+            let op = binary.op_token()?;
+
+            ast::ExprData::Binary(
+                spanned_token(
+                    match op.kind() {
+                        SyntaxKind::OR_OP => Or,
+                        SyntaxKind::XOR_OP => Xor,
+                        SyntaxKind::AND_OP => And,
+                        SyntaxKind::BAR => BitOr,
+                        SyntaxKind::CARET => BitXor,
+                        SyntaxKind::AMPERSAND => BitAnd,
+                        SyntaxKind::EQ_OP => Equal,
+                        SyntaxKind::NE_OP => NonEqual,
+                        SyntaxKind::LANGLE => Lt,
+                        SyntaxKind::RANGLE => Gt,
+                        SyntaxKind::LE_OP => Lte,
+                        SyntaxKind::GE_OP => Gte,
+                        SyntaxKind::LEFT_OP => LShift,
+                        SyntaxKind::RIGHT_OP => RShift,
+                        SyntaxKind::PLUS => Add,
+                        SyntaxKind::DASH => Sub,
+                        SyntaxKind::ASTERISK => Mult,
+                        SyntaxKind::SLASH => Div,
+                        SyntaxKind::PERCENT => Mod,
+                        _ => return None,
+                    },
+                    &op,
+                ),
+                lower_boxed_expr(binary.lhs())?,
+                lower_boxed_expr(binary.rhs())?,
+            )
+        }
+        Expr::Ternary(ternary) => ast::ExprData::Ternary(
+            lower_boxed_expr(ternary.condition())?,
+            lower_boxed_expr(ternary.then_expr())?,
+            lower_boxed_expr(ternary.else_expr())?,
+        ),
+        Expr::Assignment(assignment) => {
+            use ast::AssignmentOpData::*;
+
+            let op = assignment.op_token()?;
+
+            ast::ExprData::Assignment(
+                lower_boxed_expr(assignment.lhs())?,
+                spanned_token(
+                    match op.kind() {
+                        SyntaxKind::EQUAL => Equal,
+                        SyntaxKind::MUL_ASSIGN => Mult,
+                        SyntaxKind::DIV_ASSIGN => Div,
+                        SyntaxKind::MOD_ASSIGN => Mod,
+                        SyntaxKind::ADD_ASSIGN => Add,
+                        SyntaxKind::SUB_ASSIGN => Sub,
+                        SyntaxKind::LEFT_ASSIGN => LShift,
+                        SyntaxKind::RIGHT_ASSIGN => RShift,
+                        SyntaxKind::AND_ASSIGN => And,
+                        SyntaxKind::XOR_ASSIGN => Xor,
+                        SyntaxKind::OR_ASSIGN => Or,
+                        _ => return None,
+                    },
+                    &op,
+                ),
+                lower_boxed_expr(assignment.rhs())?,
+            )
+        }
+        Expr::Comma(comma) => ast::ExprData::Comma(
+            lower_boxed_expr(comma.lhs())?,
+            lower_boxed_expr(comma.rhs())?,
+        ),
+    };
+
+    Some(spanned(data, expr))
+}
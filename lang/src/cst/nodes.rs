@@ -0,0 +1,1230 @@
+use super::{SyntaxKind, SyntaxNode, SyntaxToken};
+
+/// A typed view over an untyped tree node
+pub trait CstNode: Sized {
+    /// Return true if nodes of the given kind can be wrapped by this type
+    fn can_cast(kind: SyntaxKind) -> bool;
+
+    /// Wrap the given node, if it has a compatible kind
+    fn cast(syntax: SyntaxNode) -> Option<Self>;
+
+    /// Get the wrapped untyped node
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+macro_rules! cst_node {
+    ($(#[$m:meta])* $name:ident => $kind:ident) => {
+        $(#[$m])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name(SyntaxNode);
+
+        impl CstNode for $name {
+            fn can_cast(kind: SyntaxKind) -> bool {
+                kind == SyntaxKind::$kind
+            }
+
+            fn cast(syntax: SyntaxNode) -> Option<Self> {
+                if Self::can_cast(syntax.kind()) {
+                    Some(Self(syntax))
+                } else {
+                    None
+                }
+            }
+
+            fn syntax(&self) -> &SyntaxNode {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+macro_rules! cst_enum {
+    ($(#[$m:meta])* $name:ident { $($(#[$vm:meta])* $variant:ident($ty:ident),)* }) => {
+        $(#[$m])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vm])* $variant($ty),)*
+        }
+
+        impl CstNode for $name {
+            fn can_cast(kind: SyntaxKind) -> bool {
+                $($ty::can_cast(kind))||*
+            }
+
+            fn cast(syntax: SyntaxNode) -> Option<Self> {
+                $(
+                    if $ty::can_cast(syntax.kind()) {
+                        return $ty::cast(syntax).map(Self::$variant);
+                    }
+                )*
+
+                None
+            }
+
+            fn syntax(&self) -> &SyntaxNode {
+                match self {
+                    $(Self::$variant(inner) => inner.syntax(),)*
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(self.syntax(), f)
+            }
+        }
+    };
+}
+
+/// Iterate over the tokens of a node which are not trivia or newlines
+fn significant_tokens(node: &SyntaxNode) -> impl Iterator<Item = SyntaxToken> {
+    node.children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| !token.kind().is_whitespace())
+}
+
+fn token(node: &SyntaxNode, kind: SyntaxKind) -> Option<SyntaxToken> {
+    significant_tokens(node).find(|token| token.kind() == kind)
+}
+
+fn child<N: CstNode>(node: &SyntaxNode) -> Option<N> {
+    node.children().find_map(N::cast)
+}
+
+fn children<N: CstNode>(node: &SyntaxNode) -> impl Iterator<Item = N> {
+    node.children().filter_map(N::cast)
+}
+
+cst_node!(
+    /// Root of the concrete syntax tree
+    TranslationUnit => ROOT
+);
+cst_node!(
+    /// Preprocessor directive, from the `#` to the end of the line
+    PreprocessorDirective => PP_DIRECTIVE
+);
+cst_node!(
+    /// Tokens which could not be parsed, or the location of a missing token
+    ErrorNode => ERROR_NODE
+);
+
+cst_enum!(
+    /// Top-level item of a translation unit
+    ExternalDeclaration {
+        /// Preprocessor directive
+        Preprocessor(PreprocessorDirective),
+        /// Declaration
+        Declaration(Declaration),
+        /// Function definition
+        FunctionDefinition(FunctionDefinition),
+        /// Unexpected tokens
+        Error(ErrorNode),
+    }
+);
+
+impl TranslationUnit {
+    /// Iterate over the top-level items of this translation unit
+    pub fn external_declarations(&self) -> impl Iterator<Item = ExternalDeclaration> {
+        children(&self.0)
+    }
+}
+
+impl PreprocessorDirective {
+    /// Get the `#` token starting this directive
+    pub fn hash_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::HASH)
+    }
+
+    /// Get the name of this directive, i.e. `version` for `#version 460`
+    pub fn name(&self) -> Option<SyntaxToken> {
+        significant_tokens(&self.0)
+            .nth(1)
+            .filter(|token| token.kind() == SyntaxKind::IDENT_KW)
+    }
+}
+
+// Declarations
+
+cst_node!(
+    /// Declaration, including its terminating `;`
+    Declaration => DECLARATION
+);
+cst_node!(
+    /// Function definition, including its attributes and its body
+    FunctionDefinition => FUNCTION_DEFINITION
+);
+cst_node!(
+    /// Function prototype, i.e. `void main()`
+    FunctionPrototype => FUNCTION_PROTOTYPE
+);
+cst_node!(
+    /// Parameter of a function prototype
+    FunctionParameter => FUNCTION_PARAMETER
+);
+cst_node!(
+    /// List of declared variables sharing the same type
+    InitDeclaratorList => INIT_DECLARATOR_LIST
+);
+cst_node!(
+    /// First declarator of a declaration, with its type
+    SingleDeclaration => SINGLE_DECLARATION
+);
+cst_node!(
+    /// Declarator following the first one in a declaration
+    SingleDeclarationNoType => SINGLE_DECLARATION_NO_TYPE
+);
+cst_node!(
+    /// Default precision declaration, i.e. `precision highp float`
+    PrecisionDeclaration => PRECISION_DECLARATION
+);
+cst_node!(
+    /// Interface block declaration
+    Block => BLOCK
+);
+cst_node!(
+    /// Invariant redeclaration of a variable, i.e. `invariant gl_Position`
+    InvariantDeclaration => INVARIANT_DECLARATION
+);
+cst_node!(
+    /// Redeclaration of variables with new qualifiers
+    QualifierRedeclaration => QUALIFIER_REDECLARATION
+);
+cst_node!(
+    /// Identifier with an optional array specifier
+    ArrayedIdentifier => ARRAYED_IDENTIFIER
+);
+cst_node!(
+    /// Braced list of initializers
+    InitializerList => INITIALIZER_LIST
+);
+
+cst_enum!(
+    /// Initial value of a declared variable
+    Initializer {
+        /// Braced list of initializers
+        List(InitializerList),
+        /// Expression
+        Simple(Expr),
+    }
+);
+
+/// Content of a declaration, without its terminating `;`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeclarationKind {
+    /// Function prototype
+    FunctionPrototype(FunctionPrototype),
+    /// List of declared variables
+    InitDeclaratorList(InitDeclaratorList),
+    /// Default precision declaration
+    Precision(PrecisionDeclaration),
+    /// Interface block declaration
+    Block(Block),
+    /// Invariant redeclaration
+    Invariant(InvariantDeclaration),
+    /// Redeclaration of variables with new qualifiers
+    QualifierRedeclaration(QualifierRedeclaration),
+    /// Qualifier on its own, i.e. `layout(local_size_x = 1) in;`
+    TypeOnly(TypeQualifier),
+}
+
+impl Declaration {
+    /// Get the content of this declaration
+    pub fn kind(&self) -> Option<DeclarationKind> {
+        self.0.children().find_map(|node| {
+            Some(match node.kind() {
+                SyntaxKind::FUNCTION_PROTOTYPE => {
+                    DeclarationKind::FunctionPrototype(FunctionPrototype(node))
+                }
+                SyntaxKind::INIT_DECLARATOR_LIST => {
+                    DeclarationKind::InitDeclaratorList(InitDeclaratorList(node))
+                }
+                SyntaxKind::PRECISION_DECLARATION => {
+                    DeclarationKind::Precision(PrecisionDeclaration(node))
+                }
+                SyntaxKind::BLOCK => DeclarationKind::Block(Block(node)),
+                SyntaxKind::INVARIANT_DECLARATION => {
+                    DeclarationKind::Invariant(InvariantDeclaration(node))
+                }
+                SyntaxKind::QUALIFIER_REDECLARATION => {
+                    DeclarationKind::QualifierRedeclaration(QualifierRedeclaration(node))
+                }
+                SyntaxKind::TYPE_QUALIFIER => DeclarationKind::TypeOnly(TypeQualifier(node)),
+                _ => return None,
+            })
+        })
+    }
+
+    /// Get the `;` token terminating this declaration, if any
+    pub fn semicolon_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::SEMICOLON)
+    }
+}
+
+impl FunctionDefinition {
+    /// Get the attributes preceding the prototype of this function
+    pub fn attributes(&self) -> Option<AttributeList> {
+        self.0
+            .children()
+            .take_while(|node| node.kind() != SyntaxKind::FUNCTION_PROTOTYPE)
+            .find_map(AttributeList::cast)
+    }
+
+    /// Get the prototype of this function
+    pub fn prototype(&self) -> Option<FunctionPrototype> {
+        child(&self.0)
+    }
+
+    /// Get the attributes between the prototype and the body of this function
+    pub fn body_attributes(&self) -> Option<AttributeList> {
+        self.0
+            .children()
+            .skip_while(|node| node.kind() != SyntaxKind::FUNCTION_PROTOTYPE)
+            .find_map(AttributeList::cast)
+    }
+
+    /// Get the body of this function
+    pub fn body(&self) -> Option<CompoundStatement> {
+        child(&self.0)
+    }
+
+    /// Get the identifier naming this function
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.prototype().and_then(|prototype| prototype.name())
+    }
+}
+
+impl FunctionPrototype {
+    /// Get the return type of this function
+    pub fn ty(&self) -> Option<FullySpecifiedType> {
+        child(&self.0)
+    }
+
+    /// Get the identifier naming this function
+    pub fn name(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+
+    /// Iterate over the parameters of this function
+    pub fn parameters(&self) -> impl Iterator<Item = FunctionParameter> {
+        children(&self.0)
+    }
+}
+
+impl FunctionParameter {
+    /// Get the qualifier of this parameter
+    pub fn qualifier(&self) -> Option<TypeQualifier> {
+        child(&self.0)
+    }
+
+    /// Get the type of this parameter
+    pub fn ty(&self) -> Option<TypeSpecifier> {
+        child(&self.0)
+    }
+
+    /// Get the name of this parameter, if it is named
+    pub fn ident(&self) -> Option<ArrayedIdentifier> {
+        child(&self.0)
+    }
+}
+
+impl InitDeclaratorList {
+    /// Get the first declarator, with the type of the declaration
+    pub fn head(&self) -> Option<SingleDeclaration> {
+        child(&self.0)
+    }
+
+    /// Iterate over the declarators following the first one
+    pub fn tail(&self) -> impl Iterator<Item = SingleDeclarationNoType> {
+        children(&self.0)
+    }
+}
+
+impl SingleDeclaration {
+    /// Get the type of this declaration
+    pub fn ty(&self) -> Option<FullySpecifiedType> {
+        child(&self.0)
+    }
+
+    /// Get the declared identifier, which is optional for struct declarations
+    pub fn ident(&self) -> Option<ArrayedIdentifier> {
+        child(&self.0)
+    }
+
+    /// Get the initial value of the declared variable
+    pub fn initializer(&self) -> Option<Initializer> {
+        child(&self.0)
+    }
+}
+
+impl SingleDeclarationNoType {
+    /// Get the declared identifier
+    pub fn ident(&self) -> Option<ArrayedIdentifier> {
+        child(&self.0)
+    }
+
+    /// Get the initial value of the declared variable
+    pub fn initializer(&self) -> Option<Initializer> {
+        child(&self.0)
+    }
+}
+
+impl PrecisionDeclaration {
+    /// Get the precision qualifier
+    pub fn precision(&self) -> Option<PrecisionQualifier> {
+        child(&self.0)
+    }
+
+    /// Get the type the default precision applies to
+    pub fn ty(&self) -> Option<TypeSpecifier> {
+        child(&self.0)
+    }
+}
+
+impl Block {
+    /// Get the qualifier of this block
+    pub fn qualifier(&self) -> Option<TypeQualifier> {
+        child(&self.0)
+    }
+
+    /// Get the name of this block
+    pub fn name(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+
+    /// Iterate over the fields of this block
+    pub fn fields(&self) -> impl Iterator<Item = StructField> {
+        children(&self.0)
+    }
+
+    /// Get the instance name of this block
+    pub fn identifier(&self) -> Option<ArrayedIdentifier> {
+        child(&self.0)
+    }
+}
+
+impl InvariantDeclaration {
+    /// Get the redeclared identifier
+    pub fn ident(&self) -> Option<SyntaxToken> {
+        significant_tokens(&self.0)
+            .nth(1)
+            .filter(|token| token.kind() == SyntaxKind::IDENT_KW)
+    }
+}
+
+impl QualifierRedeclaration {
+    /// Get the new qualifier
+    pub fn qualifier(&self) -> Option<TypeQualifier> {
+        child(&self.0)
+    }
+
+    /// Iterate over the redeclared identifiers
+    pub fn names(&self) -> impl Iterator<Item = SyntaxToken> {
+        significant_tokens(&self.0).filter(|token| token.kind() == SyntaxKind::IDENT_KW)
+    }
+}
+
+impl ArrayedIdentifier {
+    /// Get the identifier
+    pub fn ident(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+
+    /// Get the array specifier following the identifier
+    pub fn array_specifier(&self) -> Option<ArraySpecifier> {
+        child(&self.0)
+    }
+}
+
+impl InitializerList {
+    /// Iterate over the initializers of this list
+    pub fn initializers(&self) -> impl Iterator<Item = Initializer> {
+        children(&self.0)
+    }
+}
+
+// Types
+
+cst_node!(
+    /// Type with its optional qualifiers
+    FullySpecifiedType => FULLY_SPECIFIED_TYPE
+);
+cst_node!(
+    /// Type with its optional array specifier
+    TypeSpecifier => TYPE_SPECIFIER
+);
+cst_node!(
+    /// Struct type, with its fields
+    StructSpecifier => STRUCT_SPECIFIER
+);
+cst_node!(
+    /// Field declaration of a struct or block
+    StructField => STRUCT_FIELD
+);
+cst_node!(
+    /// Array dimensions, i.e. `[3][]`
+    ArraySpecifier => ARRAY_SPECIFIER
+);
+cst_node!(
+    /// Single array dimension, i.e. `[3]`
+    ArrayDimension => ARRAY_DIMENSION
+);
+cst_node!(
+    /// GL_EXT_spirv_intrinsics type, i.e. `spirv_type(id = 21)`
+    SpirvType => SPIRV_TYPE
+);
+
+impl FullySpecifiedType {
+    /// Get the qualifier of this type
+    pub fn qualifier(&self) -> Option<TypeQualifier> {
+        child(&self.0)
+    }
+
+    /// Get the qualified type
+    pub fn ty(&self) -> Option<TypeSpecifier> {
+        child(&self.0)
+    }
+}
+
+impl TypeSpecifier {
+    /// Get the name of this type, if it is not a struct or a SPIR-V type
+    pub fn type_name(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+
+    /// Get the struct declared by this type
+    pub fn struct_specifier(&self) -> Option<StructSpecifier> {
+        child(&self.0)
+    }
+
+    /// Get the SPIR-V type declared by this type
+    pub fn spirv_type(&self) -> Option<SpirvType> {
+        child(&self.0)
+    }
+
+    /// Get the array specifier of this type
+    pub fn array_specifier(&self) -> Option<ArraySpecifier> {
+        child(&self.0)
+    }
+}
+
+impl StructSpecifier {
+    /// Get the name of this struct
+    pub fn name(&self) -> Option<SyntaxToken> {
+        significant_tokens(&self.0)
+            .filter(|token| token.kind() == SyntaxKind::IDENT_KW)
+            .nth(1)
+    }
+
+    /// Iterate over the fields of this struct
+    pub fn fields(&self) -> impl Iterator<Item = StructField> {
+        children(&self.0)
+    }
+}
+
+impl StructField {
+    /// Get the qualifier of this field
+    pub fn qualifier(&self) -> Option<TypeQualifier> {
+        child(&self.0)
+    }
+
+    /// Get the type of this field
+    pub fn ty(&self) -> Option<TypeSpecifier> {
+        child(&self.0)
+    }
+
+    /// Iterate over the identifiers declared by this field
+    pub fn identifiers(&self) -> impl Iterator<Item = ArrayedIdentifier> {
+        children(&self.0)
+    }
+}
+
+impl ArraySpecifier {
+    /// Iterate over the dimensions of this specifier
+    pub fn dimensions(&self) -> impl Iterator<Item = ArrayDimension> {
+        children(&self.0)
+    }
+}
+
+impl ArrayDimension {
+    /// Get the size of this dimension, if it is explicitly sized
+    pub fn size(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl SpirvType {
+    /// Iterate over the arguments of this type
+    pub fn arguments(&self) -> impl Iterator<Item = SpirvArgument> {
+        children(&self.0)
+    }
+}
+
+// Qualifiers
+
+cst_node!(
+    /// List of type qualifiers
+    TypeQualifier => TYPE_QUALIFIER
+);
+cst_node!(
+    /// Storage qualifier, i.e. `uniform` or `subroutine(...)`
+    StorageQualifier => STORAGE_QUALIFIER
+);
+cst_node!(
+    /// Layout qualifier, i.e. `layout(std140, binding = 0)`
+    LayoutQualifier => LAYOUT_QUALIFIER
+);
+cst_node!(
+    /// Single layout qualifier, i.e. `binding = 0`
+    LayoutQualifierId => LAYOUT_QUALIFIER_ID
+);
+cst_node!(
+    /// Precision qualifier, i.e. `highp`
+    PrecisionQualifier => PRECISION_QUALIFIER
+);
+cst_node!(
+    /// Interpolation qualifier, i.e. `flat`
+    InterpolationQualifier => INTERPOLATION_QUALIFIER
+);
+cst_node!(
+    /// `invariant` qualifier
+    InvariantQualifier => INVARIANT_QUALIFIER
+);
+cst_node!(
+    /// `precise` qualifier
+    PreciseQualifier => PRECISE_QUALIFIER
+);
+cst_node!(
+    /// GL_EXT_spirv_intrinsics qualifier, i.e. `spirv_decorate(...)`
+    SpirvQualifier => SPIRV_QUALIFIER
+);
+cst_node!(
+    /// Argument of a GL_EXT_spirv_intrinsics qualifier or type
+    SpirvArgument => SPIRV_ARGUMENT
+);
+
+cst_enum!(
+    /// Single type qualifier
+    TypeQualifierSpec {
+        /// Storage qualifier
+        Storage(StorageQualifier),
+        /// Layout qualifier
+        Layout(LayoutQualifier),
+        /// Precision qualifier
+        Precision(PrecisionQualifier),
+        /// Interpolation qualifier
+        Interpolation(InterpolationQualifier),
+        /// `invariant` qualifier
+        Invariant(InvariantQualifier),
+        /// `precise` qualifier
+        Precise(PreciseQualifier),
+        /// GL_EXT_spirv_intrinsics qualifier
+        Spirv(SpirvQualifier),
+    }
+);
+
+/// Value of a GL_EXT_spirv_intrinsics argument
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SpirvValue {
+    /// String literal
+    String(SyntaxToken),
+    /// Type
+    Type(TypeSpecifier),
+    /// Constant expression
+    Expr(Expr),
+}
+
+impl TypeQualifier {
+    /// Iterate over the qualifiers of this list
+    pub fn qualifiers(&self) -> impl Iterator<Item = TypeQualifierSpec> {
+        children(&self.0)
+    }
+}
+
+impl StorageQualifier {
+    /// Get the keyword of this qualifier
+    pub fn keyword(&self) -> Option<SyntaxToken> {
+        significant_tokens(&self.0).next()
+    }
+
+    /// Iterate over the types listed by a `subroutine` qualifier
+    pub fn subroutine_types(&self) -> impl Iterator<Item = TypeSpecifier> {
+        children(&self.0)
+    }
+}
+
+impl LayoutQualifier {
+    /// Iterate over the qualifiers in this layout
+    pub fn ids(&self) -> impl Iterator<Item = LayoutQualifierId> {
+        children(&self.0)
+    }
+}
+
+impl LayoutQualifierId {
+    /// Get the name of this qualifier
+    pub fn name(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+
+    /// Get the value of this qualifier
+    pub fn value(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+macro_rules! impl_keyword {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                /// Get the keyword of this qualifier
+                pub fn keyword(&self) -> Option<SyntaxToken> {
+                    significant_tokens(&self.0).next()
+                }
+            }
+        )*
+    };
+}
+
+impl_keyword!(
+    PrecisionQualifier,
+    InterpolationQualifier,
+    InvariantQualifier,
+    PreciseQualifier,
+    SpirvQualifier
+);
+
+impl SpirvQualifier {
+    /// Iterate over the arguments of this qualifier
+    pub fn arguments(&self) -> impl Iterator<Item = SpirvArgument> {
+        children(&self.0)
+    }
+}
+
+impl SpirvArgument {
+    /// Get the name of this argument, if it is named
+    pub fn name(&self) -> Option<SyntaxToken> {
+        let mut tokens = significant_tokens(&self.0);
+        let name = tokens.next()?;
+
+        if name.kind() == SyntaxKind::IDENT_KW
+            && tokens.next().map(|token| token.kind()) == Some(SyntaxKind::EQUAL)
+        {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    /// Return true if this argument is a bracketed list of values, i.e. `extensions = ["..."]`
+    pub fn is_list(&self) -> bool {
+        token(&self.0, SyntaxKind::LBRACKET).is_some()
+    }
+
+    /// Iterate over the values of this argument
+    pub fn values(&self) -> impl Iterator<Item = SpirvValue> {
+        self.0
+            .children_with_tokens()
+            .filter_map(|element| match element {
+                rowan::NodeOrToken::Token(token) => Some(token)
+                    .filter(|token| token.kind() == SyntaxKind::QUOTE_STRING)
+                    .map(SpirvValue::String),
+                rowan::NodeOrToken::Node(node) => TypeSpecifier::cast(node.clone())
+                    .map(SpirvValue::Type)
+                    .or_else(|| Expr::cast(node).map(SpirvValue::Expr)),
+            })
+    }
+}
+
+// Attributes
+
+cst_node!(
+    /// List of attributes, i.e. `[[unroll, dependency_length(4)]]`
+    AttributeList => ATTRIBUTE_LIST
+);
+cst_node!(
+    /// Single attribute, i.e. `dependency_length(4)`
+    Attribute => ATTRIBUTE
+);
+
+impl AttributeList {
+    /// Iterate over the attributes of this list
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute> {
+        children(&self.0)
+    }
+}
+
+impl Attribute {
+    /// Get the name of this attribute
+    pub fn name(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+
+    /// Get the argument of this attribute
+    pub fn argument(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+// Statements
+
+cst_node!(
+    /// Braced list of statements
+    CompoundStatement => COMPOUND_STATEMENT
+);
+cst_node!(
+    /// Expression statement, or empty statement
+    ExprStatement => EXPR_STATEMENT
+);
+cst_node!(
+    /// `if` statement
+    SelectionStatement => SELECTION_STATEMENT
+);
+cst_node!(
+    /// `switch` statement
+    SwitchStatement => SWITCH_STATEMENT
+);
+cst_node!(
+    /// `case` or `default` label
+    CaseLabel => CASE_LABEL
+);
+cst_node!(
+    /// `while` loop
+    WhileStatement => WHILE_STATEMENT
+);
+cst_node!(
+    /// `do`-`while` loop
+    DoWhileStatement => DO_WHILE_STATEMENT
+);
+cst_node!(
+    /// `for` loop
+    ForStatement => FOR_STATEMENT
+);
+cst_node!(
+    /// Condition of a `while` or `for` loop
+    Condition => CONDITION
+);
+cst_node!(
+    /// `break`, `continue`, `return`, `discard` and ray tracing jump statements
+    JumpStatement => JUMP_STATEMENT
+);
+cst_node!(
+    /// Control flow statement with attributes
+    AttributedStatement => ATTRIBUTED_STATEMENT
+);
+
+cst_enum!(
+    /// Statement
+    Statement {
+        /// Braced list of statements
+        Compound(CompoundStatement),
+        /// Expression statement
+        Expr(ExprStatement),
+        /// Declaration
+        Declaration(Declaration),
+        /// `if` statement
+        Selection(SelectionStatement),
+        /// `switch` statement
+        Switch(SwitchStatement),
+        /// `case` or `default` label
+        CaseLabel(CaseLabel),
+        /// `while` loop
+        While(WhileStatement),
+        /// `do`-`while` loop
+        DoWhile(DoWhileStatement),
+        /// `for` loop
+        For(ForStatement),
+        /// Jump statement
+        Jump(JumpStatement),
+        /// Control flow statement with attributes
+        Attributed(AttributedStatement),
+        /// Unexpected tokens
+        Error(ErrorNode),
+    }
+);
+
+impl CompoundStatement {
+    /// Iterate over the statements of this block
+    pub fn statements(&self) -> impl Iterator<Item = Statement> {
+        children(&self.0)
+    }
+}
+
+impl ExprStatement {
+    /// Get the expression of this statement, if it is not empty
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl SelectionStatement {
+    /// Get the condition of this statement
+    pub fn condition(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    /// Get the statement executed when the condition holds
+    pub fn then_branch(&self) -> Option<Statement> {
+        children(&self.0).next()
+    }
+
+    /// Get the statement following `else`
+    pub fn else_branch(&self) -> Option<Statement> {
+        children(&self.0).nth(1)
+    }
+}
+
+impl SwitchStatement {
+    /// Get the expression this statement switches on
+    pub fn head(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    /// Iterate over the statements of the body of this statement
+    pub fn statements(&self) -> impl Iterator<Item = Statement> {
+        children(&self.0)
+    }
+}
+
+impl CaseLabel {
+    /// Get the value of a `case` label, `None` for `default`
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl WhileStatement {
+    /// Get the condition of this loop
+    pub fn condition(&self) -> Option<Condition> {
+        child(&self.0)
+    }
+
+    /// Get the body of this loop
+    pub fn body(&self) -> Option<Statement> {
+        child(&self.0)
+    }
+}
+
+impl DoWhileStatement {
+    /// Get the body of this loop
+    pub fn body(&self) -> Option<Statement> {
+        child(&self.0)
+    }
+
+    /// Get the condition of this loop
+    pub fn condition(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl ForStatement {
+    /// Get the initialization statement of this loop, which is a declaration or an expression
+    /// statement
+    pub fn init(&self) -> Option<Statement> {
+        children(&self.0).next()
+    }
+
+    /// Get the condition of this loop
+    pub fn condition(&self) -> Option<Condition> {
+        child(&self.0)
+    }
+
+    /// Get the `;` separating the condition of this loop from its post-iteration expression
+    pub fn semicolon_token(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::SEMICOLON)
+    }
+
+    /// Get the expression evaluated after each iteration
+    pub fn post_expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    /// Get the body of this loop
+    pub fn body(&self) -> Option<Statement> {
+        children(&self.0).nth(1)
+    }
+}
+
+impl Condition {
+    /// Get the expression of this condition, if it is not a declaration
+    pub fn expr(&self) -> Option<Expr> {
+        if self.ty().is_some() {
+            None
+        } else {
+            child(&self.0)
+        }
+    }
+
+    /// Get the type of the variable declared by this condition
+    pub fn ty(&self) -> Option<FullySpecifiedType> {
+        child(&self.0)
+    }
+
+    /// Get the name of the variable declared by this condition
+    pub fn ident(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+
+    /// Get the initial value of the variable declared by this condition
+    pub fn initializer(&self) -> Option<Initializer> {
+        self.ty().and_then(|_| child(&self.0))
+    }
+}
+
+impl JumpStatement {
+    /// Get the keyword of this statement
+    pub fn keyword(&self) -> Option<SyntaxToken> {
+        significant_tokens(&self.0).next()
+    }
+
+    /// Get the returned value
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl AttributedStatement {
+    /// Get the attributes of this statement
+    pub fn attributes(&self) -> Option<AttributeList> {
+        child(&self.0)
+    }
+
+    /// Get the statement the attributes apply to
+    pub fn statement(&self) -> Option<Statement> {
+        child(&self.0)
+    }
+}
+
+// Expressions
+
+cst_node!(
+    /// Variable reference
+    VariableExpr => VARIABLE_EXPR
+);
+cst_node!(
+    /// Numeric or boolean literal
+    LiteralExpr => LITERAL_EXPR
+);
+cst_node!(
+    /// Parenthesized expression
+    ParenExpr => PAREN_EXPR
+);
+cst_node!(
+    /// Function or constructor call
+    CallExpr => CALL_EXPR
+);
+cst_node!(
+    /// Arguments of a call, with their parentheses
+    ArgList => ARG_LIST
+);
+cst_node!(
+    /// Array or vector indexing, i.e. `a[0]`
+    IndexExpr => INDEX_EXPR
+);
+cst_node!(
+    /// Field or method selection, i.e. `a.xyz`
+    FieldExpr => FIELD_EXPR
+);
+cst_node!(
+    /// Postfix increment or decrement
+    PostfixExpr => POSTFIX_EXPR
+);
+cst_node!(
+    /// Unary prefix operation
+    PrefixExpr => PREFIX_EXPR
+);
+cst_node!(
+    /// Binary operation
+    BinaryExpr => BINARY_EXPR
+);
+cst_node!(
+    /// Ternary conditional, i.e. `a ? b : c`
+    TernaryExpr => TERNARY_EXPR
+);
+cst_node!(
+    /// Assignment, including compound assignments
+    AssignmentExpr => ASSIGNMENT_EXPR
+);
+cst_node!(
+    /// Sequence of expressions, i.e. `a, b`
+    CommaExpr => COMMA_EXPR
+);
+
+cst_enum!(
+    /// Expression
+    Expr {
+        /// Variable reference
+        Variable(VariableExpr),
+        /// Literal
+        Literal(LiteralExpr),
+        /// Parenthesized expression
+        Paren(ParenExpr),
+        /// Function or constructor call
+        Call(CallExpr),
+        /// Indexing
+        Index(IndexExpr),
+        /// Field selection
+        Field(FieldExpr),
+        /// Postfix increment or decrement
+        Postfix(PostfixExpr),
+        /// Unary prefix operation
+        Prefix(PrefixExpr),
+        /// Binary operation
+        Binary(BinaryExpr),
+        /// Ternary conditional
+        Ternary(TernaryExpr),
+        /// Assignment
+        Assignment(AssignmentExpr),
+        /// Sequence of expressions
+        Comma(CommaExpr),
+    }
+);
+
+cst_enum!(
+    /// Function called by a call expression
+    FunIdentifier {
+        /// Constructor
+        TypeSpecifier(TypeSpecifier),
+        /// Function name, or method selection
+        Expr(Expr),
+    }
+);
+
+/// Get the first operator token of an operation node
+fn operator(node: &SyntaxNode) -> Option<SyntaxToken> {
+    significant_tokens(node).next()
+}
+
+impl VariableExpr {
+    /// Get the identifier of this variable
+    pub fn ident(&self) -> Option<SyntaxToken> {
+        significant_tokens(&self.0).next()
+    }
+}
+
+impl LiteralExpr {
+    /// Get the literal token
+    pub fn token(&self) -> Option<SyntaxToken> {
+        significant_tokens(&self.0).next()
+    }
+}
+
+impl ParenExpr {
+    /// Get the parenthesized expression
+    pub fn expr(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+impl CallExpr {
+    /// Get the called function
+    pub fn function(&self) -> Option<FunIdentifier> {
+        child(&self.0)
+    }
+
+    /// Get the arguments of this call
+    pub fn arg_list(&self) -> Option<ArgList> {
+        child(&self.0)
+    }
+}
+
+impl ArgList {
+    /// Iterate over the arguments of this list
+    pub fn args(&self) -> impl Iterator<Item = Expr> {
+        children(&self.0)
+    }
+}
+
+impl IndexExpr {
+    /// Get the indexed expression
+    pub fn base(&self) -> Option<Expr> {
+        children(&self.0).next()
+    }
+
+    /// Get the index
+    pub fn index(&self) -> Option<Expr> {
+        children(&self.0).nth(1)
+    }
+}
+
+impl FieldExpr {
+    /// Get the expression the field is selected from
+    pub fn base(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    /// Get the selected field
+    pub fn field(&self) -> Option<SyntaxToken> {
+        token(&self.0, SyntaxKind::IDENT_KW)
+    }
+}
+
+impl PostfixExpr {
+    /// Get the incremented or decremented expression
+    pub fn operand(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+
+    /// Get the operator of this expression
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        operator(&self.0)
+    }
+}
+
+impl PrefixExpr {
+    /// Get the operator of this expression
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        operator(&self.0)
+    }
+
+    /// Get the operand of this expression
+    pub fn operand(&self) -> Option<Expr> {
+        child(&self.0)
+    }
+}
+
+macro_rules! impl_binary {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                /// Get the left-hand side operand
+                pub fn lhs(&self) -> Option<Expr> {
+                    children(&self.0).next()
+                }
+
+                /// Get the operator of this expression
+                pub fn op_token(&self) -> Option<SyntaxToken> {
+                    operator(&self.0)
+                }
+
+                /// Get the right-hand side operand
+                pub fn rhs(&self) -> Option<Expr> {
+                    children(&self.0).nth(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary!(BinaryExpr, AssignmentExpr, CommaExpr);
+
+impl TernaryExpr {
+    /// Get the condition
+    pub fn condition(&self) -> Option<Expr> {
+        children(&self.0).next()
+    }
+
+    /// Get the value if the condition holds
+    pub fn then_expr(&self) -> Option<Expr> {
+        children(&self.0).nth(1)
+    }
+
+    /// Get the value if the condition doesn't hold
+    pub fn else_expr(&self) -> Option<Expr> {
+        children(&self.0).nth(2)
+    }
+}
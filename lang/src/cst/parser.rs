@@ -0,0 +1,1379 @@
+use std::collections::HashSet;
+
+use glsl_lang_pp::{
+    lexer::{Lexer, TextToken},
+    types::{type_names::TypeNameState, Token, TypeName},
+};
+use lang_util::{SmolStr, TextRange, TextSize};
+use rowan::{Checkpoint, GreenNode, GreenNodeBuilder};
+
+use super::{Error, ErrorKind, SyntaxKind};
+use crate::parse::ParseOptions;
+
+/// Recursive descent parser for the GLSL concrete syntax tree
+///
+/// The input is first split into preprocessor tokens (which are lossless). Whitespace, comments
+/// and preprocessor directives are attached to the tree as they are encountered, while the other
+/// tokens are classified into keywords, type names and literals the same way the default lexer
+/// does, and parsed following the GLSL grammar.
+pub(super) struct CstParser<'i> {
+    builder: GreenNodeBuilder<'static>,
+    errors: Vec<Error>,
+    source: &'i str,
+    opts: ParseOptions,
+    tokens: Vec<TextToken>,
+    /// true for the tokens which are part of a preprocessor directive
+    in_directive: Vec<bool>,
+    /// Indices of the tokens the grammar applies to
+    significant: Vec<usize>,
+    /// Position of the next significant token
+    pos: usize,
+    /// Index of the next token to be added to the tree
+    emitted: usize,
+    /// Kind of the last significant token added to the tree
+    last_bumped: Option<SyntaxKind>,
+    /// Type names declared in the source, by nesting level
+    type_names: Vec<HashSet<SmolStr>>,
+}
+
+/// What a declaration turned out to be, once its type and declarators have been parsed
+enum DeclarationBody {
+    /// Declaration which still expects its terminating `;`
+    Declaration,
+    /// Function prototype, which may be followed by a `;` or a function body
+    Prototype,
+    /// Expression statement starting with a constructor call, including its `;`
+    ExprStatement,
+}
+
+/// Binding power of binary operators, from lowest to highest
+fn binary_precedence(kind: SyntaxKind) -> Option<u8> {
+    use SyntaxKind::*;
+
+    Some(match kind {
+        OR_OP => 1,
+        XOR_OP => 2,
+        AND_OP => 3,
+        BAR => 4,
+        CARET => 5,
+        AMPERSAND => 6,
+        EQ_OP | NE_OP => 7,
+        LANGLE | RANGLE | LE_OP | GE_OP => 8,
+        LEFT_OP | RIGHT_OP => 9,
+        PLUS | DASH => 10,
+        ASTERISK | SLASH | PERCENT => 11,
+        _ => return None,
+    })
+}
+
+fn is_assignment_op(kind: SyntaxKind) -> bool {
+    use SyntaxKind::*;
+
+    matches!(
+        kind,
+        EQUAL
+            | MUL_ASSIGN
+            | DIV_ASSIGN
+            | MOD_ASSIGN
+            | ADD_ASSIGN
+            | SUB_ASSIGN
+            | LEFT_ASSIGN
+            | RIGHT_ASSIGN
+            | AND_ASSIGN
+            | XOR_ASSIGN
+            | OR_ASSIGN
+    )
+}
+
+fn is_prefix_op(kind: SyntaxKind) -> bool {
+    use SyntaxKind::*;
+
+    matches!(kind, PLUS | DASH | BANG | TILDE | INC_OP | DEC_OP)
+}
+
+fn is_storage_qualifier(token: &Token) -> bool {
+    use Token::*;
+
+    matches!(
+        token,
+        CONST
+            | INOUT
+            | IN
+            | OUT
+            | CENTROID
+            | PATCH
+            | SAMPLE
+            | UNIFORM
+            | BUFFER
+            | SHARED
+            | COHERENT
+            | VOLATILE
+            | RESTRICT
+            | READONLY
+            | WRITEONLY
+            | ATTRIBUTE
+            | VARYING
+            | SHADERCALLCOHERENT
+            | RAYPAYLOADEXT
+            | RAYPAYLOADINEXT
+            | HITATTRIBUTEEXT
+            | CALLABLEDATAEXT
+            | CALLABLEDATAINEXT
+            | TASKPAYLOADSHAREDEXT
+            | SUBROUTINE
+    )
+}
+
+fn is_precision_qualifier(token: &Token) -> bool {
+    matches!(token, Token::HIGHP | Token::MEDIUMP | Token::LOWP)
+}
+
+fn is_interpolation_qualifier(token: &Token) -> bool {
+    use Token::*;
+
+    matches!(
+        token,
+        SMOOTH | FLAT | NOPERSPECTIVE | PERPRIMITIVEEXT | PERPRIMITIVENV | PERVIEWNV | TASKNV
+    )
+}
+
+fn is_spirv_qualifier(token: &Token) -> bool {
+    use Token::*;
+
+    matches!(
+        token,
+        SPIRV_INSTRUCTION
+            | SPIRV_EXECUTION_MODE
+            | SPIRV_EXECUTION_MODE_ID
+            | SPIRV_DECORATE
+            | SPIRV_DECORATE_ID
+            | SPIRV_DECORATE_STRING
+            | SPIRV_STORAGE_CLASS
+            | SPIRV_BY_REFERENCE
+            | SPIRV_LITERAL
+    )
+}
+
+fn is_type_qualifier(token: &Token) -> bool {
+    is_storage_qualifier(token)
+        || is_precision_qualifier(token)
+        || is_interpolation_qualifier(token)
+        || is_spirv_qualifier(token)
+        || matches!(token, Token::LAYOUT | Token::INVARIANT | Token::PRECISE)
+}
+
+fn is_type_start(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::TYPE_NAME(_) | Token::STRUCT | Token::SPIRV_TYPE
+    )
+}
+
+fn is_literal(token: &Token) -> bool {
+    use Token::*;
+
+    matches!(
+        token,
+        INT_CONST(_)
+            | UINT_CONST(_)
+            | FLOAT_CONST(_)
+            | DOUBLE_CONST(_)
+            | BOOL_CONST(_)
+            | INT16_CONST(_)
+            | UINT16_CONST(_)
+            | INT64_CONST(_)
+            | UINT64_CONST(_)
+            | FLOAT16_CONST(_)
+    )
+}
+
+impl<'i> CstParser<'i> {
+    pub fn new(source: &'i str, opts: &ParseOptions) -> Self {
+        let tokens: Vec<TextToken> = Lexer::new(source).collect();
+
+        // Preprocessor directives span from a `#` starting a line to the end of the line
+        let mut in_directive = Vec::with_capacity(tokens.len());
+        let mut line_start = true;
+        let mut directive = false;
+        for token in &tokens {
+            let kind = SyntaxKind::from(token.token);
+
+            if kind == SyntaxKind::NEWLINE {
+                line_start = true;
+                directive = false;
+            } else if !kind.is_trivia() {
+                directive |= line_start && kind == SyntaxKind::HASH;
+                line_start = false;
+            }
+
+            in_directive.push(directive && kind != SyntaxKind::NEWLINE);
+        }
+
+        let significant = tokens
+            .iter()
+            .enumerate()
+            .filter(|(i, token)| {
+                !in_directive[*i] && !SyntaxKind::from(token.token).is_whitespace()
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        Self {
+            builder: GreenNodeBuilder::new(),
+            errors: Vec::new(),
+            source,
+            opts: *opts,
+            tokens,
+            in_directive,
+            significant,
+            pos: 0,
+            emitted: 0,
+            last_bumped: None,
+            type_names: vec![HashSet::new()],
+        }
+    }
+
+    pub fn parse(mut self) -> (GreenNode, Vec<Error>) {
+        self.builder.start_node(SyntaxKind::ROOT.into());
+        self.file();
+        self.builder.finish_node();
+
+        (self.builder.finish(), self.errors)
+    }
+}
+
+// Declarations
+impl CstParser<'_> {
+    fn file(&mut self) {
+        while !self.at_end() {
+            let start = self.pos;
+            let errors = self.errors.len();
+
+            if self.at(SyntaxKind::SEMICOLON) {
+                // Empty declaration, which has no AST counterpart
+                self.bump();
+                continue;
+            }
+
+            if self.at(SyntaxKind::RBRACE) {
+                // Unbalanced `}`, which would otherwise stop the recovery of every declaration
+                self.push_error("declaration");
+                self.single_token_node(SyntaxKind::ERROR_NODE);
+                continue;
+            }
+
+            self.external_declaration();
+            self.recover_after(errors);
+            self.ensure_progress(start, "declaration");
+        }
+
+        self.flush();
+    }
+
+    /// Parse a declaration or a function definition, with its optional attributes
+    fn external_declaration(&mut self) {
+        let start = self.checkpoint();
+
+        let has_attributes = self.at(SyntaxKind::LBRACKET);
+        if has_attributes {
+            self.attribute_list();
+        }
+
+        if let DeclarationBody::Prototype = self.declaration_body(false) {
+            if self.at(SyntaxKind::LBRACKET) || self.at(SyntaxKind::LBRACE) {
+                if self.at(SyntaxKind::LBRACKET) {
+                    self.attribute_list();
+                }
+
+                self.compound_statement();
+                self.start_node_at(start, SyntaxKind::FUNCTION_DEFINITION);
+                self.finish_node();
+                return;
+            }
+        }
+
+        if has_attributes {
+            // Only function definitions accept attributes
+            self.error("function body");
+        }
+
+        self.finish_declaration(start);
+    }
+
+    /// Parse a declaration, or an expression statement starting with a constructor call
+    fn declaration_or_expr_statement(&mut self) {
+        let start = self.checkpoint();
+
+        match self.declaration_body(true) {
+            DeclarationBody::ExprStatement => {}
+            DeclarationBody::Declaration | DeclarationBody::Prototype => {
+                self.finish_declaration(start)
+            }
+        }
+    }
+
+    /// Wrap the declaration started at `start` in a DECLARATION node, with its `;`
+    fn finish_declaration(&mut self, start: Checkpoint) {
+        self.start_node_at(start, SyntaxKind::DECLARATION);
+        self.expect(SyntaxKind::SEMICOLON, "`;`");
+        self.finish_node();
+    }
+
+    /// Parse the part of a declaration before its `;`
+    fn declaration_body(&mut self, in_statement: bool) -> DeclarationBody {
+        let start = self.checkpoint();
+
+        match self.peek() {
+            Some(Token::PRECISION) => {
+                self.start_node(SyntaxKind::PRECISION_DECLARATION);
+                self.bump();
+                self.precision_qualifier();
+                self.type_specifier();
+                self.finish_node();
+                return DeclarationBody::Declaration;
+            }
+            Some(Token::INVARIANT) if matches!(self.nth(1), Some(Token::IDENT(_))) => {
+                self.start_node(SyntaxKind::INVARIANT_DECLARATION);
+                self.bump();
+                self.bump();
+                self.finish_node();
+                return DeclarationBody::Declaration;
+            }
+            _ => {}
+        }
+
+        if self.peek().is_some_and(|token| is_type_qualifier(&token)) {
+            let buffer_reference = self.type_qualifier();
+
+            match (self.nth_kind(0), self.nth(1)) {
+                (Some(SyntaxKind::SEMICOLON), _) => {
+                    // Type-only declaration, the qualifier is the declaration itself
+                    return DeclarationBody::Declaration;
+                }
+                (Some(SyntaxKind::IDENT_KW), Some(Token::LBRACE))
+                    if self.peek() != Some(Token::STRUCT) =>
+                {
+                    self.start_node_at(start, SyntaxKind::BLOCK);
+                    let name = self.bump_text();
+                    self.struct_body();
+                    if self.at_ident() {
+                        self.arrayed_identifier();
+                    }
+                    self.finish_node();
+
+                    if buffer_reference {
+                        self.add_type_name(name);
+                    }
+
+                    return DeclarationBody::Declaration;
+                }
+                (Some(SyntaxKind::IDENT_KW), Some(Token::COMMA | Token::SEMICOLON))
+                    if self.at_ident() =>
+                {
+                    self.start_node_at(start, SyntaxKind::QUALIFIER_REDECLARATION);
+                    let mut names = vec![self.bump_text()];
+                    while self.eat(SyntaxKind::COMMA) {
+                        if self.at_ident() {
+                            names.push(self.bump_text());
+                        } else {
+                            self.error("identifier");
+                            break;
+                        }
+                    }
+                    self.finish_node();
+
+                    if buffer_reference {
+                        for name in names {
+                            self.add_type_name(name);
+                        }
+                    }
+
+                    return DeclarationBody::Declaration;
+                }
+                _ => {
+                    self.type_specifier();
+                }
+            }
+        } else {
+            self.type_specifier();
+
+            if in_statement && self.at(SyntaxKind::LPAREN) {
+                // Constructor call, i.e. `vec4(1.);`
+                self.expr(Some(start));
+                self.start_node_at(start, SyntaxKind::EXPR_STATEMENT);
+                self.expect(SyntaxKind::SEMICOLON, "`;`");
+                self.finish_node();
+                return DeclarationBody::ExprStatement;
+            }
+        }
+
+        self.start_node_at(start, SyntaxKind::FULLY_SPECIFIED_TYPE);
+        self.finish_node();
+
+        if self.at_ident() && self.nth_kind(1) == Some(SyntaxKind::LPAREN) {
+            self.start_node_at(start, SyntaxKind::FUNCTION_PROTOTYPE);
+            self.bump();
+            self.parameter_list();
+            self.finish_node();
+            return DeclarationBody::Prototype;
+        }
+
+        self.start_node_at(start, SyntaxKind::SINGLE_DECLARATION);
+        if self.at_ident() {
+            self.arrayed_identifier();
+            if self.eat(SyntaxKind::EQUAL) {
+                self.initializer();
+            }
+        }
+        self.finish_node();
+
+        self.start_node_at(start, SyntaxKind::INIT_DECLARATOR_LIST);
+        while self.eat(SyntaxKind::COMMA) {
+            self.start_node(SyntaxKind::SINGLE_DECLARATION_NO_TYPE);
+            self.arrayed_identifier();
+            if self.eat(SyntaxKind::EQUAL) {
+                self.initializer();
+            }
+            self.finish_node();
+        }
+        self.finish_node();
+
+        DeclarationBody::Declaration
+    }
+
+    fn parameter_list(&mut self) {
+        self.expect(SyntaxKind::LPAREN, "`(`");
+
+        if !self.at(SyntaxKind::RPAREN) {
+            loop {
+                self.start_node(SyntaxKind::FUNCTION_PARAMETER);
+                if self.peek().is_some_and(|token| is_type_qualifier(&token)) {
+                    self.type_qualifier();
+                }
+                self.type_specifier();
+                if self.at_ident() {
+                    self.arrayed_identifier();
+                }
+                self.finish_node();
+
+                if !self.eat(SyntaxKind::COMMA) {
+                    break;
+                }
+            }
+        }
+
+        self.expect(SyntaxKind::RPAREN, "`)`");
+    }
+
+    fn arrayed_identifier(&mut self) {
+        self.start_node(SyntaxKind::ARRAYED_IDENTIFIER);
+        self.expect_ident();
+        if self.at(SyntaxKind::LBRACKET) {
+            self.array_specifier();
+        }
+        self.finish_node();
+    }
+
+    fn initializer(&mut self) {
+        if !self.at(SyntaxKind::LBRACE) {
+            self.assignment_expr(None);
+            return;
+        }
+
+        self.start_node(SyntaxKind::INITIALIZER_LIST);
+        self.bump();
+        while !self.at(SyntaxKind::RBRACE) && !self.at_end() {
+            self.initializer();
+
+            if !self.eat(SyntaxKind::COMMA) {
+                break;
+            }
+        }
+        self.expect(SyntaxKind::RBRACE, "`}`");
+        self.finish_node();
+    }
+
+    /// Parse the braced field list of a struct or a block
+    fn struct_body(&mut self) {
+        self.expect(SyntaxKind::LBRACE, "`{`");
+
+        while !self.at(SyntaxKind::RBRACE) && !self.at_end() {
+            let start = self.pos;
+            let errors = self.errors.len();
+
+            self.start_node(SyntaxKind::STRUCT_FIELD);
+            if self.peek().is_some_and(|token| is_type_qualifier(&token)) {
+                self.type_qualifier();
+            }
+            self.type_specifier();
+            if self.at_ident() {
+                self.arrayed_identifier();
+                while self.eat(SyntaxKind::COMMA) {
+                    self.arrayed_identifier();
+                }
+            }
+            self.expect(SyntaxKind::SEMICOLON, "`;`");
+            self.finish_node();
+
+            self.recover_after(errors);
+            self.ensure_progress(start, "field declaration");
+        }
+
+        self.expect(SyntaxKind::RBRACE, "`}`");
+    }
+}
+
+// Types and qualifiers
+impl CstParser<'_> {
+    fn type_specifier(&mut self) {
+        self.start_node(SyntaxKind::TYPE_SPECIFIER);
+        self.type_specifier_non_array();
+        if self.at(SyntaxKind::LBRACKET) {
+            self.array_specifier();
+        }
+        self.finish_node();
+    }
+
+    fn type_specifier_non_array(&mut self) {
+        match self.peek() {
+            Some(Token::STRUCT) => self.struct_specifier(),
+            Some(Token::SPIRV_TYPE) => {
+                self.start_node(SyntaxKind::SPIRV_TYPE);
+                self.bump();
+                self.spirv_arguments();
+                self.finish_node();
+            }
+            Some(Token::TYPE_NAME(_)) => self.bump(),
+            _ => self.error_bump("type"),
+        }
+    }
+
+    fn struct_specifier(&mut self) {
+        self.start_node(SyntaxKind::STRUCT_SPECIFIER);
+        self.bump();
+        let name = if self.at_ident() {
+            Some(self.bump_text())
+        } else {
+            None
+        };
+        self.struct_body();
+        self.finish_node();
+
+        if let Some(name) = name {
+            self.add_type_name(name);
+        }
+    }
+
+    fn array_specifier(&mut self) {
+        self.start_node(SyntaxKind::ARRAY_SPECIFIER);
+        while self.at(SyntaxKind::LBRACKET) {
+            self.start_node(SyntaxKind::ARRAY_DIMENSION);
+            self.bump();
+            if !self.at(SyntaxKind::RBRACKET) {
+                self.conditional_expr(None);
+            }
+            self.expect(SyntaxKind::RBRACKET, "`]`");
+            self.finish_node();
+        }
+        self.finish_node();
+    }
+
+    /// Parse a list of type qualifiers, returning true if it includes the `buffer_reference`
+    /// layout qualifier
+    fn type_qualifier(&mut self) -> bool {
+        let mut buffer_reference = false;
+
+        self.start_node(SyntaxKind::TYPE_QUALIFIER);
+        while let Some(token) = self.peek().filter(is_type_qualifier) {
+            if is_storage_qualifier(&token) {
+                self.start_node(SyntaxKind::STORAGE_QUALIFIER);
+                self.bump();
+                if token == Token::SUBROUTINE && self.eat(SyntaxKind::LPAREN) {
+                    if !self.at(SyntaxKind::RPAREN) {
+                        loop {
+                            self.type_specifier();
+                            if !self.eat(SyntaxKind::COMMA) {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect(SyntaxKind::RPAREN, "`)`");
+                }
+                self.finish_node();
+            } else if token == Token::LAYOUT {
+                buffer_reference |= self.layout_qualifier();
+            } else if is_precision_qualifier(&token) {
+                self.precision_qualifier();
+            } else if is_interpolation_qualifier(&token) {
+                self.single_token_node(SyntaxKind::INTERPOLATION_QUALIFIER);
+            } else if token == Token::INVARIANT {
+                self.single_token_node(SyntaxKind::INVARIANT_QUALIFIER);
+            } else if token == Token::PRECISE {
+                self.single_token_node(SyntaxKind::PRECISE_QUALIFIER);
+            } else {
+                self.start_node(SyntaxKind::SPIRV_QUALIFIER);
+                self.bump();
+                if !matches!(token, Token::SPIRV_BY_REFERENCE | Token::SPIRV_LITERAL) {
+                    self.spirv_arguments();
+                }
+                self.finish_node();
+            }
+        }
+        self.finish_node();
+
+        buffer_reference
+    }
+
+    /// Parse a layout qualifier, returning true if it includes `buffer_reference`
+    fn layout_qualifier(&mut self) -> bool {
+        let mut buffer_reference = false;
+
+        self.start_node(SyntaxKind::LAYOUT_QUALIFIER);
+        self.bump();
+        self.expect(SyntaxKind::LPAREN, "`(`");
+        if !self.at(SyntaxKind::RPAREN) {
+            loop {
+                self.start_node(SyntaxKind::LAYOUT_QUALIFIER_ID);
+                if self.peek() == Some(Token::SHARED) {
+                    self.bump();
+                } else if self.at_ident() {
+                    buffer_reference |= self.bump_text() == "buffer_reference";
+                    if self.eat(SyntaxKind::EQUAL) {
+                        self.conditional_expr(None);
+                    }
+                } else {
+                    self.error_bump("layout qualifier");
+                }
+                self.finish_node();
+
+                if !self.eat(SyntaxKind::COMMA) {
+                    break;
+                }
+            }
+        }
+        self.expect(SyntaxKind::RPAREN, "`)`");
+        self.finish_node();
+
+        buffer_reference
+    }
+
+    fn precision_qualifier(&mut self) {
+        if self
+            .peek()
+            .is_some_and(|token| is_precision_qualifier(&token))
+        {
+            self.single_token_node(SyntaxKind::PRECISION_QUALIFIER);
+        } else {
+            self.error_bump("precision qualifier");
+        }
+    }
+
+    /// Parse the parenthesized arguments of a GL_EXT_spirv_intrinsics qualifier or type
+    fn spirv_arguments(&mut self) {
+        self.expect(SyntaxKind::LPAREN, "`(`");
+        if !self.at(SyntaxKind::RPAREN) {
+            loop {
+                self.start_node(SyntaxKind::SPIRV_ARGUMENT);
+                if self.at_ident() && self.nth_kind(1) == Some(SyntaxKind::EQUAL) {
+                    self.bump();
+                    self.bump();
+
+                    if self.eat(SyntaxKind::LBRACKET) {
+                        if !self.at(SyntaxKind::RBRACKET) {
+                            loop {
+                                self.spirv_value();
+                                if !self.eat(SyntaxKind::COMMA) {
+                                    break;
+                                }
+                            }
+                        }
+                        self.expect(SyntaxKind::RBRACKET, "`]`");
+                    } else {
+                        self.spirv_value();
+                    }
+                } else {
+                    self.spirv_value();
+                }
+                self.finish_node();
+
+                if !self.eat(SyntaxKind::COMMA) {
+                    break;
+                }
+            }
+        }
+        self.expect(SyntaxKind::RPAREN, "`)`");
+    }
+
+    fn spirv_value(&mut self) {
+        match self.peek() {
+            Some(Token::STRING_CONST(_)) => self.bump(),
+            Some(token) if is_type_start(&token) => {
+                let start = self.checkpoint();
+                self.start_node(SyntaxKind::TYPE_SPECIFIER);
+                self.type_specifier_non_array();
+                self.finish_node();
+
+                if self.at(SyntaxKind::LPAREN) {
+                    self.conditional_expr(Some(start));
+                }
+            }
+            _ => self.conditional_expr(None),
+        }
+    }
+
+    fn attribute_list(&mut self) {
+        self.start_node(SyntaxKind::ATTRIBUTE_LIST);
+        self.bump();
+        self.expect(SyntaxKind::LBRACKET, "`[`");
+        loop {
+            self.start_node(SyntaxKind::ATTRIBUTE);
+            self.expect_ident();
+            if self.eat(SyntaxKind::LPAREN) {
+                self.conditional_expr(None);
+                self.expect(SyntaxKind::RPAREN, "`)`");
+            }
+            self.finish_node();
+
+            if !self.eat(SyntaxKind::COMMA) {
+                break;
+            }
+        }
+        self.expect(SyntaxKind::RBRACKET, "`]`");
+        self.expect(SyntaxKind::RBRACKET, "`]`");
+        self.finish_node();
+    }
+}
+
+// Statements
+impl CstParser<'_> {
+    fn statement(&mut self) {
+        let Some(token) = self.peek() else {
+            self.error("statement");
+            return;
+        };
+
+        match token {
+            Token::LBRACE => self.compound_statement(),
+            Token::LBRACKET => {
+                self.start_node(SyntaxKind::ATTRIBUTED_STATEMENT);
+                self.attribute_list();
+                match self.peek() {
+                    Some(Token::IF | Token::SWITCH | Token::WHILE | Token::DO | Token::FOR) => {
+                        self.statement()
+                    }
+                    _ => self.error("control flow statement"),
+                }
+                self.finish_node();
+            }
+            Token::SEMICOLON => self.single_token_node(SyntaxKind::EXPR_STATEMENT),
+            Token::IF => self.selection_statement(),
+            Token::SWITCH => self.switch_statement(),
+            Token::WHILE => self.while_statement(),
+            Token::DO => self.do_while_statement(),
+            Token::FOR => self.for_statement(),
+            Token::CASE => {
+                self.start_node(SyntaxKind::CASE_LABEL);
+                self.bump();
+                self.expr(None);
+                self.expect(SyntaxKind::COLON, "`:`");
+                self.finish_node();
+            }
+            Token::DEFAULT => {
+                self.start_node(SyntaxKind::CASE_LABEL);
+                self.bump();
+                self.expect(SyntaxKind::COLON, "`:`");
+                self.finish_node();
+            }
+            Token::CONTINUE
+            | Token::BREAK
+            | Token::DISCARD
+            | Token::IGNOREINTERSECTIONEXT
+            | Token::TERMINATERAYEXT => {
+                self.start_node(SyntaxKind::JUMP_STATEMENT);
+                self.bump();
+                self.expect(SyntaxKind::SEMICOLON, "`;`");
+                self.finish_node();
+            }
+            Token::RETURN => {
+                self.start_node(SyntaxKind::JUMP_STATEMENT);
+                self.bump();
+                if !self.at(SyntaxKind::SEMICOLON) {
+                    self.expr(None);
+                }
+                self.expect(SyntaxKind::SEMICOLON, "`;`");
+                self.finish_node();
+            }
+            token if self.at_declaration(&token) => self.declaration_or_expr_statement(),
+            _ => self.expr_statement(),
+        }
+    }
+
+    fn at_declaration(&self, token: &Token) -> bool {
+        is_type_start(token) || is_type_qualifier(token) || *token == Token::PRECISION
+    }
+
+    fn expr_statement(&mut self) {
+        self.start_node(SyntaxKind::EXPR_STATEMENT);
+        if !self.at(SyntaxKind::SEMICOLON) {
+            self.expr(None);
+        }
+        self.expect(SyntaxKind::SEMICOLON, "`;`");
+        self.finish_node();
+    }
+
+    fn compound_statement(&mut self) {
+        self.start_node(SyntaxKind::COMPOUND_STATEMENT);
+        self.expect(SyntaxKind::LBRACE, "`{`");
+        self.statement_list();
+        self.expect(SyntaxKind::RBRACE, "`}`");
+        self.finish_node();
+    }
+
+    /// Parse statements until the closing `}` of the current block
+    fn statement_list(&mut self) {
+        while !self.at(SyntaxKind::RBRACE) && !self.at_end() {
+            let start = self.pos;
+            let errors = self.errors.len();
+
+            self.statement();
+            self.recover_after(errors);
+            self.ensure_progress(start, "statement");
+        }
+    }
+
+    fn selection_statement(&mut self) {
+        self.start_node(SyntaxKind::SELECTION_STATEMENT);
+        self.bump();
+        self.expect(SyntaxKind::LPAREN, "`(`");
+        self.expr(None);
+        self.expect(SyntaxKind::RPAREN, "`)`");
+        self.statement();
+        if self.peek() == Some(Token::ELSE) {
+            self.bump();
+            self.statement();
+        }
+        self.finish_node();
+    }
+
+    fn switch_statement(&mut self) {
+        self.start_node(SyntaxKind::SWITCH_STATEMENT);
+        self.bump();
+        self.expect(SyntaxKind::LPAREN, "`(`");
+        self.expr(None);
+        self.expect(SyntaxKind::RPAREN, "`)`");
+        self.expect(SyntaxKind::LBRACE, "`{`");
+        self.statement_list();
+        self.expect(SyntaxKind::RBRACE, "`}`");
+        self.finish_node();
+    }
+
+    fn while_statement(&mut self) {
+        self.start_node(SyntaxKind::WHILE_STATEMENT);
+        self.bump();
+        self.expect(SyntaxKind::LPAREN, "`(`");
+        self.condition();
+        self.expect(SyntaxKind::RPAREN, "`)`");
+        self.statement();
+        self.finish_node();
+    }
+
+    fn do_while_statement(&mut self) {
+        self.start_node(SyntaxKind::DO_WHILE_STATEMENT);
+        self.bump();
+        self.statement();
+        if self.peek() == Some(Token::WHILE) {
+            self.bump();
+        } else {
+            self.error("`while`");
+        }
+        self.expect(SyntaxKind::LPAREN, "`(`");
+        self.expr(None);
+        self.expect(SyntaxKind::RPAREN, "`)`");
+        self.expect(SyntaxKind::SEMICOLON, "`;`");
+        self.finish_node();
+    }
+
+    fn for_statement(&mut self) {
+        self.start_node(SyntaxKind::FOR_STATEMENT);
+        self.bump();
+        self.expect(SyntaxKind::LPAREN, "`(`");
+
+        match self.peek() {
+            Some(token) if self.at_declaration(&token) => self.declaration_or_expr_statement(),
+            _ => self.expr_statement(),
+        }
+
+        if !self.at(SyntaxKind::SEMICOLON) {
+            self.condition();
+        }
+        self.expect(SyntaxKind::SEMICOLON, "`;`");
+
+        if !self.at(SyntaxKind::RPAREN) {
+            self.expr(None);
+        }
+        self.expect(SyntaxKind::RPAREN, "`)`");
+        self.statement();
+        self.finish_node();
+    }
+
+    /// Parse a loop condition, which is either an expression or an initialized declaration
+    fn condition(&mut self) {
+        self.start_node(SyntaxKind::CONDITION);
+
+        match self.peek() {
+            Some(token) if is_type_qualifier(&token) || is_type_start(&token) => {
+                let start = self.checkpoint();
+                if is_type_qualifier(&token) {
+                    self.type_qualifier();
+                }
+                self.type_specifier();
+
+                if !is_type_qualifier(&token) && self.at(SyntaxKind::LPAREN) {
+                    self.expr(Some(start));
+                } else {
+                    self.start_node_at(start, SyntaxKind::FULLY_SPECIFIED_TYPE);
+                    self.finish_node();
+                    self.expect_ident();
+                    self.expect(SyntaxKind::EQUAL, "`=`");
+                    self.initializer();
+                }
+            }
+            _ => self.expr(None),
+        }
+
+        self.finish_node();
+    }
+}
+
+// Expressions
+impl CstParser<'_> {
+    /// Parse an expression, including comma-separated sequences
+    ///
+    /// If `start` is set, the first operand has already been parsed from this checkpoint.
+    fn expr(&mut self, start: Option<Checkpoint>) {
+        let checkpoint = start.unwrap_or_else(|| self.checkpoint());
+        self.assignment_expr(start);
+
+        while self.at(SyntaxKind::COMMA) {
+            self.start_node_at(checkpoint, SyntaxKind::COMMA_EXPR);
+            self.bump();
+            self.assignment_expr(None);
+            self.finish_node();
+        }
+    }
+
+    fn assignment_expr(&mut self, start: Option<Checkpoint>) {
+        let checkpoint = start.unwrap_or_else(|| self.checkpoint());
+        self.conditional_expr(start);
+
+        if self.nth_kind(0).is_some_and(is_assignment_op) {
+            self.start_node_at(checkpoint, SyntaxKind::ASSIGNMENT_EXPR);
+            self.bump();
+            self.assignment_expr(None);
+            self.finish_node();
+        }
+    }
+
+    fn conditional_expr(&mut self, start: Option<Checkpoint>) {
+        let checkpoint = start.unwrap_or_else(|| self.checkpoint());
+        self.binary_expr(0, start);
+
+        if self.at(SyntaxKind::QUESTION) {
+            self.start_node_at(checkpoint, SyntaxKind::TERNARY_EXPR);
+            self.bump();
+            self.expr(None);
+            self.expect(SyntaxKind::COLON, "`:`");
+            self.assignment_expr(None);
+            self.finish_node();
+        }
+    }
+
+    fn binary_expr(&mut self, min_precedence: u8, start: Option<Checkpoint>) {
+        let checkpoint = start.unwrap_or_else(|| self.checkpoint());
+        self.unary_expr(start);
+
+        while let Some(precedence) = self.nth_kind(0).and_then(binary_precedence) {
+            if precedence < min_precedence {
+                break;
+            }
+
+            self.start_node_at(checkpoint, SyntaxKind::BINARY_EXPR);
+            self.bump();
+            self.binary_expr(precedence + 1, None);
+            self.finish_node();
+        }
+    }
+
+    fn unary_expr(&mut self, start: Option<Checkpoint>) {
+        if start.is_none() && self.nth_kind(0).is_some_and(is_prefix_op) {
+            self.start_node(SyntaxKind::PREFIX_EXPR);
+            self.bump();
+            self.unary_expr(None);
+            self.finish_node();
+        } else {
+            self.postfix_expr(start);
+        }
+    }
+
+    fn postfix_expr(&mut self, start: Option<Checkpoint>) {
+        let checkpoint = match start {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let checkpoint = self.checkpoint();
+                self.primary_expr();
+                checkpoint
+            }
+        };
+
+        loop {
+            match self.nth_kind(0) {
+                Some(SyntaxKind::LBRACKET) => {
+                    self.start_node_at(checkpoint, SyntaxKind::INDEX_EXPR);
+                    self.bump();
+                    self.expr(None);
+                    self.expect(SyntaxKind::RBRACKET, "`]`");
+                    self.finish_node();
+                }
+                Some(SyntaxKind::LPAREN) => {
+                    self.start_node_at(checkpoint, SyntaxKind::CALL_EXPR);
+                    self.arg_list();
+                    self.finish_node();
+                }
+                Some(SyntaxKind::PERIOD) => {
+                    self.start_node_at(checkpoint, SyntaxKind::FIELD_EXPR);
+                    self.bump();
+                    self.expect_ident();
+                    self.finish_node();
+                }
+                Some(SyntaxKind::INC_OP | SyntaxKind::DEC_OP) => {
+                    self.start_node_at(checkpoint, SyntaxKind::POSTFIX_EXPR);
+                    self.bump();
+                    self.finish_node();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn primary_expr(&mut self) {
+        match self.peek() {
+            Some(Token::IDENT(_)) => self.single_token_node(SyntaxKind::VARIABLE_EXPR),
+            Some(token) if is_literal(&token) => self.single_token_node(SyntaxKind::LITERAL_EXPR),
+            Some(Token::LPAREN) => {
+                self.start_node(SyntaxKind::PAREN_EXPR);
+                self.bump();
+                self.expr(None);
+                self.expect(SyntaxKind::RPAREN, "`)`");
+                self.finish_node();
+            }
+            Some(token) if is_type_start(&token) => {
+                // Constructor, the argument list is parsed as a postfix operator
+                self.type_specifier();
+                if !self.at(SyntaxKind::LPAREN) {
+                    self.error("`(`");
+                }
+            }
+            _ => self.error_bump("expression"),
+        }
+    }
+
+    fn arg_list(&mut self) {
+        self.start_node(SyntaxKind::ARG_LIST);
+        self.bump();
+
+        if self.peek() == Some(Token::TYPE_NAME(TypeName::VOID))
+            && self.nth_kind(1) == Some(SyntaxKind::RPAREN)
+        {
+            self.bump();
+        } else if !self.at(SyntaxKind::RPAREN) {
+            loop {
+                self.assignment_expr(None);
+                if !self.eat(SyntaxKind::COMMA) {
+                    break;
+                }
+            }
+        }
+
+        self.expect(SyntaxKind::RPAREN, "`)`");
+        self.finish_node();
+    }
+}
+
+// Token handling
+impl CstParser<'_> {
+    fn at_end(&self) -> bool {
+        self.pos >= self.significant.len()
+    }
+
+    /// Get the raw kind of the n-th next significant token
+    fn nth_kind(&self, n: usize) -> Option<SyntaxKind> {
+        self.significant
+            .get(self.pos + n)
+            .map(|index| self.tokens[*index].token.into())
+    }
+
+    /// Classify the n-th next significant token as a language token
+    fn nth(&self, n: usize) -> Option<Token> {
+        let token = *self.significant.get(self.pos + n)?;
+        let token = self.tokens[token];
+
+        Some(
+            Token::from_token(
+                token,
+                self.source,
+                self.opts.default_version,
+                self.opts.target_vulkan,
+                |name| {
+                    // Extensions are not tracked, so type names introduced by extensions are
+                    // always recognized, like the default lexer does
+                    if self.is_type_name(name.as_ref()) || TypeName::parse_extension(name).is_some()
+                    {
+                        TypeNameState::Type
+                    } else {
+                        TypeNameState::Ident
+                    }
+                },
+            )
+            .0,
+        )
+    }
+
+    fn peek(&self) -> Option<Token> {
+        self.nth(0)
+    }
+
+    fn at(&self, kind: SyntaxKind) -> bool {
+        self.nth_kind(0) == Some(kind)
+    }
+
+    fn at_ident(&self) -> bool {
+        matches!(self.peek(), Some(Token::IDENT(_)))
+    }
+
+    fn eat(&mut self, kind: SyntaxKind) -> bool {
+        if self.at(kind) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: SyntaxKind, expected: &'static str) -> bool {
+        if self.eat(kind) {
+            true
+        } else {
+            self.error(expected);
+            false
+        }
+    }
+
+    fn expect_ident(&mut self) {
+        if self.at_ident() {
+            self.bump();
+        } else {
+            self.error_bump("identifier");
+        }
+    }
+
+    /// Add the pending trivia and directives, and the next significant token to the tree
+    fn bump(&mut self) {
+        self.flush();
+
+        let index = self.significant[self.pos];
+        let token = self.tokens[index];
+        let kind = SyntaxKind::from(token.token);
+        self.builder.token(kind.into(), token.raw(self.source));
+
+        self.pos += 1;
+        self.emitted = index + 1;
+        self.last_bumped = Some(kind);
+
+        // Type names are scoped to the braces they are declared in, like the default lexer does
+        if kind == SyntaxKind::LBRACE {
+            self.type_names.push(HashSet::new());
+        } else if kind == SyntaxKind::RBRACE && self.type_names.len() > 1 {
+            self.type_names.pop();
+        }
+    }
+
+    /// Same as [Self::bump], returning the unescaped text of the token
+    fn bump_text(&mut self) -> SmolStr {
+        let token = self.tokens[self.significant[self.pos]];
+        let text = token.text(self.source).to_string().into();
+        self.bump();
+        text
+    }
+
+    /// Add the trivia and directives before the next significant token to the tree
+    fn flush(&mut self) {
+        let end = self
+            .significant
+            .get(self.pos)
+            .copied()
+            .unwrap_or(self.tokens.len());
+
+        while self.emitted < end {
+            if self.in_directive[self.emitted] {
+                self.builder.start_node(SyntaxKind::PP_DIRECTIVE.into());
+                while self.emitted < end && self.in_directive[self.emitted] {
+                    self.emit(self.emitted);
+                }
+                self.builder.finish_node();
+            } else {
+                self.emit(self.emitted);
+            }
+        }
+    }
+
+    fn emit(&mut self, index: usize) {
+        let token = self.tokens[index];
+        self.builder
+            .token(SyntaxKind::from(token.token).into(), token.raw(self.source));
+        self.emitted = index + 1;
+    }
+
+    fn single_token_node(&mut self, kind: SyntaxKind) {
+        self.start_node(kind);
+        self.bump();
+        self.finish_node();
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        // Leading trivia is left to the parent node
+        self.flush();
+        self.builder.checkpoint()
+    }
+
+    fn start_node(&mut self, kind: SyntaxKind) {
+        self.flush();
+        self.builder.start_node(kind.into());
+    }
+
+    fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        self.builder.start_node_at(checkpoint, kind.into());
+    }
+
+    fn finish_node(&mut self) {
+        self.builder.finish_node();
+    }
+
+    fn is_type_name(&self, name: &str) -> bool {
+        self.type_names.iter().any(|level| level.contains(name))
+    }
+
+    fn add_type_name(&mut self, name: SmolStr) {
+        // unwrap: there is always at least the global nesting level
+        self.type_names.last_mut().unwrap().insert(name);
+    }
+}
+
+// Error handling
+impl CstParser<'_> {
+    /// Record an error at the next significant token, and mark its location with an empty
+    /// ERROR_NODE so erroneous trees can be told apart without the error list
+    fn error(&mut self, expected: &'static str) {
+        self.push_error(expected);
+        self.start_node(SyntaxKind::ERROR_NODE);
+        self.finish_node();
+    }
+
+    fn push_error(&mut self, expected: &'static str) {
+        let (error_kind, range) = match self.significant.get(self.pos) {
+            Some(index) => {
+                let token = self.tokens[*index];
+                (
+                    ErrorKind::UnexpectedToken {
+                        found: token.raw(self.source).into(),
+                        expected,
+                    },
+                    token.range,
+                )
+            }
+            None => (
+                ErrorKind::UnexpectedEndOfInput { expected },
+                TextRange::empty(TextSize::of(self.source)),
+            ),
+        };
+
+        // Only report the first error at a given position, the following ones are a consequence
+        // of it
+        if self.errors.last().is_some_and(|error| error.pos() == range) {
+            return;
+        }
+
+        self.errors.push(
+            Error::builder()
+                .pos(range)
+                .resolve(&self.source)
+                .finish(error_kind),
+        );
+    }
+
+    /// Record an error, and skip the next token unless it can end the current construct
+    fn error_bump(&mut self, expected: &'static str) {
+        if matches!(
+            self.nth_kind(0),
+            None | Some(
+                SyntaxKind::SEMICOLON
+                    | SyntaxKind::COMMA
+                    | SyntaxKind::LBRACE
+                    | SyntaxKind::RBRACE
+                    | SyntaxKind::RPAREN
+                    | SyntaxKind::RBRACKET
+            )
+        ) {
+            self.error(expected);
+        } else {
+            self.push_error(expected);
+            self.single_token_node(SyntaxKind::ERROR_NODE);
+        }
+    }
+
+    /// If errors were found since `errors`, and the construct that was just parsed didn't end
+    /// cleanly, skip tokens until the end of the current statement or block
+    fn recover_after(&mut self, errors: usize) {
+        if self.errors.len() == errors
+            || matches!(
+                self.last_bumped,
+                Some(SyntaxKind::SEMICOLON | SyntaxKind::RBRACE)
+            )
+            || self.at_end()
+            || self.at(SyntaxKind::RBRACE)
+        {
+            return;
+        }
+
+        self.start_node(SyntaxKind::ERROR_NODE);
+        let mut depth = 0usize;
+        while let Some(kind) = self.nth_kind(0) {
+            match kind {
+                SyntaxKind::SEMICOLON if depth == 0 => {
+                    self.bump();
+                    break;
+                }
+                SyntaxKind::LBRACE => depth += 1,
+                SyntaxKind::RBRACE if depth == 0 => break,
+                SyntaxKind::RBRACE => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.bump();
+                        break;
+                    }
+                }
+                _ => {}
+            }
+
+            self.bump();
+        }
+        self.finish_node();
+    }
+
+    /// Skip the next token if nothing was parsed since `start`
+    fn ensure_progress(&mut self, start: usize, expected: &'static str) {
+        if self.pos == start && !self.at_end() {
+            self.push_error(expected);
+            self.single_token_node(SyntaxKind::ERROR_NODE);
+        }
+    }
+}
@@ -0,0 +1,429 @@
+use glsl_lang_pp::lexer;
+
+/// Kind of a node or token in the GLSL concrete syntax tree
+///
+/// Token kinds are the same as the ones produced by the [preprocessor
+/// lexer](glsl_lang_pp::lexer::Token), so every character of the input (including whitespace,
+/// comments and line continuations) ends up in a token of the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[repr(u16)]
+pub enum SyntaxKind {
+    /// Identifier or keyword
+    IDENT_KW = 1,
+    /// defined preprocessor keyword
+    DEFINED = 2,
+    /// Digit sequence
+    DIGITS = 3,
+    // Single-char tokens
+    /// .
+    PERIOD = 4,
+    /// +
+    PLUS = 5,
+    /// -
+    DASH = 6,
+    /// /
+    SLASH = 7,
+    /// *
+    ASTERISK = 8,
+    /// %
+    PERCENT = 9,
+    /// <
+    LANGLE = 10,
+    /// >
+    RANGLE = 11,
+    /// [
+    LBRACKET = 12,
+    /// ]
+    RBRACKET = 13,
+    /// (
+    LPAREN = 14,
+    /// )
+    RPAREN = 15,
+    /// {
+    LBRACE = 16,
+    /// }
+    RBRACE = 17,
+    /// ^
+    CARET = 18,
+    /// |
+    BAR = 19,
+    /// &
+    AMPERSAND = 20,
+    /// ~
+    TILDE = 21,
+    /// =
+    EQUAL = 22,
+    /// !
+    BANG = 23,
+    /// :
+    COLON = 24,
+    /// ;
+    SEMICOLON = 25,
+    /// ,
+    COMMA = 26,
+    /// ?
+    QUESTION = 27,
+    /// #
+    HASH = 28,
+    // Other
+    /// "string"
+    QUOTE_STRING = 29,
+    /// <string>
+    ANGLE_STRING = 30,
+    /// \
+    BACKSLASH = 31,
+    /// Whitespace
+    WS = 32,
+    /// Newline
+    NEWLINE = 33,
+    /// Comment (single-line or multi-line)
+    COMMENT = 34,
+    /// Line continuation (required for tracking exact offsets)
+    LINECONT = 35,
+    /// Invalid token
+    ERROR = 36,
+    // Multi-char tokens
+    /// <<
+    LEFT_OP = 37,
+    /// >>
+    RIGHT_OP = 38,
+    /// ++
+    INC_OP = 39,
+    /// --
+    DEC_OP = 40,
+    /// <=
+    LE_OP = 41,
+    /// >=
+    GE_OP = 42,
+    /// ==
+    EQ_OP = 43,
+    /// !=
+    NE_OP = 44,
+    /// &&
+    AND_OP = 45,
+    /// ||
+    OR_OP = 46,
+    /// ^^
+    XOR_OP = 47,
+    /// *=
+    MUL_ASSIGN = 48,
+    /// /=
+    DIV_ASSIGN = 49,
+    /// +=
+    ADD_ASSIGN = 50,
+    /// %=
+    MOD_ASSIGN = 51,
+    /// <<=
+    LEFT_ASSIGN = 52,
+    /// >>=
+    RIGHT_ASSIGN = 53,
+    /// &=
+    AND_ASSIGN = 54,
+    /// ^=
+    XOR_ASSIGN = 55,
+    /// |=
+    OR_ASSIGN = 56,
+    /// -=
+    SUB_ASSIGN = 57,
+    /// ##
+    PP_CONCAT = 58,
+    // Node types
+    /// Preprocessor directive, from the `#` to the end of the line
+    PP_DIRECTIVE,
+    /// Function definition, with its optional attributes
+    FUNCTION_DEFINITION,
+    /// Declaration, up to and including its terminating `;`
+    DECLARATION,
+    /// Function prototype: return type, name and parameters
+    FUNCTION_PROTOTYPE,
+    /// Function parameter declaration
+    FUNCTION_PARAMETER,
+    /// List of declared variables sharing the same type
+    INIT_DECLARATOR_LIST,
+    /// First declared variable of a list, with its type
+    SINGLE_DECLARATION,
+    /// Additional declared variable of a list
+    SINGLE_DECLARATION_NO_TYPE,
+    /// Default precision declaration
+    PRECISION_DECLARATION,
+    /// Interface block declaration
+    BLOCK,
+    /// `invariant` redeclaration of a variable
+    INVARIANT_DECLARATION,
+    /// Qualifier redeclaration of existing variables
+    QUALIFIER_REDECLARATION,
+    /// Identifier with an optional array specifier
+    ARRAYED_IDENTIFIER,
+    /// Braced initializer list
+    INITIALIZER_LIST,
+    /// Type specifier with its optional qualifiers
+    FULLY_SPECIFIED_TYPE,
+    /// Type specifier with its optional array specifier
+    TYPE_SPECIFIER,
+    /// Struct definition
+    STRUCT_SPECIFIER,
+    /// Field declaration of a struct or block
+    STRUCT_FIELD,
+    /// Array specifier, made of one or more dimensions
+    ARRAY_SPECIFIER,
+    /// Array dimension, with its optional size
+    ARRAY_DIMENSION,
+    /// `spirv_type` type specifier
+    SPIRV_TYPE,
+    /// List of type qualifiers
+    TYPE_QUALIFIER,
+    /// Storage qualifier
+    STORAGE_QUALIFIER,
+    /// Layout qualifier
+    LAYOUT_QUALIFIER,
+    /// Layout qualifier identifier, with its optional value
+    LAYOUT_QUALIFIER_ID,
+    /// Precision qualifier
+    PRECISION_QUALIFIER,
+    /// Interpolation qualifier
+    INTERPOLATION_QUALIFIER,
+    /// `invariant` qualifier
+    INVARIANT_QUALIFIER,
+    /// `precise` qualifier
+    PRECISE_QUALIFIER,
+    /// GL_EXT_spirv_intrinsics qualifier
+    SPIRV_QUALIFIER,
+    /// Argument of a GL_EXT_spirv_intrinsics qualifier or type
+    SPIRV_ARGUMENT,
+    /// Attribute list, enclosed in `[[` and `]]`
+    ATTRIBUTE_LIST,
+    /// Single attribute, with its optional argument
+    ATTRIBUTE,
+    /// Braced list of statements
+    COMPOUND_STATEMENT,
+    /// Expression statement, with its optional expression
+    EXPR_STATEMENT,
+    /// `if` statement, with its optional `else` branch
+    SELECTION_STATEMENT,
+    /// `switch` statement
+    SWITCH_STATEMENT,
+    /// `case` or `default` label
+    CASE_LABEL,
+    /// `while` loop
+    WHILE_STATEMENT,
+    /// `do`-`while` loop
+    DO_WHILE_STATEMENT,
+    /// `for` loop
+    FOR_STATEMENT,
+    /// Loop condition, either an expression or a variable declaration
+    CONDITION,
+    /// `continue`, `break`, `return`, `discard` and ray tracing jump statements
+    JUMP_STATEMENT,
+    /// Control flow statement with an attribute list
+    ATTRIBUTED_STATEMENT,
+    /// Variable reference
+    VARIABLE_EXPR,
+    /// Literal constant
+    LITERAL_EXPR,
+    /// Parenthesized expression
+    PAREN_EXPR,
+    /// Function call or constructor
+    CALL_EXPR,
+    /// Parenthesized argument list of a function call
+    ARG_LIST,
+    /// Array indexing expression
+    INDEX_EXPR,
+    /// Field or swizzle selection
+    FIELD_EXPR,
+    /// Postfix increment or decrement
+    POSTFIX_EXPR,
+    /// Unary operator expression
+    PREFIX_EXPR,
+    /// Binary operator expression
+    BINARY_EXPR,
+    /// Ternary conditional expression
+    TERNARY_EXPR,
+    /// Assignment expression
+    ASSIGNMENT_EXPR,
+    /// Comma-separated sequence of expressions
+    COMMA_EXPR,
+    /// Tokens that could not be parsed
+    ERROR_NODE,
+    /// CST root
+    ROOT,
+    #[doc(hidden)]
+    _LAST,
+}
+
+impl SyntaxKind {
+    /// All the syntax kinds, in discriminant order
+    pub(super) const ALL: &'static [SyntaxKind] = {
+        use SyntaxKind::*;
+        &[
+            IDENT_KW,
+            DEFINED,
+            DIGITS,
+            PERIOD,
+            PLUS,
+            DASH,
+            SLASH,
+            ASTERISK,
+            PERCENT,
+            LANGLE,
+            RANGLE,
+            LBRACKET,
+            RBRACKET,
+            LPAREN,
+            RPAREN,
+            LBRACE,
+            RBRACE,
+            CARET,
+            BAR,
+            AMPERSAND,
+            TILDE,
+            EQUAL,
+            BANG,
+            COLON,
+            SEMICOLON,
+            COMMA,
+            QUESTION,
+            HASH,
+            QUOTE_STRING,
+            ANGLE_STRING,
+            BACKSLASH,
+            WS,
+            NEWLINE,
+            COMMENT,
+            LINECONT,
+            ERROR,
+            LEFT_OP,
+            RIGHT_OP,
+            INC_OP,
+            DEC_OP,
+            LE_OP,
+            GE_OP,
+            EQ_OP,
+            NE_OP,
+            AND_OP,
+            OR_OP,
+            XOR_OP,
+            MUL_ASSIGN,
+            DIV_ASSIGN,
+            ADD_ASSIGN,
+            MOD_ASSIGN,
+            LEFT_ASSIGN,
+            RIGHT_ASSIGN,
+            AND_ASSIGN,
+            XOR_ASSIGN,
+            OR_ASSIGN,
+            SUB_ASSIGN,
+            PP_CONCAT,
+            PP_DIRECTIVE,
+            FUNCTION_DEFINITION,
+            DECLARATION,
+            FUNCTION_PROTOTYPE,
+            FUNCTION_PARAMETER,
+            INIT_DECLARATOR_LIST,
+            SINGLE_DECLARATION,
+            SINGLE_DECLARATION_NO_TYPE,
+            PRECISION_DECLARATION,
+            BLOCK,
+            INVARIANT_DECLARATION,
+            QUALIFIER_REDECLARATION,
+            ARRAYED_IDENTIFIER,
+            INITIALIZER_LIST,
+            FULLY_SPECIFIED_TYPE,
+            TYPE_SPECIFIER,
+            STRUCT_SPECIFIER,
+            STRUCT_FIELD,
+            ARRAY_SPECIFIER,
+            ARRAY_DIMENSION,
+            SPIRV_TYPE,
+            TYPE_QUALIFIER,
+            STORAGE_QUALIFIER,
+            LAYOUT_QUALIFIER,
+            LAYOUT_QUALIFIER_ID,
+            PRECISION_QUALIFIER,
+            INTERPOLATION_QUALIFIER,
+            INVARIANT_QUALIFIER,
+            PRECISE_QUALIFIER,
+            SPIRV_QUALIFIER,
+            SPIRV_ARGUMENT,
+            ATTRIBUTE_LIST,
+            ATTRIBUTE,
+            COMPOUND_STATEMENT,
+            EXPR_STATEMENT,
+            SELECTION_STATEMENT,
+            SWITCH_STATEMENT,
+            CASE_LABEL,
+            WHILE_STATEMENT,
+            DO_WHILE_STATEMENT,
+            FOR_STATEMENT,
+            CONDITION,
+            JUMP_STATEMENT,
+            ATTRIBUTED_STATEMENT,
+            VARIABLE_EXPR,
+            LITERAL_EXPR,
+            PAREN_EXPR,
+            CALL_EXPR,
+            ARG_LIST,
+            INDEX_EXPR,
+            FIELD_EXPR,
+            POSTFIX_EXPR,
+            PREFIX_EXPR,
+            BINARY_EXPR,
+            TERNARY_EXPR,
+            ASSIGNMENT_EXPR,
+            COMMA_EXPR,
+            ERROR_NODE,
+            ROOT,
+        ]
+    };
+
+    /// Return true if this kind is whitespace, a line continuation or a comment
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, Self::WS | Self::LINECONT | Self::COMMENT)
+    }
+
+    /// Return true if this kind is trivia or a newline
+    pub fn is_whitespace(&self) -> bool {
+        self.is_trivia() || matches!(self, Self::NEWLINE)
+    }
+
+    /// Return true if this kind is a CST node, false if it is a token
+    pub fn is_node(&self) -> bool {
+        *self >= Self::PP_DIRECTIVE
+    }
+
+    fn from_raw(raw: u16) -> Self {
+        let kind = Self::ALL[raw as usize - 1];
+        debug_assert_eq!(kind as u16, raw);
+        kind
+    }
+}
+
+impl From<lexer::Token> for SyntaxKind {
+    fn from(token: lexer::Token) -> Self {
+        // Token kinds share their discriminants with the lexer tokens
+        Self::from_raw(token as u16)
+    }
+}
+
+impl From<SyntaxKind> for rowan::SyntaxKind {
+    fn from(kind: SyntaxKind) -> Self {
+        Self(kind as u16)
+    }
+}
+
+/// Language definition for the GLSL concrete syntax tree
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlslLang;
+
+impl rowan::Language for GlslLang {
+    type Kind = SyntaxKind;
+
+    fn kind_from_raw(raw: rowan::SyntaxKind) -> Self::Kind {
+        assert!(raw.0 > 0 && raw.0 < SyntaxKind::_LAST as u16);
+        SyntaxKind::from_raw(raw.0)
+    }
+
+    fn kind_to_raw(kind: Self::Kind) -> rowan::SyntaxKind {
+        kind.into()
+    }
+}
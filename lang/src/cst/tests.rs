@@ -0,0 +1,340 @@
+use expect_test::expect;
+
+use super::*;
+
+const SOURCE: &str = "#version 460 core\r\n\
+// Uniforms\n\
+layout(std140) uniform Block {\n\
+    vec4 color; /* rgba */\n\
+} block;\n\
+\n\
+float weights[3] = float[3](0.25, 0.5, \\\n\
+    0.25);\n\
+\n\
+void main() {\n\
+#ifdef RED\n\
+    gl_FragColor = vec4(1., 0., 0., 1.);\n\
+#else\n\
+    gl_FragColor = block.color * weights[0];\n\
+#endif\n\
+}\n";
+
+/// Parse the source with the min lexer, which keeps preprocessor directives in the AST
+fn parse_ast(source: &str) -> crate::ast::TranslationUnit {
+    <crate::ast::TranslationUnit as crate::parse::Parse>::parse::<glsl_lang_lexer::min::str::Lexer>(
+        source,
+    )
+    .unwrap()
+}
+
+#[track_caller]
+fn assert_to_ast(source: &str) {
+    let cst = Cst::parse(source);
+
+    assert_eq!(cst.syntax().to_string(), source);
+    assert!(cst.errors().is_empty(), "{:?}", cst.errors());
+    assert_eq!(cst.tree().to_ast().unwrap(), parse_ast(source));
+}
+
+#[test]
+fn syntax_kind_discriminants() {
+    for (i, kind) in SyntaxKind::ALL.iter().enumerate() {
+        assert_eq!(*kind as usize, i + 1);
+    }
+
+    assert_eq!(SyntaxKind::ALL.len(), SyntaxKind::_LAST as usize - 1);
+}
+
+#[test]
+fn lossless() {
+    let cst = Cst::parse(SOURCE);
+
+    assert_eq!(cst.syntax().to_string(), SOURCE);
+    assert!(cst.errors().is_empty());
+}
+
+#[test]
+fn lossless_with_errors() {
+    let source = "int a = (1 + 2;\n} void f() { [ }\nfloat b";
+    let cst = Cst::parse(source);
+
+    assert_eq!(cst.syntax().to_string(), source);
+    assert!(!cst.errors().is_empty());
+}
+
+#[test]
+fn structure() {
+    let cst = Cst::parse("#define X 1\nvoid f(int a) { return -a; }\n");
+
+    expect![[r##"
+        ROOT@0..41
+          PP_DIRECTIVE@0..11
+            HASH@0..1 "#"
+            IDENT_KW@1..7 "define"
+            WS@7..8 " "
+            IDENT_KW@8..9 "X"
+            WS@9..10 " "
+            DIGITS@10..11 "1"
+          NEWLINE@11..12 "\n"
+          FUNCTION_DEFINITION@12..40
+            FUNCTION_PROTOTYPE@12..25
+              FULLY_SPECIFIED_TYPE@12..16
+                TYPE_SPECIFIER@12..16
+                  IDENT_KW@12..16 "void"
+              WS@16..17 " "
+              IDENT_KW@17..18 "f"
+              LPAREN@18..19 "("
+              FUNCTION_PARAMETER@19..24
+                TYPE_SPECIFIER@19..22
+                  IDENT_KW@19..22 "int"
+                WS@22..23 " "
+                ARRAYED_IDENTIFIER@23..24
+                  IDENT_KW@23..24 "a"
+              RPAREN@24..25 ")"
+            WS@25..26 " "
+            COMPOUND_STATEMENT@26..40
+              LBRACE@26..27 "{"
+              WS@27..28 " "
+              JUMP_STATEMENT@28..38
+                IDENT_KW@28..34 "return"
+                WS@34..35 " "
+                PREFIX_EXPR@35..37
+                  DASH@35..36 "-"
+                  VARIABLE_EXPR@36..37
+                    IDENT_KW@36..37 "a"
+                SEMICOLON@37..38 ";"
+              WS@38..39 " "
+              RBRACE@39..40 "}"
+          NEWLINE@40..41 "\n"
+    "##]]
+    .assert_eq(&format!("{:#?}", cst.syntax()));
+}
+
+#[test]
+fn typed_wrappers() {
+    let tu = Cst::parse(SOURCE).tree();
+    let items: Vec<_> = tu.external_declarations().collect();
+    assert_eq!(items.len(), 4);
+
+    let ExternalDeclaration::Preprocessor(version) = &items[0] else {
+        panic!("expected a preprocessor directive");
+    };
+    assert_eq!(version.name().unwrap().text(), "version");
+    assert_eq!(version.to_string(), "#version 460 core");
+
+    let ExternalDeclaration::Declaration(block) = &items[1] else {
+        panic!("expected a declaration");
+    };
+    assert!(block.semicolon_token().is_some());
+    let Some(DeclarationKind::Block(block)) = block.kind() else {
+        panic!("expected a block");
+    };
+    assert_eq!(block.name().unwrap().text(), "Block");
+    assert_eq!(
+        block.qualifier().unwrap().to_string(),
+        "layout(std140) uniform"
+    );
+    assert_eq!(block.fields().count(), 1);
+    assert_eq!(block.identifier().unwrap().to_string(), "block");
+
+    let ExternalDeclaration::Declaration(weights) = &items[2] else {
+        panic!("expected a declaration");
+    };
+    let Some(DeclarationKind::InitDeclaratorList(weights)) = weights.kind() else {
+        panic!("expected an init declarator list");
+    };
+    let head = weights.head().unwrap();
+    assert_eq!(head.ty().unwrap().to_string(), "float");
+    assert_eq!(head.ident().unwrap().to_string(), "weights[3]");
+    let Some(Initializer::Simple(Expr::Call(call))) = head.initializer() else {
+        panic!("expected a call");
+    };
+    assert!(matches!(
+        call.function(),
+        Some(FunIdentifier::TypeSpecifier(_))
+    ));
+    assert_eq!(call.arg_list().unwrap().args().count(), 3);
+
+    let ExternalDeclaration::FunctionDefinition(main) = &items[3] else {
+        panic!("expected a function definition");
+    };
+    assert_eq!(main.name().unwrap().text(), "main");
+    assert_eq!(main.prototype().unwrap().parameters().count(), 0);
+
+    let body = main.body().unwrap();
+    assert_eq!(body.statements().count(), 2);
+    assert_eq!(
+        body.syntax()
+            .children()
+            .filter_map(PreprocessorDirective::cast)
+            .map(|directive| directive.name().unwrap().text().to_owned())
+            .collect::<Vec<_>>(),
+        ["ifdef", "else", "endif"]
+    );
+}
+
+#[test]
+fn expressions() {
+    let cst = Cst::parse("int a = b = c ? d : e || f && g | h + i * -j++;");
+    assert!(cst.errors().is_empty());
+
+    let Some(ExternalDeclaration::Declaration(declaration)) =
+        cst.tree().external_declarations().next()
+    else {
+        panic!("expected a declaration");
+    };
+    let Some(DeclarationKind::InitDeclaratorList(list)) = declaration.kind() else {
+        panic!("expected an init declarator list");
+    };
+    let Some(Initializer::Simple(Expr::Assignment(assignment))) =
+        list.head().unwrap().initializer()
+    else {
+        panic!("expected an assignment");
+    };
+    assert_eq!(assignment.lhs().unwrap().to_string(), "b");
+
+    let Some(Expr::Ternary(ternary)) = assignment.rhs() else {
+        panic!("expected a ternary expression");
+    };
+    let Some(Expr::Binary(or)) = ternary.else_expr() else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(or.op_token().unwrap().text(), "||");
+    assert_eq!(or.rhs().unwrap().to_string(), "f && g | h + i * -j++");
+}
+
+#[test]
+fn function_definition_with_attributes() {
+    let cst = Cst::parse("[[random(4)]] void main() [[subgroup_uniform_control_flow]]\n{\n}\n");
+    assert!(cst.errors().is_empty());
+
+    let Some(ExternalDeclaration::FunctionDefinition(main)) =
+        cst.tree().external_declarations().next()
+    else {
+        panic!("expected a function definition");
+    };
+    assert_eq!(main.name().unwrap().text(), "main");
+    assert_eq!(main.attributes().unwrap().to_string(), "[[random(4)]]");
+    assert_eq!(
+        main.body_attributes().unwrap().to_string(),
+        "[[subgroup_uniform_control_flow]]"
+    );
+}
+
+#[test]
+fn errors() {
+    let cst = Cst::parse("int a = (1 + 2;\n} void f() { [ }\nfloat b");
+
+    assert_eq!(
+        cst.errors()
+            .iter()
+            .map(|error| format!("{}: {}", error.line(), error.inner()))
+            .collect::<Vec<_>>(),
+        [
+            "0: expected `)`, found `;`",
+            "1: expected declaration, found `}`",
+            "1: expected `[`, found `}`",
+            "2: expected `;`, found end of input",
+        ]
+    );
+
+    let items: Vec<_> = cst.tree().external_declarations().collect();
+    assert!(matches!(items[0], ExternalDeclaration::Declaration(_)));
+    assert!(matches!(items[1], ExternalDeclaration::Error(_)));
+    assert!(matches!(
+        items[2],
+        ExternalDeclaration::FunctionDefinition(_)
+    ));
+    assert!(matches!(items[3], ExternalDeclaration::Declaration(_)));
+
+    assert!(cst.tree().to_ast().is_none());
+}
+
+#[test]
+fn type_names() {
+    // Struct names and buffer_reference block names are type names, in the scope they are
+    // declared in
+    let cst = Cst::parse(
+        "struct S { int a; };\n\
+        layout(buffer_reference) buffer B;\n\
+        void f() { struct T { S s; }; T t; B b; }\n\
+        T t;\n",
+    );
+
+    assert_eq!(
+        cst.errors()
+            .iter()
+            .map(|error| format!("{}: {}", error.line(), error.inner()))
+            .collect::<Vec<_>>(),
+        ["3: expected type, found `T`"]
+    );
+}
+
+#[test]
+fn to_ast() {
+    assert_to_ast("uniform vec4 color;\n\nvoid main() {\n    gl_FragColor = color;\n}\n");
+}
+
+#[test]
+fn to_ast_declarations() {
+    assert_to_ast(
+        "#version 460 core\n\
+        #extension GL_EXT_buffer_reference : require\n\
+        #define SQUARE(x) ((x) * (x))\n\
+        #define N 4\n\
+        precision highp float;\n\
+        invariant gl_Position;\n\
+        layout(local_size_x = 8, local_size_y = 1) in;\n\
+        layout(std430, binding = 0) readonly buffer Data {\n\
+            float values[];\n\
+        } data[2];\n\
+        layout(buffer_reference) buffer Node;\n\
+        layout(buffer_reference, std430) buffer Node {\n\
+            Node next;\n\
+            uint value;\n\
+        };\n\
+        struct Light { vec3 position, color; float radius[2]; };\n\
+        const Light lights[2] = { Light(vec3(0.), vec3(1.), float[2](1., 2.)), \
+            Light(vec3(1.), vec3(0.), float[](1., 2.)) };\n\
+        uniform struct { float r, g; } parts[2] = { { 1., 0. }, { 0., 1. } };\n\
+        flat out int a, b[3] = int[3](1, 2u, 0x3), c;\n\
+        subroutine vec4 colorize(vec3);\n\
+        float f(const in float x, inout vec2[2] y, float[3]);\n\
+        void g(void);\n\
+        ;\n",
+    );
+}
+
+#[test]
+fn to_ast_statements() {
+    assert_to_ast(
+        "void main() {\n\
+            int i = 0, j;\n\
+            float x = 1.5e3, y = .5lf;\n\
+            vec4(1.);\n\
+            if (i == 0) x += 1.; else if (i > 1) { x -= 1.; } else ;\n\
+            switch (i) { case 0: case 1: break; default: discard; }\n\
+            while (bool b = i < 10) ++i;\n\
+            do { i--; } while (i > 0);\n\
+            for (int k = 0; k < 10; k++) continue;\n\
+            for (;;) { break; }\n\
+            for (i = 0; ; ) {}\n\
+            [[unroll, dependency_length(4)]] for (i = 0; i < 4; ++i) {}\n\
+            x = i > 0 ? float(i) : -x, y = x;\n\
+            y = data[1].values[i].x + f(x, y).length() * ~i % 2 << 1 ^^ true != false;\n\
+            return;\n\
+        }\n",
+    );
+}
+
+#[test]
+fn to_ast_spirv_intrinsics() {
+    assert_to_ast(
+        "#extension GL_EXT_spirv_intrinsics : enable\n\
+        spirv_execution_mode(4446);\n\
+        spirv_instruction(extensions = [\"SPV_KHR_shader_clock\"], capabilities = [5055], id = 5056)\n\
+        uvec2 clockRealtime2x32EXT(spirv_literal uint scope);\n\
+        spirv_decorate(11, 5302, \"x\") in float x;\n\
+        spirv_type(id = 22, 32) s;\n",
+    );
+}
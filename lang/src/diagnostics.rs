@@ -0,0 +1,540 @@
+//! Human-friendly diagnostics for GLSL sources
+//!
+//! The errors returned by the parser describe the set of tokens the grammar could accept at the
+//! error location, which quickly gets unwieldy: a missing `;` after an expression lists every
+//! binary operator. This module post-processes these errors into [`Diagnostic`]s, which carry a
+//! stable [`DiagnosticCode`], a short message and, when possible, machine-readable [`FixIt`]
+//! edits to apply to the source.
+//!
+//! ```
+//! use glsl_lang::{ast, diagnostics::{Diagnostic, DiagnosticCode}, parse::DefaultParse};
+//!
+//! let source = "void main() {\n    vce3 color = vec3(1.);\n}\n";
+//! let error = ast::TranslationUnit::parse(source).unwrap_err();
+//! let diagnostic = Diagnostic::from_parse_error(&error, source);
+//!
+//! assert_eq!(diagnostic.code, DiagnosticCode::UnknownName);
+//! assert_eq!(diagnostic.message, "unknown type name `vce3`");
+//! assert_eq!(diagnostic.fix_its[0].message, "did you mean `vec3`?");
+//! ```
+//!
+//! Some errors are only detected once the source has been parsed: see
+//! [`version_diagnostics`] for qualifiers which are not allowed by the `#version` of the source.
+
+use glsl_lang_lexer::{min::str::Lexer, LangLexer, ParseContext, ParseOptions, Token};
+use lang_util::{
+    error::{LexicalError, ParseErrorKind},
+    token::Token as _,
+    TextRange, TextSize,
+};
+
+use crate::{
+    ast,
+    parse::{ParseError, TextEdit},
+    visitor::{Host, Visit, Visitor},
+};
+
+/// Stable code identifying the kind of a [`Diagnostic`]
+///
+/// The codes are part of the public interface of this crate: new codes may be added, but the
+/// existing ones will not be renumbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+    /// `E0001`: unexpected token
+    UnexpectedToken,
+    /// `E0002`: unexpected end of input
+    UnexpectedEof,
+    /// `E0003`: extra token after the end of the input
+    ExtraToken,
+    /// `E0004`: invalid token
+    InvalidToken,
+    /// `E0005`: lexical or preprocessing error
+    LexicalError,
+    /// `E0006`: missing `;` after a declaration or statement
+    MissingSemicolon,
+    /// `E0007`: unknown type name or keyword
+    UnknownName,
+    /// `E0008`: qualifier not allowed by the `#version` of the source
+    UnsupportedQualifier,
+}
+
+impl DiagnosticCode {
+    /// Get the string representation of this code, i.e. `E0006`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnexpectedToken => "E0001",
+            Self::UnexpectedEof => "E0002",
+            Self::ExtraToken => "E0003",
+            Self::InvalidToken => "E0004",
+            Self::LexicalError => "E0005",
+            Self::MissingSemicolon => "E0006",
+            Self::UnknownName => "E0007",
+            Self::UnsupportedQualifier => "E0008",
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Suggested edit fixing a [`Diagnostic`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixIt {
+    /// Human-readable description of the fix
+    pub message: String,
+    /// Edit to apply to the source
+    pub edit: TextEdit,
+}
+
+impl FixIt {
+    fn new(message: impl Into<String>, edit: TextEdit) -> Self {
+        Self {
+            message: message.into(),
+            edit,
+        }
+    }
+}
+
+/// Diagnostic about a GLSL source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Kind of this diagnostic
+    pub code: DiagnosticCode,
+    /// Human-readable message
+    pub message: String,
+    /// Range of the source this diagnostic applies to
+    pub range: TextRange,
+    /// Suggested fixes, from the most to the least likely
+    pub fix_its: Vec<FixIt>,
+    /// Context of the diagnostic, such as the macro expansions it occurred in
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    fn new(code: DiagnosticCode, message: impl Into<String>, range: TextRange) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            range,
+            fix_its: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    fn with_fix_it(mut self, fix_it: FixIt) -> Self {
+        self.fix_its.push(fix_it);
+        self
+    }
+
+    /// Build a diagnostic for an error returned when parsing `source`
+    ///
+    /// `source` must be the text the error location refers to. Suggestions for misspelled names
+    /// consider built-in type names and keywords, and the structs declared in `source` before the
+    /// error location. Use [`Diagnostic::from_parse_error_with_context`] to include type names
+    /// declared elsewhere.
+    pub fn from_parse_error<E: LexicalError>(error: &ParseError<E>, source: &str) -> Self {
+        Self::build(error, source, Vec::new())
+    }
+
+    /// Build a diagnostic for an error returned when parsing `source` with the given context
+    ///
+    /// The type names known to `ctx` are also considered when suggesting replacements for
+    /// misspelled names. Note that parsing functions work on a copy of the context they are given,
+    /// so `ctx` does not contain the type names declared by the source that failed to parse.
+    pub fn from_parse_error_with_context<E: LexicalError>(
+        error: &ParseError<E>,
+        source: &str,
+        ctx: &ParseContext,
+    ) -> Self {
+        Self::build(
+            error,
+            source,
+            ctx.data().type_names().map(str::to_owned).collect(),
+        )
+    }
+
+    fn build<E: LexicalError>(
+        error: &ParseError<E>,
+        source: &str,
+        type_names: Vec<String>,
+    ) -> Self {
+        let mut diagnostic = Self::build_message(error, source, type_names);
+
+        // Innermost expansion first, like compilers do
+        diagnostic.notes = error
+            .expansions()
+            .iter()
+            .rev()
+            .map(ToString::to_string)
+            .collect();
+
+        diagnostic
+    }
+
+    fn build_message<E: LexicalError>(
+        error: &ParseError<E>,
+        source: &str,
+        type_names: Vec<String>,
+    ) -> Self {
+        let range = error.pos();
+
+        let (token, expected) = match error.inner() {
+            ParseErrorKind::UnrecognizedToken { token, expected } => (Some(token), expected),
+            ParseErrorKind::UnrecognizedEof { expected } => (None, expected),
+            ParseErrorKind::InvalidToken => {
+                return Self::new(DiagnosticCode::InvalidToken, "invalid token", range);
+            }
+            ParseErrorKind::ExtraToken { .. } => {
+                return Self::new(DiagnosticCode::ExtraToken, error.inner().to_string(), range);
+            }
+            ParseErrorKind::LexicalError { error } => {
+                return Self::new(DiagnosticCode::LexicalError, error.to_string(), range);
+            }
+        };
+
+        let expects = |name: &str| expected.iter().any(|item| item == name);
+        let expects_semicolon = expects(";");
+        let is_identifier = token.is_some_and(|token| token.variant_name == "Identifier");
+        let before = TokensBefore::new(source, range.start());
+        let candidates = Candidates::new(type_names, before.struct_names());
+
+        // An identifier where a type is expected, such as `vce3 color;` at the top level
+        if is_identifier && (expects("type name") || expects("type qualifier")) {
+            if let Some(name) = source.get(std::ops::Range::<usize>::from(range)) {
+                return candidates.unknown_name(name, range, false);
+            }
+        }
+
+        if let (true, Some((_, previous))) = (expects_semicolon, before.last()) {
+            let next_line = source
+                .get(usize::from(previous.end())..usize::from(range.start()))
+                .is_some_and(|between| between.contains('\n'));
+
+            // The declaration most likely ended on the previous line
+            if token.is_none() || next_line || token.unwrap().variant_name == "RightBrace" {
+                return before.missing_semicolon();
+            }
+        }
+
+        // An identifier followed by another identifier or a literal at the start of a statement,
+        // such as `vce3 color = ...` or `retrun 0;`
+        let is_operand =
+            is_identifier || token.is_some_and(|token| token.kinds.contains(&"literal"));
+        if is_operand && before.at_statement_start(1) {
+            if let Some((Token::Identifier(name), previous)) = before.last() {
+                return candidates.unknown_name(name, *previous, true);
+            }
+        }
+
+        if expects_semicolon && before.last().is_some() {
+            return before.missing_semicolon();
+        }
+
+        // Summarize the start of an expression instead of listing all the literal kinds
+        let message = match (token, expects("identifier") && expects("unary operator")) {
+            (Some(token), true) => format!("unexpected {}, expected an expression", token),
+            (None, true) => "unexpected end of input, expected an expression".to_owned(),
+            _ => error.inner().to_string(),
+        };
+
+        let code = if token.is_some() {
+            DiagnosticCode::UnexpectedToken
+        } else {
+            DiagnosticCode::UnexpectedEof
+        };
+
+        Self::new(code, message, range)
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Tokens of a source preceding an error location
+struct TokensBefore {
+    tokens: Vec<(Token, TextRange)>,
+}
+
+impl TokensBefore {
+    fn new(source: &str, offset: TextSize) -> Self {
+        // The source is lexed on its own, so user-defined type names are seen as identifiers
+        let tokens = <Lexer as LangLexer>::new(source, &ParseOptions::default())
+            .run(ParseContext::new())
+            .map_while(Result::ok)
+            .map(|(start, token, end)| (token, TextRange::new(start.offset, end.offset)))
+            .take_while(|(_, range)| range.end() <= offset)
+            .collect();
+
+        Self { tokens }
+    }
+
+    fn last(&self) -> Option<&(Token, TextRange)> {
+        self.tokens.last()
+    }
+
+    /// Return true if the last `n` tokens start a new statement or declaration
+    fn at_statement_start(&self, n: usize) -> bool {
+        match self.tokens.len().checked_sub(n + 1) {
+            Some(index) => matches!(
+                self.tokens[index].0,
+                Token::Semicolon | Token::LeftBrace | Token::RightBrace
+            ),
+            None => self.tokens.len() == n,
+        }
+    }
+
+    /// Iterate over the names of the structs declared in the tokens
+    fn struct_names(&self) -> impl Iterator<Item = String> + '_ {
+        self.tokens.windows(2).filter_map(|window| match window {
+            [(Token::Struct, _), (Token::Identifier(name), _)] => Some(name.to_string()),
+            _ => None,
+        })
+    }
+
+    /// Return true if the error location is in a block of statements, rather than at the top
+    /// level or in a struct or interface block
+    fn in_statement(&self) -> bool {
+        let mut blocks = Vec::new();
+        let mut previous: Option<&Token> = None;
+
+        for (token, _) in &self.tokens {
+            match token {
+                Token::LeftBrace => {
+                    let statement =
+                        matches!(previous, Some(Token::RightParen | Token::Else | Token::Do))
+                            || blocks.last().copied().unwrap_or(false);
+                    blocks.push(statement);
+                }
+                Token::RightBrace => {
+                    blocks.pop();
+                }
+                _ => {}
+            }
+
+            previous = Some(token);
+        }
+
+        blocks.last().copied().unwrap_or(false)
+    }
+
+    fn missing_semicolon(&self) -> Diagnostic {
+        // unwrap: only called if there is a previous token
+        let end = self.last().unwrap().1.end();
+        let what = if self.in_statement() {
+            "statement"
+        } else {
+            "declaration"
+        };
+
+        Diagnostic::new(
+            DiagnosticCode::MissingSemicolon,
+            format!("missing `;` after {}", what),
+            TextRange::empty(end),
+        )
+        .with_fix_it(FixIt::new("insert `;`", TextEdit::insert(end, ";")))
+    }
+}
+
+/// Known names to suggest in place of misspelled ones
+struct Candidates {
+    type_names: Vec<String>,
+}
+
+impl Candidates {
+    fn new(type_names: Vec<String>, struct_names: impl Iterator<Item = String>) -> Self {
+        let mut type_names: Vec<_> = type_names.into_iter().chain(struct_names).collect();
+        type_names.sort_unstable();
+        type_names.dedup();
+
+        Self { type_names }
+    }
+
+    /// Iterate over the known names, and whether they are type names
+    ///
+    /// Keywords which are not type qualifiers, such as `return`, are only included if `keywords`
+    /// is true.
+    fn iter(&self, keywords: bool) -> impl Iterator<Item = (&str, bool)> {
+        let tokens = Token::all_tokens().iter().filter_map(move |descriptor| {
+            let is_type = descriptor
+                .kinds
+                .iter()
+                .any(|kind| kind.ends_with("type name"));
+            let is_keyword = descriptor
+                .kinds
+                .iter()
+                .any(|kind| *kind == "type qualifier" || (keywords && *kind == "keyword"));
+
+            // Keyword tokens are quoted in the grammar
+            let name = descriptor
+                .parser_token
+                .strip_prefix('"')?
+                .strip_suffix('"')?;
+
+            (is_type || is_keyword).then_some((name, is_type))
+        });
+
+        self.type_names
+            .iter()
+            .map(|name| (name.as_str(), true))
+            .chain(tokens)
+    }
+
+    /// Build the diagnostic for an unknown name, suggesting the closest known name
+    fn unknown_name(&self, name: &str, range: TextRange, keywords: bool) -> Diagnostic {
+        // Short names are too likely to be close to a keyword by chance
+        let max_distance = name.chars().count() / 3;
+
+        let suggestion = self
+            .iter(keywords)
+            .map(|(candidate, is_type)| (edit_distance(name, candidate), candidate, is_type))
+            .filter(|(distance, _, _)| *distance > 0 && *distance <= max_distance)
+            .min_by_key(|(distance, _, _)| *distance);
+
+        match suggestion {
+            Some((_, candidate, is_type)) => Diagnostic::new(
+                DiagnosticCode::UnknownName,
+                if is_type {
+                    format!("unknown type name `{}`", name)
+                } else {
+                    format!("unknown keyword `{}`", name)
+                },
+                range,
+            )
+            .with_fix_it(FixIt::new(
+                format!("did you mean `{}`?", candidate),
+                TextEdit::new(range, candidate),
+            )),
+            None => Diagnostic::new(
+                DiagnosticCode::UnknownName,
+                format!("unknown type name `{}`", name),
+                range,
+            ),
+        }
+    }
+}
+
+/// Edit distance between two strings, counting transpositions of adjacent characters as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+
+    // Rows of the distance matrix for the two previous characters of a
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Check the qualifiers used in a translation unit against its `#version` directive
+///
+/// This reports the `attribute` and `varying` storage qualifiers, which are not available in
+/// the core profile starting with GLSL 1.40, and in GLSL ES starting with GLSL ES 3.00.
+///
+/// Only the first `#version` directive of the translation unit is considered. When parsing
+/// with the full lexer, preprocessor directives are not part of the AST, so they must be
+/// injected back into `tu` before calling this function.
+pub fn version_diagnostics(tu: &ast::TranslationUnit) -> Vec<Diagnostic> {
+    let Some(version) = tu.0.iter().find_map(|decl| match &**decl {
+        ast::ExternalDeclarationData::Preprocessor(pp) => match &**pp {
+            ast::PreprocessorData::Version(version) => Some(version),
+            _ => None,
+        },
+        _ => None,
+    }) else {
+        return Vec::new();
+    };
+
+    let removed = match version.profile.as_deref() {
+        Some(ast::PreprocessorVersionProfileData::Es) => version.version >= 300,
+        Some(ast::PreprocessorVersionProfileData::Compatibility) => false,
+        Some(ast::PreprocessorVersionProfileData::Core) | None => version.version >= 140,
+    };
+
+    if !removed {
+        return Vec::new();
+    }
+
+    let mut qualifiers = RemovedQualifiers::default();
+    tu.visit(&mut qualifiers);
+
+    let directive = match version.profile.as_deref() {
+        Some(ast::PreprocessorVersionProfileData::Core) => format!("{} core", version.version),
+        Some(ast::PreprocessorVersionProfileData::Compatibility) => {
+            format!("{} compatibility", version.version)
+        }
+        Some(ast::PreprocessorVersionProfileData::Es) => format!("{} es", version.version),
+        None => version.version.to_string(),
+    };
+
+    qualifiers
+        .0
+        .into_iter()
+        .map(|(qualifier, range)| {
+            let diagnostic = Diagnostic::new(
+                DiagnosticCode::UnsupportedQualifier,
+                format!("`{}` is not allowed in #version {}", qualifier, directive),
+                range,
+            );
+
+            match qualifier {
+                "attribute" => {
+                    diagnostic.with_fix_it(FixIt::new("use `in`", TextEdit::new(range, "in")))
+                }
+                _ => diagnostic
+                    .with_fix_it(FixIt::new(
+                        "use `out` in vertex shaders",
+                        TextEdit::new(range, "out"),
+                    ))
+                    .with_fix_it(FixIt::new(
+                        "use `in` in fragment shaders",
+                        TextEdit::new(range, "in"),
+                    )),
+            }
+        })
+        .collect()
+}
+
+/// Visitor collecting the `attribute` and `varying` qualifiers
+#[derive(Default)]
+struct RemovedQualifiers(Vec<(&'static str, TextRange)>);
+
+impl Visitor for RemovedQualifiers {
+    fn visit_storage_qualifier(&mut self, qualifier: &ast::StorageQualifier) -> Visit {
+        let name = match &**qualifier {
+            ast::StorageQualifierData::Attribute => "attribute",
+            ast::StorageQualifierData::Varying => "varying",
+            _ => return Visit::Children,
+        };
+
+        if let Some(span) = qualifier.span {
+            self.0.push((name, span.range()));
+        }
+
+        Visit::Children
+    }
+}
@@ -27,11 +27,22 @@
 //! This crate has the following features:
 //! - `parser-expr`: generate parser code for parsing GLSL expressions
 //! - `parser-statement`: generate parser code for parsing GLSL statements
+//! - `parser-declaration`: generate parser code for parsing GLSL declarations
+//! - `parser-function-definition`: generate parser code for parsing GLSL function definitions
+//! - `parser-type-specifier`: generate parser code for parsing GLSL type specifiers
+//! - `parser-layout-qualifier`: generate parser code for parsing GLSL layout qualifiers
+//! - `parser-initializer`: generate parser code for parsing GLSL initializers
+//! - `parser-all`: all of the above
+//! - `parser-recovering`: generate the error-recovering parsers used by
+//!   [`ParseBuilder::parse_recovering`](crate::parse::ParseBuilder::parse_recovering)
+//! - `cst`: build lossless concrete syntax trees with the [`cst`](crate::cst) module
 //!
 //! None of these features are enabled by default, as they significantly increase the compile
-//! times. As an alternative, you may use the [`Parsable`](crate::parse::Parsable) trait, which
-//! wraps grammar rules in suitable source and matches the result to extract the part of the AST
-//! we're interested in.
+//! times. With one of them enabled, the corresponding AST node implements
+//! [`Parse`](crate::parse::Parse), and the resulting spans match the input exactly. As an
+//! alternative, you may use the [`Parsable`](crate::parse::Parsable) trait, which wraps grammar
+//! rules in suitable source and matches the result to extract the part of the AST we're
+//! interested in.
 //!
 //! ```
 //! // parse::Parse is not implemented for ast::Expr with the default features
@@ -58,6 +69,14 @@ lalrpop_mod!(
     #[allow(clippy::all)]
     parser
 );
+#[cfg(feature = "parser-recovering")]
+lalrpop_mod!(
+    #[allow(clippy::all)]
+    recovering_parser
+);
+#[cfg(feature = "cst")]
+pub mod cst;
+pub mod diagnostics;
 pub mod parse;
 pub mod transpiler;
 pub mod visitor;
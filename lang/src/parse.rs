@@ -4,6 +4,9 @@ use lang_util::position::LexerPosition;
 
 use crate::{ast, parser};
 
+#[cfg(feature = "parser-recovering")]
+use crate::recovering_parser;
+
 use glsl_lang_lexer::{HasLexerError, LangLexer, Token};
 
 pub use glsl_lang_lexer::{ParseContext, ParseContextData, ParseOptions};
@@ -11,10 +14,19 @@ pub use glsl_lang_lexer::{ParseContext, ParseContextData, ParseOptions};
 mod builder;
 pub use builder::*;
 
+mod incremental;
+pub use incremental::{IncrementalParseError, IncrementalParser, Reparse, TextEdit};
+
 mod parsable;
 pub use parsable::Extractable;
 pub use parsable::Parsable;
 
+mod stream;
+pub use stream::DeclarationStream;
+
+/// Error type of a lexer, as used by the generated parser
+pub(crate) type LexerError<L> = <L as HasLexerError>::Error;
+
 /// GLSL language parser
 pub trait LangParser: Sized {
     /// AST node returned by this parser
@@ -31,6 +43,21 @@ pub trait LangParser: Sized {
         ctx: ParseContext,
         input: &mut L,
     ) -> Result<Self::Item, lalrpop_util::ParseError<LexerPosition, Token, L::Error>>;
+
+    /// Parse the input, recovering from syntax errors
+    ///
+    /// The syntax errors the parser recovered from are added to `errors`, and the regions they
+    /// cover are represented by error nodes in the result.
+    #[cfg(feature = "parser-recovering")]
+    #[allow(clippy::type_complexity)]
+    fn parse_recovering<
+        L: HasLexerError + Iterator<Item = Result<(LexerPosition, Token, LexerPosition), L::Error>>,
+    >(
+        &self,
+        ctx: ParseContext,
+        input: &mut L,
+        errors: &mut Vec<lalrpop_util::ErrorRecovery<LexerPosition, Token, L::Error>>,
+    ) -> Result<Self::Item, lalrpop_util::ParseError<LexerPosition, Token, L::Error>>;
 }
 
 /// GLSL language parsing capability
@@ -87,6 +114,15 @@ impl<T: HasParser> Parse for T {
 /// Result of a parsing operation
 pub type ParseResult<L, E, T> = Result<(T, ParseContext, L), ParseError<E>>;
 
+/// Result of a parsing operation that recovers from syntax errors
+///
+/// On success, contains the parsed node, which may include error nodes, and the list of all the
+/// syntax errors that were found. If the parser could not recover, contains the list of errors up
+/// to and including the one that stopped it.
+#[cfg(feature = "parser-recovering")]
+pub type RecoveringParseResult<L, E, T> =
+    Result<(T, Vec<ParseError<E>>, ParseContext, L), Vec<ParseError<E>>>;
+
 /// Errors returned by the parsing operation
 pub type ParseError<E> = lang_util::error::ParseError<E>;
 
@@ -159,6 +195,9 @@ impl<T: Parse> DefaultParse for T {
 
 macro_rules! impl_parse {
     ($t:ty => $p:ty) => {
+        impl_parse!($t => $p, $p);
+    };
+    ($t:ty => $p:ty, $r:ty) => {
         impl LangParser for $p {
             type Item = $t;
 
@@ -174,7 +213,21 @@ macro_rules! impl_parse {
                 ctx: ParseContext,
                 input: &mut L,
             ) -> Result<Self::Item, lalrpop_util::ParseError<LexerPosition, Token, L::Error>> {
-                self.parse::<L, _, _>(&ctx, input)
+                // The strict parsers have no error recovery rules, so no errors are recorded
+                self.parse::<L, _, _>(&ctx, &mut Vec::new(), input)
+            }
+
+            #[cfg(feature = "parser-recovering")]
+            fn parse_recovering<
+                L: HasLexerError
+                    + Iterator<Item = Result<(LexerPosition, Token, LexerPosition), L::Error>>,
+            >(
+                &self,
+                ctx: ParseContext,
+                input: &mut L,
+                errors: &mut Vec<lalrpop_util::ErrorRecovery<LexerPosition, Token, L::Error>>,
+            ) -> Result<Self::Item, lalrpop_util::ParseError<LexerPosition, Token, L::Error>> {
+                <$r>::new().parse::<L, _, _>(&ctx, errors, input)
             }
         }
 
@@ -185,7 +238,17 @@ macro_rules! impl_parse {
 }
 
 #[cfg(feature = "parser-expr")]
-impl_parse!(ast::Expr            => parser::ExprParser);
+impl_parse!(ast::Expr               => parser::ExprParser);
 #[cfg(feature = "parser-statement")]
-impl_parse!(ast::Statement       => parser::StatementParser);
-impl_parse!(ast::TranslationUnit => parser::TranslationUnitParser);
+impl_parse!(ast::Statement          => parser::StatementParser, recovering_parser::StatementParser);
+#[cfg(feature = "parser-declaration")]
+impl_parse!(ast::Declaration        => parser::DeclarationParser);
+#[cfg(feature = "parser-function-definition")]
+impl_parse!(ast::FunctionDefinition => parser::FunctionDefinitionParser, recovering_parser::FunctionDefinitionParser);
+#[cfg(feature = "parser-type-specifier")]
+impl_parse!(ast::TypeSpecifier      => parser::TypeSpecifierParser);
+#[cfg(feature = "parser-layout-qualifier")]
+impl_parse!(ast::LayoutQualifier    => parser::LayoutQualifierParser);
+#[cfg(feature = "parser-initializer")]
+impl_parse!(ast::Initializer        => parser::InitializerParser);
+impl_parse!(ast::TranslationUnit    => parser::TranslationUnitParser, recovering_parser::TranslationUnitParser);
@@ -2,7 +2,15 @@
 
 use glsl_lang_lexer::{HasLexerError, LangLexerIterator, ParseOptions};
 
-use super::{Extractable, HasParser, LangLexer, LangParser, ParseContext, ParseResult};
+use crate::ast;
+
+use super::{
+    DeclarationStream, Extractable, HasParser, LangLexer, LangParser, ParseContext, ParseError,
+    ParseResult,
+};
+
+#[cfg(feature = "parser-recovering")]
+use super::RecoveringParseResult;
 
 /// Builder structure for a parsing operation
 pub struct ParseBuilder<'i, 'o, 'c, 'p, L: LangLexer<'i>, T: HasParser> {
@@ -50,6 +58,8 @@ impl<'i, 'o, 'c, 'p, L: LangLexer<'i>, T: HasParser> ParseBuilder<'i, 'o, 'c, 'p
     }
 
     /// Execute the parsing operation
+    ///
+    /// If `errors` is set, the parser recovers from syntax errors and adds them to this list.
     #[allow(clippy::result_large_err)]
     fn parse_source(
         source: L::Input,
@@ -57,6 +67,7 @@ impl<'i, 'o, 'c, 'p, L: LangLexer<'i>, T: HasParser> ParseBuilder<'i, 'o, 'c, 'p
         mut context: Option<&'c ParseContext>,
         mut lexer: Option<L>,
         mut parser: Option<&'p T::Parser>,
+        errors: Option<&mut Vec<ParseError<<L::Iter as HasLexerError>::Error>>>,
     ) -> ParseResult<L::Iter, <L::Iter as HasLexerError>::Error, T> {
         // Get parse options
         let default_opts = Default::default();
@@ -87,7 +98,23 @@ impl<'i, 'o, 'c, 'p, L: LangLexer<'i>, T: HasParser> ParseBuilder<'i, 'o, 'c, 'p
 
         // Invoke the parser
         let mut iter = lexer.run(cloned_context.clone());
-        match parser.parse(cloned_context.clone(), &mut iter) {
+        let result = match errors {
+            #[cfg(feature = "parser-recovering")]
+            Some(errors) => {
+                let mut recovered = Vec::new();
+                let result =
+                    parser.parse_recovering(cloned_context.clone(), &mut iter, &mut recovered);
+                errors.extend(
+                    recovered
+                        .into_iter()
+                        .map(|recovery| iter.resolve_err(recovery.error)),
+                );
+                result
+            }
+            _ => parser.parse(cloned_context.clone(), &mut iter),
+        };
+
+        match result {
             Ok(t) => Ok((t, cloned_context, iter)),
             Err(err) => Err(iter.resolve_err(err)),
         }
@@ -102,9 +129,39 @@ impl<'i, 'o, 'c, 'p, L: LangLexer<'i>, T: HasParser> ParseBuilder<'i, 'o, 'c, 'p
             self.context,
             self.lexer,
             self.parser,
+            None,
         )
     }
 
+    /// Execute the parsing operation, recovering from syntax errors
+    ///
+    /// Instead of stopping at the first syntax error, the parser skips to the end of the current
+    /// statement, block or external declaration and resumes from there. The skipped regions are
+    /// represented by [`StatementData::Error`](crate::ast::StatementData::Error) and
+    /// [`ExternalDeclarationData::Error`](crate::ast::ExternalDeclarationData::Error) nodes, and
+    /// all the errors that were found are returned along with the partial result.
+    #[cfg(feature = "parser-recovering")]
+    pub fn parse_recovering(
+        self,
+    ) -> RecoveringParseResult<L::Iter, <L::Iter as HasLexerError>::Error, T> {
+        let mut errors = Vec::new();
+
+        match Self::parse_source(
+            self.source,
+            self.opts,
+            self.context,
+            self.lexer,
+            self.parser,
+            Some(&mut errors),
+        ) {
+            Ok((t, ctx, iter)) => Ok((t, errors, ctx, iter)),
+            Err(err) => {
+                errors.push(err);
+                Err(errors)
+            }
+        }
+    }
+
     /// Execute the parsing operation, and extract the wanted node
     #[allow(clippy::result_large_err)]
     pub fn extract<U: Extractable<T>>(
@@ -116,11 +173,33 @@ impl<'i, 'o, 'c, 'p, L: LangLexer<'i>, T: HasParser> ParseBuilder<'i, 'o, 'c, 'p
             self.context,
             self.lexer,
             self.parser,
+            None,
         )
         .map(|(root, ctx, l)| (U::extract(root), ctx, l))
     }
 }
 
+impl<'i, 'o, 'c, 'p, L: LangLexer<'i>> ParseBuilder<'i, 'o, 'c, 'p, L, ast::TranslationUnit> {
+    /// Parse the external declarations of the source one at a time
+    ///
+    /// Instead of returning the whole translation unit, this returns an iterator which parses
+    /// the external declarations as they are requested. See [`DeclarationStream`] for details.
+    /// The stream uses its own parser instance, so the one set using [`ParseBuilder::parser`] is
+    /// ignored.
+    pub fn stream(self) -> DeclarationStream<L::Iter> {
+        let default_opts = Default::default();
+        let opts = self.opts.unwrap_or(&default_opts);
+
+        let context = self
+            .context
+            .map(ParseContext::clone_inner)
+            .unwrap_or_default();
+        let lexer = self.lexer.unwrap_or_else(|| L::new(self.source, opts));
+
+        DeclarationStream::new(lexer.run(context.clone()), context)
+    }
+}
+
 impl<'i, 'o, 'c, 'p, T: HasParser> ParseBuilder<'i, 'o, 'c, 'p, super::DefaultLexer<'i>, T> {
     /// Create a new parse builder from the given input string
     pub fn default(source: <super::DefaultLexer<'i> as LangLexer<'i>>::Input) -> Self {
@@ -0,0 +1,640 @@
+//! Incremental reparsing of translation units
+
+use std::ops::Range;
+
+use glsl_lang_lexer::{min::str::Lexer, HasLexerError};
+use lang_util::{position::NodeSpan, TextRange, TextSize};
+
+use crate::{
+    ast,
+    visitor::{Host, HostMut, Visit, Visitor, VisitorMut},
+};
+
+use super::{ParseBuilder, ParseContext, ParseError, ParseOptions};
+
+/// Error type for incremental parsing
+pub type IncrementalParseError = ParseError<<Lexer<'static> as HasLexerError>::Error>;
+
+/// A change to a source string
+///
+/// The range of the edit is expressed in terms of the source text before the edit is applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Range of the replaced text
+    pub range: TextRange,
+    /// Text to insert in place of the replaced range
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Create a new edit replacing the given range
+    pub fn new(range: TextRange, replacement: impl Into<String>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Create a new edit inserting text at the given offset
+    pub fn insert(offset: TextSize, text: impl Into<String>) -> Self {
+        Self::new(TextRange::empty(offset), text)
+    }
+
+    /// Create a new edit deleting the given range
+    pub fn delete(range: TextRange) -> Self {
+        Self::new(range, String::new())
+    }
+}
+
+/// Outcome of applying an edit to an [`IncrementalParser`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reparse {
+    /// Only the external declarations around the edit were parsed again
+    Incremental {
+        /// Indices of the parsed external declarations in the updated translation unit
+        reparsed: Range<usize>,
+        /// Number of external declarations that were reused from the previous result
+        reused: usize,
+    },
+    /// The whole source was parsed again
+    Full,
+    /// The edited source failed to parse, see [`IncrementalParser::translation_unit`]
+    Failed,
+}
+
+/// Parser for sources that are repeatedly edited, such as documents in an editor
+///
+/// Sources are parsed with the [`min`](glsl_lang_lexer::min) lexer, i.e. preprocessor
+/// directives are represented in the AST but not evaluated. When applying an edit, only the
+/// external declarations overlapping the edited range are parsed again, and the ones following
+/// it are reused with their spans shifted. The parser falls back to parsing the whole source when
+/// the edit touches preprocessor directives or declarations of type names (such as structs),
+/// since those may change the meaning of the rest of the source.
+#[derive(Debug)]
+pub struct IncrementalParser {
+    source: String,
+    opts: ParseOptions,
+    result: Result<ast::TranslationUnit, IncrementalParseError>,
+}
+
+impl IncrementalParser {
+    /// Parse the given source with the default options
+    pub fn new(source: impl Into<String>) -> Self {
+        Self::new_with_options(source, &Default::default())
+    }
+
+    /// Parse the given source with the given options
+    pub fn new_with_options(source: impl Into<String>, opts: &ParseOptions) -> Self {
+        let source = source.into();
+        let result = Self::parse_source(&source, opts, &ParseContext::new());
+
+        Self {
+            source,
+            opts: *opts,
+            result,
+        }
+    }
+
+    /// Get the current source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the result of parsing the current source text
+    pub fn translation_unit(&self) -> Result<&ast::TranslationUnit, &IncrementalParseError> {
+        self.result.as_ref()
+    }
+
+    /// Consume this parser and return the result of parsing the current source text
+    pub fn into_translation_unit(self) -> Result<ast::TranslationUnit, IncrementalParseError> {
+        self.result
+    }
+
+    /// Apply an edit to the current source, and update the parse result
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of the edit is out of bounds or does not lie on UTF-8 character
+    /// boundaries.
+    pub fn edit(&mut self, edit: &TextEdit) -> Reparse {
+        let old_len = TextSize::of(self.source.as_str());
+        self.source
+            .replace_range(Range::<usize>::from(edit.range), &edit.replacement);
+
+        if let Some(reparse) = self.reparse_region(edit, old_len) {
+            return reparse;
+        }
+
+        self.result = Self::parse_source(&self.source, &self.opts, &ParseContext::new());
+        if self.result.is_ok() {
+            Reparse::Full
+        } else {
+            Reparse::Failed
+        }
+    }
+
+    fn parse_source(
+        source: &str,
+        opts: &ParseOptions,
+        ctx: &ParseContext,
+    ) -> Result<ast::TranslationUnit, IncrementalParseError> {
+        ParseBuilder::<Lexer, ast::TranslationUnit>::new(source)
+            .opts(opts)
+            .context(ctx)
+            .parse()
+            .map(|(tu, _ctx, _lexer)| tu)
+    }
+
+    /// Try to parse only the declarations affected by the edit, which has already been applied
+    /// to the source text
+    fn reparse_region(&mut self, edit: &TextEdit, old_len: TextSize) -> Option<Reparse> {
+        let tu = self.result.as_mut().ok()?;
+        let spans =
+            tu.0.iter()
+                .map(|decl| decl.span.map(|span| span.range()))
+                .collect::<Option<Vec<_>>>()?;
+
+        // Find the declarations touching the edited range
+        let first = spans.partition_point(|span| span.end() < edit.range.start());
+        let last = spans
+            .partition_point(|span| span.start() <= edit.range.end())
+            .max(first);
+
+        if tu.0[first..last].iter().any(|decl| !is_reparsable(decl)) {
+            return None;
+        }
+
+        // The reparsed region extends to the neighbouring declarations, so it includes any
+        // whitespace or comments around the edit
+        let region_start = first
+            .checked_sub(1)
+            .map(|prev| spans[prev].end())
+            .unwrap_or_default();
+        let old_region_end = spans.get(last).map(|span| span.start()).unwrap_or(old_len);
+        let new_region_end =
+            old_region_end + TextSize::of(edit.replacement.as_str()) - edit.range.len();
+
+        // Type names declared before the region must be known to parse it
+        let ctx = ParseContext::new();
+        let mut type_names = TypeNames::default();
+        for decl in &tu.0[..first] {
+            decl.visit(&mut type_names);
+        }
+        for name in type_names.0 {
+            ctx.add_type_name(name);
+        }
+
+        let mut region = Self::parse_source(
+            &self.source[TextRange::new(region_start, new_region_end)],
+            &self.opts,
+            &ctx,
+        )
+        .ok()?;
+
+        if !region.0.iter().all(is_reparsable) {
+            return None;
+        }
+
+        // Move the new declarations to their location in the source, and the following ones by
+        // the length difference of the edit
+        let mut shift = ShiftSpans {
+            delta: i64::from(u32::from(region_start)),
+        };
+        for decl in &mut region.0 {
+            decl.visit_mut(&mut shift);
+        }
+
+        shift.delta = i64::from(u32::from(new_region_end)) - i64::from(u32::from(old_region_end));
+        if shift.delta != 0 {
+            for decl in &mut tu.0[last..] {
+                decl.visit_mut(&mut shift);
+            }
+        }
+
+        let reparsed = first..(first + region.0.len());
+        tu.0.splice(first..last, region.0);
+
+        Some(Reparse::Incremental {
+            reused: tu.0.len() - reparsed.len(),
+            reparsed,
+        })
+    }
+}
+
+/// Return true if the given declaration doesn't change how the rest of the source is parsed
+fn is_reparsable(decl: &ast::ExternalDeclaration) -> bool {
+    if matches!(**decl, ast::ExternalDeclarationData::Preprocessor(_)) {
+        return false;
+    }
+
+    let mut type_names = TypeNames::default();
+    decl.visit(&mut type_names);
+    type_names.0.is_empty()
+}
+
+/// Visitor collecting the type names declared by a declaration
+#[derive(Default)]
+struct TypeNames(Vec<ast::Identifier>);
+
+impl Visitor for TypeNames {
+    fn visit_struct_specifier(&mut self, specifier: &ast::StructSpecifier) -> Visit {
+        if let Some(name) = &specifier.name {
+            self.0.push(ast::IdentifierData(name.0.clone()).into());
+        }
+
+        Visit::Children
+    }
+
+    fn visit_block(&mut self, block: &ast::Block) -> Visit {
+        if block.is_buffer_reference() {
+            self.0.push(block.name.clone());
+        }
+
+        Visit::Children
+    }
+
+    fn visit_declaration(&mut self, declaration: &ast::Declaration) -> Visit {
+        if let ast::DeclarationData::QualifierRedeclaration(qualifier, names) = &**declaration {
+            if qualifier.is_buffer_reference() {
+                self.0.extend(names.iter().cloned());
+            }
+        }
+
+        Visit::Children
+    }
+}
+
+/// Visitor moving the spans of all the nodes it visits
+struct ShiftSpans {
+    delta: i64,
+}
+
+impl ShiftSpans {
+    fn shift(&self, span: &mut Option<NodeSpan>) {
+        if let Some(span) = span {
+            let shift = |offset: TextSize| {
+                TextSize::from((i64::from(u32::from(offset)) + self.delta) as u32)
+            };
+
+            *span = NodeSpan::new(
+                span.source_id(),
+                TextRange::new(shift(span.range().start()), shift(span.range().end())),
+            );
+        }
+    }
+}
+
+impl VisitorMut for ShiftSpans {
+    fn visit_external_declaration(&mut self, node: &mut ast::ExternalDeclaration) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_identifier(&mut self, node: &mut ast::Identifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_arrayed_identifier(&mut self, node: &mut ast::ArrayedIdentifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_type_name(&mut self, node: &mut ast::TypeName) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_block(&mut self, node: &mut ast::Block) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_for_init_statement(&mut self, node: &mut ast::ForInitStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_for_rest_statement(&mut self, node: &mut ast::ForRestStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_function_definition(&mut self, node: &mut ast::FunctionDefinition) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_function_parameter_declarator(
+        &mut self,
+        node: &mut ast::FunctionParameterDeclarator,
+    ) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_function_prototype(&mut self, node: &mut ast::FunctionPrototype) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_init_declarator_list(&mut self, node: &mut ast::InitDeclaratorList) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_layout_qualifier(&mut self, node: &mut ast::LayoutQualifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor(&mut self, node: &mut ast::Preprocessor) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_define(&mut self, node: &mut ast::PreprocessorDefine) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_elseif(&mut self, node: &mut ast::PreprocessorElseIf) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_error(&mut self, node: &mut ast::PreprocessorError) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_extension(&mut self, node: &mut ast::PreprocessorExtension) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_extension_behavior(
+        &mut self,
+        node: &mut ast::PreprocessorExtensionBehavior,
+    ) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_extension_name(
+        &mut self,
+        node: &mut ast::PreprocessorExtensionName,
+    ) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_if(&mut self, node: &mut ast::PreprocessorIf) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_ifdef(&mut self, node: &mut ast::PreprocessorIfDef) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_ifndef(&mut self, node: &mut ast::PreprocessorIfNDef) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_include(&mut self, node: &mut ast::PreprocessorInclude) -> Visit {
+        self.shift(&mut node.span);
+        self.shift(&mut node.path.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_moj_import(&mut self, node: &mut ast::PreprocessorMojImport) -> Visit {
+        self.shift(&mut node.span);
+        self.shift(&mut node.path.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_line(&mut self, node: &mut ast::PreprocessorLine) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_pragma(&mut self, node: &mut ast::PreprocessorPragma) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_undef(&mut self, node: &mut ast::PreprocessorUndef) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_version(&mut self, node: &mut ast::PreprocessorVersion) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_preprocessor_version_profile(
+        &mut self,
+        node: &mut ast::PreprocessorVersionProfile,
+    ) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_selection_statement(&mut self, node: &mut ast::SelectionStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_selection_rest_statement(&mut self, node: &mut ast::SelectionRestStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_single_declaration(&mut self, node: &mut ast::SingleDeclaration) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_single_declaration_no_type(
+        &mut self,
+        node: &mut ast::SingleDeclarationNoType,
+    ) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_struct_field_specifier(&mut self, node: &mut ast::StructFieldSpecifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_struct_specifier(&mut self, node: &mut ast::StructSpecifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_switch_statement(&mut self, node: &mut ast::SwitchStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_type_qualifier(&mut self, node: &mut ast::TypeQualifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_type_specifier(&mut self, node: &mut ast::TypeSpecifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_full_specified_type(&mut self, node: &mut ast::FullySpecifiedType) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_array_specifier(&mut self, node: &mut ast::ArraySpecifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_array_specifier_dimension(
+        &mut self,
+        node: &mut ast::ArraySpecifierDimension,
+    ) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_attribute(&mut self, node: &mut ast::Attribute) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_assignment_op(&mut self, node: &mut ast::AssignmentOp) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_binary_op(&mut self, node: &mut ast::BinaryOp) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_case_label(&mut self, node: &mut ast::CaseLabel) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_condition(&mut self, node: &mut ast::Condition) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_declaration(&mut self, node: &mut ast::Declaration) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_expr(&mut self, node: &mut ast::Expr) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_fun_identifier(&mut self, node: &mut ast::FunIdentifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_function_parameter_declaration(
+        &mut self,
+        node: &mut ast::FunctionParameterDeclaration,
+    ) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_initializer(&mut self, node: &mut ast::Initializer) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_interpolation_qualifier(&mut self, node: &mut ast::InterpolationQualifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_iteration_statement(&mut self, node: &mut ast::IterationStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_jump_statement(&mut self, node: &mut ast::JumpStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_layout_qualifier_spec(&mut self, node: &mut ast::LayoutQualifierSpec) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_spirv_qualifier(&mut self, node: &mut ast::SpirvQualifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_spirv_argument(&mut self, node: &mut ast::SpirvArgument) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_spirv_value(&mut self, node: &mut ast::SpirvValue) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_precision_qualifier(&mut self, node: &mut ast::PrecisionQualifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_statement(&mut self, node: &mut ast::Statement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_compound_statement(&mut self, node: &mut ast::CompoundStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_storage_qualifier(&mut self, node: &mut ast::StorageQualifier) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_type_qualifier_spec(&mut self, node: &mut ast::TypeQualifierSpec) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_type_specifier_non_array(&mut self, node: &mut ast::TypeSpecifierNonArray) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_unary_op(&mut self, node: &mut ast::UnaryOp) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+
+    fn visit_expr_statement(&mut self, node: &mut ast::ExprStatement) -> Visit {
+        self.shift(&mut node.span);
+        Visit::Children
+    }
+}
@@ -10,7 +10,8 @@ use super::{DefaultLexer, HasLexerError, ParseContext, ParseError, ParseOptions}
 /// This allows us to parse specific AST items even though we don't export a LALR parser for it.
 /// Due to the way it is currently implemented, we have to generate extra code around the input,
 /// thus, if you are matching on span positions, you will get a different result than if using the
-/// parser directly.
+/// parser directly. If you need exact spans, enable the `parser-*` feature for the node type you
+/// are parsing and use [`Parse`](super::Parse) instead.
 pub trait Parsable: Sized {
     /// Parse the input source
     fn parse(source: &str) -> Result<Self, ParseError<<DefaultLexer as HasLexerError>::Error>> {
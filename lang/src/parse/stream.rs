@@ -0,0 +1,183 @@
+//! Streaming parser for external declarations
+
+use std::collections::VecDeque;
+
+use glsl_lang_lexer::{HasLexerError, LangLexerIterator, Token};
+use lang_util::position::LexerPosition;
+
+use crate::ast;
+
+use super::{HasParser, LangParser, ParseContext, ParseError};
+
+type LexerItem<E> = Result<(LexerPosition, Token, LexerPosition), E>;
+
+/// Iterator over the external declarations of a source, parsed as they are needed
+///
+/// The source is split into chunks at top-level `;` and `}` tokens, and each chunk is parsed on
+/// its own as soon as the next declaration is requested, so the whole
+/// [`TranslationUnit`](ast::TranslationUnit) is never materialized. If a chunk turns out to be
+/// incomplete (such as a struct declaration stopped at its closing brace), it is extended up to
+/// the next candidate boundary.
+///
+/// The same [`ParseContext`] is used for all chunks, so type names declared by earlier
+/// declarations are known when parsing the following ones.
+///
+/// The iterator stops after the first error.
+pub struct DeclarationStream<I: LangLexerIterator> {
+    iter: I,
+    ctx: ParseContext,
+    parser: <ast::TranslationUnit as HasParser>::Parser,
+    pending: VecDeque<ast::ExternalDeclaration>,
+    finished: bool,
+}
+
+impl<I: LangLexerIterator> DeclarationStream<I> {
+    pub(super) fn new(iter: I, ctx: ParseContext) -> Self {
+        Self {
+            iter,
+            ctx,
+            parser: LangParser::new(),
+            pending: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Get the parsing context, which holds the type names declared so far
+    pub fn context(&self) -> &ParseContext {
+        &self.ctx
+    }
+
+    /// Consume this stream and return the parsing context and the underlying lexer
+    pub fn into_inner(self) -> (ParseContext, I) {
+        (self.ctx, self.iter)
+    }
+
+    /// Parse the next chunk of the input, and queue the resulting declarations
+    #[allow(clippy::result_large_err)]
+    fn parse_chunk(&mut self) -> Result<(), ParseError<I::Error>> {
+        let mut replay = Vec::new();
+
+        loop {
+            let mut chunk = Chunk::new(&mut self.iter, replay);
+            let result = LangParser::parse(&self.parser, self.ctx.clone(), &mut chunk);
+            let exhausted = chunk.exhausted;
+            replay = chunk.tokens;
+
+            match result {
+                Ok(tu) => {
+                    self.pending.extend(tu.0);
+                    self.finished = exhausted;
+                    return Ok(());
+                }
+                Err(lalrpop_util::ParseError::UnrecognizedEof { .. }) if !exhausted => {
+                    // The chunk ended in the middle of a declaration, extend it
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Err(self.iter.resolve_err(err));
+                }
+            }
+        }
+    }
+}
+
+impl<I: LangLexerIterator> Iterator for DeclarationStream<I> {
+    type Item = Result<ast::ExternalDeclaration, ParseError<I::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(decl) = self.pending.pop_front() {
+                return Some(Ok(decl));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            if let Err(err) = self.parse_chunk() {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+/// Token iterator ending at the next top-level `;` or `}`
+///
+/// Tokens are pulled lazily from the lexer, so declarations are reduced (and type names
+/// registered) in the same order as when parsing the whole source. The tokens are recorded so
+/// they can be replayed if the chunk needs to be extended.
+struct Chunk<'a, I: HasLexerError> {
+    inner: &'a mut I,
+    /// Tokens yielded so far, starting with the ones replayed from a previous attempt
+    tokens: Vec<(LexerPosition, Token, LexerPosition)>,
+    replay_len: usize,
+    pos: usize,
+    depth: usize,
+    at_boundary: bool,
+    exhausted: bool,
+}
+
+impl<'a, I: HasLexerError> Chunk<'a, I> {
+    fn new(inner: &'a mut I, tokens: Vec<(LexerPosition, Token, LexerPosition)>) -> Self {
+        Self {
+            inner,
+            replay_len: tokens.len(),
+            tokens,
+            pos: 0,
+            depth: 0,
+            at_boundary: false,
+            exhausted: false,
+        }
+    }
+
+    fn track(&mut self, token: &Token) {
+        match token {
+            Token::LeftBrace => self.depth += 1,
+            Token::RightBrace => {
+                self.depth = self.depth.saturating_sub(1);
+                self.at_boundary = self.depth == 0;
+            }
+            Token::Semicolon => self.at_boundary = self.depth == 0,
+            _ => {}
+        }
+    }
+}
+
+impl<I: HasLexerError> HasLexerError for Chunk<'_, I> {
+    type Error = I::Error;
+}
+
+impl<I: Iterator<Item = LexerItem<<I as HasLexerError>::Error>> + HasLexerError> Iterator
+    for Chunk<'_, I>
+{
+    type Item = LexerItem<I::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos < self.replay_len {
+            // Replayed tokens end at a boundary that we now know to be incomplete, so only track
+            // the nesting depth
+            let token = self.tokens[self.pos].clone();
+            self.pos += 1;
+            self.track(&token.1);
+            self.at_boundary = false;
+            return Some(Ok(token));
+        }
+
+        if self.at_boundary {
+            return None;
+        }
+
+        match self.inner.next() {
+            Some(Ok(token)) => {
+                self.track(&token.1);
+                self.tokens.push(token.clone());
+                Some(Ok(token))
+            }
+            Some(Err(err)) => Some(Err(err)),
+            None => {
+                self.exhausted = true;
+                None
+            }
+        }
+    }
+}
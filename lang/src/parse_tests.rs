@@ -76,7 +76,7 @@ fn parse_array_specifier_dimension_unsized() {
 
 #[test]
 fn parse_array_specifier_dimension_sized() {
-    let ix: ast::Expr = ast::ExprData::IntConst(0).into();
+    let ix: ast::Expr = ast::ExprData::IntConst(0.into()).into();
 
     assert_eq!(
         ast::ArraySpecifierDimension::parse("[0]"),
@@ -101,7 +101,7 @@ fn parse_array_specifier_unsized() {
 
 #[test]
 fn parse_array_specifier_sized() {
-    let ix: ast::Expr = ast::ExprData::IntConst(123).into();
+    let ix: ast::Expr = ast::ExprData::IntConst(123.into()).into();
 
     assert_eq!(
         ast::ArraySpecifier::parse("[123]"),
@@ -116,9 +116,9 @@ fn parse_array_specifier_sized() {
 
 #[test]
 fn parse_array_specifier_sized_multiple() {
-    let a = ast::ExprData::IntConst(2);
-    let b = ast::ExprData::IntConst(100);
-    let d = ast::ExprData::IntConst(5);
+    let a = ast::ExprData::IntConst(2.into());
+    let b = ast::ExprData::IntConst(100.into());
+    let d = ast::ExprData::IntConst(5.into());
 
     assert_eq!(
         ast::ArraySpecifier::parse("[2][100][][5]"),
@@ -148,6 +148,22 @@ fn parse_interpolation_qualifier() {
         ast::InterpolationQualifier::parse("noperspective"),
         Ok(ast::InterpolationQualifierData::NoPerspective.into())
     );
+    assert_eq!(
+        ast::InterpolationQualifier::parse("perprimitiveEXT"),
+        Ok(ast::InterpolationQualifierData::PerPrimitiveExt.into())
+    );
+    assert_eq!(
+        ast::InterpolationQualifier::parse("perprimitiveNV"),
+        Ok(ast::InterpolationQualifierData::PerPrimitiveNv.into())
+    );
+    assert_eq!(
+        ast::InterpolationQualifier::parse("perviewNV"),
+        Ok(ast::InterpolationQualifierData::PerViewNv.into())
+    );
+    assert_eq!(
+        ast::InterpolationQualifier::parse("taskNV"),
+        Ok(ast::InterpolationQualifierData::TaskNv.into())
+    );
 }
 
 #[test]
@@ -232,6 +248,34 @@ fn parse_storage_qualifier() {
         ast::StorageQualifier::parse("subroutine"),
         Ok(ast::StorageQualifierData::Subroutine(vec![]).into())
     );
+    assert_eq!(
+        ast::StorageQualifier::parse("shadercallcoherent"),
+        Ok(ast::StorageQualifierData::ShaderCallCoherent.into())
+    );
+    assert_eq!(
+        ast::StorageQualifier::parse("rayPayloadEXT"),
+        Ok(ast::StorageQualifierData::RayPayloadExt.into())
+    );
+    assert_eq!(
+        ast::StorageQualifier::parse("rayPayloadInEXT"),
+        Ok(ast::StorageQualifierData::RayPayloadInExt.into())
+    );
+    assert_eq!(
+        ast::StorageQualifier::parse("hitAttributeEXT"),
+        Ok(ast::StorageQualifierData::HitAttributeExt.into())
+    );
+    assert_eq!(
+        ast::StorageQualifier::parse("callableDataEXT"),
+        Ok(ast::StorageQualifierData::CallableDataExt.into())
+    );
+    assert_eq!(
+        ast::StorageQualifier::parse("callableDataInEXT"),
+        Ok(ast::StorageQualifierData::CallableDataInExt.into())
+    );
+    assert_eq!(
+        ast::StorageQualifier::parse("taskPayloadSharedEXT"),
+        Ok(ast::StorageQualifierData::TaskPayloadSharedExt.into())
+    );
 }
 
 #[test]
@@ -280,7 +324,7 @@ fn parse_layout_qualifier_list() {
     let id_1 = ast::LayoutQualifierSpecData::Identifier("std140".into_node(), None);
     let id_2 = ast::LayoutQualifierSpecData::Identifier(
         "max_vertices".into_node(),
-        Some(Box::new(ast::ExprData::IntConst(3).into())),
+        Some(Box::new(ast::ExprData::IntConst(3.into()).into())),
     );
     let expected: ast::LayoutQualifier = ast::LayoutQualifierData {
         ids: vec![id_0.into(), id_1.into(), id_2.into()],
@@ -308,7 +352,7 @@ fn parse_type_qualifier() {
     let id_1 = ast::LayoutQualifierSpecData::Identifier("std140".into_node(), None);
     let id_2 = ast::LayoutQualifierSpecData::Identifier(
         "max_vertices".into_node(),
-        Some(Box::new(ast::ExprData::IntConst(3).into())),
+        Some(Box::new(ast::ExprData::IntConst(3.into()).into())),
     );
     let layout_qual = ast::TypeQualifierSpecData::Layout(
         ast::LayoutQualifierData {
@@ -1020,7 +1064,7 @@ fn parse_type_specifier() {
             array_specifier: Some(
                 ast::ArraySpecifierData {
                     dimensions: vec![ast::ArraySpecifierDimensionData::ExplicitlySized(Box::new(
-                        ast::ExprData::IntConst(35).into()
+                        ast::ExprData::IntConst(35.into()).into()
                     ))
                     .into()]
                 }
@@ -1104,11 +1148,11 @@ fn parse_fully_specified_type_with_qualifier() {
 fn parse_primary_expr_intconst() {
     assert_eq!(
         ast::Expr::parse("0 "),
-        Ok(ast::ExprData::IntConst(0).into())
+        Ok(ast::ExprData::IntConst(0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("1 "),
-        Ok(ast::ExprData::IntConst(1).into())
+        Ok(ast::ExprData::IntConst(1.into()).into())
     );
 }
 
@@ -1116,11 +1160,11 @@ fn parse_primary_expr_intconst() {
 fn parse_primary_expr_uintconst() {
     assert_eq!(
         ast::Expr::parse("0u "),
-        Ok(ast::ExprData::UIntConst(0).into())
+        Ok(ast::ExprData::UIntConst(0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("1u "),
-        Ok(ast::ExprData::UIntConst(1).into())
+        Ok(ast::ExprData::UIntConst(1.into()).into())
     );
 }
 
@@ -1128,19 +1172,19 @@ fn parse_primary_expr_uintconst() {
 fn parse_primary_expr_floatconst() {
     assert_eq!(
         ast::Expr::parse("0.f "),
-        Ok(ast::ExprData::FloatConst(0.).into())
+        Ok(ast::ExprData::FloatConst(0.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("1.f "),
-        Ok(ast::ExprData::FloatConst(1.).into())
+        Ok(ast::ExprData::FloatConst(1.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("0.F "),
-        Ok(ast::ExprData::FloatConst(0.).into())
+        Ok(ast::ExprData::FloatConst(0.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("1.F "),
-        Ok(ast::ExprData::FloatConst(1.).into())
+        Ok(ast::ExprData::FloatConst(1.0.into()).into())
     );
 }
 
@@ -1148,27 +1192,85 @@ fn parse_primary_expr_floatconst() {
 fn parse_primary_expr_doubleconst() {
     assert_eq!(
         ast::Expr::parse("0. "),
-        Ok(ast::ExprData::FloatConst(0.).into())
+        Ok(ast::ExprData::FloatConst(0.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("1. "),
-        Ok(ast::ExprData::FloatConst(1.).into())
+        Ok(ast::ExprData::FloatConst(1.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("0.lf "),
-        Ok(ast::ExprData::DoubleConst(0.).into())
+        Ok(ast::ExprData::DoubleConst(0.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("1.lf "),
-        Ok(ast::ExprData::DoubleConst(1.).into())
+        Ok(ast::ExprData::DoubleConst(1.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("0.LF "),
-        Ok(ast::ExprData::DoubleConst(0.).into())
+        Ok(ast::ExprData::DoubleConst(0.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("1.LF "),
-        Ok(ast::ExprData::DoubleConst(1.).into())
+        Ok(ast::ExprData::DoubleConst(1.0.into()).into())
+    );
+}
+
+#[test]
+fn parse_primary_expr_explicit_arithmetic_consts() {
+    assert_eq!(
+        ast::Expr::parse("12s "),
+        Ok(ast::ExprData::Int16Const(12i16.into()).into())
+    );
+    assert_eq!(
+        ast::Expr::parse("0xffffus "),
+        Ok(ast::ExprData::UInt16Const(0xffffu16.into()).into())
+    );
+    assert_eq!(
+        ast::Expr::parse("4000000000l "),
+        Ok(ast::ExprData::Int64Const(4000000000i64.into()).into())
+    );
+    assert_eq!(
+        ast::Expr::parse("18446744073709551615UL "),
+        Ok(ast::ExprData::UInt64Const(u64::MAX.into()).into())
+    );
+    assert_eq!(
+        ast::Expr::parse("1.5hf "),
+        Ok(ast::ExprData::Float16Const(1.5.into()).into())
+    );
+}
+
+#[test]
+fn parse_explicit_arithmetic_types() {
+    let tu = ast::TranslationUnit::parse(
+        "#extension GL_EXT_shader_explicit_arithmetic_types : enable
+int8_t a;
+u16vec3 b;
+f64mat2x3 c;
+",
+    )
+    .unwrap();
+
+    let types: Vec<_> =
+        tu.0.iter()
+            .filter_map(|decl| match &**decl {
+                ast::ExternalDeclarationData::Declaration(decl) => match &**decl {
+                    ast::DeclarationData::InitDeclaratorList(list) => {
+                        Some(list.head.ty.ty.ty.content.clone())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+    assert_eq!(
+        types,
+        vec![
+            ast::TypeSpecifierNonArrayData::Int8,
+            ast::TypeSpecifierNonArrayData::U16Vec3,
+            ast::TypeSpecifierNonArrayData::F64Mat23,
+        ]
     );
 }
 
@@ -1188,19 +1290,19 @@ fn parse_primary_expr_boolconst() {
 fn parse_primary_expr_parens() {
     assert_eq!(
         ast::Expr::parse("(0)"),
-        Ok(ast::ExprData::IntConst(0).into())
+        Ok(ast::ExprData::IntConst(0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("(  0 )"),
-        Ok(ast::ExprData::IntConst(0).into())
+        Ok(ast::ExprData::IntConst(0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("(  .0 )"),
-        Ok(ast::ExprData::FloatConst(0.).into())
+        Ok(ast::ExprData::FloatConst(0.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("(  (.0) )"),
-        Ok(ast::ExprData::FloatConst(0.).into())
+        Ok(ast::ExprData::FloatConst(0.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("(true) "),
@@ -1224,7 +1326,7 @@ fn parse_postfix_function_call_no_args() {
 #[test]
 fn parse_postfix_function_call_one_arg() {
     let fun = ast::FunIdentifierData::ident("foo");
-    let args = vec![ast::ExprData::IntConst(0).into()];
+    let args = vec![ast::ExprData::IntConst(0.into()).into()];
     let expected: ast::Expr = ast::ExprData::FunCall(fun.into(), args).into();
 
     assert_eq!(ast::Expr::parse("foo(0)"), Ok(expected.clone()));
@@ -1236,7 +1338,7 @@ fn parse_postfix_function_call_one_arg() {
 fn parse_postfix_function_call_multi_arg() {
     let fun = ast::FunIdentifierData::ident("foo");
     let args = vec![
-        ast::ExprData::IntConst(0).into(),
+        ast::ExprData::IntConst(0.into()).into(),
         ast::ExprData::BoolConst(false).into(),
         ast::ExprData::Variable("bar".into_node()).into(),
     ];
@@ -1252,8 +1354,11 @@ fn parse_postfix_function_call_multi_arg() {
 #[test]
 fn parse_postfix_expr_bracket() {
     let id = ast::ExprData::Variable("foo".into_node()).into();
-    let expected: ast::Expr =
-        ast::ExprData::Bracket(Box::new(id), Box::new(ast::ExprData::IntConst(7354).into())).into();
+    let expected: ast::Expr = ast::ExprData::Bracket(
+        Box::new(id),
+        Box::new(ast::ExprData::IntConst(7354.into()).into()),
+    )
+    .into();
 
     assert_eq!(ast::Expr::parse("foo[7354]"), Ok(expected.clone()));
     assert_eq!(ast::Expr::parse("foo[\n  7354    ]"), Ok(expected));
@@ -1353,21 +1458,21 @@ fn parse_unary_dec() {
 fn parse_expr_float() {
     assert_eq!(
         ast::Expr::parse("314."),
-        Ok(ast::ExprData::FloatConst(314.).into())
+        Ok(ast::ExprData::FloatConst(314.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("314.f"),
-        Ok(ast::ExprData::FloatConst(314.).into())
+        Ok(ast::ExprData::FloatConst(314.0.into()).into())
     );
     assert_eq!(
         ast::Expr::parse("314.LF"),
-        Ok(ast::ExprData::DoubleConst(314.).into())
+        Ok(ast::ExprData::DoubleConst(314.0.into()).into())
     );
 }
 
 #[test]
 fn parse_expr_add_2() {
-    let one: Box<ast::Expr> = Box::new(ast::ExprData::IntConst(1).into());
+    let one: Box<ast::Expr> = Box::new(ast::ExprData::IntConst(1.into()).into());
     let expected: ast::Expr =
         ast::ExprData::Binary(ast::BinaryOpData::Add.into(), one.clone(), one).into();
 
@@ -1378,9 +1483,9 @@ fn parse_expr_add_2() {
 
 #[test]
 fn parse_expr_add_3() {
-    let one = Box::new(ast::ExprData::UIntConst(1).into());
-    let two = Box::new(ast::ExprData::UIntConst(2).into());
-    let three = Box::new(ast::ExprData::UIntConst(3).into());
+    let one = Box::new(ast::ExprData::UIntConst(1.into()).into());
+    let two = Box::new(ast::ExprData::UIntConst(2.into()).into());
+    let three = Box::new(ast::ExprData::UIntConst(3.into()).into());
     let expected: ast::Expr = ast::ExprData::Binary(
         ast::BinaryOpData::Add.into(),
         Box::new(ast::ExprData::Binary(ast::BinaryOpData::Add.into(), one, two).into()),
@@ -1395,9 +1500,9 @@ fn parse_expr_add_3() {
 
 #[test]
 fn parse_expr_add_mult_3() {
-    let one = Box::new(ast::ExprData::UIntConst(1).into());
-    let two = Box::new(ast::ExprData::UIntConst(2).into());
-    let three: Box<ast::Expr> = Box::new(ast::ExprData::UIntConst(3).into());
+    let one = Box::new(ast::ExprData::UIntConst(1.into()).into());
+    let two = Box::new(ast::ExprData::UIntConst(2.into()).into());
+    let three: Box<ast::Expr> = Box::new(ast::ExprData::UIntConst(3.into()).into());
     let expected: ast::Expr = ast::ExprData::Binary(
         ast::BinaryOpData::Add.into(),
         Box::new(ast::ExprData::Binary(ast::BinaryOpData::Mult.into(), one, two).into()),
@@ -1412,12 +1517,12 @@ fn parse_expr_add_mult_3() {
 
 #[test]
 fn parse_expr_add_sub_mult_div() {
-    let one = Box::new(ast::ExprData::IntConst(1).into());
-    let two = Box::new(ast::ExprData::IntConst(2).into());
-    let three = Box::new(ast::ExprData::IntConst(3).into());
-    let four = Box::new(ast::ExprData::IntConst(4).into());
-    let five = Box::new(ast::ExprData::IntConst(5).into());
-    let six = Box::new(ast::ExprData::IntConst(6).into());
+    let one = Box::new(ast::ExprData::IntConst(1.into()).into());
+    let two = Box::new(ast::ExprData::IntConst(2.into()).into());
+    let three = Box::new(ast::ExprData::IntConst(3.into()).into());
+    let four = Box::new(ast::ExprData::IntConst(4.into()).into());
+    let five = Box::new(ast::ExprData::IntConst(5.into()).into());
+    let six = Box::new(ast::ExprData::IntConst(6.into()).into());
     let expected = ast::ExprData::Binary(
         ast::BinaryOpData::Add.into(),
         Box::new(
@@ -1445,7 +1550,7 @@ fn parse_expr_add_sub_mult_div() {
 #[test]
 fn parse_complex_expr() {
     let input = "normalize((inverse(view) * vec4(ray.dir, 0.)).xyz)";
-    let zero = ast::ExprData::FloatConst(0.);
+    let zero = ast::ExprData::FloatConst(0.0.into());
     let ray = ast::ExprData::Variable("ray".into_node());
     let raydir = ast::ExprData::Dot(Box::new(ray.into()), "dir".into_node());
     let vec4 = ast::ExprData::FunCall(
@@ -1521,7 +1626,7 @@ fn parse_function_identifier_cast_array_sized() {
             array_specifier: Some(
                 ast::ArraySpecifierData {
                     dimensions: vec![ast::ArraySpecifierDimensionData::ExplicitlySized(Box::new(
-                        ast::ExprData::IntConst(12).into(),
+                        ast::ExprData::IntConst(12.into()).into(),
                     ))
                     .into()],
                 }
@@ -1590,7 +1695,7 @@ fn parse_expr_statement() {
         ast::ExprData::Assignment(
             Box::new(ast::ExprData::Variable("foo".into_node()).into()),
             ast::AssignmentOpData::Equal.into(),
-            Box::new(ast::ExprData::FloatConst(314.).into()),
+            Box::new(ast::ExprData::FloatConst(314.0.into()).into()),
         )
         .into(),
     ))
@@ -1678,7 +1783,8 @@ fn parse_declaration_init_declarator_list_single() {
         name: Some("foo".into_node()),
         array_specifier: None,
         initializer: Some(
-            ast::InitializerData::Simple(Box::new(ast::ExprData::IntConst(34).into())).into(),
+            ast::InitializerData::Simple(Box::new(ast::ExprData::IntConst(34.into()).into()))
+                .into(),
         ),
     };
     let idl = ast::InitDeclaratorListData {
@@ -1713,13 +1819,15 @@ fn parse_declaration_init_declarator_list_complex() {
         name: Some("foo".into_node()),
         array_specifier: None,
         initializer: Some(
-            ast::InitializerData::Simple(Box::new(ast::ExprData::IntConst(34).into())).into(),
+            ast::InitializerData::Simple(Box::new(ast::ExprData::IntConst(34.into()).into()))
+                .into(),
         ),
     };
     let sdnt = ast::SingleDeclarationNoTypeData {
         ident: "bar".into_node(),
         initializer: Some(
-            ast::InitializerData::Simple(Box::new(ast::ExprData::IntConst(12).into())).into(),
+            ast::InitializerData::Simple(Box::new(ast::ExprData::IntConst(12.into()).into()))
+                .into(),
         ),
     };
     let expected: ast::Declaration = ast::DeclarationData::InitDeclaratorList(
@@ -1936,7 +2044,7 @@ fn parse_selection_statement_if() {
     let cond = ast::ExprData::Binary(
         ast::BinaryOpData::Lt.into(),
         Box::new(ast::ExprData::Variable("foo".into_node()).into()),
-        Box::new(ast::ExprData::IntConst(10).into()),
+        Box::new(ast::ExprData::IntConst(10.into()).into()),
     );
     let ret = Box::new(ast::ExprData::BoolConst(false).into());
     let st = ast::StatementData::Jump(ast::JumpStatementData::Return(Some(ret)).into());
@@ -1968,9 +2076,9 @@ fn parse_selection_statement_if_else() {
     let cond = ast::ExprData::Binary(
         ast::BinaryOpData::Lt.into(),
         Box::new(ast::ExprData::Variable("foo".into_node()).into()),
-        Box::new(ast::ExprData::IntConst(10).into()),
+        Box::new(ast::ExprData::IntConst(10.into()).into()),
     );
-    let if_ret = Box::new(ast::ExprData::FloatConst(0.).into());
+    let if_ret = Box::new(ast::ExprData::FloatConst(0.0.into()).into());
     let if_st = ast::StatementData::Jump(ast::JumpStatementData::Return(Some(if_ret)).into());
     let if_body = ast::StatementData::Compound(
         ast::CompoundStatementData {
@@ -2034,13 +2142,14 @@ fn parse_switch_statement_empty() {
 fn parse_switch_statement_cases() {
     let head = Box::new(ast::ExprData::Variable("foo".into_node()).into());
     let case0 = ast::StatementData::CaseLabel(
-        ast::CaseLabelData::Case(Box::new(ast::ExprData::IntConst(0).into())).into(),
+        ast::CaseLabelData::Case(Box::new(ast::ExprData::IntConst(0.into()).into())).into(),
     );
     let case1 = ast::StatementData::CaseLabel(
-        ast::CaseLabelData::Case(Box::new(ast::ExprData::IntConst(1).into())).into(),
+        ast::CaseLabelData::Case(Box::new(ast::ExprData::IntConst(1.into()).into())).into(),
     );
     let ret = ast::StatementData::Jump(
-        ast::JumpStatementData::Return(Some(Box::new(ast::ExprData::UIntConst(12).into()))).into(),
+        ast::JumpStatementData::Return(Some(Box::new(ast::ExprData::UIntConst(12.into()).into())))
+            .into(),
     );
     let expected: ast::SwitchStatement = ast::SwitchStatementData {
         head,
@@ -2069,7 +2178,7 @@ fn parse_case_label_def() {
 #[test]
 fn parse_case_label() {
     let expected: ast::CaseLabel =
-        ast::CaseLabelData::Case(Box::new(ast::ExprData::IntConst(3).into())).into();
+        ast::CaseLabelData::Case(Box::new(ast::ExprData::IntConst(3.into()).into())).into();
 
     assert_eq!(ast::CaseLabel::parse("case 3:"), Ok(expected.clone()));
     assert_eq!(ast::CaseLabel::parse("case\n\t 3   :"), Ok(expected));
@@ -2161,7 +2270,7 @@ fn parse_iteration_statement_for_empty() {
                     array_specifier: None,
                     initializer: Some(
                         ast::InitializerData::Simple(Box::new(
-                            ast::ExprData::FloatConst(0.).into(),
+                            ast::ExprData::FloatConst(0.0.into()).into(),
                         ))
                         .into(),
                     ),
@@ -2180,7 +2289,7 @@ fn parse_iteration_statement_for_empty() {
                 ast::ExprData::Binary(
                     ast::BinaryOpData::Lte.into(),
                     Box::new(ast::ExprData::Variable("i".into_node()).into()),
-                    Box::new(ast::ExprData::FloatConst(10.).into()),
+                    Box::new(ast::ExprData::FloatConst(10.0.into()).into()),
                 )
                 .into(),
             )
@@ -2220,6 +2329,81 @@ fn parse_iteration_statement_for_empty() {
     );
 }
 
+#[test]
+fn parse_iteration_statement_attributes() {
+    let attributes = vec![
+        ast::AttributeData {
+            name: "unroll".into_node(),
+            arg: None,
+        }
+        .into(),
+        ast::AttributeData {
+            name: "dependency_length".into_node(),
+            arg: Some(Box::new(ast::ExprData::IntConst(2.into()).into())),
+        }
+        .into(),
+    ];
+    let cond = ast::ConditionData::Expr(ast::ExprData::BoolConst(true).into()).into();
+    let st = ast::StatementData::Compound(
+        ast::CompoundStatementData {
+            statement_list: Vec::new(),
+        }
+        .into(),
+    );
+    let expected: ast::Statement = ast::StatementData::Attributed(
+        ast::AttributedStatementData {
+            attributes,
+            statement: Box::new(
+                ast::StatementData::Iteration(
+                    ast::IterationStatementData::While(cond, Box::new(st.into())).into(),
+                )
+                .into(),
+            ),
+        }
+        .into(),
+    )
+    .into();
+
+    assert_eq!(
+        parse_attributed_body("[[unroll, dependency_length(2)]] while (true) {}"),
+        expected
+    );
+    assert_eq!(
+        parse_attributed_body("[ [unroll,dependency_length( 2 )] ]\nwhile(true){}"),
+        expected
+    );
+}
+
+/// Parse the first external declaration of a translation unit which enables attributes
+fn parse_attributed_declaration(src: &str) -> ast::ExternalDeclarationData {
+    let tu = ast::TranslationUnit::parse(&format!(
+        "#extension GL_EXT_control_flow_attributes : enable\n{}",
+        src
+    ))
+    .unwrap();
+
+    // The min lexer keeps the #extension directive as an external declaration
+    tu.0.into_iter()
+        .map(ast::Node::into_inner)
+        .find(|decl| !matches!(decl, ast::ExternalDeclarationData::Preprocessor(_)))
+        .unwrap()
+}
+
+/// Parse the first statement of a function body which enables attributes
+fn parse_attributed_body(src: &str) -> ast::Statement {
+    let ast::ExternalDeclarationData::FunctionDefinition(fd) =
+        parse_attributed_declaration(&format!("void main() {{ {} }}", src))
+    else {
+        panic!("expected a function definition");
+    };
+
+    fd.into_inner()
+        .statement
+        .into_inner()
+        .statement_list
+        .remove(0)
+}
+
 #[test]
 fn parse_jump_continue() {
     assert_eq!(
@@ -2239,7 +2423,7 @@ fn parse_jump_break() {
 #[test]
 fn parse_jump_return() {
     let expected =
-        ast::JumpStatementData::Return(Some(Box::new(ast::ExprData::IntConst(3).into())));
+        ast::JumpStatementData::Return(Some(Box::new(ast::ExprData::IntConst(3.into()).into())));
     assert_eq!(ast::JumpStatement::parse("return 3;"), Ok(expected.into()));
 }
 
@@ -2250,6 +2434,232 @@ fn parse_jump_empty_return() {
     assert_eq!(ast::Statement::parse("return;"), Ok(expected));
 }
 
+#[test]
+fn parse_jump_ignore_intersection() {
+    assert_eq!(
+        ast::JumpStatement::parse("ignoreIntersectionEXT;"),
+        Ok(ast::JumpStatementData::IgnoreIntersectionExt.into())
+    );
+}
+
+#[test]
+fn parse_jump_terminate_ray() {
+    assert_eq!(
+        ast::JumpStatement::parse("terminateRayEXT;"),
+        Ok(ast::JumpStatementData::TerminateRayExt.into())
+    );
+}
+
+#[test]
+fn parse_declaration_buffer_reference_forward() {
+    let qual = ast::TypeQualifierData {
+        qualifiers: vec![
+            ast::TypeQualifierSpecData::Layout(
+                ast::LayoutQualifierData {
+                    ids: vec![ast::LayoutQualifierSpecData::Identifier(
+                        "buffer_reference".into_node(),
+                        None,
+                    )
+                    .into()],
+                }
+                .into(),
+            )
+            .into(),
+            ast::TypeQualifierSpecData::Storage(ast::StorageQualifierData::Buffer.into()).into(),
+        ],
+    };
+    assert!(qual.is_buffer_reference());
+
+    assert_eq!(
+        ast::Declaration::parse("layout(buffer_reference) buffer Node"),
+        Ok(
+            ast::DeclarationData::QualifierRedeclaration(qual.into(), vec!["Node".into_node()])
+                .into()
+        )
+    );
+}
+
+#[test]
+fn parse_declaration_qualifier_redeclaration() {
+    let qual = ast::TypeQualifierData {
+        qualifiers: vec![ast::TypeQualifierSpecData::Precise.into()],
+    };
+
+    assert_eq!(
+        ast::Declaration::parse("precise a, b"),
+        Ok(ast::DeclarationData::QualifierRedeclaration(
+            qual.into(),
+            vec!["a".into_node(), "b".into_node()]
+        )
+        .into())
+    );
+}
+
+#[test]
+fn parse_buffer_reference_type_name() {
+    let ctx = ParseContext::new();
+    let (tu, _) = ast::TranslationUnit::parse_with_context(
+        "layout(buffer_reference) buffer Node;
+        layout(buffer_reference) buffer Node { Node next; int value; };
+        void main() { Node n; }",
+        &ctx,
+    )
+    .unwrap();
+
+    let block = match &*tu.0[1] {
+        ast::ExternalDeclarationData::Declaration(decl) => match &**decl {
+            ast::DeclarationData::Block(block) => block,
+            other => panic!("expected a block, got {other:?}"),
+        },
+        other => panic!("expected a declaration, got {other:?}"),
+    };
+
+    assert!(block.is_buffer_reference());
+    assert_eq!(block.name.as_str(), "Node");
+    assert_eq!(
+        *block.fields[0].ty.ty,
+        ast::TypeSpecifierNonArrayData::TypeName("Node".into_node())
+    );
+}
+
+#[test]
+fn parse_type_qualifier_spirv_decorate() {
+    let expected: ast::TypeQualifier = ast::TypeQualifierData {
+        qualifiers: vec![ast::TypeQualifierSpecData::Spirv(
+            ast::SpirvQualifierData::Decorate(vec![
+                ast::SpirvArgumentData::Requirement(
+                    "extensions".into_node(),
+                    vec![ast::SpirvValueData::String(
+                        "SPV_AMD_shader_explicit_vertex_parameter".into(),
+                    )
+                    .into()],
+                )
+                .into(),
+                ast::SpirvArgumentData::Value(
+                    ast::SpirvValueData::Expr(Box::new(ast::ExprData::IntConst(11.into()).into()))
+                        .into(),
+                )
+                .into(),
+                ast::SpirvArgumentData::Value(
+                    ast::SpirvValueData::Expr(Box::new(
+                        ast::ExprData::IntConst(4992.into()).into(),
+                    ))
+                    .into(),
+                )
+                .into(),
+            ])
+            .into(),
+        )
+        .into()],
+    }
+    .into();
+
+    assert_eq!(
+        parse_spirv_qualifier(
+            r#"spirv_decorate(extensions = ["SPV_AMD_shader_explicit_vertex_parameter"], 11, 4992)"#
+        ),
+        expected
+    );
+}
+
+#[test]
+fn parse_type_qualifier_spirv_string_escapes() {
+    let expected: ast::TypeQualifier = ast::TypeQualifierData {
+        qualifiers: vec![ast::TypeQualifierSpecData::Spirv(
+            ast::SpirvQualifierData::DecorateString(vec![
+                ast::SpirvArgumentData::Value(
+                    ast::SpirvValueData::Expr(Box::new(
+                        ast::ExprData::IntConst(5635.into()).into(),
+                    ))
+                    .into(),
+                )
+                .into(),
+                ast::SpirvArgumentData::Value(
+                    ast::SpirvValueData::String("a \"b\" \\ c\n".into()).into(),
+                )
+                .into(),
+            ])
+            .into(),
+        )
+        .into()],
+    }
+    .into();
+
+    assert_eq!(
+        parse_spirv_qualifier(r#"spirv_decorate_string(5635, "a \"b\" \\ c\n")"#),
+        expected
+    );
+}
+
+/// Parse the qualifier of a declaration in a translation unit which enables SPIR-V intrinsics
+fn parse_spirv_qualifier(qualifier: &str) -> ast::TypeQualifier {
+    let tu = ast::TranslationUnit::parse(&format!(
+        "#extension GL_EXT_spirv_intrinsics : enable\n{} float x;",
+        qualifier
+    ))
+    .unwrap();
+
+    tu.0.into_iter()
+        .find_map(|decl| match decl.into_inner() {
+            ast::ExternalDeclarationData::Declaration(decl) => match decl.into_inner() {
+                ast::DeclarationData::InitDeclaratorList(list) => {
+                    list.into_inner()
+                        .head
+                        .into_inner()
+                        .ty
+                        .into_inner()
+                        .qualifier
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn parse_type_qualifier_spirv_by_reference() {
+    assert_eq!(
+        ast::TypeQualifier::parse("spirv_by_reference spirv_literal"),
+        Ok(ast::TypeQualifierData {
+            qualifiers: vec![
+                ast::TypeQualifierSpecData::Spirv(ast::SpirvQualifierData::ByReference.into())
+                    .into(),
+                ast::TypeQualifierSpecData::Spirv(ast::SpirvQualifierData::Literal.into()).into(),
+            ],
+        }
+        .into())
+    );
+}
+
+#[test]
+fn parse_type_specifier_spirv_type() {
+    assert_eq!(
+        ast::TypeSpecifierNonArray::parse("spirv_type(capabilities = [4472], id = 4472, uint)"),
+        Ok(ast::TypeSpecifierNonArrayData::SpirvType(vec![
+            ast::SpirvArgumentData::Requirement(
+                "capabilities".into_node(),
+                vec![ast::SpirvValueData::Expr(Box::new(
+                    ast::ExprData::IntConst(4472.into()).into()
+                ))
+                .into()],
+            )
+            .into(),
+            ast::SpirvArgumentData::Named(
+                "id".into_node(),
+                ast::SpirvValueData::Expr(Box::new(ast::ExprData::IntConst(4472.into()).into()))
+                    .into(),
+            )
+            .into(),
+            ast::SpirvArgumentData::Value(
+                ast::SpirvValueData::Type(ast::TypeSpecifierNonArrayData::UInt.into()).into()
+            )
+            .into(),
+        ])
+        .into())
+    );
+}
+
 #[test]
 fn parse_jump_discard() {
     assert_eq!(
@@ -2320,7 +2730,8 @@ fn parse_compound_statement() {
         .into(),
     );
     let st2 = ast::StatementData::Jump(
-        ast::JumpStatementData::Return(Some(Box::new(ast::ExprData::IntConst(42).into()))).into(),
+        ast::JumpStatementData::Return(Some(Box::new(ast::ExprData::IntConst(42.into()).into())))
+            .into(),
     );
     let expected: ast::CompoundStatement = ast::CompoundStatementData {
         statement_list: vec![st0.into(), st1.into(), st2.into()],
@@ -2382,6 +2793,35 @@ fn parse_function_definition() {
     );
 }
 
+#[test]
+fn parse_function_definition_attributes() {
+    let ast::ExternalDeclarationData::AttributedFunctionDefinition(fd) =
+        parse_attributed_declaration(
+            "[[random(4)]] void main() [[subgroup_uniform_control_flow]] {}",
+        )
+    else {
+        panic!("expected an attributed function definition");
+    };
+
+    assert_eq!(
+        fd.attributes,
+        vec![ast::AttributeData {
+            name: "random".into_node(),
+            arg: Some(Box::new(ast::ExprData::IntConst(4.into()).into())),
+        }
+        .into()]
+    );
+    assert_eq!(
+        fd.body_attributes,
+        vec![ast::AttributeData {
+            name: "subgroup_uniform_control_flow".into_node(),
+            arg: None,
+        }
+        .into()]
+    );
+    assert_eq!(fd.definition.prototype.name.as_str(), "main");
+}
+
 #[test]
 fn parse_buffer_block_0() {
     let src = include_str!("../data/tests/buffer_block_0.glsl");
@@ -2458,12 +2898,12 @@ fn parse_layout_buffer_block_0() {
         ids: vec![
             ast::LayoutQualifierSpecData::Identifier(
                 "set".into_node(),
-                Some(Box::new(ast::ExprData::IntConst(0).into())),
+                Some(Box::new(ast::ExprData::IntConst(0.into()).into())),
             )
             .into(),
             ast::LayoutQualifierSpecData::Identifier(
                 "binding".into_node(),
-                Some(Box::new(ast::ExprData::IntConst(0).into())),
+                Some(Box::new(ast::ExprData::IntConst(0.into()).into())),
             )
             .into(),
         ],
@@ -2500,7 +2940,6 @@ fn parse_layout_buffer_block_0() {
 }
 
 #[test]
-#[ignore = "uses Google include directives not supported on this fork"]
 #[cfg(feature = "lexer-full")]
 fn parse_multifile_pp_a() {
     use crate::lexer::full::fs::PreprocessorExt;
@@ -2522,7 +2961,7 @@ fn parse_multifile_pp_a() {
     iter.into_directives().inject(&mut tu);
 
     // Note that there's only one version directive, and it has the highest number
-    expect![[r#"TranslationUnit([Node { content: Preprocessor(Node { content: Version(Node { content: PreprocessorVersionData { version: 460, profile: Some(Node { content: Core, span: None }) }, span: None }), span: Some(NodeSpan { source_id: FileId(0), range: 0..18 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 0..18 }) }, Node { content: Preprocessor(Node { content: Extension(Node { content: PreprocessorExtensionData { name: Node { content: Specific("GL_GOOGLE_include_directive"), span: None }, behavior: Some(Node { content: Require, span: None }) }, span: None }), span: Some(NodeSpan { source_id: FileId(0), range: 18..67 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 18..67 }) }, Node { content: Declaration(Node { content: InitDeclaratorList(Node { content: InitDeclaratorListData { head: Node { content: SingleDeclarationData { ty: Node { content: FullySpecifiedTypeData { qualifier: Some(Node { content: TypeQualifierData { qualifiers: [Node { content: Storage(Node { content: Uniform, span: Some(NodeSpan { source_id: FileId(1), range: 18..25 }) }), span: Some(NodeSpan { source_id: FileId(1), range: 18..25 }) }] }, span: Some(NodeSpan { source_id: FileId(1), range: 18..25 }) }), ty: Node { content: TypeSpecifierData { ty: Node { content: Vec3, span: Some(NodeSpan { source_id: FileId(1), range: 26..30 }) }, array_specifier: None }, span: Some(NodeSpan { source_id: FileId(1), range: 26..30 }) } }, span: Some(NodeSpan { source_id: FileId(1), range: 18..30 }) }, name: Some(Node { content: IdentifierData("x"), span: Some(NodeSpan { source_id: FileId(1), range: 31..32 }) }), array_specifier: None, initializer: None }, span: Some(NodeSpan { source_id: FileId(1), range: 18..32 }) }, tail: [] }, span: Some(NodeSpan { source_id: FileId(1), range: 18..32 }) }), span: Some(NodeSpan { source_id: FileId(1), range: 18..33 }) }), span: Some(NodeSpan { source_id: FileId(1), range: 18..33 }) }, Node { content: Declaration(Node { content: InitDeclaratorList(Node { content: InitDeclaratorListData { head: Node { content: SingleDeclarationData { ty: Node { content: FullySpecifiedTypeData { qualifier: Some(Node { content: TypeQualifierData { qualifiers: [Node { content: Storage(Node { content: Uniform, span: Some(NodeSpan { source_id: FileId(0), range: 100..107 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 100..107 }) }] }, span: Some(NodeSpan { source_id: FileId(0), range: 100..107 }) }), ty: Node { content: TypeSpecifierData { ty: Node { content: Vec2, span: Some(NodeSpan { source_id: FileId(0), range: 108..112 }) }, array_specifier: None }, span: Some(NodeSpan { source_id: FileId(0), range: 108..112 }) } }, span: Some(NodeSpan { source_id: FileId(0), range: 100..112 }) }, name: Some(Node { content: IdentifierData("y"), span: Some(NodeSpan { source_id: FileId(0), range: 113..114 }) }), array_specifier: None, initializer: None }, span: Some(NodeSpan { source_id: FileId(0), range: 100..114 }) }, tail: [] }, span: Some(NodeSpan { source_id: FileId(0), range: 100..114 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 100..115 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 100..115 }) }])"#]].assert_eq(&format!("{:?}", tu))
+    expect![[r#"TranslationUnit([Node { content: Preprocessor(Node { content: Version(Node { content: PreprocessorVersionData { version: 460, profile: Some(Node { content: Core, span: None }) }, span: None }), span: Some(NodeSpan { source_id: FileId(0), range: 0..18 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 0..18 }) }, Node { content: Preprocessor(Node { content: Extension(Node { content: PreprocessorExtensionData { name: Node { content: Specific("GL_GOOGLE_include_directive"), span: None }, behavior: Some(Node { content: Require, span: None }) }, span: None }), span: Some(NodeSpan { source_id: FileId(0), range: 18..67 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 18..67 }) }, Node { content: Preprocessor(Node { content: Include(Node { content: PreprocessorIncludeData { path: Node { content: Relative("multifile_pp_b.glsl"), span: None } }, span: None }), span: Some(NodeSpan { source_id: FileId(0), range: 68..99 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 68..99 }) }, Node { content: Declaration(Node { content: InitDeclaratorList(Node { content: InitDeclaratorListData { head: Node { content: SingleDeclarationData { ty: Node { content: FullySpecifiedTypeData { qualifier: Some(Node { content: TypeQualifierData { qualifiers: [Node { content: Storage(Node { content: Uniform, span: Some(NodeSpan { source_id: FileId(1), range: 18..25 }) }), span: Some(NodeSpan { source_id: FileId(1), range: 18..25 }) }] }, span: Some(NodeSpan { source_id: FileId(1), range: 18..25 }) }), ty: Node { content: TypeSpecifierData { ty: Node { content: Vec3, span: Some(NodeSpan { source_id: FileId(1), range: 26..30 }) }, array_specifier: None }, span: Some(NodeSpan { source_id: FileId(1), range: 26..30 }) } }, span: Some(NodeSpan { source_id: FileId(1), range: 18..30 }) }, name: Some(Node { content: IdentifierData("x"), span: Some(NodeSpan { source_id: FileId(1), range: 31..32 }) }), array_specifier: None, initializer: None }, span: Some(NodeSpan { source_id: FileId(1), range: 18..32 }) }, tail: [] }, span: Some(NodeSpan { source_id: FileId(1), range: 18..32 }) }), span: Some(NodeSpan { source_id: FileId(1), range: 18..33 }) }), span: Some(NodeSpan { source_id: FileId(1), range: 18..33 }) }, Node { content: Declaration(Node { content: InitDeclaratorList(Node { content: InitDeclaratorListData { head: Node { content: SingleDeclarationData { ty: Node { content: FullySpecifiedTypeData { qualifier: Some(Node { content: TypeQualifierData { qualifiers: [Node { content: Storage(Node { content: Uniform, span: Some(NodeSpan { source_id: FileId(0), range: 100..107 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 100..107 }) }] }, span: Some(NodeSpan { source_id: FileId(0), range: 100..107 }) }), ty: Node { content: TypeSpecifierData { ty: Node { content: Vec2, span: Some(NodeSpan { source_id: FileId(0), range: 108..112 }) }, array_specifier: None }, span: Some(NodeSpan { source_id: FileId(0), range: 108..112 }) } }, span: Some(NodeSpan { source_id: FileId(0), range: 100..112 }) }, name: Some(Node { content: IdentifierData("y"), span: Some(NodeSpan { source_id: FileId(0), range: 113..114 }) }), array_specifier: None, initializer: None }, span: Some(NodeSpan { source_id: FileId(0), range: 100..114 }) }, tail: [] }, span: Some(NodeSpan { source_id: FileId(0), range: 100..114 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 100..115 }) }), span: Some(NodeSpan { source_id: FileId(0), range: 100..115 }) }])"#]].assert_eq(&format!("{:?}", tu))
 }
 
 #[test]
@@ -2939,6 +3378,35 @@ fn parse_pp_ifndef() {
     );
 }
 
+#[test]
+#[cfg_attr(
+    feature = "lexer-full",
+    ignore = "no preprocessor directives in ast with lexer-full"
+)]
+fn parse_pp_include() {
+    assert_eq!(
+        ast::Preprocessor::parse("#include <filename>\n"),
+        Ok(ast::PreprocessorData::Include(
+            ast::PreprocessorIncludeData {
+                path: ast::PathData::Absolute("filename".to_owned()).into()
+            }
+            .into()
+        )
+        .into())
+    );
+
+    assert_eq!(
+        ast::Preprocessor::parse("#include \\\n\"filename\"\n"),
+        Ok(ast::PreprocessorData::Include(
+            ast::PreprocessorIncludeData {
+                path: ast::PathData::Relative("filename".to_owned()).into()
+            }
+            .into()
+        )
+        .into())
+    );
+}
+
 #[test]
 #[cfg_attr(
     feature = "lexer-full",
@@ -3120,7 +3588,7 @@ fn parse_dot_field_expr_array() {
         Box::new(
             ast::ExprData::Bracket(
                 Box::new(ast::ExprData::Variable("a".into_node()).into()),
-                Box::new(ast::ExprData::IntConst(0).into()),
+                Box::new(ast::ExprData::IntConst(0.into()).into()),
             )
             .into(),
         ),
@@ -3149,7 +3617,7 @@ fn parse_dot_field_expr_statement() {
                 ast::TypeSpecifierData::from(ast::TypeSpecifierNonArrayData::Vec3).into(),
             ))
             .into(),
-            vec![ast::ExprData::FloatConst(0.).into()],
+            vec![ast::ExprData::FloatConst(0.0.into()).into()],
         )
         .into(),
         ast::ExprData::Variable("v_barycenter".into_node()).into(),
@@ -3268,3 +3736,484 @@ fn parse_dangling_else() {
         .into())
     );
 }
+
+#[cfg(feature = "parser-recovering")]
+#[test]
+fn parse_recovering() {
+    use expect_test::expect;
+
+    let src = r#"int x = ;
+void main() {
+    int a = 1 +;
+    a = 2;
+    float b = a a;
+}
+void f() {
+    a = (2
+}
+float y;
+void g() {
+    int b;
+"#;
+
+    let (tu, errors, _, _): (ast::TranslationUnit, _, _, _) =
+        src.builder().parse_recovering().unwrap();
+
+    let mut actual = String::new();
+    crate::transpiler::glsl::show_translation_unit(&mut actual, &tu, Default::default()).unwrap();
+    actual.push('\n');
+    for error in errors {
+        actual.push_str(&format!("{:?}\n", error.pos()));
+    }
+
+    expect![[r#"
+        void main() {
+            ;
+            a = 2;
+            ;
+        }
+
+        void f() {
+            ;
+        }
+
+        float y;
+
+        8..9
+        39..40
+        68..69
+        95..96
+        127..127
+    "#]]
+    .assert_eq(&actual);
+
+    assert_eq!(*tu.0[0], ast::ExternalDeclarationData::Error);
+    assert_eq!(
+        tu.0[0].span.map(|span| format!("{:?}", span.range())),
+        Some("0..7".to_owned())
+    );
+
+    // Strict parsing still fails on the first error
+    assert_eq!(
+        ast::TranslationUnit::parse(src).map_err(|err| format!("{:?}", err.pos())),
+        Err("8..9".to_owned())
+    );
+}
+
+#[cfg(feature = "parser-recovering")]
+#[test]
+fn parse_recovering_lexer_error() {
+    // Lexical errors stop the parser
+    let errors = "int a = 1 +;\nint b = 99999999999;\nint c;\n"
+        .builder::<ast::TranslationUnit>()
+        .parse_recovering()
+        .map(|_| ())
+        .unwrap_err();
+
+    assert_eq!(
+        errors
+            .iter()
+            .map(|err| format!("{:?}", err.pos()))
+            .collect::<Vec<_>>(),
+        ["11..12", "21..32"]
+    );
+
+    // Strict parsing stops at the syntax error, before reaching the lexical error
+    assert_eq!(
+        ast::TranslationUnit::parse("int a = 1 +;\nint b = 99999999999;\nint c;\n")
+            .map_err(|err| format!("{:?}", err.pos())),
+        Err("11..12".to_owned())
+    );
+}
+
+#[cfg(feature = "parser-declaration")]
+#[test]
+fn parse_native_declaration_span() {
+    let decl = <ast::Declaration as parse::DefaultParse>::parse("float x = 1.0;").unwrap();
+
+    assert_eq!(decl, ast::Declaration::parse("float x = 1.0").unwrap());
+    assert_eq!(
+        decl.span.map(|span| format!("{:?}", span.range())),
+        Some("0..14".to_owned())
+    );
+}
+
+#[cfg(feature = "parser-function-definition")]
+#[test]
+fn parse_native_function_definition_span() {
+    let def = <ast::FunctionDefinition as parse::DefaultParse>::parse("void main() {}").unwrap();
+
+    assert_eq!(
+        def,
+        ast::FunctionDefinition::parse("void main() {}").unwrap()
+    );
+    assert_eq!(
+        def.span.map(|span| format!("{:?}", span.range())),
+        Some("0..14".to_owned())
+    );
+}
+
+#[cfg(feature = "parser-type-specifier")]
+#[test]
+fn parse_native_type_specifier_span() {
+    let ty = <ast::TypeSpecifier as parse::DefaultParse>::parse("vec3[2]").unwrap();
+
+    assert_eq!(ty, ast::TypeSpecifier::parse("vec3[2]").unwrap());
+    assert_eq!(
+        ty.span.map(|span| format!("{:?}", span.range())),
+        Some("0..7".to_owned())
+    );
+}
+
+#[cfg(feature = "parser-layout-qualifier")]
+#[test]
+fn parse_native_layout_qualifier_span() {
+    let layout =
+        <ast::LayoutQualifier as parse::DefaultParse>::parse("layout(location = 0)").unwrap();
+
+    assert_eq!(
+        layout,
+        ast::LayoutQualifier::parse("layout(location = 0)").unwrap()
+    );
+    assert_eq!(
+        layout.span.map(|span| format!("{:?}", span.range())),
+        Some("0..20".to_owned())
+    );
+}
+
+#[cfg(feature = "parser-initializer")]
+#[test]
+fn parse_native_initializer_span() {
+    let init = <ast::Initializer as parse::DefaultParse>::parse("{ 1.0, 2.0 }").unwrap();
+
+    assert!(matches!(*init, ast::InitializerData::List(ref list) if list.len() == 2));
+    assert_eq!(
+        init.span.map(|span| format!("{:?}", span.range())),
+        Some("0..12".to_owned())
+    );
+}
+
+#[test]
+fn incremental_reparse() {
+    use lang_util::{TextRange, TextSize};
+    use parse::{IncrementalParser, Reparse, TextEdit};
+
+    let source = r#"#version 460 core
+struct Light { vec3 position; float radius; };
+uniform Light lights[4];
+
+// Shade a single light
+float shade(Light light, vec3 p) {
+    return clamp(1.0 - length(light.position - p) / light.radius, 0.0, 1.0);
+}
+
+void main() {
+    float sum = 0.0;
+    for (int i = 0; i < 4; ++i) {
+        sum += shade(lights[i], gl_FragCoord.xyz);
+    }
+    gl_FragColor = vec4(sum);
+}
+"#;
+
+    let mut parser = IncrementalParser::new(source);
+    assert!(parser.translation_unit().is_ok());
+
+    let check = |parser: &IncrementalParser| {
+        // The result must be the same as a full parse, including spans
+        let full = IncrementalParser::new(parser.source());
+        assert_eq!(
+            format!("{:?}", parser.translation_unit()),
+            format!("{:?}", full.translation_unit())
+        );
+    };
+
+    let offset = |parser: &IncrementalParser, needle: &str| {
+        TextSize::try_from(parser.source().find(needle).unwrap()).unwrap()
+    };
+
+    // Editing a function body only reparses the function
+    let at = offset(&parser, "1.0 - length");
+    assert_eq!(
+        parser.edit(&TextEdit::new(TextRange::at(at, 3.into()), "2.0 * 0.5")),
+        Reparse::Incremental {
+            reparsed: 3..4,
+            reused: 4
+        }
+    );
+    check(&parser);
+
+    // Inserting a declaration between two others
+    let at = offset(&parser, "\n\n// Shade");
+    assert_eq!(
+        parser.edit(&TextEdit::insert(at, "\nconst float scale = 2.0;")),
+        Reparse::Incremental {
+            reparsed: 2..4,
+            reused: 4
+        }
+    );
+    check(&parser);
+
+    // Editing a comment
+    let at = offset(&parser, "Shade a single");
+    assert_eq!(
+        parser.edit(&TextEdit::delete(TextRange::at(at, 6.into()))),
+        Reparse::Incremental {
+            reparsed: 4..4,
+            reused: 6
+        }
+    );
+    check(&parser);
+
+    // Edits to declarations of type names require a full reparse
+    let at = offset(&parser, "float radius;");
+    assert_eq!(
+        parser.edit(&TextEdit::insert(at, "vec3 color; ")),
+        Reparse::Full
+    );
+    check(&parser);
+
+    // Edits to preprocessor directives require a full reparse
+    let at = offset(&parser, " core");
+    assert_eq!(
+        parser.edit(&TextEdit::delete(TextRange::at(at, 5.into()))),
+        Reparse::Full
+    );
+    check(&parser);
+
+    // Errors are reported, and the next edit recovers
+    let at = offset(&parser, "sum = 0.0;");
+    let semicolon = at + TextSize::from(9);
+    assert_eq!(
+        parser.edit(&TextEdit::delete(TextRange::at(semicolon, 1.into()))),
+        Reparse::Failed
+    );
+    assert!(parser.translation_unit().is_err());
+
+    assert_eq!(
+        parser.edit(&TextEdit::insert(semicolon, ";")),
+        Reparse::Full
+    );
+    check(&parser);
+
+    // The result is the same as parsing the final source
+    assert_eq!(
+        parser.source(),
+        source
+            .replace("1.0 - length", "2.0 * 0.5 - length")
+            .replace("];\n\n//", "];\nconst float scale = 2.0;\n\n//")
+            .replace("Shade a single", "a single")
+            .replace("float radius;", "vec3 color; float radius;")
+            .replace("460 core", "460")
+    );
+}
+
+#[test]
+fn parse_stream() {
+    let src = r#"struct Light { vec3 position; } sun;
+layout(std140) uniform Lights { Light lights[4]; };
+;
+Light shade(Light light) {
+    struct Local { float x; };
+    Local l;
+    for (int i = 0; i < 4; ++i) {}
+    return light;
+}
+const Light moon = Light(vec3(0.));
+"#;
+
+    let stream = src.builder::<ast::TranslationUnit>().stream();
+    let decls: Vec<_> = stream.collect::<Result<_, _>>().unwrap();
+
+    // Same result as parsing the whole translation unit, including type names resolved through
+    // the shared context
+    assert_eq!(
+        ast::TranslationUnit(decls),
+        ast::TranslationUnit::parse(src).unwrap()
+    );
+}
+
+#[test]
+fn parse_stream_error() {
+    let mut stream = "float a;\nvoid main() {}\nfloat b = ;\nfloat c;\n"
+        .builder::<ast::TranslationUnit>()
+        .stream();
+
+    // Declarations before the error are returned as soon as they are parsed
+    assert!(matches!(
+        stream.next().map(|decl| decl.map(|decl| decl.span.unwrap().range())),
+        Some(Ok(range)) if range == lang_util::TextRange::new(0.into(), 8.into())
+    ));
+    assert!(matches!(stream.next(), Some(Ok(_))));
+
+    // The error is reported with its position in the whole source
+    assert_eq!(
+        stream
+            .next()
+            .map(|decl| decl.map_err(|err| format!("{:?}", err.pos()))),
+        Some(Err("34..35".to_owned()))
+    );
+    assert!(stream.next().is_none());
+}
+
+#[test]
+#[cfg(feature = "lexer-full")]
+fn parse_diagnostics_macro_expansion() {
+    use crate::diagnostics::Diagnostic;
+
+    let src = "#define ADD(a, b) (a + b)
+#define BAD(x) ADD(x, })
+void main() {
+    float y = BAD(1.0);
+}
+";
+
+    let err = parse_pp(src).err().unwrap();
+    assert_eq!(
+        err.expansions()
+            .iter()
+            .map(|expansion| expansion.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["BAD", "ADD"]
+    );
+
+    let diagnostic = Diagnostic::from_parse_error(&err, src);
+    assert_eq!(
+        diagnostic.notes,
+        vec![
+            "in expansion of macro `ADD` at line 2".to_owned(),
+            "in expansion of macro `BAD` at line 4".to_owned()
+        ]
+    );
+}
+
+#[test]
+fn parse_diagnostics() {
+    use crate::diagnostics::{Diagnostic, DiagnosticCode};
+
+    let diagnose = |src: &str| {
+        let err = ast::TranslationUnit::parse(src).unwrap_err();
+        let diagnostic = Diagnostic::from_parse_error(&err, src);
+        let fix_its: Vec<_> = diagnostic
+            .fix_its
+            .iter()
+            .map(|fix_it| {
+                (
+                    fix_it.message.as_str(),
+                    format!("{:?}", fix_it.edit.range),
+                    fix_it.edit.replacement.as_str(),
+                )
+            })
+            .collect();
+
+        (
+            diagnostic.code,
+            diagnostic.message.clone(),
+            format!("{:?}", fix_its),
+        )
+    };
+
+    assert_eq!(
+        diagnose("float x = 1.0\nfloat y;"),
+        (
+            DiagnosticCode::MissingSemicolon,
+            "missing `;` after declaration".to_owned(),
+            r#"[("insert `;`", "13..13", ";")]"#.to_owned()
+        )
+    );
+    assert_eq!(
+        diagnose("void main() {\n    int x = 1\n}"),
+        (
+            DiagnosticCode::MissingSemicolon,
+            "missing `;` after statement".to_owned(),
+            r#"[("insert `;`", "27..27", ";")]"#.to_owned()
+        )
+    );
+    assert_eq!(
+        diagnose("void main() {\n    vce3 color = vec3(1.);\n}"),
+        (
+            DiagnosticCode::UnknownName,
+            "unknown type name `vce3`".to_owned(),
+            r#"[("did you mean `vec3`?", "18..22", "vec3")]"#.to_owned()
+        )
+    );
+    assert_eq!(
+        diagnose("struct Light { vec3 p; };\nvoid main() { Ligth l; }"),
+        (
+            DiagnosticCode::UnknownName,
+            "unknown type name `Ligth`".to_owned(),
+            r#"[("did you mean `Light`?", "40..45", "Light")]"#.to_owned()
+        )
+    );
+    assert_eq!(
+        diagnose("unifrom vec3 x;"),
+        (
+            DiagnosticCode::UnknownName,
+            "unknown keyword `unifrom`".to_owned(),
+            r#"[("did you mean `uniform`?", "0..7", "uniform")]"#.to_owned()
+        )
+    );
+    assert_eq!(
+        diagnose("void main() { retrun 1; }"),
+        (
+            DiagnosticCode::UnknownName,
+            "unknown keyword `retrun`".to_owned(),
+            r#"[("did you mean `return`?", "14..20", "return")]"#.to_owned()
+        )
+    );
+    assert_eq!(
+        diagnose("foo bar;"),
+        (
+            DiagnosticCode::UnknownName,
+            "unknown type name `foo`".to_owned(),
+            "[]".to_owned()
+        )
+    );
+    assert_eq!(
+        diagnose("void main() { x = 1 + ; }"),
+        (
+            DiagnosticCode::UnexpectedToken,
+            "unexpected `;`, expected an expression".to_owned(),
+            "[]".to_owned()
+        )
+    );
+}
+
+#[test]
+#[cfg_attr(
+    feature = "lexer-full",
+    ignore = "no preprocessor directives in ast with lexer-full"
+)]
+fn parse_version_diagnostics() {
+    use crate::diagnostics::{version_diagnostics, DiagnosticCode};
+
+    let src = "#version 330 core\nattribute vec3 position;\nvarying vec2 uv;\n";
+    let diagnostics = version_diagnostics(&ast::TranslationUnit::parse(src).unwrap());
+
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.code == DiagnosticCode::UnsupportedQualifier));
+    assert_eq!(
+        diagnostics[0].message,
+        "`attribute` is not allowed in #version 330 core"
+    );
+    assert_eq!(diagnostics[0].fix_its[0].edit.replacement, "in");
+    assert_eq!(
+        &src[std::ops::Range::<usize>::from(diagnostics[1].range)],
+        "varying"
+    );
+    assert_eq!(diagnostics[1].fix_its.len(), 2);
+
+    // Still available in older versions and the compatibility profile
+    for src in [
+        "#version 120\nattribute vec3 position;\n",
+        "#version 330 compatibility\nattribute vec3 position;\n",
+        "#version 100\nvarying vec2 uv;\n",
+    ] {
+        assert_eq!(
+            version_diagnostics(&ast::TranslationUnit::parse(src).unwrap()),
+            vec![]
+        );
+    }
+}
@@ -35,8 +35,22 @@ use std::fmt::Write;
 
 use once_cell::sync::Lazy;
 
+#[cfg(feature = "serde")]
+use rserde::{Deserialize, Serialize};
+
+pub mod comments;
+use comments::{Comments, Placement};
+
+mod layout;
+use layout::{Container, Layout};
+
+pub mod source_map;
+use source_map::{NodeGuard, SourceMapRecorder};
+
 /// Indentation style of the output
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde", rename_all = "snake_case"))]
 pub enum IndentStyle {
     /// No indentation is generated
     None,
@@ -87,6 +101,8 @@ impl Default for IndentStyle {
 
 /// Formatter whitespace
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(crate = "rserde", rename_all = "snake_case"))]
 pub enum Whitespace {
     /// No whitespace
     None,
@@ -111,7 +127,15 @@ impl Whitespace {
 }
 
 /// Formatting settings for the GLSL transpiler
+///
+/// When the `serde` feature is enabled, missing fields are deserialized from the
+/// [default settings](FormattingSettings::default).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(crate = "rserde", default, deny_unknown_fields)
+)]
 pub struct FormattingSettings {
     /// Indentation style of the output
     pub indent_style: IndentStyle,
@@ -155,6 +179,12 @@ pub struct FormattingSettings {
     pub spaces_surrounding_initializer_list_expressions: bool,
     /// Insert a space before the ( and after the ) that are part of a statement
     pub spaces_surrounding_statement_parentheses: bool,
+    /// Maximum width of the output lines. If set, binary operator chains, argument lists,
+    /// parameter lists and initializer lists that do not fit are wrapped over multiple lines.
+    /// Applied when transpiling translation units, external declarations, function definitions,
+    /// declarations, statements, expressions and initializers. Other nodes are only wrapped as
+    /// part of one of these.
+    pub max_line_width: Option<u32>,
 }
 
 impl FormattingSettings {
@@ -182,6 +212,7 @@ impl FormattingSettings {
             space_after_for_statement_separator: false,
             spaces_surrounding_initializer_list_expressions: false,
             spaces_surrounding_statement_parentheses: false,
+            max_line_width: None,
         }
     }
 }
@@ -209,6 +240,7 @@ impl Default for FormattingSettings {
             space_after_for_statement_separator: true,
             spaces_surrounding_initializer_list_expressions: true,
             spaces_surrounding_statement_parentheses: true,
+            max_line_width: None,
         }
     }
 }
@@ -223,9 +255,109 @@ pub struct FormattingState<'s> {
     in_function_definition_statement: bool,
     last_flush_line_flushed_line: bool,
     is_first_external_declaration: bool,
+    source_map: Option<&'s SourceMapRecorder>,
+    comments: Option<&'s Comments<'s>>,
+    line_comment_pending: bool,
+    layout: Option<&'s Layout>,
 }
 
 impl<'s> FormattingState<'s> {
+    /// Record the mappings between the output and the transpiled nodes into `recorder`
+    ///
+    /// The output must be written through [`SourceMapRecorder::writer`] for the mappings to be
+    /// accurate. See the [`source_map`] module for more details.
+    pub fn with_source_map(self, recorder: &'s SourceMapRecorder) -> Self {
+        Self {
+            source_map: Some(recorder),
+            ..self
+        }
+    }
+
+    /// Write the comments from `comments` back to the output
+    ///
+    /// See the [`comments`] module for more details.
+    pub fn with_comments(self, comments: &'s Comments<'s>) -> Self {
+        Self {
+            comments: Some(comments),
+            ..self
+        }
+    }
+
+    /// Start recording the output of a node into the source map, if any
+    fn enter_node<T: ast::NodeContent>(&self, node: &ast::Node<T>) -> NodeScope<'s> {
+        match (self.source_map, node.span) {
+            (Some(recorder), Some(span)) => match self.layout {
+                Some(layout) if layout.is_active() => {
+                    layout.enter_node(span);
+                    NodeScope::Layout(layout, recorder)
+                }
+                _ => NodeScope::SourceMap(recorder.enter(span)),
+            },
+            _ => NodeScope::None,
+        }
+    }
+
+    /// Update the position in the output from `other`, a copy of this state used to transpile
+    /// a node with a different lifetime
+    fn update_from(&mut self, other: &FormattingState<'_>) {
+        self.indentation_level = other.indentation_level;
+        self.new_line_pending = other.new_line_pending;
+        self.in_function_definition_statement = other.in_function_definition_statement;
+        self.last_flush_line_flushed_line = other.last_flush_line_flushed_line;
+        self.is_first_external_declaration = other.is_first_external_declaration;
+        self.line_comment_pending = other.line_comment_pending;
+    }
+
+    /// Start recording the output so it can be wrapped to the maximum line width
+    fn begin_layout(&self) {
+        if let Some(layout) = self.layout {
+            layout.begin();
+        }
+    }
+
+    /// Stop recording the output, and write it wrapped to the maximum line width
+    fn end_layout<F>(&self, f: &mut F) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+    {
+        if let Some(layout) = self.layout {
+            if let Some(doc) = layout.end() {
+                let indent = self.indentation_level * layout.level_width();
+                layout.render(f, &doc, indent, self.source_map)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Open a layout container, which controls how the output is wrapped
+    fn enter_wrap(&self, container: Container) {
+        if let Some(layout) = self.layout {
+            layout.open(container);
+        }
+    }
+
+    /// Close the current layout container
+    fn exit_wrap(&self) {
+        if let Some(layout) = self.layout {
+            layout.close();
+        }
+    }
+
+    /// Write a place where the output can be wrapped, or `flat` if it is not wrapped
+    fn write_break<F>(&self, f: &mut F, flat: &'static str) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+    {
+        match self.layout {
+            Some(layout) if layout.is_active() => {
+                layout.line_break(flat);
+                Ok(())
+            }
+            _ => f.write_str(flat),
+        }
+    }
+
     fn write_indent<F>(&self, f: &mut F) -> std::fmt::Result
     where
         F: Write + ?Sized,
@@ -263,6 +395,7 @@ impl<'s> FormattingState<'s> {
         if self.new_line_pending {
             self.write_line(f)?;
             self.new_line_pending = false;
+            self.line_comment_pending = false;
             self.last_flush_line_flushed_line = true;
         } else {
             self.last_flush_line_flushed_line = false;
@@ -291,7 +424,9 @@ impl<'s> FormattingState<'s> {
     where
         F: Write + ?Sized,
     {
-        if self.new_line_pending {
+        if self.line_comment_pending {
+            self.flush_line(f)?;
+        } else if self.new_line_pending {
             f.write_char(' ')?;
             self.new_line_pending = false;
         }
@@ -417,16 +552,18 @@ impl<'s> FormattingState<'s> {
     where
         F: Write + ?Sized,
     {
+        self.enter_wrap(Container::Group);
         f.write_char('{')?;
+        self.enter_wrap(Container::Indent);
 
         if self
             .settings
             .spaces_surrounding_initializer_list_expressions
         {
-            f.write_char(' ')?;
+            self.write_break(f, " ")
+        } else {
+            self.write_break(f, "")
         }
-
-        Ok(())
     }
 
     /// Exit the current list initializer
@@ -434,14 +571,21 @@ impl<'s> FormattingState<'s> {
     where
         F: Write + ?Sized,
     {
+        self.exit_wrap();
+
         if self
             .settings
             .spaces_surrounding_initializer_list_expressions
         {
-            f.write_char(' ')?;
+            self.write_break(f, " ")?;
+        } else {
+            self.write_break(f, "")?;
         }
 
-        f.write_char('}')
+        f.write_char('}')?;
+        self.exit_wrap();
+
+        Ok(())
     }
 
     /// Enter a case label
@@ -458,7 +602,7 @@ impl<'s> FormattingState<'s> {
     where
         F: Write + ?Sized,
     {
-        if self.settings.space_before_else {
+        if !self.end_line_comment(f)? && self.settings.space_before_else {
             f.write_char(' ')?;
         }
 
@@ -481,10 +625,10 @@ impl<'s> FormattingState<'s> {
     {
         f.write_char(',')?;
         if self.settings.space_after_list_separator {
-            f.write_char(' ')?;
+            self.write_break(f, " ")
+        } else {
+            self.write_break(f, "")
         }
-
-        Ok(())
     }
 
     /// Write a for statement separator
@@ -520,6 +664,27 @@ impl<'s> FormattingState<'s> {
 
     /// Write a binary operator
     pub fn write_binary_op<F>(&self, f: &mut F, op: &str) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+    {
+        // Long expressions are wrapped before binary operators
+        if self.settings.spaces_around_binary_ops {
+            self.write_break(f, " ")?;
+        } else {
+            self.write_break(f, "")?;
+        }
+
+        f.write_str(op)?;
+
+        if self.settings.spaces_around_binary_ops {
+            f.write_char(' ')?;
+        }
+
+        Ok(())
+    }
+
+    /// Write an assignment operator
+    pub fn write_assignment_op<F>(&self, f: &mut F, op: &str) -> std::fmt::Result
     where
         F: Write + ?Sized,
     {
@@ -553,6 +718,161 @@ impl<'s> FormattingState<'s> {
         self.settings.function_definition_terminator.write(f, self)
     }
 
+    /// Write a comment to the output
+    fn write_comment<F>(&mut self, f: &mut F, comment: &ast::Comment) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+    {
+        match **comment {
+            ast::CommentData::Single(ref text) => {
+                write!(f, "//{}", text)?;
+
+                // Nothing else can be written on this line
+                self.new_line(true)?;
+                self.line_comment_pending = true;
+
+                Ok(())
+            }
+            ast::CommentData::Multi(ref text) => write!(f, "/*{}*/", text),
+        }
+    }
+
+    /// Write the comments that precede the given node
+    pub fn write_leading_comments<F, T>(
+        &mut self,
+        f: &mut F,
+        node: &ast::Node<T>,
+    ) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+        T: ast::NodeContent,
+    {
+        if let Some(span) = node.span {
+            self.write_comments_before(f, span.source_id(), span.start().offset)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the comments that precede the given expression, without moving it to its own line
+    /// unless a single-line comment requires it
+    pub fn write_expr_comments<F>(&mut self, f: &mut F, expr: &ast::Expr) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+    {
+        let (Some(comments), Some(span)) = (self.comments, expr.span) else {
+            return Ok(());
+        };
+
+        for (comment, _) in comments.leading(span.source_id(), span.start().offset) {
+            self.write_comment(f, comment)?;
+
+            if self.line_comment_pending {
+                // Indent the rest of the expression as a continuation line
+                self.indentation_level += 1;
+                self.flush_line(f)?;
+                self.indentation_level -= 1;
+            } else {
+                f.write_char(' ')?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the comments that are left at the end of the given block node
+    pub fn write_block_end_comments<F, T>(
+        &mut self,
+        f: &mut F,
+        node: &ast::Node<T>,
+    ) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+        T: ast::NodeContent,
+    {
+        if let Some(span) = node.span {
+            self.write_comments_before(f, span.source_id(), span.end().offset)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_comments_before<F>(
+        &mut self,
+        f: &mut F,
+        source_id: ast::FileId,
+        offset: ast::TextSize,
+    ) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+    {
+        let Some(comments) = self.comments else {
+            return Ok(());
+        };
+
+        for (comment, placement) in comments.leading(source_id, offset) {
+            self.flush_line(f)?;
+            self.write_comment(f, comment)?;
+
+            match placement {
+                Placement::OwnLine => self.new_line(true)?,
+                Placement::Inline => f.write_char(' ')?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the comments that follow the given node on the same line
+    pub fn write_trailing_comments<F, T>(
+        &mut self,
+        f: &mut F,
+        node: &ast::Node<T>,
+    ) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+        T: ast::NodeContent,
+    {
+        if let (Some(comments), Some(span)) = (self.comments, node.span) {
+            for comment in comments.trailing(span) {
+                f.write_char(' ')?;
+                self.write_comment(f, comment)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the comments that have not been written yet
+    pub fn write_remaining_comments<F>(&mut self, f: &mut F) -> std::fmt::Result
+    where
+        F: Write + ?Sized,
+    {
+        if let Some(comments) = self.comments {
+            for comment in comments.remaining() {
+                self.flush_line(f)?;
+                self.write_comment(f, comment)?;
+                self.new_line(true)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flush the pending newline if a single-line comment was written on the current line.
+    /// Returns `true` if a newline was written.
+    pub fn end_line_comment<F>(&mut self, f: &mut F) -> Result<bool, std::fmt::Error>
+    where
+        F: Write + ?Sized,
+    {
+        if self.line_comment_pending {
+            self.flush_line(f)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
     /// Write an opening parenthesis for a statement
     pub fn write_statement_opening_parenthesis<F>(&mut self, f: &mut F) -> std::fmt::Result
     where
@@ -580,6 +900,29 @@ impl<'s> FormattingState<'s> {
     }
 }
 
+/// Scope of the output of a node in the source map
+enum NodeScope<'s> {
+    /// No source map is being recorded
+    None,
+    /// The output is written directly to the source map. The guard ends the node when dropped.
+    SourceMap(#[allow(dead_code)] NodeGuard<'s>),
+    /// The output is recorded by the layout engine before being written
+    Layout(&'s Layout, &'s SourceMapRecorder),
+}
+
+impl Drop for NodeScope<'_> {
+    fn drop(&mut self) {
+        if let Self::Layout(layout, recorder) = self {
+            if layout.is_active() {
+                layout.exit_node();
+            } else {
+                // The layout was already rendered, including the start of this node
+                recorder.exit();
+            }
+        }
+    }
+}
+
 impl<'s> From<&'s FormattingSettings> for FormattingState<'s> {
     fn from(settings: &'s FormattingSettings) -> Self {
         Self {
@@ -589,6 +932,10 @@ impl<'s> From<&'s FormattingSettings> for FormattingState<'s> {
             in_function_definition_statement: false,
             last_flush_line_flushed_line: false,
             is_first_external_declaration: true,
+            source_map: None,
+            comments: None,
+            line_comment_pending: false,
+            layout: None,
         }
     }
 }
@@ -604,6 +951,10 @@ impl Default for FormattingState<'static> {
             in_function_definition_statement: false,
             last_flush_line_flushed_line: false,
             is_first_external_declaration: true,
+            source_map: None,
+            comments: None,
+            line_comment_pending: false,
+            layout: None,
         }
     }
 }
@@ -625,7 +976,12 @@ impl HasPrecedence for ast::ExprData {
             | Self::UIntConst(_)
             | Self::BoolConst(_)
             | Self::FloatConst(_)
-            | Self::DoubleConst(_) => 0,
+            | Self::DoubleConst(_)
+            | Self::Int16Const(_)
+            | Self::UInt16Const(_)
+            | Self::Int64Const(_)
+            | Self::UInt64Const(_)
+            | Self::Float16Const(_) => 0,
             // Precedence operator expression is precedence of operator
             Self::Unary(op, _) => op.precedence(),
             Self::Binary(op, _, _) => op.precedence(),
@@ -675,11 +1031,13 @@ impl HasPrecedence for ast::AssignmentOp {
 pub fn show_identifier<F>(
     f: &mut F,
     i: &ast::Identifier,
-    _: &mut FormattingState<'_>,
+    state: &mut FormattingState<'_>,
 ) -> std::fmt::Result
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(i);
+
     f.write_str(&i.0)
 }
 
@@ -687,11 +1045,13 @@ where
 pub fn show_type_name<F>(
     f: &mut F,
     t: &ast::TypeName,
-    _: &mut FormattingState<'_>,
+    state: &mut FormattingState<'_>,
 ) -> std::fmt::Result
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(t);
+
     f.write_str(&t.0)
 }
 
@@ -856,6 +1216,94 @@ where
         ast::TypeSpecifierNonArrayData::SubpassInputMs => f.write_str("subpassInputMS"),
         ast::TypeSpecifierNonArrayData::ISubpassInputMs => f.write_str("isubpassInputMS"),
         ast::TypeSpecifierNonArrayData::USubpassInputMs => f.write_str("usubpassInputMS"),
+        ast::TypeSpecifierNonArrayData::Int8 => f.write_str("int8_t"),
+        ast::TypeSpecifierNonArrayData::I8Vec2 => f.write_str("i8vec2"),
+        ast::TypeSpecifierNonArrayData::I8Vec3 => f.write_str("i8vec3"),
+        ast::TypeSpecifierNonArrayData::I8Vec4 => f.write_str("i8vec4"),
+        ast::TypeSpecifierNonArrayData::UInt8 => f.write_str("uint8_t"),
+        ast::TypeSpecifierNonArrayData::U8Vec2 => f.write_str("u8vec2"),
+        ast::TypeSpecifierNonArrayData::U8Vec3 => f.write_str("u8vec3"),
+        ast::TypeSpecifierNonArrayData::U8Vec4 => f.write_str("u8vec4"),
+        ast::TypeSpecifierNonArrayData::Int16 => f.write_str("int16_t"),
+        ast::TypeSpecifierNonArrayData::I16Vec2 => f.write_str("i16vec2"),
+        ast::TypeSpecifierNonArrayData::I16Vec3 => f.write_str("i16vec3"),
+        ast::TypeSpecifierNonArrayData::I16Vec4 => f.write_str("i16vec4"),
+        ast::TypeSpecifierNonArrayData::UInt16 => f.write_str("uint16_t"),
+        ast::TypeSpecifierNonArrayData::U16Vec2 => f.write_str("u16vec2"),
+        ast::TypeSpecifierNonArrayData::U16Vec3 => f.write_str("u16vec3"),
+        ast::TypeSpecifierNonArrayData::U16Vec4 => f.write_str("u16vec4"),
+        ast::TypeSpecifierNonArrayData::Int32 => f.write_str("int32_t"),
+        ast::TypeSpecifierNonArrayData::I32Vec2 => f.write_str("i32vec2"),
+        ast::TypeSpecifierNonArrayData::I32Vec3 => f.write_str("i32vec3"),
+        ast::TypeSpecifierNonArrayData::I32Vec4 => f.write_str("i32vec4"),
+        ast::TypeSpecifierNonArrayData::UInt32 => f.write_str("uint32_t"),
+        ast::TypeSpecifierNonArrayData::U32Vec2 => f.write_str("u32vec2"),
+        ast::TypeSpecifierNonArrayData::U32Vec3 => f.write_str("u32vec3"),
+        ast::TypeSpecifierNonArrayData::U32Vec4 => f.write_str("u32vec4"),
+        ast::TypeSpecifierNonArrayData::Int64 => f.write_str("int64_t"),
+        ast::TypeSpecifierNonArrayData::I64Vec2 => f.write_str("i64vec2"),
+        ast::TypeSpecifierNonArrayData::I64Vec3 => f.write_str("i64vec3"),
+        ast::TypeSpecifierNonArrayData::I64Vec4 => f.write_str("i64vec4"),
+        ast::TypeSpecifierNonArrayData::UInt64 => f.write_str("uint64_t"),
+        ast::TypeSpecifierNonArrayData::U64Vec2 => f.write_str("u64vec2"),
+        ast::TypeSpecifierNonArrayData::U64Vec3 => f.write_str("u64vec3"),
+        ast::TypeSpecifierNonArrayData::U64Vec4 => f.write_str("u64vec4"),
+        ast::TypeSpecifierNonArrayData::Float16 => f.write_str("float16_t"),
+        ast::TypeSpecifierNonArrayData::F16Vec2 => f.write_str("f16vec2"),
+        ast::TypeSpecifierNonArrayData::F16Vec3 => f.write_str("f16vec3"),
+        ast::TypeSpecifierNonArrayData::F16Vec4 => f.write_str("f16vec4"),
+        ast::TypeSpecifierNonArrayData::F16Mat2 => f.write_str("f16mat2"),
+        ast::TypeSpecifierNonArrayData::F16Mat3 => f.write_str("f16mat3"),
+        ast::TypeSpecifierNonArrayData::F16Mat4 => f.write_str("f16mat4"),
+        ast::TypeSpecifierNonArrayData::F16Mat22 => f.write_str("f16mat2x2"),
+        ast::TypeSpecifierNonArrayData::F16Mat23 => f.write_str("f16mat2x3"),
+        ast::TypeSpecifierNonArrayData::F16Mat24 => f.write_str("f16mat2x4"),
+        ast::TypeSpecifierNonArrayData::F16Mat32 => f.write_str("f16mat3x2"),
+        ast::TypeSpecifierNonArrayData::F16Mat33 => f.write_str("f16mat3x3"),
+        ast::TypeSpecifierNonArrayData::F16Mat34 => f.write_str("f16mat3x4"),
+        ast::TypeSpecifierNonArrayData::F16Mat42 => f.write_str("f16mat4x2"),
+        ast::TypeSpecifierNonArrayData::F16Mat43 => f.write_str("f16mat4x3"),
+        ast::TypeSpecifierNonArrayData::F16Mat44 => f.write_str("f16mat4x4"),
+        ast::TypeSpecifierNonArrayData::Float32 => f.write_str("float32_t"),
+        ast::TypeSpecifierNonArrayData::F32Vec2 => f.write_str("f32vec2"),
+        ast::TypeSpecifierNonArrayData::F32Vec3 => f.write_str("f32vec3"),
+        ast::TypeSpecifierNonArrayData::F32Vec4 => f.write_str("f32vec4"),
+        ast::TypeSpecifierNonArrayData::F32Mat2 => f.write_str("f32mat2"),
+        ast::TypeSpecifierNonArrayData::F32Mat3 => f.write_str("f32mat3"),
+        ast::TypeSpecifierNonArrayData::F32Mat4 => f.write_str("f32mat4"),
+        ast::TypeSpecifierNonArrayData::F32Mat22 => f.write_str("f32mat2x2"),
+        ast::TypeSpecifierNonArrayData::F32Mat23 => f.write_str("f32mat2x3"),
+        ast::TypeSpecifierNonArrayData::F32Mat24 => f.write_str("f32mat2x4"),
+        ast::TypeSpecifierNonArrayData::F32Mat32 => f.write_str("f32mat3x2"),
+        ast::TypeSpecifierNonArrayData::F32Mat33 => f.write_str("f32mat3x3"),
+        ast::TypeSpecifierNonArrayData::F32Mat34 => f.write_str("f32mat3x4"),
+        ast::TypeSpecifierNonArrayData::F32Mat42 => f.write_str("f32mat4x2"),
+        ast::TypeSpecifierNonArrayData::F32Mat43 => f.write_str("f32mat4x3"),
+        ast::TypeSpecifierNonArrayData::F32Mat44 => f.write_str("f32mat4x4"),
+        ast::TypeSpecifierNonArrayData::Float64 => f.write_str("float64_t"),
+        ast::TypeSpecifierNonArrayData::F64Vec2 => f.write_str("f64vec2"),
+        ast::TypeSpecifierNonArrayData::F64Vec3 => f.write_str("f64vec3"),
+        ast::TypeSpecifierNonArrayData::F64Vec4 => f.write_str("f64vec4"),
+        ast::TypeSpecifierNonArrayData::F64Mat2 => f.write_str("f64mat2"),
+        ast::TypeSpecifierNonArrayData::F64Mat3 => f.write_str("f64mat3"),
+        ast::TypeSpecifierNonArrayData::F64Mat4 => f.write_str("f64mat4"),
+        ast::TypeSpecifierNonArrayData::F64Mat22 => f.write_str("f64mat2x2"),
+        ast::TypeSpecifierNonArrayData::F64Mat23 => f.write_str("f64mat2x3"),
+        ast::TypeSpecifierNonArrayData::F64Mat24 => f.write_str("f64mat2x4"),
+        ast::TypeSpecifierNonArrayData::F64Mat32 => f.write_str("f64mat3x2"),
+        ast::TypeSpecifierNonArrayData::F64Mat33 => f.write_str("f64mat3x3"),
+        ast::TypeSpecifierNonArrayData::F64Mat34 => f.write_str("f64mat3x4"),
+        ast::TypeSpecifierNonArrayData::F64Mat42 => f.write_str("f64mat4x2"),
+        ast::TypeSpecifierNonArrayData::F64Mat43 => f.write_str("f64mat4x3"),
+        ast::TypeSpecifierNonArrayData::F64Mat44 => f.write_str("f64mat4x4"),
+        ast::TypeSpecifierNonArrayData::AccelerationStructureExt => {
+            f.write_str("accelerationStructureEXT")
+        }
+        ast::TypeSpecifierNonArrayData::RayQueryExt => f.write_str("rayQueryEXT"),
+        ast::TypeSpecifierNonArrayData::SpirvType(ref args) => {
+            f.write_str("spirv_type")?;
+            show_spirv_arguments(f, args, state)
+        }
         ast::TypeSpecifierNonArrayData::Struct(ref st) => show_struct_non_declaration(f, st, state),
         ast::TypeSpecifierNonArrayData::TypeName(ref tn) => show_type_name(f, tn, state),
     }
@@ -870,6 +1318,8 @@ pub fn show_type_specifier<F>(
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(t);
+
     show_type_specifier_non_array(f, &t.ty, state)?;
 
     if let Some(ref arr_spec) = t.array_specifier {
@@ -888,6 +1338,8 @@ pub fn show_fully_specified_type<F>(
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(t);
+
     if let Some(ref qual) = t.qualifier {
         show_type_qualifier(f, qual, state)?;
         f.write_char(' ')?;
@@ -914,11 +1366,14 @@ where
     state.enter_block(f)?;
 
     for field in &st.fields {
+        state.write_leading_comments(f, field)?;
         state.flush_line(f)?;
         show_struct_field(f, field, state)?;
         state.write_struct_field_separator(f)?;
+        state.write_trailing_comments(f, field)?;
     }
 
+    state.write_block_end_comments(f, st)?;
     state.exit_block(f)?;
 
     Ok(())
@@ -946,6 +1401,8 @@ pub fn show_struct_field<F>(
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(field);
+
     if let Some(ref qual) = field.qualifier {
         show_type_qualifier(f, qual, state)?;
         f.write_char(' ')?;
@@ -1050,6 +1507,7 @@ where
         }
         ast::TypeQualifierSpecData::Invariant => f.write_str("invariant"),
         ast::TypeQualifierSpecData::Precise => f.write_str("precise"),
+        ast::TypeQualifierSpecData::Spirv(ref s) => show_spirv_qualifier(f, s, state),
     }
 }
 
@@ -1081,6 +1539,13 @@ where
         ast::StorageQualifierData::Attribute => f.write_str("attribute"),
         ast::StorageQualifierData::Varying => f.write_str("varying"),
         ast::StorageQualifierData::Subroutine(ref n) => show_subroutine(f, n, state),
+        ast::StorageQualifierData::ShaderCallCoherent => f.write_str("shadercallcoherent"),
+        ast::StorageQualifierData::RayPayloadExt => f.write_str("rayPayloadEXT"),
+        ast::StorageQualifierData::RayPayloadInExt => f.write_str("rayPayloadInEXT"),
+        ast::StorageQualifierData::HitAttributeExt => f.write_str("hitAttributeEXT"),
+        ast::StorageQualifierData::CallableDataExt => f.write_str("callableDataEXT"),
+        ast::StorageQualifierData::CallableDataInExt => f.write_str("callableDataInEXT"),
+        ast::StorageQualifierData::TaskPayloadSharedExt => f.write_str("taskPayloadSharedEXT"),
     }
 }
 
@@ -1157,6 +1622,127 @@ where
     }
 }
 
+/// Transpile a GL_EXT_spirv_intrinsics qualifier to GLSL
+pub fn show_spirv_qualifier<F>(
+    f: &mut F,
+    q: &ast::SpirvQualifier,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    let (name, args) = match **q {
+        ast::SpirvQualifierData::Instruction(ref args) => ("spirv_instruction", args),
+        ast::SpirvQualifierData::ExecutionMode(ref args) => ("spirv_execution_mode", args),
+        ast::SpirvQualifierData::ExecutionModeId(ref args) => ("spirv_execution_mode_id", args),
+        ast::SpirvQualifierData::Decorate(ref args) => ("spirv_decorate", args),
+        ast::SpirvQualifierData::DecorateId(ref args) => ("spirv_decorate_id", args),
+        ast::SpirvQualifierData::DecorateString(ref args) => ("spirv_decorate_string", args),
+        ast::SpirvQualifierData::StorageClass(ref args) => ("spirv_storage_class", args),
+        ast::SpirvQualifierData::ByReference => return f.write_str("spirv_by_reference"),
+        ast::SpirvQualifierData::Literal => return f.write_str("spirv_literal"),
+    };
+
+    f.write_str(name)?;
+    show_spirv_arguments(f, args, state)
+}
+
+/// Transpile a parenthesized list of GL_EXT_spirv_intrinsics arguments to GLSL
+pub fn show_spirv_arguments<F>(
+    f: &mut F,
+    args: &[ast::SpirvArgument],
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    f.write_char('(')?;
+
+    let mut args = args.iter();
+    if let Some(first) = args.next() {
+        show_spirv_argument(f, first, state)?;
+
+        for arg in args {
+            state.write_list_separator(f)?;
+            show_spirv_argument(f, arg, state)?;
+        }
+    }
+
+    f.write_char(')')
+}
+
+/// Transpile a GL_EXT_spirv_intrinsics argument to GLSL
+pub fn show_spirv_argument<F>(
+    f: &mut F,
+    a: &ast::SpirvArgument,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    match **a {
+        ast::SpirvArgumentData::Requirement(ref i, ref values) => {
+            show_identifier(f, i, state)?;
+            state.write_binary_op(f, "=")?;
+            f.write_char('[')?;
+
+            let mut values = values.iter();
+            if let Some(first) = values.next() {
+                show_spirv_value(f, first, state)?;
+
+                for value in values {
+                    state.write_list_separator(f)?;
+                    show_spirv_value(f, value, state)?;
+                }
+            }
+
+            f.write_char(']')
+        }
+        ast::SpirvArgumentData::Named(ref i, ref value) => {
+            show_identifier(f, i, state)?;
+            state.write_binary_op(f, "=")?;
+            show_spirv_value(f, value, state)
+        }
+        ast::SpirvArgumentData::Value(ref value) => show_spirv_value(f, value, state),
+    }
+}
+
+/// Transpile a GL_EXT_spirv_intrinsics argument value to GLSL
+pub fn show_spirv_value<F>(
+    f: &mut F,
+    v: &ast::SpirvValue,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    match **v {
+        ast::SpirvValueData::String(ref s) => show_string_constant(f, s),
+        ast::SpirvValueData::Expr(ref e) => show_expr(f, e, state),
+        ast::SpirvValueData::Type(ref t) => show_type_specifier_non_array(f, t, state),
+    }
+}
+
+/// Transpile a string literal to GLSL, escaping the characters which need it
+pub fn show_string_constant<F>(f: &mut F, s: &str) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\t' => f.write_str("\\t")?,
+            '\r' => f.write_str("\\r")?,
+            '\0' => f.write_str("\\0")?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
 /// Transpile a precision_qualifier to GLSL
 pub fn show_precision_qualifier<F>(
     f: &mut F,
@@ -1186,30 +1772,96 @@ where
         ast::InterpolationQualifierData::Smooth => f.write_str("smooth"),
         ast::InterpolationQualifierData::Flat => f.write_str("flat"),
         ast::InterpolationQualifierData::NoPerspective => f.write_str("noperspective"),
+        ast::InterpolationQualifierData::PerPrimitiveExt => f.write_str("perprimitiveEXT"),
+        ast::InterpolationQualifierData::PerPrimitiveNv => f.write_str("perprimitiveNV"),
+        ast::InterpolationQualifierData::PerViewNv => f.write_str("perviewNV"),
+        ast::InterpolationQualifierData::TaskNv => f.write_str("taskNV"),
     }
 }
 
 /// Transpile a float<F>(f: &mut F, x: f32, _: &mut FormattingState to GLSL
+///
+/// The shortest literal that parses back to the same value is written. Infinities and NaNs, which
+/// have no literal representation, are written as constant divisions instead.
 pub fn show_float<F>(f: &mut F, x: f32, _: &mut FormattingState<'_>) -> std::fmt::Result
 where
     F: Write + ?Sized,
 {
-    if x.fract() == 0. {
-        write!(f, "{}.", x)
-    } else {
-        write!(f, "{}", x)
-    }
+    write_float_value(f, x.into(), format!("{}", x), format!("{:e}", x), "")
 }
 
 /// Transpile a double<F>(f: &mut F, x: f64, _: &mut FormattingState to GLSL
+///
+/// The shortest literal that parses back to the same value is written. Infinities and NaNs, which
+/// have no literal representation, are written as constant divisions instead.
 pub fn show_double<F>(f: &mut F, x: f64, _: &mut FormattingState<'_>) -> std::fmt::Result
 where
     F: Write + ?Sized,
 {
-    if x.fract() == 0. {
-        write!(f, "{}.lf", x)
+    write_float_value(f, x, format!("{}", x), format!("{:e}", x), "lf")
+}
+
+/// Transpile a half precision float to GLSL
+///
+/// The value is written with the `hf` suffix of GL_EXT_shader_explicit_arithmetic_types_float16.
+pub fn show_float16<F>(f: &mut F, x: f32, _: &mut FormattingState<'_>) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    write_float_value(f, x.into(), format!("{}", x), format!("{:e}", x), "hf")
+}
+
+/// Write a floating-point value, given its decimal and exponent representations
+fn write_float_value<F>(
+    f: &mut F,
+    x: f64,
+    decimal: String,
+    exponent: String,
+    suffix: &str,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    if x.is_nan() {
+        return write!(f, "(0.0{0}/0.0{0})", suffix);
+    } else if x.is_infinite() {
+        let sign = if x.is_sign_negative() { "-" } else { "" };
+        return write!(f, "({1}1.0{0}/0.0{0})", suffix, sign);
+    }
+
+    let decimal = if decimal.contains('.') {
+        decimal
     } else {
-        write!(f, "{}lf", x)
+        decimal + "."
+    };
+
+    if exponent.len() < decimal.len() {
+        write!(f, "{}{}", exponent, suffix)
+    } else {
+        write!(f, "{}{}", decimal, suffix)
+    }
+}
+
+/// Write an integer literal, using its source spelling if it has one
+fn write_int_literal<F, T>(f: &mut F, x: &ast::Literal<T>, suffix: &str) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+    T: Copy + Default + PartialOrd + std::fmt::Display + std::fmt::UpperHex + std::fmt::Octal,
+{
+    if let Some(text) = x.text() {
+        return f.write_str(text);
+    }
+
+    let value = x.value();
+    if value <= T::default() {
+        // Negative values are only valid in decimal form
+        return write!(f, "{}{}", value, suffix);
+    }
+
+    match x.radix() {
+        ast::IntRadix::Decimal => write!(f, "{}{}", value, suffix),
+        ast::IntRadix::Octal => write!(f, "0{:o}{}", value, suffix),
+        ast::IntRadix::Hexadecimal => write!(f, "0x{:X}{}", value, suffix),
     }
 }
 
@@ -1222,13 +1874,36 @@ pub fn show_expr<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) = show_with_max_line_width(f, state, |f, state| show_expr(f, expr, state)) {
+        return result;
+    }
+
+    state.write_expr_comments(f, expr)?;
+
+    let _node = state.enter_node(expr);
+    state.begin_layout();
+
     match **expr {
         ast::ExprData::Variable(ref i) => show_identifier(f, i, state),
-        ast::ExprData::IntConst(ref x) => write!(f, "{}", x),
-        ast::ExprData::UIntConst(ref x) => write!(f, "{}u", x),
+        ast::ExprData::IntConst(ref x) => write_int_literal(f, x, ""),
+        ast::ExprData::UIntConst(ref x) => write_int_literal(f, x, "u"),
         ast::ExprData::BoolConst(ref x) => write!(f, "{}", x),
-        ast::ExprData::FloatConst(ref x) => show_float(f, *x, state),
-        ast::ExprData::DoubleConst(ref x) => show_double(f, *x, state),
+        ast::ExprData::FloatConst(ref x) => match x.text() {
+            Some(text) => f.write_str(text),
+            None => show_float(f, x.value(), state),
+        },
+        ast::ExprData::DoubleConst(ref x) => match x.text() {
+            Some(text) => f.write_str(text),
+            None => show_double(f, x.value(), state),
+        },
+        ast::ExprData::Int16Const(ref x) => write_int_literal(f, x, "s"),
+        ast::ExprData::UInt16Const(ref x) => write_int_literal(f, x, "us"),
+        ast::ExprData::Int64Const(ref x) => write_int_literal(f, x, "l"),
+        ast::ExprData::UInt64Const(ref x) => write_int_literal(f, x, "ul"),
+        ast::ExprData::Float16Const(ref x) => match x.text() {
+            Some(text) => f.write_str(text),
+            None => show_float16(f, x.value(), state),
+        },
         ast::ExprData::Unary(ref op, ref e) => {
             // Note: all unary ops are right-to-left associative
             show_unary_op(f, op, state)?;
@@ -1251,29 +1926,11 @@ where
                 show_expr(f, e, state)
             }
         }
-        ast::ExprData::Binary(ref op, ref l, ref r) => {
-            // Note: all binary ops are left-to-right associative (<= for left part)
-
-            if l.precedence() <= op.precedence() {
-                show_expr(f, l, state)?;
-            } else {
-                f.write_char('(')?;
-                show_expr(f, l, state)?;
-                f.write_char(')')?;
-            }
-
-            show_binary_op(f, op, state)?;
-
-            if r.precedence() < op.precedence() {
-                show_expr(f, r, state)
-            } else {
-                f.write_char('(')?;
-                show_expr(f, r, state)?;
-                f.write_char(')')
-            }
-        }
+        ast::ExprData::Binary(ref op, ref l, ref r) => show_binary_expr(f, op, l, r, state, false),
         ast::ExprData::Ternary(ref c, ref st, ref e) => {
             // Note: ternary is right-to-left associative (<= for right part)
+            state.enter_wrap(Container::Group);
+            state.enter_wrap(Container::Align);
 
             if c.precedence() < expr.precedence() {
                 show_expr(f, c, state)?;
@@ -1286,12 +1943,16 @@ where
             show_expr(f, st, state)?;
             state.write_binary_op(f, ":")?;
             if e.precedence() <= expr.precedence() {
-                show_expr(f, e, state)
+                show_expr(f, e, state)?;
             } else {
                 f.write_char('(')?;
                 show_expr(f, e, state)?;
-                f.write_char(')')
+                f.write_char(')')?;
             }
+
+            state.exit_wrap();
+            state.exit_wrap();
+            Ok(())
         }
         ast::ExprData::Assignment(ref v, ref op, ref e) => {
             // Note: all assignment ops are right-to-left associative
@@ -1332,6 +1993,8 @@ where
         ast::ExprData::FunCall(ref fun, ref args) => {
             show_function_identifier(f, fun, state)?;
             f.write_char('(')?;
+            state.enter_wrap(Container::Group);
+            state.enter_wrap(Container::Align);
 
             if !args.is_empty() {
                 let mut args_iter = args.iter();
@@ -1344,6 +2007,8 @@ where
                 }
             }
 
+            state.exit_wrap();
+            state.exit_wrap();
             f.write_char(')')
         }
         ast::ExprData::Dot(ref e, ref i) => {
@@ -1406,7 +2071,65 @@ where
                 f.write_char(')')
             }
         }
+    }?;
+
+    state.end_layout(f)
+}
+
+/// Transpile a binary expression to GLSL
+///
+/// `chained` is true if this expression is the left operand of a binary expression with the
+/// same precedence, in which case it is wrapped along with its parent.
+fn show_binary_expr<F>(
+    f: &mut F,
+    op: &ast::BinaryOp,
+    l: &ast::Expr,
+    r: &ast::Expr,
+    state: &mut FormattingState<'_>,
+    chained: bool,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    // Note: all binary ops are left-to-right associative (<= for left part)
+
+    if !chained {
+        state.enter_wrap(Container::Group);
+        state.enter_wrap(Container::Align);
+    }
+
+    match **l {
+        ast::ExprData::Binary(ref lop, ref ll, ref lr) if l.precedence() == op.precedence() => {
+            let _node = state.enter_node(l);
+            show_binary_expr(f, lop, ll, lr, state, true)?;
+        }
+        _ => {
+            if l.precedence() <= op.precedence() {
+                show_expr(f, l, state)?;
+            } else {
+                f.write_char('(')?;
+                show_expr(f, l, state)?;
+                f.write_char(')')?;
+            }
+        }
     }
+
+    show_binary_op(f, op, state)?;
+
+    if r.precedence() < op.precedence() {
+        show_expr(f, r, state)?;
+    } else {
+        f.write_char('(')?;
+        show_expr(f, r, state)?;
+        f.write_char(')')?;
+    }
+
+    if !chained {
+        state.exit_wrap();
+        state.exit_wrap();
+    }
+
+    Ok(())
 }
 
 /// Transpile a path<F>(f: &mut F, path: &ast::Path, _: &mut FormattingState to GLSL
@@ -1481,17 +2204,17 @@ where
     F: Write + ?Sized,
 {
     match **op {
-        ast::AssignmentOpData::Equal => state.write_binary_op(f, "="),
-        ast::AssignmentOpData::Mult => state.write_binary_op(f, "*="),
-        ast::AssignmentOpData::Div => state.write_binary_op(f, "/="),
-        ast::AssignmentOpData::Mod => state.write_binary_op(f, "%="),
-        ast::AssignmentOpData::Add => state.write_binary_op(f, "+="),
-        ast::AssignmentOpData::Sub => state.write_binary_op(f, "-="),
-        ast::AssignmentOpData::LShift => state.write_binary_op(f, "<<="),
-        ast::AssignmentOpData::RShift => state.write_binary_op(f, ">>="),
-        ast::AssignmentOpData::And => state.write_binary_op(f, "&="),
-        ast::AssignmentOpData::Xor => state.write_binary_op(f, "^="),
-        ast::AssignmentOpData::Or => state.write_binary_op(f, "|="),
+        ast::AssignmentOpData::Equal => state.write_assignment_op(f, "="),
+        ast::AssignmentOpData::Mult => state.write_assignment_op(f, "*="),
+        ast::AssignmentOpData::Div => state.write_assignment_op(f, "/="),
+        ast::AssignmentOpData::Mod => state.write_assignment_op(f, "%="),
+        ast::AssignmentOpData::Add => state.write_assignment_op(f, "+="),
+        ast::AssignmentOpData::Sub => state.write_assignment_op(f, "-="),
+        ast::AssignmentOpData::LShift => state.write_assignment_op(f, "<<="),
+        ast::AssignmentOpData::RShift => state.write_assignment_op(f, ">>="),
+        ast::AssignmentOpData::And => state.write_assignment_op(f, "&="),
+        ast::AssignmentOpData::Xor => state.write_assignment_op(f, "^="),
+        ast::AssignmentOpData::Or => state.write_assignment_op(f, "|="),
     }
 }
 
@@ -1519,6 +2242,15 @@ pub fn show_declaration<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) =
+        show_with_max_line_width(f, state, |f, state| show_declaration(f, d, state))
+    {
+        return result;
+    }
+
+    let _node = state.enter_node(d);
+    state.begin_layout();
+
     match **d {
         ast::DeclarationData::FunctionPrototype(ref proto) => {
             show_function_prototype(f, proto, state)?;
@@ -1540,12 +2272,27 @@ where
             f.write_char(' ')?;
             show_identifier(f, ident, state)?;
         }
+        ast::DeclarationData::QualifierRedeclaration(ref q, ref idents) => {
+            show_type_qualifier(f, q, state)?;
+            f.write_char(' ')?;
+
+            // there’s at least one identifier
+            let mut idents = idents.iter();
+            show_identifier(f, idents.next().unwrap(), state)?;
+
+            // write the rest of the identifiers
+            for ident in idents {
+                state.write_list_separator(f)?;
+                show_identifier(f, ident, state)?;
+            }
+        }
         ast::DeclarationData::TypeOnly(ref q) => {
             show_type_qualifier(f, q, state)?;
         }
     }
 
-    state.write_declaration_terminator(f)
+    state.write_declaration_terminator(f)?;
+    state.end_layout(f)
 }
 
 /// Transpile a function_prototype to GLSL
@@ -1557,11 +2304,22 @@ pub fn show_function_prototype<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) =
+        show_with_max_line_width(f, state, |f, state| show_function_prototype(f, fp, state))
+    {
+        return result;
+    }
+
+    let _node = state.enter_node(fp);
+    state.begin_layout();
+
     show_fully_specified_type(f, &fp.ty, state)?;
     f.write_char(' ')?;
     show_identifier(f, &fp.name, state)?;
 
     f.write_char('(')?;
+    state.enter_wrap(Container::Group);
+    state.enter_wrap(Container::Align);
 
     if !fp.parameters.is_empty() {
         let mut iter = fp.parameters.iter();
@@ -1574,7 +2332,11 @@ where
         }
     }
 
-    f.write_char(')')
+    state.exit_wrap();
+    state.exit_wrap();
+    f.write_char(')')?;
+
+    state.end_layout(f)
 }
 /// Transpile a function_parameter_declaration to GLSL
 pub fn show_function_parameter_declaration<F>(
@@ -1585,6 +2347,8 @@ pub fn show_function_parameter_declaration<F>(
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(p);
+
     match **p {
         ast::FunctionParameterDeclarationData::Named(ref qual, ref fpd) => {
             if let Some(ref q) = *qual {
@@ -1647,6 +2411,8 @@ pub fn show_single_declaration<F>(
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(d);
+
     show_fully_specified_type(f, &d.ty, state)?;
 
     if let Some(ref name) = d.name {
@@ -1694,6 +2460,15 @@ pub fn show_initializer<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) =
+        show_with_max_line_width(f, state, |f, state| show_initializer(f, i, state))
+    {
+        return result;
+    }
+
+    let _node = state.enter_node(i);
+    state.begin_layout();
+
     match **i {
         ast::InitializerData::Simple(ref e) => show_expr(f, e, state),
         ast::InitializerData::List(ref list) => {
@@ -1711,7 +2486,9 @@ where
 
             state.end_initializer_list(f)
         }
-    }
+    }?;
+
+    state.end_layout(f)
 }
 
 /// Transpile a block<F>(f: &mut F, b: &ast::Block, state: &mut FormattingState to GLSL
@@ -1726,11 +2503,14 @@ where
     state.enter_block(f)?;
 
     for field in &b.fields {
+        state.write_leading_comments(f, field)?;
         state.flush_line(f)?;
         show_struct_field(f, field, state)?;
         state.write_struct_field_separator(f)?;
+        state.write_trailing_comments(f, field)?;
     }
 
+    state.write_block_end_comments(f, b)?;
     state.exit_block(f)?;
 
     if let Some(ref ident) = b.identifier {
@@ -1749,6 +2529,14 @@ pub fn show_function_definition<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) =
+        show_with_max_line_width(f, state, |f, state| show_function_definition(f, fd, state))
+    {
+        return result;
+    }
+
+    let _node = state.enter_node(fd);
+
     show_function_prototype(f, &fd.prototype, state)?;
     state.enter_function_definition_statement();
     show_compound_statement(f, &fd.statement, state)?;
@@ -1756,6 +2544,44 @@ where
     state.write_function_definition_terminator(f)
 }
 
+/// Transpile a function_definition with attributes to GLSL
+pub fn show_attributed_function_definition<F>(
+    f: &mut F,
+    afd: &ast::AttributedFunctionDefinition,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    if let Some(result) = show_with_max_line_width(f, state, |f, state| {
+        show_attributed_function_definition(f, afd, state)
+    }) {
+        return result;
+    }
+
+    let _node = state.enter_node(afd);
+
+    if !afd.attributes.is_empty() {
+        show_attributes(f, &afd.attributes, state)?;
+        f.write_char(' ')?;
+    }
+
+    let fd = &afd.definition;
+    let _definition = state.enter_node(fd);
+
+    show_function_prototype(f, &fd.prototype, state)?;
+
+    if !afd.body_attributes.is_empty() {
+        f.write_char(' ')?;
+        show_attributes(f, &afd.body_attributes, state)?;
+    }
+
+    state.enter_function_definition_statement();
+    show_compound_statement(f, &fd.statement, state)?;
+    state.flush_line(f)?;
+    state.write_function_definition_terminator(f)
+}
+
 /// Transpile a compound_statement to GLSL
 pub fn show_compound_statement<F>(
     f: &mut F,
@@ -1765,6 +2591,12 @@ pub fn show_compound_statement<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) =
+        show_with_max_line_width(f, state, |f, state| show_compound_statement(f, cst, state))
+    {
+        return result;
+    }
+
     // Function definitions are the only symbols that require compound statements
     let collapse = !state.in_function_definition_statement
         && state.settings.collapse_single_item_compound_statements
@@ -1783,6 +2615,8 @@ where
         show_statement(f, st, state)?;
     }
 
+    state.write_block_end_comments(f, cst)?;
+
     if collapse {
         state.exit_collapsed_compound_statement()?;
     } else {
@@ -1801,6 +2635,16 @@ pub fn show_statement<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) =
+        show_with_max_line_width(f, state, |f, state| show_statement(f, st, state))
+    {
+        return result;
+    }
+
+    state.write_leading_comments(f, st)?;
+
+    let _node = state.enter_node(st);
+
     state.flush_line(f)?;
 
     match **st {
@@ -1812,7 +2656,41 @@ where
         ast::StatementData::Iteration(ref i) => show_iteration_statement(f, i, state),
         ast::StatementData::Jump(ref j) => show_jump_statement(f, j, state),
         ast::StatementData::Compound(ref c) => show_compound_statement(f, c, state),
+        ast::StatementData::Attributed(ref a) => show_attributed_statement(f, a, state),
+        // Syntax errors are written as empty statements to keep the output valid
+        ast::StatementData::Error => state.write_statement_terminator(f),
+    }?;
+
+    state.write_trailing_comments(f, st)
+}
+
+/// Transpile the body of a selection or iteration statement to GLSL
+///
+/// If a single-line comment before the body forced a line break, a body which is not a block is
+/// indented on its own line, and the rest of the parent statement starts on the next line.
+pub fn show_statement_body<F>(
+    f: &mut F,
+    st: &ast::Statement,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    state.write_leading_comments(f, st)?;
+
+    if !state.line_comment_pending || matches!(**st, ast::StatementData::Compound(_)) {
+        return show_statement(f, st, state);
     }
+
+    state.indentation_level += 1;
+    show_statement(f, st, state)?;
+    state.indentation_level -= 1;
+
+    // Nothing else can be written on the line of the nested body
+    state.new_line(true)?;
+    state.line_comment_pending = true;
+
+    Ok(())
 }
 
 /// Transpile an expression_statement to GLSL
@@ -1824,11 +2702,14 @@ pub fn show_expression_statement<F>(
 where
     F: Write + ?Sized,
 {
+    state.begin_layout();
+
     if let Some(ref e) = est.0 {
         show_expr(f, e, state)?;
     }
 
-    state.write_statement_terminator(f)
+    state.write_statement_terminator(f)?;
+    state.end_layout(f)
 }
 
 /// Transpile a selection_statement to GLSL
@@ -1847,6 +2728,65 @@ where
     show_selection_rest_statement(f, &sst.rest, state)
 }
 
+/// Transpile an attribute to GLSL
+pub fn show_attribute<F>(
+    f: &mut F,
+    a: &ast::Attribute,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    show_identifier(f, &a.name, state)?;
+
+    if let Some(ref arg) = a.arg {
+        f.write_char('(')?;
+        show_expr(f, arg, state)?;
+        f.write_char(')')?;
+    }
+
+    Ok(())
+}
+
+/// Transpile an attributed statement to GLSL
+pub fn show_attributed_statement<F>(
+    f: &mut F,
+    ast_st: &ast::AttributedStatement,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    show_attributes(f, &ast_st.attributes, state)?;
+    f.write_char(' ')?;
+    show_statement(f, &ast_st.statement, state)
+}
+
+/// Transpile a list of attributes to GLSL, as a single `[[...]]` group
+pub fn show_attributes<F>(
+    f: &mut F,
+    attributes: &[ast::Attribute],
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    let mut attributes = attributes.iter();
+    let Some(first) = attributes.next() else {
+        return Ok(());
+    };
+
+    f.write_str("[[")?;
+    show_attribute(f, first, state)?;
+
+    for attribute in attributes {
+        state.write_list_separator(f)?;
+        show_attribute(f, attribute, state)?;
+    }
+
+    f.write_str("]]")
+}
+
 /// Transpile a selection_rest_statement to GLSL
 pub fn show_selection_rest_statement<F>(
     f: &mut F,
@@ -1857,14 +2797,16 @@ where
     F: Write + ?Sized,
 {
     match **sst {
-        ast::SelectionRestStatementData::Statement(ref if_st) => show_statement(f, if_st, state),
+        ast::SelectionRestStatementData::Statement(ref if_st) => {
+            show_statement_body(f, if_st, state)
+        }
         ast::SelectionRestStatementData::Else(ref if_st, ref else_st) => {
-            show_statement(f, if_st, state)?;
+            show_statement_body(f, if_st, state)?;
             state.write_else(f)?;
             // TODO: This should be configurable instead of relying on show_statement's calling
             // flush_line
             state.consume_newline();
-            show_statement(f, else_st, state)
+            show_statement_body(f, else_st, state)
         }
     }
 }
@@ -1891,6 +2833,7 @@ where
         show_statement(f, st, state)?;
     }
 
+    state.write_block_end_comments(f, sst)?;
     state.exit_block(f)
 }
 
@@ -1931,12 +2874,15 @@ where
             state.write_statement_opening_parenthesis(f)?;
             show_condition(f, cond, state)?;
             state.write_statement_closing_parenthesis(f)?;
-            show_statement(f, body, state)
+            show_statement_body(f, body, state)
         }
         ast::IterationStatementData::DoWhile(ref body, ref cond) => {
             f.write_str("do ")?;
-            show_statement(f, body, state)?;
-            f.write_str(" while")?;
+            show_statement_body(f, body, state)?;
+            if !state.end_line_comment(f)? {
+                f.write_char(' ')?;
+            }
+            f.write_str("while")?;
             state.write_statement_opening_parenthesis(f)?;
             show_expr(f, cond, state)?;
             // Do not call write_statement_closing_parenthesis to maybe save an unexpected
@@ -1951,7 +2897,7 @@ where
             state.flush_space(f)?;
             show_for_rest_statement(f, rest, state)?;
             state.write_statement_closing_parenthesis(f)?;
-            show_statement(f, body, state)
+            show_statement_body(f, body, state)
         }
     }
 }
@@ -2029,10 +2975,14 @@ pub fn show_jump_statement<F>(
 where
     F: Write + ?Sized,
 {
+    state.begin_layout();
+
     match **j {
         ast::JumpStatementData::Continue => f.write_str("continue")?,
         ast::JumpStatementData::Break => f.write_str("break")?,
         ast::JumpStatementData::Discard => f.write_str("discard")?,
+        ast::JumpStatementData::IgnoreIntersectionExt => f.write_str("ignoreIntersectionEXT")?,
+        ast::JumpStatementData::TerminateRayExt => f.write_str("terminateRayEXT")?,
         ast::JumpStatementData::Return(ref e) => {
             f.write_str("return")?;
             if let Some(e) = e {
@@ -2042,7 +2992,8 @@ where
         }
     }
 
-    state.write_statement_terminator(f)
+    state.write_statement_terminator(f)?;
+    state.end_layout(f)
 }
 
 /// Transpile a preprocessor to GLSL
@@ -2054,6 +3005,8 @@ pub fn show_preprocessor<F>(
 where
     F: Write + ?Sized,
 {
+    let _node = state.enter_node(pp);
+
     // As per spec, preprocessor directives must start on a line prefixed with
     // zero or more horizontal space characters
     state.move_to_empty_line(f)?;
@@ -2067,6 +3020,7 @@ where
         ast::PreprocessorData::If(ref pi) => show_preprocessor_if(f, pi, state)?,
         ast::PreprocessorData::IfDef(ref pid) => show_preprocessor_ifdef(f, pid, state)?,
         ast::PreprocessorData::IfNDef(ref pind) => show_preprocessor_ifndef(f, pind, state)?,
+        ast::PreprocessorData::Include(ref pi) => show_preprocessor_include(f, pi, state)?,
         ast::PreprocessorData::MojImport(ref pmi) => show_preprocessor_moj_import(f, pmi, state)?,
         ast::PreprocessorData::Line(ref pl) => show_preprocessor_line(f, pl, state)?,
         ast::PreprocessorData::Pragma(ref pp) => show_preprocessor_pragma(f, pp, state)?,
@@ -2192,6 +3146,19 @@ where
     show_identifier(f, &pind.ident, state)
 }
 
+/// Transpile a preprocessor_include to GLSL
+pub fn show_preprocessor_include<F>(
+    f: &mut F,
+    pi: &ast::PreprocessorInclude,
+    state: &mut FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    f.write_str("#include ")?;
+    show_path(f, &pi.path, state)
+}
+
 /// Transpile a preprocessor_moj_import to GLSL
 pub fn show_preprocessor_moj_import<F>(
     f: &mut F,
@@ -2323,6 +3290,16 @@ pub fn show_external_declaration<F>(
 where
     F: Write + ?Sized,
 {
+    if let Some(result) =
+        show_with_max_line_width(f, state, |f, state| show_external_declaration(f, ed, state))
+    {
+        return result;
+    }
+
+    state.write_leading_comments(f, ed)?;
+
+    let _node = state.enter_node(ed);
+
     state.enter_external_declaration(f)?;
 
     match **ed {
@@ -2330,16 +3307,71 @@ where
         ast::ExternalDeclarationData::FunctionDefinition(ref fd) => {
             show_function_definition(f, fd, state)?
         }
+        ast::ExternalDeclarationData::AttributedFunctionDefinition(ref fd) => {
+            show_attributed_function_definition(f, fd, state)?
+        }
         ast::ExternalDeclarationData::Declaration(ref d) => show_declaration(f, d, state)?,
+        ast::ExternalDeclarationData::Error => {}
     }
 
     state.exit_external_declaration();
+    state.write_trailing_comments(f, ed)
+}
 
-    Ok(())
+/// Transpile a node on its own, wrapping its output to the maximum line width
+///
+/// Returns `None` if there is no maximum line width, or if the node is part of a larger construct
+/// which is already being wrapped.
+fn show_with_max_line_width<F>(
+    f: &mut F,
+    state: &mut FormattingState<'_>,
+    show: impl FnOnce(&mut dyn Write, &mut FormattingState<'_>) -> std::fmt::Result,
+) -> Option<std::fmt::Result>
+where
+    F: Write + ?Sized,
+{
+    let width = state
+        .settings
+        .max_line_width
+        .filter(|_| state.layout.is_none())?;
+    let layout = Layout::new(width, state.settings.indent_style);
+
+    let mut layout_state = FormattingState {
+        layout: Some(&layout),
+        ..*state
+    };
+    let result = show(&mut layout.writer(f), &mut layout_state);
+    state.update_from(&layout_state);
+
+    Some(result)
 }
 
 /// Transpile a translation_unit to GLSL
 pub fn show_translation_unit<F>(
+    f: &mut F,
+    tu: &ast::TranslationUnit,
+    state: FormattingState<'_>,
+) -> std::fmt::Result
+where
+    F: Write + ?Sized,
+{
+    match state.settings.max_line_width {
+        Some(width) if state.layout.is_none() => {
+            let layout = Layout::new(width, state.settings.indent_style);
+            show_translation_unit_inner(
+                &mut layout.writer(f),
+                tu,
+                FormattingState {
+                    layout: Some(&layout),
+                    ..state
+                },
+            )
+        }
+        _ => show_translation_unit_inner(f, tu, state),
+    }
+}
+
+fn show_translation_unit_inner<F>(
     f: &mut F,
     tu: &ast::TranslationUnit,
     mut state: FormattingState<'_>,
@@ -2351,7 +3383,7 @@ where
         show_external_declaration(f, ed, &mut state)?;
     }
 
-    Ok(())
+    state.write_remaining_comments(f)
 }
 
 #[cfg(test)]
@@ -2447,6 +3479,269 @@ mod tests {
         check_expr("arr[0][0]", expect![["arr[0][0]"]]);
     }
 
+    #[test]
+    fn numeric_literals_roundtrip() {
+        check_expr("0x1F + 017 + 42", expect![["0x1F + 017 + 42"]]);
+        check_expr("0xffu + 7U + 010u", expect![["0xffu + 7U + 010u"]]);
+        check_expr(
+            "1e-3 + 0.001 + 2.f + 1.5F",
+            expect![["1e-3 + 0.001 + 2.f + 1.5F"]],
+        );
+        check_expr("1.0lf + 2E10LF", expect![["1.0lf + 2E10LF"]]);
+
+        for (src, radix) in [
+            ("0x1F", ast::IntRadix::Hexadecimal),
+            ("017u", ast::IntRadix::Octal),
+            ("0", ast::IntRadix::Decimal),
+            ("42", ast::IntRadix::Decimal),
+        ] {
+            match *ast::Expr::parse(src).unwrap() {
+                ast::ExprData::IntConst(ref x) => assert_eq!(x.radix(), radix),
+                ast::ExprData::UIntConst(ref x) => assert_eq!(x.radix(), radix),
+                ref other => panic!("{} parsed as {:?}", src, other),
+            }
+        }
+    }
+
+    #[test]
+    fn synthesized_numeric_literals() {
+        let floats = [0.1f32, 1., 0., 1e-7, 1e30, 123456.79, f32::MAX, 1e-45];
+        let doubles = [0.1f64, 1., 1e-300, f64::MAX];
+
+        let mut actual = String::new();
+        for x in floats {
+            let s = to_string(&ast::ExprData::from(x).into());
+            match *ast::Expr::parse(&s).unwrap() {
+                ast::ExprData::FloatConst(ref y) => assert_eq!(x.to_bits(), y.value().to_bits()),
+                ref other => panic!("{} parsed as {:?}", s, other),
+            }
+            actual.push_str(&s);
+            actual.push(' ');
+        }
+
+        for x in doubles {
+            let s = to_string(&ast::ExprData::from(x).into());
+            match *ast::Expr::parse(&s).unwrap() {
+                ast::ExprData::DoubleConst(ref y) => assert_eq!(x.to_bits(), y.value().to_bits()),
+                ref other => panic!("{} parsed as {:?}", s, other),
+            }
+            actual.push_str(&s);
+            actual.push(' ');
+        }
+
+        for e in [
+            ast::ExprData::from(f32::INFINITY),
+            ast::ExprData::from(f32::NEG_INFINITY),
+            ast::ExprData::from(f32::NAN),
+            ast::ExprData::from(f64::INFINITY),
+            ast::ExprData::from(ast::Literal::new(255).with_radix(ast::IntRadix::Hexadecimal)),
+            ast::ExprData::from(ast::Literal::new(8u32).with_radix(ast::IntRadix::Octal)),
+            ast::ExprData::from(-1),
+        ] {
+            actual.push_str(&to_string(&e.into()));
+            actual.push(' ');
+        }
+
+        expect![[r#"0.1 1. 0. 1e-7 1e30 123456.79 3.4028235e38 1e-45 0.1lf 1.lf 1e-300lf 1.7976931348623157e308lf (1.0/0.0) (-1.0/0.0) (0.0/0.0) (1.0lf/0.0lf) 0xFF 010u -1 "#]]
+            .assert_eq(&actual);
+    }
+
+    #[test]
+    fn explicit_arithmetic_types_roundtrip() {
+        let src = r#"#extension GL_EXT_shader_explicit_arithmetic_types : enable
+void main() {
+    int8_t a = int8_t(1);
+    u16vec2 b = u16vec2(0x10us, 2US);
+    int64_t c = 4000000000l;
+    uint64_t d = 0xffffffffffffffffUL;
+    f16mat2x3 e = f16mat2x3(1.5hf);
+    float64_t f = 2.0lf;
+}
+"#;
+
+        // The #extension directive is only kept by the minimal lexer, so only show the function
+        let mut s = String::new();
+        for decl in ast::TranslationUnit::parse(src).unwrap().0 {
+            if let ast::ExternalDeclarationData::FunctionDefinition(ref def) = *decl {
+                show_function_definition(&mut s, def, &mut FormattingState::default()).unwrap();
+            }
+        }
+
+        for e in [
+            ast::ExprData::from(-3i16),
+            ast::ExprData::from(7u16),
+            ast::ExprData::from(ast::Literal::new(255i64).with_radix(ast::IntRadix::Hexadecimal)),
+            ast::ExprData::from(u64::MAX),
+            ast::ExprData::Float16Const(0.25.into()),
+        ] {
+            show_expr(&mut s, &e.into(), &mut FormattingState::default()).unwrap();
+            s.push('\n');
+        }
+
+        let expected = expect![[r#"
+            void main() {
+                int8_t a = int8_t(1);
+                u16vec2 b = u16vec2(0x10us, 2US);
+                int64_t c = 4000000000l;
+                uint64_t d = 0xffffffffffffffffUL;
+                f16mat2x3 e = f16mat2x3(1.5hf);
+                float64_t f = 2.0lf;
+            }
+            -3s
+            7us
+            0xFFl
+            18446744073709551615ul
+            0.25hf
+        "#]];
+
+        expected.assert_eq(&s);
+    }
+
+    #[test]
+    fn ray_tracing_roundtrip() {
+        let src = r#"#extension GL_EXT_ray_tracing : enable
+#extension GL_EXT_ray_query : enable
+#extension GL_EXT_mesh_shader : enable
+layout(binding = 0) uniform accelerationStructureEXT tlas;
+layout(location = 0) rayPayloadEXT vec4 payload;
+layout(location = 1) rayPayloadInEXT vec4 incoming;
+hitAttributeEXT vec2 attribs;
+layout(location = 2) callableDataEXT float data;
+layout(location = 3) callableDataInEXT float incomingData;
+layout(shaderRecordEXT) buffer Record { vec4 color; };
+shadercallcoherent uniform Data { float x; };
+taskPayloadSharedEXT Task { uint index; } task;
+layout(location = 4) perprimitiveEXT out vec4 color[];
+void main() {
+    rayQueryEXT query;
+    if (attribs.x > 0.5) ignoreIntersectionEXT;
+    terminateRayEXT;
+}
+"#;
+
+        // Extension directives are only kept by the minimal lexer, so skip them
+        let mut tu = ast::TranslationUnit::parse(src).unwrap();
+        tu.0.retain(|decl| !matches!(**decl, ast::ExternalDeclarationData::Preprocessor(_)));
+
+        let mut s = String::new();
+        show_translation_unit(&mut s, &tu, FormattingState::default()).unwrap();
+
+        let expected = expect![[r#"
+            layout(binding = 0) uniform accelerationStructureEXT tlas;
+            layout(location = 0) rayPayloadEXT vec4 payload;
+            layout(location = 1) rayPayloadInEXT vec4 incoming;
+            hitAttributeEXT vec2 attribs;
+            layout(location = 2) callableDataEXT float data;
+            layout(location = 3) callableDataInEXT float incomingData;
+            layout(shaderRecordEXT) buffer Record {
+                vec4 color;
+            };
+            shadercallcoherent uniform Data {
+                float x;
+            };
+            taskPayloadSharedEXT Task {
+                uint index;
+            }task;
+            layout(location = 4) perprimitiveEXT out vec4 color[];
+            void main() {
+                rayQueryEXT query;
+                if (attribs.x > 0.5) ignoreIntersectionEXT;
+                terminateRayEXT;
+            }
+        "#]];
+
+        expected.assert_eq(&s);
+    }
+
+    #[test]
+    fn buffer_reference_spirv_intrinsics_roundtrip() {
+        let src = r#"#extension GL_EXT_buffer_reference : enable
+#extension GL_EXT_spirv_intrinsics : enable
+layout(buffer_reference) buffer Node;
+layout(buffer_reference, std430) buffer Node { Node next; int value; };
+spirv_execution_mode(5027);
+spirv_decorate(extensions = ["SPV_AMD_shader_explicit_vertex_parameter"], 11, 4992) in vec2 bary;
+spirv_decorate_string(5635, "a \"b\" \\ c") in vec2 s;
+spirv_instruction(set = "GLSL.std.450", id = 35) float modf(float x, spirv_by_reference float i);
+void main() {
+    spirv_type(capabilities = [4472], id = 4472) rq;
+    Node n;
+}
+"#;
+
+        // Extension directives are only kept by the minimal lexer, so skip them
+        let mut tu = ast::TranslationUnit::parse(src).unwrap();
+        tu.0.retain(|decl| !matches!(**decl, ast::ExternalDeclarationData::Preprocessor(_)));
+
+        let mut s = String::new();
+        show_translation_unit(&mut s, &tu, FormattingState::default()).unwrap();
+
+        let expected = expect![[r#"
+            layout(buffer_reference) buffer Node;
+            layout(buffer_reference, std430) buffer Node {
+                Node next;
+                int value;
+            };
+            spirv_execution_mode(5027);
+            spirv_decorate(extensions = ["SPV_AMD_shader_explicit_vertex_parameter"], 11, 4992) in vec2 bary;
+            spirv_decorate_string(5635, "a \"b\" \\ c") in vec2 s;
+            spirv_instruction(set = "GLSL.std.450", id = 35) float modf(float x, spirv_by_reference float i);
+            void main() {
+                spirv_type(capabilities = [4472], id = 4472) rq;
+                Node n;
+            }
+        "#]];
+
+        expected.assert_eq(&s);
+    }
+
+    #[test]
+    fn attributes_roundtrip() {
+        let src = r#"#extension GL_EXT_control_flow_attributes : enable
+[[random(4)]] void main() [[subgroup_uniform_control_flow]] {
+    [[unroll]] for (int i = 0; i < 8; ++i) {}
+    [[dependency_infinite]] do {} while (true);
+    [[ unroll, dont_unroll, dependency_length(2) ]] while (cond) {}
+    [[flatten]] if (cond) {} else {}
+    [[dont_flatten]] switch (3) { case 3: break; }
+}
+"#;
+
+        // The min lexer keeps the #extension directive as an external declaration
+        let fd = ast::TranslationUnit::parse(src)
+            .unwrap()
+            .0
+            .into_iter()
+            .find_map(|decl| match decl.into_inner() {
+                ast::ExternalDeclarationData::AttributedFunctionDefinition(fd) => Some(fd),
+                _ => None,
+            })
+            .unwrap();
+
+        let mut s = String::new();
+        show_attributed_function_definition(&mut s, &fd, &mut FormattingState::default()).unwrap();
+
+        let expected = expect![[r#"
+            [[random(4)]] void main() [[subgroup_uniform_control_flow]] {
+                [[unroll]] for (int i = 0; i < 8; ++i) {
+                }
+                [[dependency_infinite]] do {
+                } while (true);
+                [[unroll, dont_unroll, dependency_length(2)]] while (cond) {
+                }
+                [[flatten]] if (cond) {
+                } else {
+                }
+                [[dont_flatten]] switch (3) {
+                    case 3:
+                    break;
+                }
+            }
+        "#]];
+
+        expected.assert_eq(&s);
+    }
+
     #[test]
     fn test_single_statement_function_is_not_collapsed() {
         const SRC: &str = r#"vec2 main() {
@@ -2583,7 +3878,7 @@ return vec2(0., 0.);
     fn roundtrip_glsl_complex_expr() {
         use lang_util::node::NodeContent;
 
-        let zero = ast::ExprData::DoubleConst(0.);
+        let zero = ast::ExprData::from(0f64);
         let ray = ast::ExprData::Variable("ray".into_node());
         let raydir = ast::ExprData::Dot(Box::new(ray.into()), "dir".into_node());
         let vec4 = ast::ExprData::FunCall(
@@ -2760,7 +4055,7 @@ return vec2(0., 0.);
                 } else {
                     return q;
                 }
-                const float e[3] = float[3](5., 7.2, 1.1);
+                const float e[3] = float[3](5.0, 7.2, 1.1);
                 int f[2] = { 3, 4 };
                 switch (x) {
                     case 0:
@@ -2819,7 +4114,7 @@ return vec2(0., 0.);
         )
         .unwrap();
 
-        let expected = expect!["void main(smooth vec2[][] x,vec3 y){if(var1)return a;else return b;if(var2)return --c;else if(var3){{vec2 x;vec2 y;}return d++;}else return q;const float e[3]=float[3](5.,7.2,1.1);int f[2]={3,4};switch(x){case 0:++x;break;case 1:return;default:discard;}while(x)--x;do --y; while(y);for(x=0;x<5;++x)continue;}"];
+        let expected = expect!["void main(smooth vec2[][] x,vec3 y){if(var1)return a;else return b;if(var2)return --c;else if(var3){{vec2 x;vec2 y;}return d++;}else return q;const float e[3]=float[3](5.0,7.2,1.1);int f[2]={3,4};switch(x){case 0:++x;break;case 1:return;default:discard;}while(x)--x;do --y; while(y);for(x=0;x<5;++x)continue;}"];
 
         expected.assert_eq(&s);
     }
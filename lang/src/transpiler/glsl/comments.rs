@@ -0,0 +1,358 @@
+//! Comment preservation for the GLSL transpiler
+//!
+//! When parsing with a [`ParseContext`] created using [`ParseContext::new_with_comments`], the
+//! comments found in the input are collected into a [`CommentList`]. A [`Comments`] table built
+//! from this list can be attached to a [`FormattingState`] using
+//! [`FormattingState::with_comments`] in order to write these comments back to the output.
+//!
+//! Comments are attached to the nearest statement, declaration or struct field by span:
+//!
+//! * comments that appear before a node are written on their own line before it, or before the
+//!   node on the same line if there was no line break between the comment and the node;
+//! * comments that appear after a node on the same line are written at the end of the line the
+//!   node was written on;
+//! * comments that appear before an expression are written before it on the same line, unless
+//!   they are single-line comments;
+//! * if a single-line comment forces a line break before the body of a selection or iteration
+//!   statement, the body is indented on its own line;
+//! * comments that appear inside a node but not inside any of its children are written after the
+//!   node, on the same line;
+//! * comments at the end of a block are written before the closing brace.
+//!
+//! # Example
+//!
+//! ```
+//! use glsl_lang::{
+//!     ast,
+//!     parse::{IntoParseBuilderExt, ParseContext},
+//!     transpiler::glsl::{comments::Comments, *},
+//! };
+//!
+//! let src = "// Entry point\nvoid main() {\n    int x = 1; // The answer\n}\n";
+//!
+//! let ctx = ParseContext::new_with_comments();
+//! let (tu, ctx, _): (ast::TranslationUnit, _, _) =
+//!     src.builder().context(&ctx).parse().unwrap();
+//!
+//! let data = ctx.data();
+//! let comments = Comments::new(data.comments().unwrap(), |_file_id| Some(src));
+//!
+//! let mut output = String::new();
+//! show_translation_unit(
+//!     &mut output,
+//!     &tu,
+//!     FormattingState::default().with_comments(&comments),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(output, src);
+//! ```
+//!
+//! [`ParseContext`]: crate::parse::ParseContext
+//! [`ParseContext::new_with_comments`]: crate::parse::ParseContext::new_with_comments
+//! [`CommentList`]: crate::lexer::CommentList
+//! [`FormattingState`]: super::FormattingState
+//! [`FormattingState::with_comments`]: super::FormattingState::with_comments
+
+use std::{cell::Cell, collections::HashMap};
+
+use lang_util::{position::NodeSpan, FileId, TextSize};
+
+use crate::{ast, lexer::CommentList};
+
+/// Comments of a single source file
+#[derive(Debug, PartialEq, Eq)]
+struct FileComments<'c> {
+    /// Source text of the file, if known
+    source: Option<&'c str>,
+    /// Comments in this file, sorted by position
+    comments: Vec<(NodeSpan, &'c ast::Comment)>,
+    /// Index of the next comment to be written
+    next: Cell<usize>,
+}
+
+impl<'c> FileComments<'c> {
+    fn peek(&self) -> Option<(NodeSpan, &'c ast::Comment)> {
+        self.comments.get(self.next.get()).copied()
+    }
+
+    fn advance(&self) {
+        self.next.set(self.next.get() + 1);
+    }
+
+    /// Return the source text between two offsets, if known
+    fn between(&self, start: usize, end: usize) -> Option<&'c str> {
+        self.source.and_then(|source| source.get(start..end))
+    }
+
+    /// true if the comment is separated from the code that follows it by a line break
+    fn ends_line(&self, span: NodeSpan, comment: &ast::Comment) -> bool {
+        if comment.is_single() {
+            return true;
+        }
+
+        let end = usize::from(span.end().offset);
+        self.source
+            .and_then(|source| source.get(end..))
+            .and_then(|rest| rest.chars().find(|ch| !ch.is_whitespace() || *ch == '\n'))
+            .map(|ch| ch == '\n')
+            .unwrap_or(true)
+    }
+}
+
+/// A placement of a comment in the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Placement {
+    /// The comment is written on its own line
+    OwnLine,
+    /// The comment is written before the code on the same line
+    Inline,
+}
+
+/// Table of comments to be written by the transpiler
+///
+/// See the [module documentation](self) for more details.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Comments<'c> {
+    files: HashMap<FileId, FileComments<'c>>,
+}
+
+impl<'c> Comments<'c> {
+    /// Create a new comment table
+    ///
+    /// # Parameters
+    ///
+    /// * `comments`: list of comments collected by the parser
+    /// * `resolve`: callback returning the source text for a given file identifier. The source
+    ///   text is used to determine if comments were on the same line as the code around them. If
+    ///   the source text is not available, all comments are written on their own line.
+    pub fn new(
+        comments: &'c CommentList,
+        mut resolve: impl FnMut(FileId) -> Option<&'c str>,
+    ) -> Self {
+        let mut files: HashMap<FileId, FileComments<'c>> = HashMap::new();
+
+        // CommentList is sorted by span, so comments are sorted per file
+        for (span, comment) in comments {
+            files
+                .entry(span.source_id())
+                .or_insert_with(|| FileComments {
+                    source: resolve(span.source_id()),
+                    comments: Vec::new(),
+                    next: Cell::new(0),
+                })
+                .comments
+                .push((*span, comment));
+        }
+
+        Self { files }
+    }
+
+    /// Return the comments of the file `source_id` that should be written before the code at
+    /// `offset`
+    pub(super) fn leading(
+        &self,
+        source_id: FileId,
+        offset: TextSize,
+    ) -> Vec<(&'c ast::Comment, Placement)> {
+        let mut result = Vec::new();
+
+        if let Some(file) = self.files.get(&source_id) {
+            while let Some((comment_span, comment)) = file.peek() {
+                if comment_span.start().offset >= offset {
+                    break;
+                }
+
+                file.advance();
+                result.push((
+                    comment,
+                    if file.ends_line(comment_span, comment) {
+                        Placement::OwnLine
+                    } else {
+                        Placement::Inline
+                    },
+                ));
+            }
+        }
+
+        result
+    }
+
+    /// Return the comments that should be written after the node at `span`, on the same line
+    pub(super) fn trailing(&self, span: NodeSpan) -> Vec<&'c ast::Comment> {
+        let mut result = Vec::new();
+
+        if let Some(file) = self.files.get(&span.source_id()) {
+            let mut end = usize::from(span.end().offset);
+
+            while let Some((comment_span, comment)) = file.peek() {
+                let start = usize::from(comment_span.start().offset);
+
+                // Comments inside the node which were not attached to one of its children
+                let inside = start < end;
+                // Comments after the node on the same line
+                let same_line = !inside
+                    && file
+                        .between(end, start)
+                        .map(|text| text.chars().all(|ch| ch.is_whitespace() && ch != '\n'))
+                        .unwrap_or(false);
+
+                if !inside && !same_line {
+                    break;
+                }
+
+                file.advance();
+                end = end.max(usize::from(comment_span.end().offset));
+                result.push(comment);
+            }
+        }
+
+        result
+    }
+
+    /// Return all the comments that have not been written yet
+    pub(super) fn remaining(&self) -> Vec<&'c ast::Comment> {
+        let mut files: Vec<_> = self.files.iter().collect();
+        files.sort_by_key(|(file_id, _)| **file_id);
+
+        let mut result = Vec::new();
+        for (_, file) in files {
+            while let Some((_, comment)) = file.peek() {
+                file.advance();
+                result.push(comment);
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use super::*;
+    use crate::{
+        parse::{IntoParseBuilderExt, ParseContext},
+        transpiler::glsl::{show_translation_unit, FormattingSettings, FormattingState},
+    };
+
+    fn check(src: &str, settings: &FormattingSettings, expected: Expect) {
+        let ctx = ParseContext::new_with_comments();
+        let (tu, ctx, _): (ast::TranslationUnit, _, _) =
+            src.builder().context(&ctx).parse().unwrap();
+
+        let data = ctx.data();
+        let comments = Comments::new(data.comments().unwrap(), |_| Some(src));
+
+        let mut output = String::new();
+        show_translation_unit(
+            &mut output,
+            &tu,
+            FormattingState::from(settings).with_comments(&comments),
+        )
+        .unwrap();
+
+        expected.assert_eq(&output);
+    }
+
+    const SOURCE: &str = r#"// Header comment
+
+/* Light
+ * description */
+struct Light {
+    vec3 position; // World position
+    /* Color */ vec3 color;
+    // End of struct
+};
+
+uniform Light light; /* Main light */
+
+float attenuation(float d) {
+    // Leading comment
+    float a = 1.0 / (d * /* squared */ d); // Trailing comment
+    if (a > 1.0) // Clamp
+        a = 1.0;
+    else
+        a = 0.0;
+    do a += 1.0; // Increment
+    while (a < 0.5);
+    return a;
+    // End of function
+}
+// Footer comment"#;
+
+    #[test]
+    fn preserve_comments() {
+        check(
+            SOURCE,
+            &FormattingSettings::default(),
+            expect![[r#"
+                // Header comment
+                /* Light
+                 * description */
+                struct Light {
+                    vec3 position; // World position
+                    /* Color */ vec3 color;
+                    // End of struct
+                };
+                uniform Light light; /* Main light */
+                float attenuation(float d) {
+                    // Leading comment
+                    float a = 1.0 / (d * /* squared */ d); // Trailing comment
+                    if (a > 1.0) // Clamp
+                        a = 1.0;
+                    else a = 0.0;
+                    do a += 1.0; // Increment
+                    while (a < 0.5);
+                    return a;
+                    // End of function
+                }
+
+                // Footer comment"#]],
+        );
+    }
+
+    #[test]
+    fn expression_comments() {
+        check(
+            r#"void main() {
+    x = first + // First
+        /* Second */ second;
+    while (x > 0) // Loop
+        x--;
+}
+"#,
+            &FormattingSettings::default(),
+            expect![[r#"
+                void main() {
+                    x = first + // First
+                        /* Second */ second;
+                    while (x > 0) // Loop
+                        x--;
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn preserve_comments_minified() {
+        check(
+            SOURCE,
+            &FormattingSettings::minifying(),
+            expect![[r#"
+                // Header comment
+                /* Light
+                 * description */
+                struct Light{vec3 position; // World position
+                /* Color */ vec3 color;// End of struct
+                };uniform Light light; /* Main light */float attenuation(float d){// Leading comment
+                float a=1.0/(d*/* squared */ d); // Trailing comment
+                if(a>1.0)// Clamp
+                a=1.0;
+                else a=0.0;do a+=1.0; // Increment
+                while(a<0.5);return a;// End of function
+                }// Footer comment"#]],
+        );
+    }
+}
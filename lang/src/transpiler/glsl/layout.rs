@@ -0,0 +1,659 @@
+//! Width-aware layout engine for the GLSL transpiler
+//!
+//! This is a document model in the style of Wadler's "prettier printer". When
+//! [`FormattingSettings::max_line_width`] is set, the output of expressions and declarations is
+//! not written directly: it is recorded as a [`Doc`] tree, where the [`FormattingState`] inserts
+//! [`Doc::Break`]s at the places the code may be wrapped and [`Doc::Group`]s around the
+//! constructs that should be wrapped consistently. Once the construct is complete, the tree is
+//! rendered to the output, breaking the outermost groups which do not fit in the remaining
+//! width.
+//!
+//! [`FormattingSettings::max_line_width`]: super::FormattingSettings::max_line_width
+//! [`FormattingState`]: super::FormattingState
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Write,
+};
+
+use lang_util::position::NodeSpan;
+
+use super::{source_map::SourceMapRecorder, IndentStyle};
+
+/// A layout document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Doc {
+    /// Text without line breaks
+    Text(String),
+    /// A potential line break, written as the given text if the enclosing group fits on the
+    /// current line
+    Break(&'static str),
+    /// A line break that was explicitly written to the output
+    HardLine,
+    /// A group of documents, laid out on a single line if possible
+    Group(Vec<Doc>),
+    /// Documents whose line breaks are indented by the given number of columns
+    Nest(u32, Vec<Doc>),
+    /// Documents whose line breaks are aligned with the column they start at
+    Align(Vec<Doc>),
+    /// Start of the output of a node, for source maps
+    EnterNode(NodeSpan),
+    /// End of the output of a node, for source maps
+    ExitNode,
+}
+
+/// Kind of container document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Container {
+    /// A [`Doc::Group`]
+    Group,
+    /// A [`Doc::Nest`], indented by one indentation level
+    Indent,
+    /// A [`Doc::Align`]
+    Align,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+/// Indentation of the lines following a line break
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Indent {
+    /// Indentation, in columns. Written using the indentation style.
+    levels: u32,
+    /// Alignment, in columns. Always written using spaces.
+    align: u32,
+}
+
+/// Layout engine state
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Layout {
+    /// Maximum line width
+    width: u32,
+    /// Indentation style, used for line breaks
+    indent_style: IndentStyle,
+    /// Current column of the output
+    column: Cell<u32>,
+    /// Number of nested layout sessions
+    depth: Cell<u32>,
+    /// Stack of the containers being built
+    stack: RefCell<Vec<(Container, Vec<Doc>)>>,
+}
+
+impl Layout {
+    pub fn new(width: u32, indent_style: IndentStyle) -> Self {
+        Self {
+            width,
+            indent_style,
+            column: Cell::new(0),
+            depth: Cell::new(0),
+            stack: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Wrap the output so its current column can be tracked
+    pub fn writer<W: Write>(&self, inner: W) -> LayoutWriter<'_, W> {
+        LayoutWriter {
+            inner,
+            layout: self,
+        }
+    }
+
+    /// Width of one indentation level, in columns
+    pub fn level_width(&self) -> u32 {
+        match self.indent_style {
+            IndentStyle::None => 0,
+            IndentStyle::Tabs { tab_size, count } => tab_size * count,
+            IndentStyle::Spaces { count } => count,
+        }
+    }
+
+    fn tab_size(&self) -> u32 {
+        match self.indent_style {
+            IndentStyle::Tabs { tab_size, .. } => tab_size.max(1),
+            _ => 4,
+        }
+    }
+
+    /// true if the output is currently being recorded
+    pub fn is_active(&self) -> bool {
+        self.depth.get() > 0
+    }
+
+    /// Start recording the output. Sessions can be nested, only the outermost session is
+    /// rendered.
+    pub fn begin(&self) {
+        if self.depth.get() == 0 {
+            self.stack.borrow_mut().push((Container::Group, Vec::new()));
+        }
+
+        self.depth.set(self.depth.get() + 1);
+    }
+
+    /// Stop recording the output, returning the recorded document if this was the outermost
+    /// session
+    pub fn end(&self) -> Option<Doc> {
+        self.depth.set(self.depth.get() - 1);
+
+        if self.depth.get() == 0 {
+            let mut stack = self.stack.borrow_mut();
+            let mut doc = None;
+
+            // Close any container that was left open
+            while let Some((container, children)) = stack.pop() {
+                let current = self.close_container(container, children);
+
+                if let Some((_, parent)) = stack.last_mut() {
+                    parent.push(current);
+                } else {
+                    doc = Some(current);
+                }
+            }
+
+            doc
+        } else {
+            None
+        }
+    }
+
+    fn close_container(&self, container: Container, children: Vec<Doc>) -> Doc {
+        match container {
+            Container::Group => Doc::Group(children),
+            Container::Indent => Doc::Nest(self.level_width(), children),
+            Container::Align => Doc::Align(children),
+        }
+    }
+
+    fn push(&self, doc: Doc) {
+        if let Some((_, children)) = self.stack.borrow_mut().last_mut() {
+            if let (Some(Doc::Text(last)), Doc::Text(text)) = (children.last_mut(), &doc) {
+                last.push_str(text);
+            } else {
+                children.push(doc);
+            }
+        }
+    }
+
+    /// Record some output text
+    pub fn text(&self, s: &str) {
+        let mut lines = s.split('\n');
+
+        if let Some(first) = lines.next() {
+            if !first.is_empty() {
+                self.push(Doc::Text(first.to_owned()));
+            }
+        }
+
+        for line in lines {
+            self.push(Doc::HardLine);
+
+            if !line.is_empty() {
+                self.push(Doc::Text(line.to_owned()));
+            }
+        }
+    }
+
+    /// Record a potential line break
+    pub fn line_break(&self, flat: &'static str) {
+        // Line breaks are only allowed in explicit groups, not at the root of a session
+        if self.stack.borrow().len() > 1 {
+            self.push(Doc::Break(flat));
+        } else {
+            self.push(Doc::Text(flat.to_owned()));
+        }
+    }
+
+    /// Record the start of the output of a node
+    pub fn enter_node(&self, span: NodeSpan) {
+        self.push(Doc::EnterNode(span));
+    }
+
+    /// Record the end of the output of a node
+    pub fn exit_node(&self) {
+        self.push(Doc::ExitNode);
+    }
+
+    /// Open a new container
+    pub fn open(&self, container: Container) {
+        if self.is_active() {
+            self.stack.borrow_mut().push((container, Vec::new()));
+        }
+    }
+
+    /// Close the current container
+    pub fn close(&self) {
+        if !self.is_active() {
+            return;
+        }
+
+        let mut stack = self.stack.borrow_mut();
+
+        // Never close the root of the session
+        if stack.len() > 1 {
+            let (container, children) = stack.pop().unwrap();
+            let doc = self.close_container(container, children);
+            stack.last_mut().unwrap().1.push(doc);
+        }
+    }
+
+    /// Track the column of text written to the output
+    fn advance(&self, s: &str) {
+        let tab_size = self.tab_size();
+        let mut column = self.column.get();
+
+        for ch in s.chars() {
+            column = match ch {
+                '\n' => 0,
+                '\t' => (column / tab_size + 1) * tab_size,
+                _ => column + 1,
+            };
+        }
+
+        self.column.set(column);
+    }
+
+    fn write_indent(&self, output: &mut String, indent: Indent) {
+        match self.indent_style {
+            IndentStyle::Tabs { tab_size, .. } if tab_size > 0 => {
+                output.extend(std::iter::repeat_n(
+                    '\t',
+                    (indent.levels / tab_size) as usize,
+                ));
+                output.extend(std::iter::repeat_n(
+                    ' ',
+                    (indent.levels % tab_size + indent.align) as usize,
+                ));
+            }
+            _ => output.extend(std::iter::repeat_n(
+                ' ',
+                (indent.levels + indent.align) as usize,
+            )),
+        }
+    }
+
+    /// Check if the given document fits in the remaining width, including the documents that
+    /// follow it up to the next line break
+    fn fits(&self, next: &Doc, rest: &[(Indent, Mode, &Doc)], mut width: i64) -> bool {
+        let mut stack: Vec<(Mode, &Doc)> = vec![(Mode::Flat, next)];
+        let mut rest = rest.iter().rev();
+
+        loop {
+            if width < 0 {
+                return false;
+            }
+
+            let (mode, doc) = match stack.pop() {
+                Some(item) => item,
+                None => match rest.next() {
+                    Some((_, mode, doc)) => (*mode, *doc),
+                    None => return true,
+                },
+            };
+
+            match doc {
+                Doc::Text(text) => width -= text.chars().count() as i64,
+                Doc::Break(flat) => {
+                    if mode == Mode::Break {
+                        return true;
+                    }
+
+                    width -= flat.len() as i64;
+                }
+                Doc::HardLine => return true,
+                Doc::Group(children) | Doc::Nest(_, children) | Doc::Align(children) => {
+                    stack.extend(children.iter().rev().map(|child| (mode, child)));
+                }
+                Doc::EnterNode(_) | Doc::ExitNode => {}
+            }
+        }
+    }
+
+    /// Render a document, starting at the current column with the given base indentation
+    pub fn render<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        doc: &Doc,
+        indent: u32,
+        source_map: Option<&SourceMapRecorder>,
+    ) -> std::fmt::Result {
+        let mut column = self.column.get();
+        let mut output = String::new();
+        let mut stack = vec![(
+            Indent {
+                levels: indent,
+                align: 0,
+            },
+            Mode::Break,
+            doc,
+        )];
+
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match doc {
+                Doc::Text(text) => {
+                    output.push_str(text);
+                    column += text.chars().count() as u32;
+                }
+                Doc::Break(flat) => {
+                    if mode == Mode::Flat {
+                        output.push_str(flat);
+                        column += flat.len() as u32;
+                    } else {
+                        // Remove trailing whitespace before the line break
+                        output.truncate(output.trim_end_matches(' ').len());
+                        output.push('\n');
+                        self.write_indent(&mut output, indent);
+                        column = indent.levels + indent.align;
+                    }
+                }
+                Doc::HardLine => {
+                    output.push('\n');
+                    column = 0;
+                }
+                Doc::Group(children) => {
+                    let mode = if mode == Mode::Flat
+                        || self.fits(doc, &stack, self.width as i64 - column as i64)
+                    {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+
+                    stack.extend(children.iter().rev().map(|child| (indent, mode, child)));
+                }
+                Doc::Nest(width, children) => {
+                    // Nested lines are only indented using the indentation style if they are
+                    // not aligned to a specific column
+                    let indent = if indent.align == 0 {
+                        Indent {
+                            levels: indent.levels + width,
+                            align: 0,
+                        }
+                    } else {
+                        Indent {
+                            levels: indent.levels,
+                            align: indent.align + width,
+                        }
+                    };
+
+                    stack.extend(children.iter().rev().map(|child| (indent, mode, child)));
+                }
+                Doc::Align(children) => {
+                    let indent = Indent {
+                        levels: indent.levels,
+                        align: column.saturating_sub(indent.levels),
+                    };
+
+                    stack.extend(children.iter().rev().map(|child| (indent, mode, child)));
+                }
+                Doc::EnterNode(span) => {
+                    if let Some(recorder) = source_map {
+                        f.write_str(&output)?;
+                        output.clear();
+                        recorder.enter_raw(*span);
+                    }
+                }
+                Doc::ExitNode => {
+                    if let Some(recorder) = source_map {
+                        f.write_str(&output)?;
+                        output.clear();
+                        recorder.exit();
+                    }
+                }
+            }
+        }
+
+        f.write_str(&output)
+    }
+}
+
+/// A [`Write`] wrapper that records the output in the current layout session, or tracks the
+/// current column if there is no active session
+#[derive(Debug)]
+pub(super) struct LayoutWriter<'l, W> {
+    inner: W,
+    layout: &'l Layout,
+}
+
+impl<W: Write> Write for LayoutWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.layout.is_active() {
+            self.layout.text(s);
+        } else {
+            self.inner.write_str(s)?;
+            self.layout.advance(s);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::{
+        ast,
+        parse::DefaultParse,
+        transpiler::glsl::{
+            show_expr, show_function_definition, show_translation_unit,
+            source_map::{show_translation_unit_with_source_map, LineCol},
+            FormattingSettings, FormattingState,
+        },
+    };
+
+    fn settings(max_line_width: u32) -> FormattingSettings {
+        FormattingSettings {
+            max_line_width: Some(max_line_width),
+            ..Default::default()
+        }
+    }
+
+    fn check(src: &str, settings: &FormattingSettings, expected: Expect) {
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+
+        let mut output = String::new();
+        show_translation_unit(&mut output, &tu, FormattingState::from(settings)).unwrap();
+
+        expected.assert_eq(&output);
+    }
+
+    const SOURCE: &str = r#"
+vec4 shade(vec3 position, vec3 normal, vec3 lightDirection, vec4 baseColor, float roughness) {
+    float attenuation = computeAttenuation(position, lightDirection) * shadowFactor(position, normal);
+    float d = lightIntensity + ambientIntensity + emissiveIntensity + indirectIntensity;
+    const float weights[5] = float[5](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+    vec3 palette[3] = { vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, 1.0) };
+    return d > threshold ? baseColor * attenuation * d : vec4(ambientColor, baseColor.a);
+}
+"#;
+
+    #[test]
+    fn no_wrapping_when_fits() {
+        check(
+            "void main() { x = f(a, b) + c; }",
+            &settings(80),
+            expect![[r#"
+                void main() {
+                    x = f(a, b) + c;
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn wrap_80() {
+        check(
+            SOURCE,
+            &settings(80),
+            expect![[r#"
+                vec4 shade(vec3 position,
+                           vec3 normal,
+                           vec3 lightDirection,
+                           vec4 baseColor,
+                           float roughness) {
+                    float attenuation = computeAttenuation(position, lightDirection)
+                                        * shadowFactor(position, normal);
+                    float d = lightIntensity
+                              + ambientIntensity
+                              + emissiveIntensity
+                              + indirectIntensity;
+                    const float weights[5] = float[5](0.227027,
+                                                      0.1945946,
+                                                      0.1216216,
+                                                      0.054054,
+                                                      0.016216);
+                    vec3 palette[3] = {
+                        vec3(1.0, 0.0, 0.0),
+                        vec3(0.0, 1.0, 0.0),
+                        vec3(0.0, 0.0, 1.0)
+                    };
+                    return d > threshold
+                           ? baseColor * attenuation * d
+                           : vec4(ambientColor, baseColor.a);
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn wrap_50() {
+        check(
+            SOURCE,
+            &settings(50),
+            expect![[r#"
+                vec4 shade(vec3 position,
+                           vec3 normal,
+                           vec3 lightDirection,
+                           vec4 baseColor,
+                           float roughness) {
+                    float attenuation = computeAttenuation(position,
+                                                           lightDirection)
+                                        * shadowFactor(position,
+                                                       normal);
+                    float d = lightIntensity
+                              + ambientIntensity
+                              + emissiveIntensity
+                              + indirectIntensity;
+                    const float weights[5] = float[5](0.227027,
+                                                      0.1945946,
+                                                      0.1216216,
+                                                      0.054054,
+                                                      0.016216);
+                    vec3 palette[3] = {
+                        vec3(1.0, 0.0, 0.0),
+                        vec3(0.0, 1.0, 0.0),
+                        vec3(0.0, 0.0, 1.0)
+                    };
+                    return d > threshold
+                           ? baseColor * attenuation * d
+                           : vec4(ambientColor, baseColor.a);
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn wrap_tabs() {
+        check(
+            SOURCE,
+            &FormattingSettings {
+                indent_style: crate::transpiler::glsl::IndentStyle::Tabs {
+                    tab_size: 4,
+                    count: 1,
+                },
+                ..settings(60)
+            },
+            expect![[r#"
+                vec4 shade(vec3 position,
+                           vec3 normal,
+                           vec3 lightDirection,
+                           vec4 baseColor,
+                           float roughness) {
+                	float attenuation = computeAttenuation(position,
+                	                                       lightDirection)
+                	                    * shadowFactor(position, normal);
+                	float d = lightIntensity
+                	          + ambientIntensity
+                	          + emissiveIntensity
+                	          + indirectIntensity;
+                	const float weights[5] = float[5](0.227027,
+                	                                  0.1945946,
+                	                                  0.1216216,
+                	                                  0.054054,
+                	                                  0.016216);
+                	vec3 palette[3] = {
+                		vec3(1.0, 0.0, 0.0),
+                		vec3(0.0, 1.0, 0.0),
+                		vec3(0.0, 0.0, 1.0)
+                	};
+                	return d > threshold
+                	       ? baseColor * attenuation * d
+                	       : vec4(ambientColor, baseColor.a);
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn wrap_single_nodes() {
+        let settings = settings(20);
+        let tu =
+            ast::TranslationUnit::parse("void main() { x = first + second + third; }").unwrap();
+        let ast::ExternalDeclarationData::FunctionDefinition(fd) = &*tu.0[0] else {
+            panic!("expected a function definition");
+        };
+
+        let mut output = String::new();
+        show_function_definition(&mut output, fd, &mut FormattingState::from(&settings)).unwrap();
+        expect![[r#"
+            void main() {
+                x = first
+                    + second
+                    + third;
+            }
+        "#]]
+        .assert_eq(&output);
+
+        let ast::StatementData::Expression(ref statement) = *fd.statement.statement_list[0] else {
+            panic!("expected an expression statement");
+        };
+        let expr = statement.0.as_ref().unwrap();
+
+        let mut output = String::new();
+        show_expr(&mut output, expr, &mut FormattingState::from(&settings)).unwrap();
+        expect![[r#"
+            x = first
+                + second
+                + third"#]]
+        .assert_eq(&output);
+    }
+
+    #[test]
+    fn wrap_with_source_map() {
+        let src = "void main() { x = first + second + third; }";
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+
+        let mut output = String::new();
+        let source_map = show_translation_unit_with_source_map(
+            &mut output,
+            &tu,
+            FormattingState::from(&settings(20)),
+        )
+        .unwrap();
+
+        expect![[r#"
+            void main() {
+                x = first
+                    + second
+                    + third;
+            }
+        "#]]
+        .assert_eq(&output);
+
+        let mapping = source_map.lookup(LineCol::new(3, 10)).unwrap();
+        assert_eq!(&src[mapping.span().range()], "third");
+    }
+}
@@ -0,0 +1,593 @@
+//! Source map generation for the GLSL transpiler
+//!
+//! The `show_*` functions of the [GLSL transpiler](super) write to a [`Write`] object, which
+//! loses the [`NodeSpan`] information carried by the syntax tree. In order to map positions in
+//! the generated code back to the original sources, a [`SourceMapRecorder`] can be attached to
+//! the [`FormattingState`] using [`FormattingState::with_source_map`]. The output must then be
+//! written through the [`SourceMapWriter`] returned by [`SourceMapRecorder::writer`], so the
+//! recorder can keep track of the current output position.
+//!
+//! Once transpilation is done, [`SourceMapRecorder::finish`] returns the list of recorded
+//! mappings as a [`SourceMap`], which can be exported in the
+//! [Source Map v3](https://sourcemaps.info/spec.html) JSON format.
+//!
+//! # Example
+//!
+//! ```
+//! use glsl_lang::{ast, parse::DefaultParse, transpiler::glsl::{*, source_map::*}};
+//!
+//! let src = "void main() {\n    gl_FragColor = vec4(1.);\n}\n";
+//! let tu = ast::TranslationUnit::parse(src).unwrap();
+//!
+//! let mut output = String::new();
+//! let source_map = show_translation_unit_with_source_map(
+//!     &mut output,
+//!     &tu,
+//!     FormattingState::default(),
+//! )
+//! .unwrap();
+//!
+//! let json = source_map.to_json(Some("main.frag"), |_file_id| {
+//!     Some(SourceFile::new("main.glsl", src))
+//! });
+//!
+//! assert!(json.starts_with(r#"{"version":3,"file":"main.frag""#));
+//! ```
+//!
+//! [`FormattingState`]: super::FormattingState
+//! [`FormattingState::with_source_map`]: super::FormattingState::with_source_map
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Write,
+};
+
+use lang_util::{position::NodeSpan, FileId, TextSize};
+
+use crate::ast;
+
+use super::FormattingState;
+
+/// A line and column position in a text
+///
+/// Both the line and the column are 0-based. Columns are counted in bytes from the start of
+/// the line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    /// 0-based line number
+    pub line: u32,
+    /// 0-based column number
+    pub col: u32,
+}
+
+impl LineCol {
+    /// Create a new line and column position
+    pub fn new(line: u32, col: u32) -> Self {
+        Self { line, col }
+    }
+}
+
+/// A mapping from a range of the generated output to the node it was generated from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+    generated_start: LineCol,
+    generated_end: LineCol,
+    span: NodeSpan,
+    depth: u32,
+}
+
+impl Mapping {
+    /// Position of the first character generated for this node
+    pub fn generated_start(&self) -> LineCol {
+        self.generated_start
+    }
+
+    /// Position right after the last non-whitespace character generated for this node
+    pub fn generated_end(&self) -> LineCol {
+        self.generated_end
+    }
+
+    /// Span of the originating node
+    pub fn span(&self) -> NodeSpan {
+        self.span
+    }
+
+    /// Identifier of the source file of the originating node
+    pub fn source_id(&self) -> FileId {
+        self.span.source_id()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct OpenNode {
+    span: NodeSpan,
+    start: Option<LineCol>,
+}
+
+/// Records the mappings between the generated output and the originating nodes
+///
+/// See the [module documentation](self) for more details.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SourceMapRecorder {
+    /// Current position in the output
+    position: Cell<LineCol>,
+    /// Position right after the last non-whitespace character
+    last_end: Cell<LineCol>,
+    /// true if some open nodes are waiting for their first character
+    pending: Cell<bool>,
+    /// Stack of the nodes being currently transpiled
+    open: RefCell<Vec<OpenNode>>,
+    /// Completed mappings
+    mappings: RefCell<Vec<Mapping>>,
+}
+
+impl SourceMapRecorder {
+    /// Create a new empty source map recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap a [`Write`] object so the recorder can track the current output position
+    ///
+    /// All the output of the transpiler must go through the returned writer for the recorded
+    /// positions to be correct.
+    pub fn writer<W: Write>(&self, inner: W) -> SourceMapWriter<'_, W> {
+        SourceMapWriter {
+            inner,
+            recorder: self,
+        }
+    }
+
+    /// Current position in the generated output
+    pub fn position(&self) -> LineCol {
+        self.position.get()
+    }
+
+    /// Start recording the output for the given node
+    ///
+    /// The returned guard ends the recording when it is dropped.
+    pub fn enter(&self, span: NodeSpan) -> NodeGuard<'_> {
+        self.enter_raw(span);
+        NodeGuard(Some(self))
+    }
+
+    /// Start recording the output for the given node, without a guard. [`Self::exit`] must be
+    /// called when the output of the node is complete.
+    pub(super) fn enter_raw(&self, span: NodeSpan) {
+        self.open.borrow_mut().push(OpenNode { span, start: None });
+        self.pending.set(true);
+    }
+
+    /// End the recording of the current node
+    pub(super) fn exit(&self) {
+        let mut open = self.open.borrow_mut();
+        if let Some(node) = open.pop() {
+            if let Some(start) = node.start {
+                self.mappings.borrow_mut().push(Mapping {
+                    generated_start: start,
+                    generated_end: self.last_end.get(),
+                    span: node.span,
+                    depth: open.len() as _,
+                });
+            }
+        }
+
+        self.pending
+            .set(open.iter().any(|node| node.start.is_none()));
+    }
+
+    fn advance(&self, s: &str) {
+        let mut position = self.position.get();
+
+        for ch in s.chars() {
+            if ch == '\n' {
+                position.line += 1;
+                position.col = 0;
+                continue;
+            }
+
+            if !ch.is_whitespace() {
+                if self.pending.get() {
+                    for node in self.open.borrow_mut().iter_mut().rev() {
+                        if node.start.is_some() {
+                            break;
+                        }
+
+                        node.start = Some(position);
+                    }
+
+                    self.pending.set(false);
+                }
+
+                position.col += ch.len_utf8() as u32;
+                self.last_end.set(position);
+            } else {
+                position.col += ch.len_utf8() as u32;
+            }
+        }
+
+        self.position.set(position);
+    }
+
+    /// Return the recorded mappings as a source map
+    pub fn finish(self) -> SourceMap {
+        let mut mappings = self.mappings.into_inner();
+        mappings.sort_by_key(|mapping| (mapping.generated_start, mapping.depth));
+        SourceMap { mappings }
+    }
+}
+
+/// Guard returned by [`SourceMapRecorder::enter`]
+///
+/// Dropping this guard ends the recording of the current node.
+#[derive(Debug)]
+#[must_use]
+pub struct NodeGuard<'r>(Option<&'r SourceMapRecorder>);
+
+impl NodeGuard<'_> {
+    /// Return a guard that doesn't record anything
+    pub fn none() -> Self {
+        Self(None)
+    }
+}
+
+impl Drop for NodeGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(recorder) = self.0 {
+            recorder.exit();
+        }
+    }
+}
+
+/// A [`Write`] wrapper that tracks the output position for a [`SourceMapRecorder`]
+#[derive(Debug)]
+pub struct SourceMapWriter<'r, W> {
+    inner: W,
+    recorder: &'r SourceMapRecorder,
+}
+
+impl<W> SourceMapWriter<'_, W> {
+    /// Return the wrapped writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for SourceMapWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_str(s)?;
+        self.recorder.advance(s);
+        Ok(())
+    }
+}
+
+/// Source file information used for exporting a source map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceFile<'a> {
+    /// Name of the source file, as it should appear in the `sources` field
+    pub name: &'a str,
+    /// Contents of the source file
+    pub content: &'a str,
+    /// true if the contents should be embedded in the `sourcesContent` field
+    pub embed_content: bool,
+}
+
+impl<'a> SourceFile<'a> {
+    /// Create a new source file description, with embedded contents
+    pub fn new(name: &'a str, content: &'a str) -> Self {
+        Self {
+            name,
+            content,
+            embed_content: true,
+        }
+    }
+
+    /// Set whether the contents should be embedded in the source map
+    pub fn embed_content(self, embed_content: bool) -> Self {
+        Self {
+            embed_content,
+            ..self
+        }
+    }
+}
+
+/// Line index of a source file, used to resolve offsets into line and column numbers
+struct LineIndex {
+    line_starts: Vec<u32>,
+}
+
+impl LineIndex {
+    fn new(content: &str) -> Self {
+        let mut line_starts = vec![0];
+        let bytes = content.as_bytes();
+
+        for (i, b) in bytes.iter().enumerate() {
+            match b {
+                b'\n' => line_starts.push(i as u32 + 1),
+                b'\r' if bytes.get(i + 1) != Some(&b'\n') => line_starts.push(i as u32 + 1),
+                _ => {}
+            }
+        }
+
+        Self { line_starts }
+    }
+
+    fn resolve(&self, offset: TextSize) -> LineCol {
+        let offset = u32::from(offset);
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+
+        LineCol {
+            line: line as _,
+            col: offset - self.line_starts[line],
+        }
+    }
+}
+
+/// A list of mappings between generated code and source nodes
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    /// Recorded mappings, ordered by generated position. Nested nodes come after the nodes that
+    /// contain them.
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// Find the innermost mapping that contains the given generated position
+    pub fn lookup(&self, position: LineCol) -> Option<&Mapping> {
+        self.mappings
+            .iter()
+            .filter(|mapping| {
+                mapping.generated_start <= position && position < mapping.generated_end
+            })
+            .max_by_key(|mapping| mapping.depth)
+    }
+
+    /// Export this source map as a Source Map v3 JSON string
+    ///
+    /// # Parameters
+    ///
+    /// * `file`: name of the generated file
+    /// * `resolve`: callback returning the source file information for a given file identifier.
+    ///   Mappings for files that cannot be resolved are not included in the output.
+    pub fn to_json<'a>(
+        &self,
+        file: Option<&str>,
+        mut resolve: impl FnMut(FileId) -> Option<SourceFile<'a>>,
+    ) -> String {
+        // Collect source files, in order of appearance
+        let mut sources: Vec<(SourceFile<'a>, LineIndex)> = Vec::new();
+        let mut source_indices: HashMap<FileId, Option<u32>> = HashMap::new();
+
+        // Build the segments, keeping only the innermost mapping for a given generated position
+        let mut segments: Vec<(LineCol, u32, LineCol)> = Vec::with_capacity(self.mappings.len());
+        for mapping in &self.mappings {
+            let source_index = *source_indices
+                .entry(mapping.source_id())
+                .or_insert_with(|| {
+                    resolve(mapping.source_id()).map(|source_file| {
+                        sources.push((source_file, LineIndex::new(source_file.content)));
+                        sources.len() as u32 - 1
+                    })
+                });
+
+            let Some(source_index) = source_index else {
+                continue;
+            };
+
+            let source_pos = sources[source_index as usize]
+                .1
+                .resolve(mapping.span.start().offset);
+
+            match segments.last_mut() {
+                Some(last) if last.0 == mapping.generated_start => {
+                    *last = (mapping.generated_start, source_index, source_pos);
+                }
+                _ => segments.push((mapping.generated_start, source_index, source_pos)),
+            }
+        }
+
+        // Encode the mappings field
+        let mut mappings = String::new();
+        let mut generated_line = 0;
+        let mut previous_col = 0i64;
+        let mut previous_source = 0i64;
+        let mut previous_source_line = 0i64;
+        let mut previous_source_col = 0i64;
+
+        for (generated, source_index, source_pos) in segments {
+            if generated.line != generated_line || mappings.is_empty() {
+                while generated_line < generated.line {
+                    mappings.push(';');
+                    generated_line += 1;
+                }
+
+                previous_col = 0;
+            } else {
+                mappings.push(',');
+            }
+
+            encode_vlq(&mut mappings, generated.col as i64 - previous_col);
+            encode_vlq(&mut mappings, source_index as i64 - previous_source);
+            encode_vlq(&mut mappings, source_pos.line as i64 - previous_source_line);
+            encode_vlq(&mut mappings, source_pos.col as i64 - previous_source_col);
+
+            previous_col = generated.col as _;
+            previous_source = source_index as _;
+            previous_source_line = source_pos.line as _;
+            previous_source_col = source_pos.col as _;
+        }
+
+        // Write the JSON object
+        let mut json = String::from("{\"version\":3");
+
+        if let Some(file) = file {
+            json.push_str(",\"file\":");
+            write_json_string(&mut json, file);
+        }
+
+        json.push_str(",\"sources\":[");
+        for (i, (source, _)) in sources.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+
+            write_json_string(&mut json, source.name);
+        }
+
+        if sources.iter().any(|(source, _)| source.embed_content) {
+            json.push_str("],\"sourcesContent\":[");
+            for (i, (source, _)) in sources.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+
+                if source.embed_content {
+                    write_json_string(&mut json, source.content);
+                } else {
+                    json.push_str("null");
+                }
+            }
+        }
+
+        json.push_str("],\"names\":[],\"mappings\":");
+        write_json_string(&mut json, &mappings);
+        json.push('}');
+
+        json
+    }
+}
+
+/// Append a base64 VLQ encoded value to the output
+fn encode_vlq(output: &mut String, value: i64) {
+    const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut value = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    } as u64;
+
+    loop {
+        let mut digit = (value & 0b11111) as usize;
+        value >>= 5;
+
+        if value > 0 {
+            digit |= 0b100000;
+        }
+
+        output.push(BASE64[digit] as char);
+
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Append a JSON string literal to the output
+fn write_json_string(output: &mut String, s: &str) {
+    output.push('"');
+
+    for ch in s.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => {
+                write!(output, "\\u{:04x}", ch as u32).unwrap();
+            }
+            ch => output.push(ch),
+        }
+    }
+
+    output.push('"');
+}
+
+/// Transpile a translation_unit to GLSL, recording a source map of the output
+pub fn show_translation_unit_with_source_map<F>(
+    f: &mut F,
+    tu: &ast::TranslationUnit,
+    state: FormattingState<'_>,
+) -> Result<SourceMap, std::fmt::Error>
+where
+    F: Write + ?Sized,
+{
+    let recorder = SourceMapRecorder::new();
+    super::show_translation_unit(
+        &mut recorder.writer(f),
+        tu,
+        state.with_source_map(&recorder),
+    )?;
+    Ok(recorder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::DefaultParse;
+    use expect_test::expect;
+
+    #[test]
+    fn vlq() {
+        let mut s = String::new();
+        for value in [0, 1, -1, 15, 16, -16, 123456] {
+            encode_vlq(&mut s, value);
+            s.push(' ');
+        }
+
+        expect![["A C D e gB hB gkxH "]].assert_eq(&s);
+    }
+
+    #[test]
+    fn records_mappings() {
+        let src = "void main(){float x=a+\nb;}";
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+
+        let mut output = String::new();
+        let source_map =
+            show_translation_unit_with_source_map(&mut output, &tu, FormattingState::default())
+                .unwrap();
+
+        expect![[r#"
+            void main() {
+                float x = a + b;
+            }
+        "#]]
+        .assert_eq(&output);
+
+        // Find the `b` variable
+        let mapping = source_map.lookup(LineCol::new(1, 18)).unwrap();
+        assert_eq!(&src[mapping.span().range()], "b");
+        assert_eq!(mapping.generated_end(), LineCol::new(1, 19));
+
+        // Find the declaration statement
+        let mapping = source_map.lookup(LineCol::new(1, 19)).unwrap();
+        assert_eq!(&src[mapping.span().range()], "float x=a+\nb;");
+        assert_eq!(mapping.generated_start(), LineCol::new(1, 4));
+    }
+
+    #[test]
+    fn to_json() {
+        let src = "void main(){float x=a+\nb;}";
+        let tu = ast::TranslationUnit::parse(src).unwrap();
+
+        let mut output = String::new();
+        let source_map =
+            show_translation_unit_with_source_map(&mut output, &tu, FormattingState::default())
+                .unwrap();
+
+        expect![[r#"{"version":3,"file":"out.glsl","sources":["in.glsl"],"sourcesContent":["void main(){float x=a+\nb;}"],"names":[],"mappings":"AAAA,KAAK;IAAO,MAAM,IAAE,IACpB"}"#]]
+        .assert_eq(&source_map.to_json(Some("out.glsl"), |_| {
+            Some(SourceFile::new("in.glsl", src))
+        }));
+
+        expect![[r#"{"version":3,"sources":[],"names":[],"mappings":""}"#]]
+            .assert_eq(&source_map.to_json(None, |_| None));
+    }
+}
@@ -193,6 +193,10 @@ macro_rules! make_visitor_trait {
         Visit::Children
       }
 
+      fn visit_preprocessor_include(&mut self, _: $($ref)* ast::PreprocessorInclude) -> Visit {
+        Visit::Children
+      }
+
       fn visit_preprocessor_moj_import(&mut self, _: $($ref)* ast::PreprocessorMojImport) -> Visit {
         Visit::Children
       }
@@ -268,6 +272,18 @@ macro_rules! make_visitor_trait {
         Visit::Children
       }
 
+      fn visit_attribute(&mut self, _: $($ref)* ast::Attribute) -> Visit {
+        Visit::Children
+      }
+
+      fn visit_attributed_statement(&mut self, _: $($ref)* ast::AttributedStatement) -> Visit {
+        Visit::Children
+      }
+
+      fn visit_attributed_function_definition(&mut self, _: $($ref)* ast::AttributedFunctionDefinition) -> Visit {
+        Visit::Children
+      }
+
       fn visit_assignment_op(&mut self, _: $($ref)* ast::AssignmentOp) -> Visit {
         Visit::Children
       }
@@ -323,6 +339,18 @@ macro_rules! make_visitor_trait {
         Visit::Children
       }
 
+      fn visit_spirv_qualifier(&mut self, _: $($ref)* ast::SpirvQualifier) -> Visit {
+        Visit::Children
+      }
+
+      fn visit_spirv_argument(&mut self, _: $($ref)* ast::SpirvArgument) -> Visit {
+        Visit::Children
+      }
+
+      fn visit_spirv_value(&mut self, _: $($ref)* ast::SpirvValue) -> Visit {
+        Visit::Children
+      }
+
       fn visit_precision_qualifier(&mut self, _: $($ref)* ast::PrecisionQualifier) -> Visit {
         Visit::Children
       }
@@ -440,7 +468,9 @@ macro_rules! make_host_trait {
           match $($ref)* **self {
             ast::ExternalDeclarationData::Preprocessor(p) => p.$mthd_name(visitor),
             ast::ExternalDeclarationData::FunctionDefinition(fd) => fd.$mthd_name(visitor),
+            ast::ExternalDeclarationData::AttributedFunctionDefinition(fd) => fd.$mthd_name(visitor),
             ast::ExternalDeclarationData::Declaration(d) => d.$mthd_name(visitor),
+            ast::ExternalDeclarationData::Error => (),
           }
         }
       }
@@ -463,6 +493,7 @@ macro_rules! make_host_trait {
             ast::PreprocessorData::If(pi) => pi.$mthd_name(visitor),
             ast::PreprocessorData::IfDef(pid) => pid.$mthd_name(visitor),
             ast::PreprocessorData::IfNDef(pind) => pind.$mthd_name(visitor),
+            ast::PreprocessorData::Include(pi) => pi.$mthd_name(visitor),
             ast::PreprocessorData::MojImport(pi) => pi.$mthd_name(visitor),
             ast::PreprocessorData::Line(pl) => pl.$mthd_name(visitor),
             ast::PreprocessorData::Pragma(pp) => pp.$mthd_name(visitor),
@@ -556,6 +587,15 @@ macro_rules! make_host_trait {
       }
     }
 
+    impl $host_ty for ast::PreprocessorInclude {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let _ = visitor.visit_preprocessor_include(self);
+      }
+    }
+
     impl $host_ty for ast::PreprocessorMojImport {
       fn $mthd_name<V>($($ref)* self, visitor: &mut V)
       where
@@ -719,6 +759,27 @@ macro_rules! make_host_trait {
       }
     }
 
+    impl $host_ty for ast::AttributedFunctionDefinition {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_attributed_function_definition(self);
+
+        if visit == Visit::Children {
+          for attribute in $($ref)* self.attributes {
+            attribute.$mthd_name(visitor);
+          }
+
+          for attribute in $($ref)* self.body_attributes {
+            attribute.$mthd_name(visitor);
+          }
+
+          self.definition.$mthd_name(visitor);
+        }
+      }
+    }
+
     impl $host_ty for ast::Declaration {
       fn $mthd_name<V>($($ref)* self, visitor: &mut V)
       where
@@ -741,6 +802,14 @@ macro_rules! make_host_trait {
 
             ast::DeclarationData::Invariant(ident) => ident.$mthd_name(visitor),
 
+            ast::DeclarationData::QualifierRedeclaration(q, idents) => {
+              q.$mthd_name(visitor);
+
+              for ident in idents {
+                ident.$mthd_name(visitor);
+              }
+            }
+
             ast::DeclarationData::TypeOnly(q) => q.$mthd_name(visitor),
           }
         }
@@ -853,6 +922,11 @@ macro_rules! make_host_trait {
           match $($ref)* **self {
             ast::TypeSpecifierNonArrayData::Struct(ss) => ss.$mthd_name(visitor),
             ast::TypeSpecifierNonArrayData::TypeName(tn) => tn.$mthd_name(visitor),
+            ast::TypeSpecifierNonArrayData::SpirvType(args) => {
+              for arg in args {
+                arg.$mthd_name(visitor);
+              }
+            }
             _ => (),
           }
         }
@@ -887,6 +961,7 @@ macro_rules! make_host_trait {
             ast::TypeQualifierSpecData::Layout(lq) => lq.$mthd_name(visitor),
             ast::TypeQualifierSpecData::Precision(pq) => pq.$mthd_name(visitor),
             ast::TypeQualifierSpecData::Interpolation(iq) => iq.$mthd_name(visitor),
+            ast::TypeQualifierSpecData::Spirv(sq) => sq.$mthd_name(visitor),
             _ => (),
           }
         }
@@ -944,6 +1019,75 @@ macro_rules! make_host_trait {
       }
     }
 
+    impl $host_ty for ast::SpirvQualifier {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_spirv_qualifier(self);
+
+        if visit == Visit::Children {
+          match $($ref)* **self {
+            ast::SpirvQualifierData::Instruction(args)
+            | ast::SpirvQualifierData::ExecutionMode(args)
+            | ast::SpirvQualifierData::ExecutionModeId(args)
+            | ast::SpirvQualifierData::Decorate(args)
+            | ast::SpirvQualifierData::DecorateId(args)
+            | ast::SpirvQualifierData::DecorateString(args)
+            | ast::SpirvQualifierData::StorageClass(args) => {
+              for arg in args {
+                arg.$mthd_name(visitor);
+              }
+            }
+            ast::SpirvQualifierData::ByReference | ast::SpirvQualifierData::Literal => (),
+          }
+        }
+      }
+    }
+
+    impl $host_ty for ast::SpirvArgument {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_spirv_argument(self);
+
+        if visit == Visit::Children {
+          match $($ref)* **self {
+            ast::SpirvArgumentData::Requirement(ident, values) => {
+              ident.$mthd_name(visitor);
+
+              for value in values {
+                value.$mthd_name(visitor);
+              }
+            }
+            ast::SpirvArgumentData::Named(ident, value) => {
+              ident.$mthd_name(visitor);
+              value.$mthd_name(visitor);
+            }
+            ast::SpirvArgumentData::Value(value) => value.$mthd_name(visitor),
+          }
+        }
+      }
+    }
+
+    impl $host_ty for ast::SpirvValue {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_spirv_value(self);
+
+        if visit == Visit::Children {
+          match $($ref)* **self {
+            ast::SpirvValueData::String(_) => (),
+            ast::SpirvValueData::Expr(e) => e.$mthd_name(visitor),
+            ast::SpirvValueData::Type(t) => t.$mthd_name(visitor),
+          }
+        }
+      }
+    }
+
     impl $host_ty for ast::PrecisionQualifier {
       fn $mthd_name<V>($($ref)* self, visitor: &mut V)
       where
@@ -1118,6 +1262,20 @@ macro_rules! make_host_trait {
       }
     }
 
+    impl $host_ty for ast::Attribute {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_attribute(self);
+
+        if visit == Visit::Children {
+          self.name.$mthd_name(visitor);
+          self.arg.$mthd_name(visitor);
+        }
+      }
+    }
+
     impl $host_ty for ast::FunIdentifier {
       fn $mthd_name<V>($($ref)* self, visitor: &mut V)
       where
@@ -1186,11 +1344,30 @@ macro_rules! make_host_trait {
             ast::StatementData::Iteration(i) => i.$mthd_name(visitor),
             ast::StatementData::Jump(j) => j.$mthd_name(visitor),
             ast::StatementData::Compound(cs) => cs.$mthd_name(visitor),
+            ast::StatementData::Attributed(a) => a.$mthd_name(visitor),
+            ast::StatementData::Error => (),
           }
         }
       }
     }
 
+    impl $host_ty for ast::AttributedStatement {
+      fn $mthd_name<V>($($ref)* self, visitor: &mut V)
+      where
+          V: $visitor_ty,
+      {
+        let visit = visitor.visit_attributed_statement(self);
+
+        if visit == Visit::Children {
+          for attribute in $($ref)* self.attributes {
+            attribute.$mthd_name(visitor);
+          }
+
+          self.statement.$mthd_name(visitor);
+        }
+      }
+    }
+
     impl $host_ty for ast::CompoundStatement {
       fn $mthd_name<V>($($ref)* self, visitor: &mut V)
       where
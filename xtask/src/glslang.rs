@@ -6,11 +6,73 @@ use std::path::{Path, PathBuf};
 use heck::ToSnakeCase;
 
 const EXCLUDE_PREFIXES: &[&str] = &[
-    "hlsl.", "spv.", /* TODO: Remove this when we support attributes */
+    "hlsl.", "spv.", /* TODO: Remove this when we support the extensions these tests use */
+];
+
+/// Tests that are generated even though they match one of the excluded prefixes
+const INCLUDE_FILES: &[&str] = &[
+    "spv.1.4.LoopControl.frag",
+    "spv.320.meshShaderUserDefined.mesh",
+    "spv.bufferhandle1.frag",
+    "spv.bufferhandle10.frag",
+    "spv.bufferhandle13.frag",
+    "spv.bufferhandle14.frag",
+    "spv.bufferhandle2.frag",
+    "spv.bufferhandle3.frag",
+    "spv.bufferhandle4.frag",
+    "spv.bufferhandle5.frag",
+    "spv.bufferhandle6.frag",
+    "spv.bufferhandle7.frag",
+    "spv.bufferhandle8.frag",
+    "spv.bufferhandle9.frag",
+    "spv.bufferhandleUvec2.frag",
+    "spv.bufferhandle_Error.frag",
+    "spv.controlFlowAttributes.frag",
+    "spv.ext.AccelDecl.frag",
+    "spv.ext.AnyHitShader.rahit",
+    "spv.ext.AnyHitShader_Errors.rahit",
+    "spv.ext.ClosestHitShader.rchit",
+    "spv.ext.ClosestHitShader_Errors.rchit",
+    "spv.ext.ClosestHitShader_Subgroup.rchit",
+    "spv.ext.IntersectShader.rint",
+    "spv.ext.IntersectShader_Errors.rint",
+    "spv.ext.MissShader.rmiss",
+    "spv.ext.MissShader_Errors.rmiss",
+    "spv.ext.RayCallable.rcall",
+    "spv.ext.RayCallable_Errors.rcall",
+    "spv.ext.RayConstants.rgen",
+    "spv.ext.RayGenSBTlayout.rgen",
+    "spv.ext.RayGenSBTlayout140.rgen",
+    "spv.ext.RayGenSBTlayout430.rgen",
+    "spv.ext.RayGenSBTlayoutscalar.rgen",
+    "spv.ext.RayGenShader.rgen",
+    "spv.ext.RayGenShader11.rgen",
+    "spv.ext.RayGenShaderArray.rgen",
+    "spv.ext.RayGenShader_Errors.rgen",
+    "spv.ext.RayPrimCull_Errors.rgen",
+    "spv.ext.RayQueryDecl.frag",
+    "spv.ext.World3x4.rahit",
+    "spv.intrinsicsSpirvByReference.vert",
+    "spv.intrinsicsSpirvInstruction.vert",
+    "spv.intrinsicsSpirvLiteral.vert",
+    "spv.intrinsicsSpirvStorageClass.rchit",
+    "spv.intrinsicsSpirvType.rgen",
+    "spv.meshShaderBuiltins.mesh",
+    "spv.meshShaderPerViewBuiltins.mesh",
+    "spv.meshShaderPerViewUserDefined.mesh",
+    "spv.meshShaderPerView_Errors.mesh",
+    "spv.meshShaderRedeclBuiltins.mesh",
+    "spv.meshShaderRedeclPerViewBuiltins.mesh",
+    "spv.meshShaderSharedMem.mesh",
+    "spv.meshShaderTaskMem.mesh",
+    "spv.meshShaderUserDefined.mesh",
+    "spv.meshTaskShader.task",
+    "spv.subgroupUniformControlFlow.vert",
 ];
 
 const SHADER_EXTS: &[&str] = &[
-    "mesh", "tese", "rgen", "tesc", "geom", "comp", "vert", "frag",
+    "mesh", "task", "tese", "rgen", "rahit", "rchit", "rint", "rmiss", "rcall", "tesc", "geom",
+    "comp", "vert", "frag",
 ];
 
 pub struct DiscoveredTests {
@@ -83,10 +145,11 @@ pub fn discover_tests(current_dir: &Path) -> DiscoveredTests {
                         .file_name()
                         .to_str()
                         .map(|file_name| {
-                            !EXCLUDE_PREFIXES
-                                .iter()
-                                .any(|prefix| file_name.starts_with(prefix))
-                                && SHADER_EXTS.iter().any(|ext| file_name.ends_with(ext))
+                            INCLUDE_FILES.contains(&file_name)
+                                || !EXCLUDE_PREFIXES
+                                    .iter()
+                                    .any(|prefix| file_name.starts_with(prefix))
+                                    && SHADER_EXTS.iter().any(|ext| file_name.ends_with(ext))
                         })
                         .unwrap_or(false)
                 })
@@ -94,7 +157,7 @@ pub fn discover_tests(current_dir: &Path) -> DiscoveredTests {
                     PathBuf::from("..").join(
                         entry
                             .path()
-                            .strip_prefix(&current_dir)
+                            .strip_prefix(current_dir)
                             .expect("failed to strip current dir"),
                     )
                 })
@@ -1,9 +1,10 @@
-use std::env;
+use std::{env, path::PathBuf};
 
 use anyhow::bail;
 use argh::FromArgs;
 
 mod glslang;
+mod parser_size;
 
 #[derive(Debug, FromArgs)]
 /// glsl-lang task runner
@@ -16,6 +17,7 @@ struct Opts {
 #[argh(subcommand)]
 enum Command {
     GenerateTests(GenerateTestsOpts),
+    ParserSize(ParserSizeOpts),
 }
 
 #[derive(Debug, FromArgs)]
@@ -23,7 +25,12 @@ enum Command {
 /// Generate glslang test files
 struct GenerateTestsOpts {}
 
-fn generate_tests(_opts: &Opts, _gen_test_opts: &GenerateTestsOpts) -> anyhow::Result<()> {
+#[derive(Debug, FromArgs)]
+#[argh(subcommand, name = "parser-size")]
+/// Check the size of the parsers generated for the glsl-lang sub-parser features
+struct ParserSizeOpts {}
+
+fn find_base_dir() -> anyhow::Result<PathBuf> {
     let base_dir = env::current_dir().expect("failed to read current dir");
 
     let mut base_dir = base_dir.as_path();
@@ -38,6 +45,13 @@ fn generate_tests(_opts: &Opts, _gen_test_opts: &GenerateTestsOpts) -> anyhow::R
 
     eprintln!("Found base directory: {}", base_dir.display());
 
+    Ok(base_dir.to_owned())
+}
+
+fn generate_tests(_opts: &Opts, _gen_test_opts: &GenerateTestsOpts) -> anyhow::Result<()> {
+    let base_dir = find_base_dir()?;
+    let base_dir = base_dir.as_path();
+
     let tests = glslang::discover_tests(base_dir);
 
     tests.write_entry(
@@ -55,5 +69,6 @@ fn main() -> anyhow::Result<()> {
         Command::GenerateTests(ref generate_tests_opts) => {
             generate_tests(&opts, generate_tests_opts)
         }
+        Command::ParserSize(_) => parser_size::check(&find_base_dir()?),
     }
 }
@@ -0,0 +1,83 @@
+//! Generated parser size budget for the glsl-lang sub-parser features
+
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context};
+
+/// Features enabling additional start symbols in the glsl-lang grammar
+const SUB_PARSER_FEATURES: &[&str] = &[
+    "parser-expr",
+    "parser-statement",
+    "parser-declaration",
+    "parser-function-definition",
+    "parser-type-specifier",
+    "parser-layout-qualifier",
+    "parser-initializer",
+];
+
+/// Maximum size of the parser generated with a single sub-parser feature, relative to the
+/// translation unit parser generated with the default features
+const SUB_PARSER_BUDGET: f64 = 2.0;
+
+/// Maximum size of the parser generated with all the sub-parser features, relative to the
+/// translation unit parser generated with the default features
+const ALL_PARSERS_BUDGET: f64 = 6.0;
+
+/// Generate the parser for the given features, and return the size of the generated code
+fn generated_size(base_dir: &Path, out_dir: &Path, features: &[&str]) -> anyhow::Result<u64> {
+    if out_dir.exists() {
+        fs::remove_dir_all(out_dir)?;
+    }
+    fs::create_dir_all(out_dir)?;
+
+    let grammar = out_dir.join("parser.lalrpop");
+    fs::copy(base_dir.join("lang/src/parser.lalrpop"), &grammar)?;
+
+    lalrpop::Configuration::new()
+        .set_in_dir(out_dir)
+        .set_out_dir(out_dir)
+        .set_features(features.iter().map(|feature| feature.to_string()))
+        .log_quiet()
+        .process_file(&grammar)
+        .map_err(|err| anyhow::anyhow!("{}", err))
+        .with_context(|| format!("failed to generate the parser for {:?}", features))?;
+
+    Ok(fs::metadata(out_dir.join("parser.rs"))?.len())
+}
+
+/// Check that the sub-parser features stay within their generated code size budget
+pub fn check(base_dir: &Path) -> anyhow::Result<()> {
+    let out_dir = base_dir.join("target/parser-size");
+
+    let base = generated_size(base_dir, &out_dir, &[])?;
+    eprintln!("{:<32} {:>10} bytes", "default", base);
+
+    let mut over_budget = Vec::new();
+    let mut check_features = |name: &str, features: &[&str], budget: f64| -> anyhow::Result<()> {
+        let size = generated_size(base_dir, &out_dir, features)?;
+        let ratio = size as f64 / base as f64;
+        eprintln!("{:<32} {:>10} bytes ({:.2}x)", name, size, ratio);
+
+        if ratio > budget {
+            over_budget.push(format!(
+                "{} generates {:.2}x the default parser, over the {:.2}x budget",
+                name, ratio, budget
+            ));
+        }
+
+        Ok(())
+    };
+
+    for feature in SUB_PARSER_FEATURES {
+        check_features(feature, &[feature], SUB_PARSER_BUDGET)?;
+    }
+    check_features("parser-all", SUB_PARSER_FEATURES, ALL_PARSERS_BUDGET)?;
+
+    fs::remove_dir_all(&out_dir)?;
+
+    if !over_budget.is_empty() {
+        bail!("{}", over_budget.join("\n"));
+    }
+
+    Ok(())
+}